@@ -42,23 +42,23 @@ fn times() {
 fn bad_times() {
     bad!(
         "foo = 199-09-09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected a four-digit year for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 199709-09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `-` after the year for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-9-09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected a two-digit month for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-9",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected a two-digit day for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-0909:09:09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: unexpected characters after the datetime for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.",
@@ -82,51 +82,51 @@ fn bad_times() {
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09+09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `:` in the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09+09:9",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09+0909",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `:` in the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09-",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09-09",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `:` in the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09-09:9",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T09:09:09.09-0909",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `:` in the offset for key `foo` at line 1 column 7"
     );
 
     bad!(
         "foo = 1997-00-09T09:09:09.09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: month out of range, expected `01` to `12` for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-00T09:09:09.09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: day out of range for the month for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T30:09:09.09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: hour out of range, expected `00` to `23` for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T12:69:09.09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: minute out of range, expected `00` to `59` for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 1997-09-09T12:09:69.09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: second out of range, expected `00` to `60` for key `foo` at line 1 column 7"
     );
 }
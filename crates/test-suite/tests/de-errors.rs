@@ -178,20 +178,19 @@ fn custom_errors() {
     );
 
     // Sub-table in the middle of a Vec has an extra field.
-    // FIXME: This location could be better.
     bad!(
         "
             p_a = 'a'
             p_b = [
                 {c_a = 'a', c_b = 'b'},
                 {c_a = 'aa', c_b = 'bb', c_d = 'd'},
-              # ^
+                                       # ^
                 {c_a = 'aaa', c_b = 'bbb'},
                 {c_a = 'aaaa', c_b = 'bbbb'},
             ]
         ",
         Parent<CasedString>,
-        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 5 column 17"
+        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 5 column 42"
     );
 
     // Sub-table in the middle of a Vec is missing a field.
@@ -237,7 +236,6 @@ fn custom_errors() {
     );
 
     // Sub-table in the middle of a Vec has an extra field.
-    // FIXME: This location is pretty off.
     bad!(
         "
             p_a = 'a'
@@ -247,16 +245,16 @@ fn custom_errors() {
             [[p_b]]
             c_a = 'aa'
             c_d = 'dd' # unknown field
+          # ^
             [[p_b]]
             c_a = 'aaa'
             c_b = 'bbb'
             [[p_b]]
-          # ^
             c_a = 'aaaa'
             c_b = 'bbbb'
         ",
         Parent<CasedString>,
-        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 12 column 13"
+        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 8 column 13"
     );
 }
 
@@ -295,17 +293,16 @@ fn serde_derive_deserialize_errors() {
         "invalid type: integer `1`, expected a string for key `p_b` at line 4 column 34"
     );
 
-    // FIXME: This location could be better.
     bad!(
         "
             p_a = ''
             p_b = [
                 {c_a = '', c_b = '', c_d = ''},
-              # ^
+                                   # ^
             ]
         ",
         Parent<String>,
-        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 4 column 17"
+        "unknown field `c_d`, expected `c_a` or `c_b` for key `p_b` at line 4 column 38"
     );
 
     bad!(
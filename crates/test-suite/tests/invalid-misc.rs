@@ -27,7 +27,7 @@ fn bad() {
     bad!("a = -0x1", "invalid number at line 1 column 5");
     bad!(
         "a = 0x-1",
-        "failed to parse datetime for key `a` at line 1 column 5"
+        "failed to parse datetime: expected a four-digit year for key `a` at line 1 column 5"
     );
 
     // Dotted keys.
@@ -15,7 +15,7 @@ macro_rules! test( ($name:ident, $s:expr, $msg:expr) => (
 test!(
     datetime_malformed_no_leads,
     include_str!("invalid/datetime-malformed-no-leads.toml"),
-    "failed to parse datetime for key `no-leads` at line 1 column 12"
+    "failed to parse datetime: expected a two-digit month for key `no-leads` at line 1 column 12"
 );
 test!(
     datetime_malformed_no_secs,
@@ -25,12 +25,12 @@ test!(
 test!(
     datetime_malformed_no_t,
     include_str!("invalid/datetime-malformed-no-t.toml"),
-    "failed to parse datetime for key `no-t` at line 1 column 8"
+    "failed to parse datetime: unexpected characters after the datetime for key `no-t` at line 1 column 8"
 );
 test!(
     datetime_malformed_with_milli,
     include_str!("invalid/datetime-malformed-with-milli.toml"),
-    "failed to parse datetime for key `with-milli` at line 1 column 14"
+    "failed to parse datetime: expected a two-digit day for key `with-milli` at line 1 column 14"
 );
 test!(
     duplicate_key_table,
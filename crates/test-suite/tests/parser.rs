@@ -652,23 +652,23 @@ fn datetimes() {
     t!("2016-09-09T09:09:09.123456789-02:00");
     bad!(
         "foo = 2016-09-09T09:09:09.Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected digits after `.` for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 2016-9-09T09:09:09Z",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected a two-digit month for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 2016-09-09T09:09:09+2:00",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 2016-09-09T09:09:09-2:00",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset for key `foo` at line 1 column 7"
     );
     bad!(
         "foo = 2016-09-09T09:09:09Z-2:00",
-        "failed to parse datetime for key `foo` at line 1 column 7"
+        "failed to parse datetime: unexpected characters after the datetime for key `foo` at line 1 column 7"
     );
 }
 
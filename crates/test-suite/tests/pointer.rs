@@ -0,0 +1,59 @@
+use toml::Value;
+
+#[test]
+fn pointer() {
+    let value: Value = toml::from_str(
+        r#"
+        title = "pointers"
+        "a/b" = 1
+        "m~n" = 2
+        "" = 3
+
+        [[bin]]
+        name = "first"
+
+        [[bin]]
+        name = "second"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/title").unwrap().as_str(), Some("pointers"));
+    assert_eq!(value.pointer("/a~1b").unwrap().as_integer(), Some(1));
+    assert_eq!(value.pointer("/m~0n").unwrap().as_integer(), Some(2));
+    assert_eq!(value.pointer("/").unwrap().as_integer(), Some(3));
+    assert_eq!(
+        value.pointer("/bin/1/name").unwrap().as_str(),
+        Some("second")
+    );
+
+    assert_eq!(value.pointer("title"), None);
+    assert_eq!(value.pointer("/missing"), None);
+    assert_eq!(value.pointer("/bin/2"), None);
+    assert_eq!(value.pointer("/bin/01"), None);
+    assert_eq!(value.pointer("/bin/+1"), None);
+    assert_eq!(value.pointer("/bin/name"), None);
+    assert_eq!(value.pointer("/title/0"), None);
+}
+
+#[test]
+fn pointer_mut() {
+    let mut value: Value = toml::from_str("[server]\nports = [80, 443]\n").unwrap();
+
+    value
+        .pointer_mut("/server/ports")
+        .and_then(Value::as_array_mut)
+        .unwrap()
+        .push(Value::Integer(8080));
+    *value.pointer_mut("/server/ports/0").unwrap() = Value::Integer(8000);
+    assert_eq!(
+        value["server"]["ports"],
+        Value::Array(vec![
+            Value::Integer(8000),
+            Value::Integer(443),
+            Value::Integer(8080)
+        ])
+    );
+    assert_eq!(value.pointer_mut("/server/host"), None);
+}
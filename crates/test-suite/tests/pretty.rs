@@ -309,3 +309,55 @@ fn pretty_tricky_non_literal() {
     println!("\nRESULT:\n{}", result);
     assert_eq!(toml, &result);
 }
+
+const ARRAY_OF_TABLES: &str = "\
+[[package.bin]]
+features = []
+name = 'demo'
+
+[package.bin.target]
+os = [
+    'linux',
+    'macos',
+]
+
+[[package.bin]]
+name = 'demo-cli'
+";
+
+const INLINE_ARRAY_OF_TABLES: &str = "\
+[package]
+bin = [
+    { features = [], name = 'demo', target = { os = ['linux', 'macos'] } },
+    { name = 'demo-cli' },
+]
+";
+
+#[test]
+fn pretty_array_of_tables() {
+    let toml = ARRAY_OF_TABLES;
+    let value: toml::Value = toml::from_str(toml).unwrap();
+    let mut result = String::with_capacity(128);
+    value
+        .serialize(&mut toml::Serializer::pretty(&mut result))
+        .unwrap();
+    println!("EXPECTED:\n{}", toml);
+    println!("\nRESULT:\n{}", result);
+    assert_eq!(toml, &result);
+}
+
+#[test]
+fn inline_array_of_tables() {
+    let toml = INLINE_ARRAY_OF_TABLES;
+    let value: toml::Value = toml::from_str(ARRAY_OF_TABLES).unwrap();
+    let mut result = String::with_capacity(128);
+    {
+        let mut serializer = toml::Serializer::pretty(&mut result);
+        serializer.inline_array_of_tables(true);
+        value.serialize(&mut serializer).unwrap();
+    }
+    println!("EXPECTED:\n{}", toml);
+    println!("\nRESULT:\n{}", result);
+    assert_eq!(toml, &result);
+    assert_eq!(value, toml::from_str::<toml::Value>(&result).unwrap());
+}
@@ -28,3 +28,69 @@ fn always_works() {
 
     toml::to_string(&a).unwrap();
 }
+
+#[derive(Serialize)]
+struct B {
+    name: &'static str,
+    vals: HashMap<&'static str, Value>,
+    list: Vec<HashMap<&'static str, &'static str>>,
+    id: Option<i32>,
+}
+
+#[test]
+fn sort_keys() {
+    let mut b = B {
+        name: "b",
+        vals: HashMap::new(),
+        list: Vec::new(),
+        id: None,
+    };
+    for (i, key) in ["zeta", "alpha", "mu"].into_iter().enumerate() {
+        b.vals.insert(key, Value::Int(i as i32));
+        let mut sub = HashMap::new();
+        sub.insert("z", "last");
+        sub.insert("a", "first");
+        b.vals.insert(&key[..1], Value::Map(sub.clone()));
+        b.list.push(sub);
+    }
+
+    let mut result = String::new();
+    let mut serializer = toml::Serializer::new(&mut result);
+    serializer.sort_keys(true);
+    serde::Serialize::serialize(&b, &mut serializer).unwrap();
+    assert_eq!(
+        result,
+        "\
+name = \"b\"
+
+[[list]]
+a = \"first\"
+z = \"last\"
+
+[[list]]
+a = \"first\"
+z = \"last\"
+
+[[list]]
+a = \"first\"
+z = \"last\"
+
+[vals]
+alpha = 1
+mu = 2
+zeta = 0
+
+[vals.a]
+a = \"first\"
+z = \"last\"
+
+[vals.m]
+a = \"first\"
+z = \"last\"
+
+[vals.z]
+a = \"first\"
+z = \"last\"
+"
+    );
+}
@@ -92,6 +92,7 @@ struct ErrorInner {
     line: Option<usize>,
     col: usize,
     at: Option<usize>,
+    end: Option<usize>,
     message: String,
     key: Vec<String>,
 }
@@ -226,7 +227,7 @@ impl<'de, 'b> de::Deserializer<'de> for &'b mut Deserializer<'de> {
             array: false,
             de: self,
         });
-        res.map_err(|mut err| {
+        res.map_err(|mut err: Error| {
             // Errors originating from this library (toml), have an offset
             // attached to them already. Other errors, like those originating
             // from serde (like "missing field") or from a custom deserializer,
@@ -732,10 +733,18 @@ impl<'de> de::Deserializer<'de> for StrDeserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        match self.key {
+        let span = self.span;
+        let res = match self.key {
             Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
             Cow::Owned(s) => visitor.visit_string(s),
-        }
+        };
+        // Errors about a key, like an unknown field, point at the key itself
+        res.map_err(|mut err: Error| {
+            if let Some(span) = span {
+                err.fix_span(span);
+            }
+            err
+        })
     }
 
     fn deserialize_struct<V>(
@@ -817,7 +826,7 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                 })
             }
         };
-        res.map_err(|mut err| {
+        res.map_err(|mut err: Error| {
             // Attribute the error to whatever value returned the error.
             err.fix_offset(|| Some(start));
             err
@@ -857,9 +866,9 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                         })
                         .collect::<Vec<_>>();
 
-                    if !extra_fields.is_empty() {
-                        return Err(Error::from_kind(
-                            Some(self.value.start),
+                    if let Some((span, _)) = extra_fields.first() {
+                        let mut err = Error::from_kind(
+                            None,
                             ErrorKind::UnexpectedKeys {
                                 keys: extra_fields
                                     .iter()
@@ -867,7 +876,9 @@ impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
                                     .collect::<Vec<_>>(),
                                 available: fields,
                             },
-                        ));
+                        );
+                        err.fix_span(*span);
+                        return Err(err);
                     }
                 }
                 _ => {}
@@ -2016,6 +2027,34 @@ impl Error {
         self.inner.line.map(|line| (line, self.inner.col))
     }
 
+    /// Produces the byte range of the input that the error is about, if known
+    ///
+    /// This is the key of errors about a key, like an unknown field rejected by
+    /// `#[serde(deny_unknown_fields)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// let input = "name = 'demo'\nnmae = 'typo'\n";
+    /// let err = toml::from_str::<Config>(input).unwrap_err();
+    /// assert_eq!(&input[err.span().unwrap()], "nmae");
+    /// assert_eq!(err.line_col(), Some((1, 0)));
+    /// ```
+    pub fn span(&self) -> Option<std::ops::Range<usize>> {
+        match (self.inner.at, self.inner.end) {
+            (Some(start), Some(end)) => Some(start..end),
+            _ => None,
+        }
+    }
+
     fn from_kind(at: Option<usize>, kind: ErrorKind) -> Error {
         Error {
             inner: Box::new(ErrorInner {
@@ -2023,6 +2062,7 @@ impl Error {
                 line: None,
                 col: 0,
                 at,
+                end: None,
                 message: String::new(),
                 key: Vec::new(),
             }),
@@ -2036,6 +2076,7 @@ impl Error {
                 line: None,
                 col: 0,
                 at,
+                end: None,
                 message: s,
                 key: Vec::new(),
             }),
@@ -2057,6 +2098,13 @@ impl Error {
         }
     }
 
+    fn fix_span(&mut self, span: Span) {
+        if self.inner.at.is_none() {
+            self.inner.at = Some(span.start);
+            self.inner.end = Some(span.end);
+        }
+    }
+
     fn fix_linecol<F>(&mut self, f: F)
     where
         F: FnOnce(usize) -> (usize, usize),
@@ -0,0 +1,132 @@
+//! Conversions to and from [`toml_edit`]'s format-preserving types.
+//!
+//! Converting into `toml_edit` produces default formatting: tables become `[header]` tables,
+//! arrays of tables become `[[header]]` tables, and everything else is written inline.
+//! Converting from `toml_edit` discards formatting but keeps all data.
+//!
+//! ```rust
+//! use std::convert::TryInto;
+//!
+//! let value = toml::toml! {
+//!     [package]
+//!     name = "foo"
+//! };
+//!
+//! let mut doc = toml_edit::Document::from(toml_edit::Item::from(value.clone()).into_table().unwrap());
+//! assert_eq!(doc.to_string(), "[package]\nname = \"foo\"\n");
+//!
+//! doc["package"]["version"] = toml_edit::value("0.1.0");
+//! // `toml::Value::try_from` is the inherent serialization method, so go through `TryInto`
+//! let roundtrip: toml::Value = doc.as_item().try_into().unwrap();
+//! assert_eq!(roundtrip["package"]["version"].as_str(), Some("0.1.0"));
+//! ```
+
+use std::convert::TryFrom;
+
+use crate::value::{Table, Value};
+
+impl From<Value> for toml_edit::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::String(v) => v.into(),
+            Value::Integer(v) => v.into(),
+            Value::Float(v) => v.into(),
+            Value::Boolean(v) => v.into(),
+            Value::Datetime(v) => v.into(),
+            Value::Array(v) => v
+                .into_iter()
+                .map(toml_edit::Value::from)
+                .collect::<toml_edit::Array>()
+                .into(),
+            Value::Table(v) => v
+                .into_iter()
+                .map(|(k, v)| (k, toml_edit::Value::from(v)))
+                .collect::<toml_edit::InlineTable>()
+                .into(),
+        }
+    }
+}
+
+impl From<Value> for toml_edit::Item {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Table(v) => toml_edit::Item::Table(to_edit_table(v)),
+            Value::Array(v) if !v.is_empty() && v.iter().all(Value::is_table) => {
+                let tables = v.into_iter().map(|v| match v {
+                    Value::Table(t) => to_edit_table(t),
+                    _ => unreachable!("all elements are tables"),
+                });
+                toml_edit::Item::ArrayOfTables(tables.collect())
+            }
+            v => toml_edit::Item::Value(v.into()),
+        }
+    }
+}
+
+fn to_edit_table(table: Table) -> toml_edit::Table {
+    let mut t = toml_edit::Table::new();
+    for (k, v) in table {
+        t.insert(&k, v.into());
+    }
+    t
+}
+
+/// Fails for integers outside of `i64`, which [`Value::Integer`] can't hold, like a
+/// [`toml_edit::Value::WideInteger`].
+impl<'a> TryFrom<&'a toml_edit::Value> for Value {
+    type Error = crate::de::Error;
+
+    fn try_from(value: &'a toml_edit::Value) -> Result<Self, Self::Error> {
+        let value = match value {
+            toml_edit::Value::String(v) => Value::String(v.value().clone()),
+            toml_edit::Value::Integer(v) => Value::Integer(*v.value()),
+            toml_edit::Value::WideInteger(v) => {
+                return Err(serde::de::Error::custom(format!(
+                    "integer `{}` is out of range for `i64`",
+                    v.value()
+                )))
+            }
+            toml_edit::Value::Float(v) => Value::Float(*v.value()),
+            toml_edit::Value::Boolean(v) => Value::Boolean(*v.value()),
+            toml_edit::Value::Datetime(v) => Value::Datetime(*v.value()),
+            toml_edit::Value::OpaqueDatetime(v) => Value::Datetime(v.value().to_datetime()),
+            toml_edit::Value::Array(v) => Value::Array(
+                v.iter()
+                    .map(<Value as TryFrom<_>>::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            toml_edit::Value::InlineTable(v) => Value::Table(
+                v.iter()
+                    .map(|(k, v)| <Value as TryFrom<_>>::try_from(v).map(|v| (k.to_owned(), v)))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(value)
+    }
+}
+
+/// Fails if the item, or any item nested in it, is [`toml_edit::Item::None`] or an integer
+/// outside of `i64`.
+impl<'a> TryFrom<&'a toml_edit::Item> for Value {
+    type Error = crate::de::Error;
+
+    fn try_from(item: &'a toml_edit::Item) -> Result<Self, Self::Error> {
+        match item {
+            toml_edit::Item::None => Err(serde::de::Error::custom("unsupported None value")),
+            toml_edit::Item::Value(v) => <Value as TryFrom<_>>::try_from(v),
+            toml_edit::Item::Table(v) => from_edit_table(v).map(Value::Table),
+            toml_edit::Item::ArrayOfTables(v) => v
+                .iter()
+                .map(|t| from_edit_table(t).map(Value::Table))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+        }
+    }
+}
+
+fn from_edit_table(table: &toml_edit::Table) -> Result<Table, crate::de::Error> {
+    table
+        .iter()
+        .map(|(k, v)| <Value as TryFrom<_>>::try_from(v).map(|v| (k.to_owned(), v)))
+        .collect()
+}
@@ -164,6 +164,9 @@ pub mod macros;
 mod spanned;
 pub use crate::spanned::Spanned;
 
+#[cfg(feature = "toml_edit")]
+pub mod edit;
+
 // Shortcuts for the module doc-comment
 #[allow(unused_imports)]
 use core::str::FromStr;
@@ -9,10 +9,11 @@
 //! A map of String to toml::Value.
 //!
 //! By default the map is backed by a [`BTreeMap`]. Enable the `preserve_order`
-//! feature of toml-rs to use [`LinkedHashMap`] instead.
+//! feature of toml-rs to use [`IndexMap`] instead, keeping keys in the order
+//! they were inserted, or parsed.
 //!
 //! [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
-//! [`LinkedHashMap`]: https://docs.rs/linked-hash-map/*/linked_hash_map/struct.LinkedHashMap.html
+//! [`IndexMap`]: https://docs.rs/indexmap/*/indexmap/map/struct.IndexMap.html
 
 use crate::value::Value;
 use serde::{de, ser};
@@ -129,13 +130,18 @@ impl Map<String, Value> {
     ///
     /// The key may be any borrowed form of the map's key type, but the ordering
     /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// With `preserve_order`, the keys after it shift down to keep their order.
     #[inline]
     pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<Value>
     where
         String: Borrow<Q>,
         Q: Ord + Eq + Hash,
     {
-        self.map.remove(key)
+        #[cfg(feature = "preserve_order")]
+        return self.map.shift_remove(key);
+        #[cfg(not(feature = "preserve_order"))]
+        return self.map.remove(key);
     }
 
     /// Gets the given key's corresponding entry in the map for in-place
@@ -485,9 +491,14 @@ impl<'a> OccupiedEntry<'a> {
     }
 
     /// Takes the value of the entry out of the map, and returns it.
+    ///
+    /// With `preserve_order`, the keys after it shift down to keep their order.
     #[inline]
     pub fn remove(self) -> Value {
-        self.occupied.remove()
+        #[cfg(feature = "preserve_order")]
+        return self.occupied.shift_remove();
+        #[cfg(not(feature = "preserve_order"))]
+        return self.occupied.remove();
     }
 }
 
@@ -26,6 +26,7 @@
 //! ```
 
 use std::cell::Cell;
+use std::cmp::Ordering;
 use std::error;
 use std::fmt::{self, Write};
 use std::marker;
@@ -180,6 +181,21 @@ impl StringSettings {
 struct Settings {
     array: Option<ArraySettings>,
     string: Option<StringSettings>,
+    /// Whether to write tables within arrays as inline tables
+    inline_array_of_tables: bool,
+    /// How to order the keys of each table, rather than in the order they are serialized
+    key_order: Option<KeyOrder>,
+}
+
+type KeyCompare = dyn Fn(&str, &str) -> Ordering;
+
+#[derive(Clone)]
+struct KeyOrder(Rc<KeyCompare>);
+
+impl fmt::Debug for KeyOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyOrder(..)")
+    }
 }
 
 /// Serialization implementation for TOML.
@@ -191,6 +207,55 @@ struct Settings {
 ///
 /// Currently a serializer always writes its output to an in-memory `String`,
 /// which is passed in when creating the serializer itself.
+///
+/// Starting from `Serializer::new` or `Serializer::pretty`, the output can be
+/// adjusted through builder methods like `Serializer::pretty_array_indent` and
+/// `Serializer::inline_array_of_tables`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Manifest {
+///     authors: Vec<&'static str>,
+///     bin: Vec<Target>,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Target {
+///     name: &'static str,
+///     path: &'static str,
+/// }
+///
+/// let value = Manifest {
+///     authors: vec!["Alice", "Bob", "Carol"],
+///     bin: vec![Target {
+///         name: "demo",
+///         path: "src/main.rs",
+///     }],
+/// };
+///
+/// let mut toml = String::new();
+/// let mut serializer = toml::Serializer::new(&mut toml);
+/// serializer
+///     .pretty_array(true)
+///     .pretty_array_indent(2)
+///     .inline_array_of_tables(true);
+/// serde::Serialize::serialize(&value, &mut serializer).unwrap();
+///
+/// assert_eq!(
+///     toml,
+///     r#"authors = [
+///   "Alice",
+///   "Bob",
+///   "Carol",
+/// ]
+/// bin = [{ name = "demo", path = "src/main.rs" }]
+/// "#
+/// );
+/// ```
 pub struct Serializer<'a> {
     dst: &'a mut String,
     state: State<'a>,
@@ -237,6 +302,11 @@ pub enum SerializeTable<'a, 'b> {
         first: Cell<bool>,
         table_emitted: Cell<bool>,
     },
+    Sorted {
+        ser: &'b mut Serializer<'a>,
+        key: String,
+        entries: Vec<(String, crate::Value)>,
+    },
 }
 
 impl<'a> Serializer<'a> {
@@ -267,6 +337,8 @@ impl<'a> Serializer<'a> {
             settings: Rc::new(Settings {
                 array: Some(ArraySettings::pretty()),
                 string: Some(StringSettings::pretty()),
+                inline_array_of_tables: false,
+                key_order: None,
             }),
         }
     }
@@ -419,6 +491,103 @@ impl<'a> Serializer<'a> {
         self
     }
 
+    /// Enable or Disable inline tables within arrays
+    ///
+    /// If enabled, tables within arrays, and any tables nested in them, are
+    /// written as inline tables of the array rather than as an array of tables.
+    ///
+    /// # Examples
+    ///
+    /// Instead of:
+    ///
+    /// ```toml,ignore
+    /// [[bin]]
+    /// name = "demo"
+    /// path = "src/main.rs"
+    /// ```
+    ///
+    /// You will have:
+    ///
+    /// ```toml,ignore
+    /// bin = [{ name = "demo", path = "src/main.rs" }]
+    /// ```
+    pub fn inline_array_of_tables(&mut self, value: bool) -> &mut Self {
+        Rc::get_mut(&mut self.settings)
+            .unwrap()
+            .inline_array_of_tables = value;
+        self
+    }
+
+    /// Enable or Disable sorting the keys of each table
+    ///
+    /// If enabled, keys are written in alphabetical order rather than in the
+    /// order of the struct's fields or the map's iteration, so the output does
+    /// not depend on the kind of map it was serialized from.  See
+    /// `Serializer::sort_keys_by` to order keys another way.
+    ///
+    /// Values are still written before tables, as TOML requires, so tables
+    /// don't have to be moved last by hand, as with `tables_last`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// let mut value = HashMap::new();
+    /// value.insert("zebra", 1);
+    /// value.insert("apple", 2);
+    /// value.insert("mango", 3);
+    ///
+    /// let mut toml = String::new();
+    /// let mut serializer = toml::Serializer::new(&mut toml);
+    /// serializer.sort_keys(true);
+    /// serde::Serialize::serialize(&value, &mut serializer).unwrap();
+    /// assert_eq!(toml, "apple = 2\nmango = 3\nzebra = 1\n");
+    /// ```
+    pub fn sort_keys(&mut self, value: bool) -> &mut Self {
+        if value {
+            self.sort_keys_by(|a, b| a.cmp(b))
+        } else {
+            Rc::get_mut(&mut self.settings).unwrap().key_order = None;
+            self
+        }
+    }
+
+    /// Sort the keys of each table with `compare`
+    ///
+    /// Keys that `compare` finds equal stay in the order they were serialized.
+    /// `compare` may capture state, like a list of keys to write first.
+    /// See `Serializer::sort_keys` for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut value = BTreeMap::new();
+    /// value.insert("authors", 1);
+    /// value.insert("version", 2);
+    /// value.insert("name", 3);
+    /// value.insert("edition", 4);
+    ///
+    /// // Keys in `first` come first, in its order, followed by the rest alphabetically
+    /// let first = vec!["name", "version"];
+    /// let priority = move |key: &str| first.iter().position(|k| *k == key).unwrap_or(first.len());
+    ///
+    /// let mut toml = String::new();
+    /// let mut serializer = toml::Serializer::new(&mut toml);
+    /// serializer.sort_keys_by(move |a, b| priority(a).cmp(&priority(b)).then(a.cmp(b)));
+    /// serde::Serialize::serialize(&value, &mut serializer).unwrap();
+    /// assert_eq!(toml, "name = 3\nversion = 2\nauthors = 1\nedition = 4\n");
+    /// ```
+    pub fn sort_keys_by(
+        &mut self,
+        compare: impl Fn(&str, &str) -> Ordering + 'static,
+    ) -> &mut Self {
+        Rc::get_mut(&mut self.settings).unwrap().key_order = Some(KeyOrder(Rc::new(compare)));
+        self
+    }
+
     fn display<T: fmt::Display>(&mut self, t: T, type_: ArrayState) -> Result<(), Error> {
         self.emit_key(type_)?;
         write!(self.dst, "{}", t).map_err(ser::Error::custom)?;
@@ -658,6 +827,53 @@ impl<'a> Serializer<'a> {
         Ok(())
     }
 
+    fn emit_inline_table(
+        &mut self,
+        table: &crate::map::Map<String, crate::Value>,
+    ) -> Result<(), Error> {
+        if table.is_empty() {
+            self.dst.push_str("{}");
+            return Ok(());
+        }
+        self.dst.push_str("{ ");
+        for (i, (key, value)) in table.iter().enumerate() {
+            if i != 0 {
+                self.dst.push_str(", ");
+            }
+            self.escape_key(key)?;
+            self.dst.push_str(" = ");
+            self.emit_inline_value(value)?;
+        }
+        self.dst.push_str(" }");
+        Ok(())
+    }
+
+    // Arrays within inline tables are always written on one line
+    fn emit_inline_value(&mut self, value: &crate::Value) -> Result<(), Error> {
+        match value {
+            crate::Value::Table(table) => self.emit_inline_table(table),
+            crate::Value::Array(array) => {
+                self.dst.push('[');
+                for (i, value) in array.iter().enumerate() {
+                    if i != 0 {
+                        self.dst.push_str(", ");
+                    }
+                    self.emit_inline_value(value)?;
+                }
+                self.dst.push(']');
+                Ok(())
+            }
+            value => ser::Serialize::serialize(
+                value,
+                &mut Serializer {
+                    dst: &mut *self.dst,
+                    state: State::End,
+                    settings: self.settings.clone(),
+                },
+            ),
+        }
+    }
+
     fn emit_table_header(&mut self, state: &State<'_>) -> Result<(), Error> {
         let array_of_tables = match *state {
             State::End => return Ok(()),
@@ -929,12 +1145,7 @@ impl<'a, 'b> ser::Serializer for &'b mut Serializer<'a> {
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         self.array_type(ArrayState::StartedAsATable)?;
-        Ok(SerializeTable::Table {
-            ser: self,
-            key: String::new(),
-            first: Cell::new(true),
-            table_emitted: Cell::new(false),
-        })
+        Ok(SerializeTable::new(self))
     }
 
     fn serialize_struct(
@@ -947,12 +1158,7 @@ impl<'a, 'b> ser::Serializer for &'b mut Serializer<'a> {
             Ok(SerializeTable::Datetime(self))
         } else {
             self.array_type(ArrayState::StartedAsATable)?;
-            Ok(SerializeTable::Table {
-                ser: self,
-                key: String::new(),
-                first: Cell::new(true),
-                table_emitted: Cell::new(false),
-            })
+            Ok(SerializeTable::new(self))
         }
     }
 
@@ -975,7 +1181,7 @@ impl<'a, 'b> ser::SerializeSeq for SerializeSeq<'a, 'b> {
     where
         T: ser::Serialize,
     {
-        value.serialize(&mut Serializer {
+        let mut ser = Serializer {
             dst: &mut *self.ser.dst,
             state: State::Array {
                 parent: &self.ser.state,
@@ -984,7 +1190,18 @@ impl<'a, 'b> ser::SerializeSeq for SerializeSeq<'a, 'b> {
                 len: self.len,
             },
             settings: self.ser.settings.clone(),
-        })?;
+        };
+        if ser.settings.inline_array_of_tables {
+            match crate::Value::try_from(value)? {
+                crate::Value::Table(table) => {
+                    ser.emit_key(ArrayState::Started)?;
+                    ser.emit_inline_table(&table)?;
+                }
+                value => ser::Serialize::serialize(&value, &mut ser)?,
+            }
+        } else {
+            value.serialize(&mut ser)?;
+        }
         self.first.set(false);
         Ok(())
     }
@@ -1064,6 +1281,75 @@ impl<'a, 'b> ser::SerializeTupleStruct for SerializeSeq<'a, 'b> {
     }
 }
 
+impl<'a, 'b> SerializeTable<'a, 'b> {
+    fn new(ser: &'b mut Serializer<'a>) -> Self {
+        if ser.settings.key_order.is_some() {
+            SerializeTable::Sorted {
+                ser,
+                key: String::new(),
+                entries: Vec::new(),
+            }
+        } else {
+            SerializeTable::Table {
+                ser,
+                key: String::new(),
+                first: Cell::new(true),
+                table_emitted: Cell::new(false),
+            }
+        }
+    }
+
+    // Entries are held until the end of a sorted table, then written in order
+    fn push_sorted<T>(
+        entries: &mut Vec<(String, crate::Value)>,
+        key: &str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ser::Serialize + ?Sized,
+    {
+        match crate::Value::try_from(value) {
+            Ok(value) => entries.push((key.to_owned(), value)),
+            Err(Error::UnsupportedNone) => {}
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    fn end_sorted(
+        ser: &'b mut Serializer<'a>,
+        mut entries: Vec<(String, crate::Value)>,
+    ) -> Result<(), Error> {
+        // Values must still come before arrays of tables, and those before tables
+        fn rank(value: &crate::Value) -> u8 {
+            match value {
+                crate::Value::Table(_) => 2,
+                crate::Value::Array(a) if a.iter().any(|v| v.is_table()) => 1,
+                _ => 0,
+            }
+        }
+        let KeyOrder(compare) = ser
+            .settings
+            .key_order
+            .clone()
+            .expect("sorted tables have a key order");
+        entries.sort_by(|(a_key, a), (b_key, b)| {
+            rank(a).cmp(&rank(b)).then_with(|| compare(a_key, b_key))
+        });
+
+        let mut table = SerializeTable::Table {
+            ser,
+            key: String::new(),
+            first: Cell::new(true),
+            table_emitted: Cell::new(false),
+        };
+        for (key, value) in &entries {
+            ser::SerializeMap::serialize_entry(&mut table, key, value)?;
+        }
+        ser::SerializeMap::end(table)
+    }
+}
+
 impl<'a, 'b> ser::SerializeMap for SerializeTable<'a, 'b> {
     type Ok = ();
     type Error = Error;
@@ -1074,7 +1360,8 @@ impl<'a, 'b> ser::SerializeMap for SerializeTable<'a, 'b> {
     {
         match *self {
             SerializeTable::Datetime(_) => panic!(), // shouldn't be possible
-            SerializeTable::Table { ref mut key, .. } => {
+            SerializeTable::Table { ref mut key, .. }
+            | SerializeTable::Sorted { ref mut key, .. } => {
                 key.truncate(0);
                 *key = input.serialize(StringExtractor)?;
             }
@@ -1111,6 +1398,11 @@ impl<'a, 'b> ser::SerializeMap for SerializeTable<'a, 'b> {
                     Err(e) => return Err(e),
                 }
             }
+            SerializeTable::Sorted {
+                ref key,
+                ref mut entries,
+                ..
+            } => SerializeTable::push_sorted(entries, key, value)?,
         }
         Ok(())
     }
@@ -1124,6 +1416,9 @@ impl<'a, 'b> ser::SerializeMap for SerializeTable<'a, 'b> {
                     ser.emit_table_header(&state)?;
                 }
             }
+            SerializeTable::Sorted { ser, entries, .. } => {
+                SerializeTable::end_sorted(ser, entries)?
+            }
         }
         Ok(())
     }
@@ -1167,6 +1462,9 @@ impl<'a, 'b> ser::SerializeStruct for SerializeTable<'a, 'b> {
                     Err(e) => return Err(e),
                 }
             }
+            SerializeTable::Sorted {
+                ref mut entries, ..
+            } => SerializeTable::push_sorted(entries, key, value)?,
         }
         Ok(())
     }
@@ -1180,6 +1478,9 @@ impl<'a, 'b> ser::SerializeStruct for SerializeTable<'a, 'b> {
                     ser.emit_table_header(&state)?;
                 }
             }
+            SerializeTable::Sorted { ser, entries, .. } => {
+                SerializeTable::end_sorted(ser, entries)?
+            }
         }
         Ok(())
     }
@@ -97,6 +97,85 @@ impl Value {
         index.index_mut(self)
     }
 
+    /// Looks up a value by a JSON Pointer, like `/package/authors/0`.
+    ///
+    /// As with [`serde_json::Value::pointer`], the pointer is a list of keys,
+    /// each after a `/`, where `~1` stands for a `/` within a key and `~0` for
+    /// a `~`.  Keys are looked up in tables, and parsed as indices into arrays.
+    /// An empty pointer refers to `self`.
+    ///
+    /// Returns `None` if a key is missing, if an index is out of bounds or has
+    /// leading zeros, or if the pointer doesn't start with `/`.
+    ///
+    /// [`serde_json::Value::pointer`]: https://docs.rs/serde_json/1/serde_json/enum.Value.html#method.pointer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let value: toml::Value = toml::from_str(r#"
+    /// [package]
+    /// authors = ["Alice", "Bob"]
+    ///
+    /// [dependencies]
+    /// "a/b" = "1.0"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(value.pointer("/package/authors/1").unwrap().as_str(), Some("Bob"));
+    /// assert_eq!(value.pointer("/dependencies/a~1b").unwrap().as_str(), Some("1.0"));
+    /// assert_eq!(value.pointer("/package/name"), None);
+    /// assert_eq!(value.pointer(""), Some(&value));
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match target {
+                Value::Table(table) => table.get(&token),
+                Value::Array(array) => parse_pointer_index(&token).and_then(|i| array.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Looks up a value by a JSON Pointer, like `/package/authors/0`, and
+    /// returns a mutable reference to it.
+    ///
+    /// See [`Value::pointer`] for the format of the pointer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut value: toml::Value = toml::from_str("[server]\nports = [80, 443]\n").unwrap();
+    ///
+    /// *value.pointer_mut("/server/ports/0").unwrap() = toml::Value::Integer(8080);
+    /// assert_eq!(value["server"]["ports"][0].as_integer(), Some(8080));
+    /// ```
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match target {
+                Value::Table(table) => table.get_mut(&token),
+                Value::Array(array) => {
+                    parse_pointer_index(&token).and_then(move |i| array.get_mut(i))
+                }
+                _ => None,
+            })
+    }
+
     /// Extracts the integer value if it is an integer.
     pub fn as_integer(&self) -> Option<i64> {
         match *self {
@@ -230,6 +309,18 @@ impl Value {
     }
 }
 
+fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+// Indices are plain decimal numbers, without signs or leading zeros
+fn parse_pointer_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() != 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
 impl<I> ops::Index<I> for Value
 where
     I: Index,
@@ -423,6 +514,7 @@ impl ser::Serialize for Value {
                         map.serialize_entry(k, v)?;
                     }
                 }
+                #[cfg(not(feature = "preserve_order"))]
                 for (k, v) in t {
                     if v.as_array()
                         .map(|a| a.iter().any(|v| v.is_table()))
@@ -431,11 +523,24 @@ impl ser::Serialize for Value {
                         map.serialize_entry(k, v)?;
                     }
                 }
+                #[cfg(not(feature = "preserve_order"))]
                 for (k, v) in t {
                     if v.is_table() {
                         map.serialize_entry(k, v)?;
                     }
                 }
+                // Tables and arrays of tables are both written under headers,
+                // so they can be mixed to keep the order of the map.
+                #[cfg(feature = "preserve_order")]
+                for (k, v) in t {
+                    if v.is_table()
+                        || v.as_array()
+                            .map(|a| a.iter().any(|v| v.is_table()))
+                            .unwrap_or(false)
+                    {
+                        map.serialize_entry(k, v)?;
+                    }
+                }
                 map.end()
             }
         }
@@ -0,0 +1,83 @@
+#![cfg(feature = "toml_edit")]
+
+use std::convert::TryInto;
+
+use toml::Value;
+
+#[test]
+fn roundtrip_through_document() {
+    let input = r#"
+# Package metadata
+[package]
+name = "foo"  # the name
+version = "0.1.0"
+authors = ["a", "b"]
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+
+[[bin]]
+name = "foo"
+
+[[bin]]
+name = "bar"
+when = 1979-05-27T07:32:00Z
+"#;
+    let doc = input.parse::<toml_edit::Document>().unwrap();
+    let value: Value = doc.as_item().try_into().unwrap();
+    assert_eq!(value, input.parse::<Value>().unwrap());
+
+    let item = toml_edit::Item::from(value.clone());
+    let roundtrip: Value = (&item).try_into().unwrap();
+    assert_eq!(roundtrip, value);
+}
+
+#[test]
+fn into_item_uses_headers() {
+    let value = toml::toml! {
+        top = 1
+        [a]
+        b = [1, 2]
+        c = { d = true }
+        [[e]]
+        f = 1.5
+    };
+    let doc = toml_edit::Document::from(toml_edit::Item::from(value).into_table().unwrap());
+    assert_eq!(
+        doc.to_string(),
+        r#"top = 1
+
+[a]
+b = [1, 2]
+
+[a.c]
+d = true
+
+[[e]]
+f = 1.5
+"#
+    );
+}
+
+#[test]
+fn empty_array_stays_inline() {
+    let item = toml_edit::Item::from(Value::Array(Vec::new()));
+    assert!(item.is_array());
+}
+
+#[test]
+fn none_item_is_an_error() {
+    let result: Result<Value, _> = (&toml_edit::Item::None).try_into();
+    assert!(result.is_err());
+}
+
+#[test]
+fn wide_integer_is_an_error() {
+    let options = toml_edit::ParseOptions::new().extended_integers(true);
+    let doc = toml_edit::Document::parse_with("a = [18446744073709551615]\n", options).unwrap();
+    let result: Result<Value, _> = doc.as_item().try_into();
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "integer `18446744073709551615` is out of range for `i64`"
+    );
+}
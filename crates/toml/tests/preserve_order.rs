@@ -0,0 +1,45 @@
+#![cfg(feature = "preserve_order")]
+
+use toml::Value;
+
+const INPUT: &str = "\
+zeta = 1
+alpha = [2, 1]
+
+[second]
+b = 1
+a = 2
+
+[[many]]
+q = 1
+p = 2
+
+[first]
+y = \"y\"
+x = \"x\"
+";
+
+#[test]
+fn roundtrip_keeps_order() {
+    let value: Value = toml::from_str(INPUT).unwrap();
+    let keys = value.as_table().unwrap().keys().collect::<Vec<_>>();
+    assert_eq!(keys, ["zeta", "alpha", "second", "many", "first"]);
+
+    let result = toml::to_string(&value).unwrap();
+    assert_eq!(INPUT, result);
+}
+
+#[test]
+fn remove_keeps_order() {
+    let mut value: Value = toml::from_str(INPUT).unwrap();
+    let table = value.as_table_mut().unwrap();
+    table.remove("alpha");
+    match table.entry("second") {
+        toml::map::Entry::Occupied(entry) => {
+            entry.remove();
+        }
+        toml::map::Entry::Vacant(_) => unreachable!(),
+    }
+    let keys = table.keys().collect::<Vec<_>>();
+    assert_eq!(keys, ["zeta", "many", "first"]);
+}
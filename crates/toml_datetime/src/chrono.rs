@@ -0,0 +1,231 @@
+//! Conversions between `Datetime` and `chrono`'s date and time types
+//!
+//! Each `chrono` type matches one kind of TOML datetime, and converting a `Datetime` of another
+//! kind fails rather than dropping or making up parts of it.
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+};
+
+use crate::{Date, Datetime, DatetimeConversionError, Offset, Time};
+
+/// An offset date-time, like `1979-05-27T00:32:00-07:00`
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{DateTime, FixedOffset};
+/// use toml_datetime::Datetime;
+///
+/// let toml = "1979-05-27T00:32:00.999-07:00".parse::<Datetime>().unwrap();
+/// let chrono = DateTime::<FixedOffset>::try_from(toml).unwrap();
+/// assert_eq!(chrono.offset().local_minus_utc(), -7 * 60 * 60);
+/// assert_eq!(chrono.timestamp_millis(), 296_638_320_999);
+/// assert_eq!(Datetime::try_from(chrono).unwrap(), toml);
+///
+/// let local = "1979-05-27T00:32:00".parse::<Datetime>().unwrap();
+/// assert!(DateTime::<FixedOffset>::try_from(local).is_err());
+/// ```
+impl TryFrom<Datetime> for DateTime<FixedOffset> {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: Some(offset),
+            } => {
+                let datetime = NaiveDateTime::new(from_date(date)?, from_time(time)?);
+                from_offset(offset)?
+                    .from_local_datetime(&datetime)
+                    .single()
+                    .ok_or(DatetimeConversionError {
+                        reason: "invalid offset date-time",
+                    })
+            }
+            _ => Err(DatetimeConversionError {
+                reason: "expected an offset date-time",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`, and the offset a whole number of minutes
+impl TryFrom<DateTime<FixedOffset>> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: DateTime<FixedOffset>) -> Result<Self, Self::Error> {
+        let datetime = value.naive_local();
+        Ok(Datetime {
+            date: Some(to_date(datetime.date())?),
+            time: Some(to_time(datetime.time())),
+            offset: Some(to_offset(*value.offset())?),
+        })
+    }
+}
+
+/// A local date-time, like `1979-05-27T07:32:00`
+impl TryFrom<Datetime> for NaiveDateTime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: None,
+            } => Ok(NaiveDateTime::new(from_date(date)?, from_time(time)?)),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local date-time",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`
+impl TryFrom<NaiveDateTime> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: NaiveDateTime) -> Result<Self, Self::Error> {
+        Ok(Datetime {
+            date: Some(to_date(value.date())?),
+            time: Some(to_time(value.time())),
+            offset: None,
+        })
+    }
+}
+
+/// A local date, like `1979-05-27`
+impl TryFrom<Datetime> for NaiveDate {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: None,
+                offset: None,
+            } => from_date(date),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local date",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`
+impl TryFrom<NaiveDate> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: NaiveDate) -> Result<Self, Self::Error> {
+        to_date(value).map(Datetime::from)
+    }
+}
+
+/// A local time, like `07:32:00`
+///
+/// A leap second, a [`Time`] with a `second` of `60`, is `chrono`'s `59` with one billion added
+/// to its nanoseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{NaiveTime, Timelike};
+/// use toml_datetime::Datetime;
+///
+/// let toml = "07:32:00.5".parse::<Datetime>().unwrap();
+/// let chrono = NaiveTime::try_from(toml).unwrap();
+/// assert_eq!(chrono.nanosecond(), 500_000_000);
+/// assert_eq!(Datetime::from(chrono), toml);
+///
+/// let date = "1979-05-27".parse::<Datetime>().unwrap();
+/// assert_eq!(
+///     NaiveTime::try_from(date).unwrap_err().to_string(),
+///     "failed to convert datetime: expected a local time"
+/// );
+/// ```
+impl TryFrom<Datetime> for NaiveTime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: None,
+                time: Some(time),
+                offset: None,
+            } => from_time(time),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local time",
+            }),
+        }
+    }
+}
+
+impl From<NaiveTime> for Datetime {
+    fn from(value: NaiveTime) -> Self {
+        to_time(value).into()
+    }
+}
+
+fn from_date(date: Date) -> Result<NaiveDate, DatetimeConversionError> {
+    NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into()).ok_or(
+        DatetimeConversionError {
+            reason: "invalid date",
+        },
+    )
+}
+
+fn to_date(date: NaiveDate) -> Result<Date, DatetimeConversionError> {
+    let year = u16::try_from(date.year())
+        .ok()
+        .filter(|year| *year <= 9999)
+        .ok_or(DatetimeConversionError {
+            reason: "year out of range, TOML years have four digits",
+        })?;
+    Ok(Date {
+        year,
+        month: date.month() as u8,
+        day: date.day() as u8,
+    })
+}
+
+fn from_time(time: Time) -> Result<NaiveTime, DatetimeConversionError> {
+    const INVALID: DatetimeConversionError = DatetimeConversionError {
+        reason: "invalid time",
+    };
+    if 1_000_000_000 <= time.nanosecond {
+        return Err(INVALID);
+    }
+    let (second, nanosecond) = if time.second == 60 {
+        (59, time.nanosecond + 1_000_000_000)
+    } else {
+        (time.second.into(), time.nanosecond)
+    };
+    NaiveTime::from_hms_nano_opt(time.hour.into(), time.minute.into(), second, nanosecond)
+        .ok_or(INVALID)
+}
+
+fn to_time(time: NaiveTime) -> Time {
+    let (second, nanosecond) = if 1_000_000_000 <= time.nanosecond() {
+        (time.second() + 1, time.nanosecond() - 1_000_000_000)
+    } else {
+        (time.second(), time.nanosecond())
+    };
+    Time {
+        hour: time.hour() as u8,
+        minute: time.minute() as u8,
+        second: second as u8,
+        nanosecond,
+    }
+}
+
+fn from_offset(offset: Offset) -> Result<FixedOffset, DatetimeConversionError> {
+    FixedOffset::east_opt(offset.seconds()).ok_or(DatetimeConversionError {
+        reason: "invalid offset",
+    })
+}
+
+fn to_offset(offset: FixedOffset) -> Result<Offset, DatetimeConversionError> {
+    Offset::from_seconds(offset.local_minus_utc())
+}
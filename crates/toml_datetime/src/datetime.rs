@@ -1,6 +1,6 @@
-use std::error;
-use std::fmt;
-use std::str::{self, FromStr};
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::{self, FromStr};
 
 #[cfg(feature = "serde")]
 use serde::{de, ser};
@@ -77,7 +77,10 @@ use serde::{de, ser};
 /// [Local Date-Time]: https://toml.io/en/v1.0.0#local-date-time
 /// [Local Date]: https://toml.io/en/v1.0.0#local-date
 /// [Local Time]: https://toml.io/en/v1.0.0#local-time
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+///
+/// Datetimes of the same kind are ordered chronologically, see the [`Ord`
+/// implementation](#impl-Ord-for-Datetime).
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub struct Datetime {
     /// Optional date.
     /// Required for: *Offset Date-Time*, *Local Date-Time*, *Local Date*.
@@ -93,9 +96,36 @@ pub struct Datetime {
 }
 
 /// Error returned from parsing a `Datetime` in the `FromStr` implementation.
+///
+/// Its message names the part of the datetime that was wrong, like a month out of range or a
+/// malformed offset.
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_datetime::Datetime;
+///
+/// let err = "1979-13-27".parse::<Datetime>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "failed to parse datetime: month out of range, expected `01` to `12`"
+/// );
+/// let err = "1979-05-27T07:32:00+7:00".parse::<Datetime>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "failed to parse datetime: expected `+hh:mm` or `-hh:mm` for the offset"
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct DatetimeParseError {
-    _private: (),
+    reason: &'static str,
+}
+
+/// Error returned when a `Datetime` can't be converted to or from another date or time type,
+/// like a `Datetime` of the wrong kind or a year outside of `0000` to `9999`.
+#[derive(Debug, Clone)]
+pub struct DatetimeConversionError {
+    pub(crate) reason: &'static str,
 }
 
 // Currently serde itself doesn't have a datetime type, so we map our `Datetime`
@@ -188,6 +218,292 @@ pub enum Offset {
     },
 }
 
+impl Datetime {
+    /// Convert an offset date-time to the same instant at another offset
+    ///
+    /// The date and time are recomputed for `offset`, so offset date-times written at different
+    /// offsets can be compared field by field once they share one.  A leap second stays the
+    /// 60th second of its minute.
+    ///
+    /// Local date-times, dates, and times aren't instants in time, so they can't be converted,
+    /// and neither can instants whose year at `offset` is outside of `0000` to `9999`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_datetime::{Datetime, Offset};
+    ///
+    /// let datetime = "1979-05-27T00:32:00.999-07:00".parse::<Datetime>().unwrap();
+    /// let tokyo = datetime.to_offset(Offset::Custom { hours: 9, minutes: 0 }).unwrap();
+    /// assert_eq!(tokyo.to_string(), "1979-05-27T16:32:00.999+09:00");
+    ///
+    /// let late = "1979-12-31T23:30:00Z".parse::<Datetime>().unwrap();
+    /// let paris = late.to_offset(Offset::Custom { hours: 1, minutes: 0 }).unwrap();
+    /// assert_eq!(paris.to_string(), "1980-01-01T00:30:00+01:00");
+    ///
+    /// let local = "1979-05-27T00:32:00".parse::<Datetime>().unwrap();
+    /// assert!(local.to_offset(Offset::Z).is_err());
+    /// ```
+    pub fn to_offset(self, offset: Offset) -> Result<Datetime, DatetimeConversionError> {
+        let (date, time, from) = self.offset_parts()?;
+        if 24 * 60 * 60 <= offset.seconds().unsigned_abs() {
+            return Err(DatetimeConversionError {
+                reason: "invalid offset",
+            });
+        }
+        // Shift a leap second's `59`, to keep it within its minute
+        let second = time.second.min(59);
+        let seconds =
+            unix_seconds(date, Time { second, ..time }, from) + i64::from(offset.seconds());
+        let (date, shifted) = from_unix_seconds(seconds, time.nanosecond)?;
+        Ok(Datetime {
+            date: Some(date),
+            time: Some(Time {
+                second: time.second,
+                ..shifted
+            }),
+            offset: Some(offset),
+        })
+    }
+
+    /// Convert an offset date-time to the same instant in UTC, see [`Datetime::to_offset`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_datetime::Datetime;
+    ///
+    /// let datetime = "1979-05-27T00:32:00.999-07:00".parse::<Datetime>().unwrap();
+    /// assert_eq!(datetime.to_utc().unwrap().to_string(), "1979-05-27T07:32:00.999Z");
+    /// ```
+    pub fn to_utc(self) -> Result<Datetime, DatetimeConversionError> {
+        self.to_offset(Offset::Z)
+    }
+
+    // The parts of an offset date-time, whose fields are checked since they may have been set
+    // directly
+    pub(crate) fn offset_parts(self) -> Result<(Date, Time, Offset), DatetimeConversionError> {
+        let (date, time, offset) = match self {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: Some(offset),
+            } => (date, time, offset),
+            _ => {
+                return Err(DatetimeConversionError {
+                    reason: "expected an offset date-time",
+                })
+            }
+        };
+        if !(1..=12).contains(&date.month)
+            || date.day == 0
+            || days_in_month(date.year, date.month) < date.day
+        {
+            return Err(DatetimeConversionError {
+                reason: "invalid date",
+            });
+        }
+        if 23 < time.hour || 59 < time.minute || 60 < time.second || 999_999_999 < time.nanosecond {
+            return Err(DatetimeConversionError {
+                reason: "invalid time",
+            });
+        }
+        match offset {
+            Offset::Custom { hours, minutes } if 23 < hours.unsigned_abs() || 59 < minutes => {
+                Err(DatetimeConversionError {
+                    reason: "invalid offset",
+                })
+            }
+            _ => Ok((date, time, offset)),
+        }
+    }
+}
+
+/// Datetimes of the same kind are ordered chronologically, and different kinds are ordered by
+/// kind: local times, then local dates, then local date-times, and last offset date-times.
+///
+/// Offset date-times are compared by the instant they represent, so `00:32:00-07:00` is after
+/// `07:00:00Z` on the same day.  The same instant written at different offsets is still not
+/// equal, and is ordered by offset with [`Offset::Z`] first.
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_datetime::Datetime;
+///
+/// let mut datetimes = [
+///     "1979-05-27T00:32:00-07:00",
+///     "1979-05-27T07:00:00Z",
+///     "1979-05-27",
+///     "1979-05-27T07:32:00Z",
+///     "07:32:00",
+///     "1979-05-27T07:32:00",
+/// ]
+/// .map(|s| s.parse::<Datetime>().unwrap());
+/// datetimes.sort();
+/// assert_eq!(
+///     datetimes.map(|d| d.to_string()),
+///     [
+///         "07:32:00",
+///         "1979-05-27",
+///         "1979-05-27T07:32:00",
+///         "1979-05-27T07:00:00Z",
+///         "1979-05-27T07:32:00Z",
+///         "1979-05-27T00:32:00-07:00",
+///     ]
+/// );
+/// ```
+impl Ord for Datetime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind_order()
+            .cmp(&other.kind_order())
+            .then_with(|| match (self, other) {
+                (
+                    Datetime {
+                        date: Some(date),
+                        time: Some(time),
+                        offset: Some(offset),
+                    },
+                    Datetime {
+                        date: Some(other_date),
+                        time: Some(other_time),
+                        offset: Some(other_offset),
+                    },
+                ) => unix_seconds(*date, *time, *offset)
+                    .cmp(&unix_seconds(*other_date, *other_time, *other_offset))
+                    .then_with(|| time.nanosecond.cmp(&other_time.nanosecond)),
+                _ => Ordering::Equal,
+            })
+            .then_with(|| self.offset.cmp(&other.offset))
+            // A leap second is the same unix second as the minute after it, but comes before it
+            .then_with(|| self.date.cmp(&other.date))
+            .then_with(|| self.time.cmp(&other.time))
+    }
+}
+
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Datetime {
+    fn kind_order(&self) -> u8 {
+        match (self.date, self.time, self.offset) {
+            (None, _, _) => 0,
+            (Some(_), None, _) => 1,
+            (Some(_), Some(_), None) => 2,
+            (Some(_), Some(_), Some(_)) => 3,
+        }
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// Seconds since 1970-01-01T00:00:00Z, without leap seconds, so a `second` of `60` is the same as
+// the first second of the next minute
+pub(crate) fn unix_seconds(date: Date, time: Time, offset: Offset) -> i64 {
+    let days = days_from_civil(date.year.into(), date.month, date.day);
+    days * SECONDS_PER_DAY
+        + i64::from(time.hour) * 3600
+        + i64::from(time.minute) * 60
+        + i64::from(time.second)
+        - i64::from(offset.seconds())
+}
+
+// The inverse of `unix_seconds` for an offset of `Z`
+pub(crate) fn from_unix_seconds(
+    seconds: i64,
+    nanosecond: u32,
+) -> Result<(Date, Time), DatetimeConversionError> {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+    let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    if !(0..=9999).contains(&year) {
+        return Err(DatetimeConversionError {
+            reason: "year out of range, TOML years have four digits",
+        });
+    }
+    let date = Date {
+        year: year as u16,
+        month,
+        day,
+    };
+    let time = Time {
+        hour: (second_of_day / 3600) as u8,
+        minute: (second_of_day / 60 % 60) as u8,
+        second: (second_of_day % 60) as u8,
+        nanosecond,
+    };
+    Ok((date, time))
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar, from
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if 2 < month { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The inverse of `days_from_civil`, from
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u8, day as u8)
+}
+
+impl Offset {
+    // Seconds east of UTC
+    pub(crate) fn seconds(self) -> i32 {
+        match self {
+            Offset::Z => 0,
+            Offset::Custom { hours, minutes } if hours < 0 => {
+                (i32::from(hours) * 60 - i32::from(minutes)) * 60
+            }
+            Offset::Custom { hours, minutes } => (i32::from(hours) * 60 + i32::from(minutes)) * 60,
+        }
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn from_seconds(seconds: i32) -> Result<Self, DatetimeConversionError> {
+        if seconds % 60 != 0 {
+            return Err(DatetimeConversionError {
+                reason: "offset has seconds, TOML offsets have minutes",
+            });
+        }
+        let minutes = seconds / 60;
+        match minutes {
+            0 => Ok(Offset::Z),
+            // `Offset::Custom` keeps the sign with the hours, so `-00:30` would become `+00:30`
+            -59..=-1 => Err(DatetimeConversionError {
+                reason: "offsets between -01:00 and 00:00 are not supported",
+            }),
+            _ => Ok(Offset::Custom {
+                hours: (minutes / 60) as i8,
+                minutes: (minutes % 60).unsigned_abs() as u8,
+            }),
+        }
+    }
+}
+
 impl From<Date> for Datetime {
     fn from(other: Date) -> Self {
         Datetime {
@@ -236,8 +552,14 @@ impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
         if self.nanosecond != 0 {
-            let s = format!("{:09}", self.nanosecond);
-            write!(f, ".{}", s.trim_end_matches('0'))?;
+            // Write the fraction without trailing zeros
+            let mut nanosecond = self.nanosecond;
+            let mut width = 9;
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", nanosecond, width = width)?;
         }
         Ok(())
     }
@@ -256,6 +578,9 @@ impl FromStr for Datetime {
     type Err = DatetimeParseError;
 
     fn from_str(date: &str) -> Result<Datetime, DatetimeParseError> {
+        const YEAR: &str = "expected a four-digit year";
+        const OFFSET: &str = "expected `+hh:mm` or `-hh:mm` for the offset";
+
         // Accepted formats:
         //
         // 0000-00-00T00:00:00.00Z
@@ -263,7 +588,9 @@ impl FromStr for Datetime {
         // 0000-00-00
         // 00:00:00.00
         if date.len() < 3 {
-            return Err(DatetimeParseError { _private: () });
+            return Err(DatetimeParseError {
+                reason: "expected a date or time",
+            });
         }
         let mut offset_allowed = true;
         let mut chars = date.chars();
@@ -273,26 +600,34 @@ impl FromStr for Datetime {
             offset_allowed = false;
             None
         } else {
-            let y1 = u16::from(digit(&mut chars)?);
-            let y2 = u16::from(digit(&mut chars)?);
-            let y3 = u16::from(digit(&mut chars)?);
-            let y4 = u16::from(digit(&mut chars)?);
+            let y1 = u16::from(digit(&mut chars, YEAR)?);
+            let y2 = u16::from(digit(&mut chars, YEAR)?);
+            let y3 = u16::from(digit(&mut chars, YEAR)?);
+            let y4 = u16::from(digit(&mut chars, YEAR)?);
 
             match chars.next() {
                 Some('-') => {}
-                _ => return Err(DatetimeParseError { _private: () }),
+                _ => {
+                    return Err(DatetimeParseError {
+                        reason: "expected `-` after the year",
+                    })
+                }
             }
 
-            let m1 = digit(&mut chars)?;
-            let m2 = digit(&mut chars)?;
+            let m1 = digit(&mut chars, "expected a two-digit month")?;
+            let m2 = digit(&mut chars, "expected a two-digit month")?;
 
             match chars.next() {
                 Some('-') => {}
-                _ => return Err(DatetimeParseError { _private: () }),
+                _ => {
+                    return Err(DatetimeParseError {
+                        reason: "expected `-` after the month",
+                    })
+                }
             }
 
-            let d1 = digit(&mut chars)?;
-            let d2 = digit(&mut chars)?;
+            let d1 = digit(&mut chars, "expected a two-digit day")?;
+            let d2 = digit(&mut chars, "expected a two-digit day")?;
 
             let date = Date {
                 year: y1 * 1000 + y2 * 100 + y3 * 10 + y4,
@@ -301,10 +636,14 @@ impl FromStr for Datetime {
             };
 
             if date.month < 1 || date.month > 12 {
-                return Err(DatetimeParseError { _private: () });
+                return Err(DatetimeParseError {
+                    reason: "month out of range, expected `01` to `12`",
+                });
             }
-            if date.day < 1 || date.day > 31 {
-                return Err(DatetimeParseError { _private: () });
+            if date.day < 1 || date.day > days_in_month(date.year, date.month) {
+                return Err(DatetimeParseError {
+                    reason: "day out of range for the month",
+                });
             }
 
             Some(date)
@@ -322,20 +661,28 @@ impl FromStr for Datetime {
         };
 
         let time = if partial_time {
-            let h1 = digit(&mut chars)?;
-            let h2 = digit(&mut chars)?;
+            let h1 = digit(&mut chars, "expected a two-digit hour")?;
+            let h2 = digit(&mut chars, "expected a two-digit hour")?;
             match chars.next() {
                 Some(':') => {}
-                _ => return Err(DatetimeParseError { _private: () }),
+                _ => {
+                    return Err(DatetimeParseError {
+                        reason: "expected `:` after the hour",
+                    })
+                }
             }
-            let m1 = digit(&mut chars)?;
-            let m2 = digit(&mut chars)?;
+            let m1 = digit(&mut chars, "expected a two-digit minute")?;
+            let m2 = digit(&mut chars, "expected a two-digit minute")?;
             match chars.next() {
                 Some(':') => {}
-                _ => return Err(DatetimeParseError { _private: () }),
+                _ => {
+                    return Err(DatetimeParseError {
+                        reason: "expected `:` after the minute",
+                    })
+                }
             }
-            let s1 = digit(&mut chars)?;
-            let s2 = digit(&mut chars)?;
+            let s1 = digit(&mut chars, "expected a two-digit second")?;
+            let s2 = digit(&mut chars, "expected a two-digit second")?;
 
             let mut nanosecond = 0;
             if chars.clone().next() == Some('.') {
@@ -358,7 +705,9 @@ impl FromStr for Datetime {
                     }
                 }
                 if end == 0 {
-                    return Err(DatetimeParseError { _private: () });
+                    return Err(DatetimeParseError {
+                        reason: "expected digits after `.`",
+                    });
                 }
                 chars = whole[end..].chars();
             }
@@ -370,17 +719,25 @@ impl FromStr for Datetime {
                 nanosecond,
             };
 
-            if time.hour > 24 {
-                return Err(DatetimeParseError { _private: () });
+            if time.hour > 23 {
+                return Err(DatetimeParseError {
+                    reason: "hour out of range, expected `00` to `23`",
+                });
             }
             if time.minute > 59 {
-                return Err(DatetimeParseError { _private: () });
+                return Err(DatetimeParseError {
+                    reason: "minute out of range, expected `00` to `59`",
+                });
             }
-            if time.second > 59 {
-                return Err(DatetimeParseError { _private: () });
+            if time.second > 60 {
+                return Err(DatetimeParseError {
+                    reason: "second out of range, expected `00` to `60`",
+                });
             }
             if time.nanosecond > 999_999_999 {
-                return Err(DatetimeParseError { _private: () });
+                return Err(DatetimeParseError {
+                    reason: "fraction of a second out of range",
+                });
             }
 
             Some(time)
@@ -401,21 +758,37 @@ impl FromStr for Datetime {
                 let sign = match next {
                     Some('+') => 1,
                     Some('-') => -1,
-                    _ => return Err(DatetimeParseError { _private: () }),
+                    _ => {
+                        return Err(DatetimeParseError {
+                            reason: "expected `Z`, `+`, or `-` for the offset",
+                        })
+                    }
                 };
                 chars.next();
-                let h1 = digit(&mut chars)? as i8;
-                let h2 = digit(&mut chars)? as i8;
+                let h1 = digit(&mut chars, OFFSET)? as i8;
+                let h2 = digit(&mut chars, OFFSET)? as i8;
                 match chars.next() {
                     Some(':') => {}
-                    _ => return Err(DatetimeParseError { _private: () }),
+                    _ => {
+                        return Err(DatetimeParseError {
+                            reason: "expected `:` in the offset",
+                        })
+                    }
+                }
+                let m1 = digit(&mut chars, OFFSET)?;
+                let m2 = digit(&mut chars, OFFSET)?;
+
+                let hours = h1 * 10 + h2;
+                let minutes = m1 * 10 + m2;
+                if hours > 23 || minutes > 59 {
+                    return Err(DatetimeParseError {
+                        reason: "offset out of range, expected less than a day",
+                    });
                 }
-                let m1 = digit(&mut chars)?;
-                let m2 = digit(&mut chars)?;
 
                 Some(Offset::Custom {
-                    hours: sign * (h1 * 10 + h2),
-                    minutes: m1 * 10 + m2,
+                    hours: sign * hours,
+                    minutes,
                 })
             }
         } else {
@@ -425,7 +798,9 @@ impl FromStr for Datetime {
         // Return an error if we didn't hit eof, otherwise return our parsed
         // date
         if chars.next().is_some() {
-            return Err(DatetimeParseError { _private: () });
+            return Err(DatetimeParseError {
+                reason: "unexpected characters after the datetime",
+            });
         }
 
         Ok(Datetime {
@@ -436,10 +811,80 @@ impl FromStr for Datetime {
     }
 }
 
-fn digit(chars: &mut str::Chars<'_>) -> Result<u8, DatetimeParseError> {
+/// The kind of a TOML datetime, see [`classify`]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum DatetimeKind {
+    /// A date, time, and offset, like `1979-05-27T07:32:00Z`
+    OffsetDateTime,
+    /// A date and time, like `1979-05-27T07:32:00`
+    LocalDateTime,
+    /// A date, like `1979-05-27`
+    LocalDate,
+    /// A time, like `07:32:00`
+    LocalTime,
+}
+
+/// Check whether `s` is a TOML datetime, and if so which kind
+///
+/// This accepts what parsing a [`Datetime`] does, enforcing the ranges of [RFC 3339] such as the
+/// number of days in each month and offsets of less than a day.  Like `toml_edit`'s parser, a
+/// leap second of `60` is accepted in any minute.
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_datetime::{classify, DatetimeKind};
+///
+/// assert_eq!(classify("1979-05-27T07:32:00Z"), Some(DatetimeKind::OffsetDateTime));
+/// assert_eq!(classify("1979-05-27 07:32:00"), Some(DatetimeKind::LocalDateTime));
+/// assert_eq!(classify("1979-05-27"), Some(DatetimeKind::LocalDate));
+/// assert_eq!(classify("07:32:00.999"), Some(DatetimeKind::LocalTime));
+/// assert_eq!(classify("1990-12-31T23:59:60Z"), Some(DatetimeKind::OffsetDateTime));
+/// assert_eq!(classify("1979-02-30"), None);
+/// assert_eq!(classify("24:00:00"), None);
+/// assert_eq!(classify("1979-05-27T07:32"), None);
+/// ```
+pub fn classify(s: &str) -> Option<DatetimeKind> {
+    let datetime = s.parse::<Datetime>().ok()?;
+    let kind = match (datetime.date, datetime.time, datetime.offset) {
+        (Some(_), Some(_), Some(_)) => DatetimeKind::OffsetDateTime,
+        (Some(_), Some(_), None) => DatetimeKind::LocalDateTime,
+        (Some(_), None, _) => DatetimeKind::LocalDate,
+        (None, _, _) => DatetimeKind::LocalTime,
+    };
+    Some(kind)
+}
+
+/// Check whether `s` is a TOML datetime, see [`classify`]
+///
+/// All four kinds of TOML datetime are accepted, including the local dates and times that
+/// RFC 3339 only defines as parts of a date-time.
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(toml_datetime::is_valid_rfc3339("2000-02-29T12:00:00+05:30"));
+/// assert!(!toml_datetime::is_valid_rfc3339("1900-02-29"));
+/// ```
+pub fn is_valid_rfc3339(s: &str) -> bool {
+    classify(s).is_some()
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn digit(chars: &mut str::Chars<'_>, reason: &'static str) -> Result<u8, DatetimeParseError> {
     match chars.next() {
         Some(c) if ('0'..='9').contains(&c) => Ok(c as u8 - b'0'),
-        _ => Err(DatetimeParseError { _private: () }),
+        _ => Err(DatetimeParseError { reason }),
     }
 }
 
@@ -449,6 +894,7 @@ impl ser::Serialize for Datetime {
     where
         S: ser::Serializer,
     {
+        use alloc::string::ToString;
         use serde::ser::SerializeStruct;
 
         let mut s = serializer.serialize_struct(NAME, 1)?;
@@ -563,8 +1009,18 @@ impl<'de> de::Deserialize<'de> for DatetimeFromString {
 
 impl fmt::Display for DatetimeParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        "failed to parse datetime".fmt(f)
+        write!(f, "failed to parse datetime: {}", self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DatetimeParseError {}
+
+impl fmt::Display for DatetimeConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to convert datetime: {}", self.reason)
     }
 }
 
-impl error::Error for DatetimeParseError {}
+#[cfg(feature = "std")]
+impl std::error::Error for DatetimeConversionError {}
@@ -1,7 +1,19 @@
 //! A [TOML]-compatible datetime type
 //!
 //! [TOML]: https://github.com/toml-lang/toml
+//!
+//! ## Feature flags
+//!
+//! * `std` (default): implement `std::error::Error`, and `TryFrom` between [`Datetime`] and
+//!   `SystemTime` for offset date-times.  Without it, this crate is `no_std`, and
+//!   only requires `alloc` with the `serde` feature.
+//! * `serde`: implement `Serialize` and `Deserialize` for [`Datetime`]
+//! * `chrono`: implement `TryFrom` between [`Datetime`] and `chrono`'s `DateTime<FixedOffset>`,
+//!   `NaiveDateTime`, `NaiveDate`, and `NaiveTime`, each matching one kind of TOML datetime
+//! * `time`: implement `TryFrom` between [`Datetime`] and `time`'s `OffsetDateTime`,
+//!   `PrimitiveDateTime`, `Date`, and `Time`, as with `chrono`
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![warn(rust_2018_idioms)]
 // Makes rustc abort compilation if there are any unsafe blocks in the crate.
@@ -10,10 +22,23 @@
 // something they couldn't detect (e.g. unsafe added via macro expansion, etc).
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "serde")]
+extern crate alloc;
+
+#[cfg(feature = "chrono")]
+mod chrono;
 mod datetime;
+#[cfg(feature = "std")]
+mod system_time;
+#[cfg(feature = "time")]
+mod time;
 
+pub use crate::datetime::classify;
+pub use crate::datetime::is_valid_rfc3339;
 pub use crate::datetime::Date;
 pub use crate::datetime::Datetime;
+pub use crate::datetime::DatetimeConversionError;
+pub use crate::datetime::DatetimeKind;
 pub use crate::datetime::DatetimeParseError;
 pub use crate::datetime::Offset;
 pub use crate::datetime::Time;
@@ -0,0 +1,76 @@
+//! Conversions between `Datetime` and `SystemTime`
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::datetime::{from_unix_seconds, unix_seconds};
+use crate::{Datetime, DatetimeConversionError, Offset};
+
+/// An offset date-time in UTC, like `1979-05-27T07:32:00Z`
+///
+/// The year must be within `0000` to `9999`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use toml_datetime::Datetime;
+///
+/// let time = UNIX_EPOCH + Duration::new(296_638_320, 999_000_000);
+/// let toml = Datetime::try_from(time).unwrap();
+/// assert_eq!(toml.to_string(), "1979-05-27T07:32:00.999Z");
+///
+/// let toml = "1979-05-27T00:32:00.999-07:00".parse::<Datetime>().unwrap();
+/// assert_eq!(SystemTime::try_from(toml).unwrap(), time);
+/// ```
+impl TryFrom<SystemTime> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: SystemTime) -> Result<Self, Self::Error> {
+        const OUT_OF_RANGE: DatetimeConversionError = DatetimeConversionError {
+            reason: "year out of range, TOML years have four digits",
+        };
+        let (seconds, nanosecond) = match value.duration_since(UNIX_EPOCH) {
+            Ok(after) => (
+                i64::try_from(after.as_secs()).map_err(|_| OUT_OF_RANGE)?,
+                after.subsec_nanos(),
+            ),
+            Err(before) => {
+                let before = before.duration();
+                let seconds = i64::try_from(before.as_secs()).map_err(|_| OUT_OF_RANGE)?;
+                match before.subsec_nanos() {
+                    0 => (-seconds, 0),
+                    nanos => (-seconds - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+
+        let (date, time) = from_unix_seconds(seconds, nanosecond)?;
+        Ok(Datetime {
+            date: Some(date),
+            time: Some(time),
+            offset: Some(Offset::Z),
+        })
+    }
+}
+
+/// An offset date-time, like `1979-05-27T00:32:00-07:00`
+///
+/// Local date-times, dates, and times aren't instants in time, so they can't be converted.
+impl TryFrom<Datetime> for SystemTime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        let (date, time, offset) = value.offset_parts()?;
+        let seconds = unix_seconds(date, time, offset);
+        let instant = if 0 <= seconds {
+            UNIX_EPOCH.checked_add(Duration::new(seconds as u64, time.nanosecond))
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(seconds.unsigned_abs()))
+                .and_then(|t| t.checked_add(Duration::from_nanos(time.nanosecond.into())))
+        };
+        instant.ok_or(DatetimeConversionError {
+            reason: "datetime out of range for `SystemTime`",
+        })
+    }
+}
@@ -0,0 +1,210 @@
+//! Conversions between `Datetime` and `time`'s date and time types
+//!
+//! Each `time` type matches one kind of TOML datetime, and converting a `Datetime` of another
+//! kind fails rather than dropping or making up parts of it.
+
+use time::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+use crate::{Date, Datetime, DatetimeConversionError, Offset, Time};
+
+/// An offset date-time, like `1979-05-27T00:32:00-07:00`
+///
+/// # Examples
+///
+/// ```rust
+/// use time::OffsetDateTime;
+/// use toml_datetime::Datetime;
+///
+/// let toml = "1979-05-27T00:32:00.999-07:00".parse::<Datetime>().unwrap();
+/// let time = OffsetDateTime::try_from(toml).unwrap();
+/// assert_eq!(time.offset().whole_hours(), -7);
+/// assert_eq!(time.unix_timestamp(), 296_638_320);
+/// assert_eq!(time.millisecond(), 999);
+/// assert_eq!(Datetime::try_from(time).unwrap(), toml);
+///
+/// let local = "1979-05-27T00:32:00".parse::<Datetime>().unwrap();
+/// assert!(OffsetDateTime::try_from(local).is_err());
+/// ```
+impl TryFrom<Datetime> for OffsetDateTime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: Some(offset),
+            } => {
+                let datetime = PrimitiveDateTime::new(from_date(date)?, from_time(time)?);
+                Ok(datetime.assume_offset(from_offset(offset)?))
+            }
+            _ => Err(DatetimeConversionError {
+                reason: "expected an offset date-time",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`, and the offset a whole number of minutes
+impl TryFrom<OffsetDateTime> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: OffsetDateTime) -> Result<Self, Self::Error> {
+        Ok(Datetime {
+            date: Some(to_date(value.date())?),
+            time: Some(to_time(value.time())),
+            offset: Some(Offset::from_seconds(value.offset().whole_seconds())?),
+        })
+    }
+}
+
+/// A local date-time, like `1979-05-27T07:32:00`
+impl TryFrom<Datetime> for PrimitiveDateTime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: None,
+            } => Ok(PrimitiveDateTime::new(from_date(date)?, from_time(time)?)),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local date-time",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`
+impl TryFrom<PrimitiveDateTime> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: PrimitiveDateTime) -> Result<Self, Self::Error> {
+        Ok(Datetime {
+            date: Some(to_date(value.date())?),
+            time: Some(to_time(value.time())),
+            offset: None,
+        })
+    }
+}
+
+/// A local date, like `1979-05-27`
+impl TryFrom<Datetime> for time::Date {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: Some(date),
+                time: None,
+                offset: None,
+            } => from_date(date),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local date",
+            }),
+        }
+    }
+}
+
+/// The year must be within `0000` to `9999`
+impl TryFrom<time::Date> for Datetime {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: time::Date) -> Result<Self, Self::Error> {
+        to_date(value).map(Datetime::from)
+    }
+}
+
+/// A local time, like `07:32:00`
+///
+/// `time` has no leap seconds, so a [`Time`] with a `second` of `60` can't be converted.
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_datetime::Datetime;
+///
+/// let toml = "07:32:00.5".parse::<Datetime>().unwrap();
+/// let time = time::Time::try_from(toml).unwrap();
+/// assert_eq!(time.millisecond(), 500);
+/// assert_eq!(Datetime::from(time), toml);
+///
+/// let date = "1979-05-27".parse::<Datetime>().unwrap();
+/// assert_eq!(
+///     time::Time::try_from(date).unwrap_err().to_string(),
+///     "failed to convert datetime: expected a local time"
+/// );
+/// ```
+impl TryFrom<Datetime> for time::Time {
+    type Error = DatetimeConversionError;
+
+    fn try_from(value: Datetime) -> Result<Self, Self::Error> {
+        match value {
+            Datetime {
+                date: None,
+                time: Some(time),
+                offset: None,
+            } => from_time(time),
+            _ => Err(DatetimeConversionError {
+                reason: "expected a local time",
+            }),
+        }
+    }
+}
+
+impl From<time::Time> for Datetime {
+    fn from(value: time::Time) -> Self {
+        to_time(value).into()
+    }
+}
+
+fn from_date(date: Date) -> Result<time::Date, DatetimeConversionError> {
+    const INVALID: DatetimeConversionError = DatetimeConversionError {
+        reason: "invalid date",
+    };
+    let month = Month::try_from(date.month).map_err(|_| INVALID)?;
+    time::Date::from_calendar_date(date.year.into(), month, date.day).map_err(|_| INVALID)
+}
+
+fn to_date(date: time::Date) -> Result<Date, DatetimeConversionError> {
+    let year = u16::try_from(date.year())
+        .ok()
+        .filter(|year| *year <= 9999)
+        .ok_or(DatetimeConversionError {
+            reason: "year out of range, TOML years have four digits",
+        })?;
+    Ok(Date {
+        year,
+        month: date.month().into(),
+        day: date.day(),
+    })
+}
+
+fn from_time(time: Time) -> Result<time::Time, DatetimeConversionError> {
+    if time.second == 60 {
+        return Err(DatetimeConversionError {
+            reason: "leap seconds are not supported by `time`",
+        });
+    }
+    time::Time::from_hms_nano(time.hour, time.minute, time.second, time.nanosecond).map_err(|_| {
+        DatetimeConversionError {
+            reason: "invalid time",
+        }
+    })
+}
+
+fn to_time(time: time::Time) -> Time {
+    Time {
+        hour: time.hour(),
+        minute: time.minute(),
+        second: time.second(),
+        nanosecond: time.nanosecond(),
+    }
+}
+
+fn from_offset(offset: Offset) -> Result<UtcOffset, DatetimeConversionError> {
+    UtcOffset::from_whole_seconds(offset.seconds()).map_err(|_| DatetimeConversionError {
+        reason: "invalid offset",
+    })
+}
@@ -1,9 +1,15 @@
-use std::iter::FromIterator;
-use std::mem;
-
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::mem;
+
+use crate::format::own_line_comments;
 use crate::repr::Decor;
 use crate::value::{DEFAULT_LEADING_VALUE_DECOR, DEFAULT_VALUE_DECOR};
-use crate::{InternalString, Item, Value};
+use crate::{ArrayOfTables, InternalString, Item, Value};
 
 /// Type representing a TOML array,
 /// payload of the `Value::Array` variant's value
@@ -19,6 +25,20 @@ pub struct Array {
     pub(crate) values: Vec<Item>,
 }
 
+/// The layout of an [`Array`], see [`Array::style`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ArrayStyle {
+    /// All values on one line, like `[1, 2]`
+    SingleLine,
+    /// Each value on its own line, like `[\n  1,\n  2,\n]`
+    Multiline {
+        /// The whitespace before each value
+        indent: String,
+        /// Whether the last value is followed by a comma
+        trailing_comma: bool,
+    },
+}
+
 /// An owned iterator type over `Table`'s key/value pairs.
 pub type ArrayIntoIter = Box<dyn Iterator<Item = Value>>;
 /// An iterator type over `Array`'s values.
@@ -47,6 +67,53 @@ impl Array {
             ..Default::default()
         }
     }
+
+    /// Convert to an array of tables, if every element is an inline table
+    ///
+    /// Inline tables become tables, see
+    /// [`InlineTable::into_table`][crate::InlineTable::into_table], with the comments around each
+    /// element moved before its header.  An empty array or one with other elements is returned
+    /// as-is.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "name = 'demo'\nbin = [\n  # the binary\n  { path = 'main.rs' },\n  { path = 'cli.rs' },\n]\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let bin = doc.remove("bin").unwrap().into_value().unwrap();
+    /// let bin = bin.as_array().unwrap().clone().into_array_of_tables().unwrap();
+    /// doc.insert("bin", toml_edit::Item::ArrayOfTables(bin));
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "name = 'demo'\n\n# the binary\n[[bin]]\npath = 'main.rs'\n\n[[bin]]\npath = 'cli.rs'\n"
+    /// );
+    /// ```
+    pub fn into_array_of_tables(self) -> Result<ArrayOfTables, Array> {
+        if self.is_empty() || !self.iter().all(Value::is_inline_table) {
+            return Err(self);
+        }
+        let tables = self
+            .values
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Value(Value::InlineTable(table)) => {
+                    let decor = table.decor();
+                    let comments = [decor.prefix(), decor.suffix()]
+                        .iter()
+                        .flat_map(|raw| own_line_comments(raw.unwrap_or_default()))
+                        .map(|comment| format!("{}\n", comment))
+                        .collect::<String>();
+                    let mut table = table.into_table();
+                    if !comments.is_empty() {
+                        table.decor_mut().set_prefix(format!("\n{}", comments));
+                    }
+                    Some(table)
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(tables)
+    }
 }
 
 /// Formatting
@@ -56,6 +123,72 @@ impl Array {
         decorate_array(self);
     }
 
+    /// Auto formats the array and everything nested within it.
+    pub fn fmt_recursive(&mut self) {
+        for value in self.values.iter_mut() {
+            value.fmt_recursive();
+        }
+        decorate_array(self);
+    }
+
+    /// Auto formats the array with each element on its own line, indented by `indent`, and the
+    /// closing bracket on a line of its own.
+    ///
+    /// Like [`Array::fmt`], this replaces the decor of the elements, including any comments.
+    /// Empty arrays are written as `[]`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "a = [1, 2]\n".parse::<toml_edit::Document>().unwrap();
+    /// let a = doc["a"].as_array_mut().unwrap();
+    /// a.fmt_multiline("  ", true);
+    /// assert_eq!(doc.to_string(), "a = [\n  1,\n  2,\n]\n");
+    /// ```
+    pub fn fmt_multiline(&mut self, indent: &str, trailing_comma: bool) {
+        decorate_multiline_array(self, indent, trailing_comma);
+    }
+
+    /// Detect the layout of the array, from the indentation of its values
+    ///
+    /// # Example
+    /// ```rust
+    /// use toml_edit::ArrayStyle;
+    ///
+    /// let mut doc = "a = [\n  1,\n  2,\n]\nb = [3, 4]\n".parse::<toml_edit::Document>().unwrap();
+    /// let style = doc["a"].as_array().unwrap().style();
+    /// assert_eq!(
+    ///     style,
+    ///     ArrayStyle::Multiline {
+    ///         indent: "  ".to_owned(),
+    ///         trailing_comma: true
+    ///     }
+    /// );
+    /// assert_eq!(doc["b"].as_array().unwrap().style(), ArrayStyle::SingleLine);
+    ///
+    /// doc["b"].as_array_mut().unwrap().apply_style(&style);
+    /// assert_eq!(doc.to_string(), "a = [\n  1,\n  2,\n]\nb = [\n  3,\n  4,\n]\n");
+    /// ```
+    pub fn style(&self) -> ArrayStyle {
+        match self.indent() {
+            Some(indent) => ArrayStyle::Multiline {
+                indent: indent.to_owned(),
+                trailing_comma: self.trailing_comma,
+            },
+            None => ArrayStyle::SingleLine,
+        }
+    }
+
+    /// Auto formats the array in `style`, see [`Array::fmt`] and [`Array::fmt_multiline`]
+    pub fn apply_style(&mut self, style: &ArrayStyle) {
+        match style {
+            ArrayStyle::SingleLine => decorate_array(self),
+            ArrayStyle::Multiline {
+                indent,
+                trailing_comma,
+            } => decorate_multiline_array(self, indent, *trailing_comma),
+        }
+    }
+
     /// Set whether the array will use a trailing comma
     pub fn set_trailing_comma(&mut self, yes: bool) {
         self.trailing_comma = yes;
@@ -219,6 +352,111 @@ impl Array {
         self.values.insert(index, Item::Value(v))
     }
 
+    /// Appends a new value to the end of the array, formatted like the values already in it.
+    ///
+    /// See [`Array::insert_matching`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a = [\n  1,\n  2, # two\n]\n".parse::<toml_edit::Document>().unwrap();
+    /// doc["a"].as_array_mut().unwrap().push_matching(3);
+    /// assert_eq!(doc.to_string(), "a = [\n  1,\n  2, # two\n  3,\n]\n");
+    /// ```
+    pub fn push_matching<V: Into<Value>>(&mut self, v: V) {
+        self.insert_matching(self.len(), v);
+    }
+
+    /// Inserts an element at the given position within the array, formatted like the values
+    /// already in it, and shifting all values after it to the right.
+    ///
+    /// In a multi-line array the new value gets a line of its own, indented like the others, and
+    /// a comment ending the line before it stays on that line.  In a single-line array it gets
+    /// the same spacing as the others.  An empty array is formatted as by [`Array::insert`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a = [ 'b', 'c' ]\n".parse::<toml_edit::Document>().unwrap();
+    /// let a = doc["a"].as_array_mut().unwrap();
+    /// a.insert_matching(0, "a");
+    /// a.push_matching("d");
+    /// assert_eq!(doc.to_string(), "a = [ \"a\", 'b', 'c', \"d\" ]\n");
+    /// ```
+    pub fn insert_matching<V: Into<Value>>(&mut self, index: usize, v: V) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "index {} out of bounds (len = {})",
+            index,
+            len
+        );
+        if len == 0 {
+            return self.insert(index, v);
+        }
+
+        let mut value = v.into();
+        let indent = self.indent().map(str::to_owned);
+        let mut line_end = String::new();
+        let mut suffix = String::new();
+        if index == len {
+            // Whitespace before the closing bracket moves after the new value
+            let last = self.value_mut(len - 1);
+            let old = last.decor().suffix().unwrap_or_default().to_owned();
+            last.decor_mut().set_suffix("");
+            match old.split_once('\n') {
+                Some((line, rest)) => {
+                    line_end.push_str(line);
+                    suffix = format!("\n{}", rest);
+                }
+                None => suffix = old,
+            }
+            if self.trailing_comma && indent.is_some() {
+                if let Some((line, rest)) = self.trailing.split_once('\n') {
+                    line_end.push_str(line);
+                    self.trailing = format!("\n{}", rest).into();
+                }
+            }
+        } else if 0 < index && indent.is_some() {
+            let next = self.value_mut(index);
+            let prefix = next.decor().prefix().unwrap_or_default();
+            if let Some((line, rest)) = prefix.split_once('\n') {
+                if !line.trim().is_empty() {
+                    line_end.push_str(line);
+                    let rest = format!("\n{}", rest);
+                    next.decor_mut().set_prefix(rest);
+                }
+            }
+        }
+
+        let prefix = match indent {
+            Some(indent) => format!("{}\n{}", line_end, indent),
+            None => {
+                let separator = self.separator().to_owned();
+                if index == 0 {
+                    // The first value keeps the padding after the opening bracket
+                    let first = self.value_mut(0);
+                    let padding = first.decor().prefix().unwrap_or_default();
+                    let padding = if padding.trim().is_empty() {
+                        padding.to_owned()
+                    } else {
+                        String::new()
+                    };
+                    first.decor_mut().set_prefix(separator);
+                    padding
+                } else {
+                    separator
+                }
+            }
+        };
+        value.decorate(prefix, suffix);
+        self.values.insert(index, Item::Value(value));
+    }
+
     /// Replaces the element at the given position within the array, preserving existing formatting.
     ///
     /// # Panics
@@ -288,6 +526,30 @@ impl Array {
         }
     }
 
+    fn value_mut(&mut self, index: usize) -> &mut Value {
+        self.values[index]
+            .as_value_mut()
+            .expect("arrays only hold values")
+    }
+
+    // The indentation of the values of a multi-line array
+    fn indent(&self) -> Option<&str> {
+        self.iter()
+            .filter_map(|value| value.decor().prefix()?.rsplit_once('\n'))
+            .last()
+            .map(|(_, line)| &line[..line.len() - line.trim_start().len()])
+    }
+
+    // The whitespace before the values after the first in a single-line array
+    fn separator(&self) -> &str {
+        self.iter()
+            .skip(1)
+            .last()
+            .and_then(|value| value.decor().prefix())
+            .filter(|prefix| prefix.trim().is_empty())
+            .unwrap_or(DEFAULT_VALUE_DECOR.0)
+    }
+
     fn value_op<T>(
         &mut self,
         v: Value,
@@ -304,8 +566,8 @@ impl Array {
     }
 }
 
-impl std::fmt::Display for Array {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Array {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, ("", ""))
     }
 }
@@ -372,3 +634,19 @@ fn decorate_array(array: &mut Array) {
     array.set_trailing_comma(false);
     array.set_trailing("");
 }
+
+fn decorate_multiline_array(array: &mut Array, indent: &str, trailing_comma: bool) {
+    if array.is_empty() {
+        decorate_array(array);
+        return;
+    }
+    for value in array.values.iter_mut().filter_map(Item::as_value_mut) {
+        // [
+        //   value1,
+        //   value2,
+        // ]
+        value.decorate(format!("\n{}", indent), "");
+    }
+    array.set_trailing_comma(trailing_comma);
+    array.set_trailing("\n");
+}
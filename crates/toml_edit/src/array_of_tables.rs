@@ -1,7 +1,14 @@
-use std::iter::FromIterator;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
 
+use crate::format::own_line_comments;
 use crate::{Array, Item, Table};
 
+const MULTILINE_INDENT: &str = "    ";
+
 /// Type representing a TOML array of tables
 #[derive(Clone, Debug, Default)]
 pub struct ArrayOfTables {
@@ -22,12 +29,49 @@ impl ArrayOfTables {
 /// Formatting
 impl ArrayOfTables {
     /// Convert to an inline array
+    ///
+    /// Tables become inline tables, see [`Table::into_inline_table`].  If any header has
+    /// comments, the array is written with one table per line, each preceded by the comments of
+    /// its header.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "# the binary\n[[bin]]\nname = 'demo'\n\n[[bin]]\nname = 'cli'\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let bin = doc.remove("bin").unwrap().into_array_of_tables().unwrap();
+    /// doc["bin"] = toml_edit::value(bin.into_array());
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "bin = [\n    # the binary\n    { name = 'demo' },\n    { name = 'cli' },\n]\n"
+    /// );
+    /// ```
     pub fn into_array(mut self) -> Array {
+        let comments = self
+            .iter()
+            .map(|table| {
+                let decor = table.decor();
+                [decor.prefix(), decor.suffix()]
+                    .iter()
+                    .flat_map(|raw| own_line_comments(raw.unwrap_or_default()))
+                    .map(|comment| format!("{}{}\n", MULTILINE_INDENT, comment))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
         for value in self.values.iter_mut() {
             value.make_value();
         }
         let mut a = Array::with_vec(self.values);
-        a.fmt();
+        if comments.iter().all(String::is_empty) {
+            a.fmt();
+        } else {
+            a.fmt_multiline(MULTILINE_INDENT, true);
+            for (value, comments) in a.iter_mut().zip(comments) {
+                value
+                    .decor_mut()
+                    .set_prefix(format!("\n{}{}", comments, MULTILINE_INDENT));
+            }
+        }
         a
     }
 }
@@ -78,6 +122,27 @@ impl ArrayOfTables {
     pub fn remove(&mut self, index: usize) {
         self.values.remove(index);
     }
+
+    /// Retains only the tables for which `keep` returns `true`, keeping their order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[[bin]]\nname = 'a'\n\n[[bin]]\nname = 'b'\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let bins = doc["bin"].as_array_of_tables_mut().unwrap();
+    /// bins.retain(|bin| bin["name"].as_str() != Some("b"));
+    /// assert_eq!(doc.to_string(), "[[bin]]\nname = 'a'\n");
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Table) -> bool,
+    {
+        self.values.retain(|item| match item.as_table() {
+            Some(table) => keep(table),
+            None => true,
+        });
+    }
 }
 
 /// An iterator type over `ArrayOfTables`'s values.
@@ -130,8 +195,8 @@ impl<'s> IntoIterator for &'s ArrayOfTables {
     }
 }
 
-impl std::fmt::Display for ArrayOfTables {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ArrayOfTables {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // HACK: Without the header, we don't really have a proper way of printing this
         self.clone().into_array().fmt(f)
     }
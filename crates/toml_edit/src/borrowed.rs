@@ -0,0 +1,425 @@
+//! Read-only documents borrowing from their input.
+//!
+//! A borrowed [`Document`] is parsed like a [`crate::Document`], but its keys, its strings, and
+//! the text of its values are slices of the input rather than owned copies.  Only keys and
+//! strings with escapes, and the tables and arrays holding everything, are allocated, which
+//! suits workloads that only read documents, like loading configuration.  Documents can't be
+//! edited or written back out; parse a [`crate::Document`] for that.
+//!
+//! ```
+//! use toml_edit::borrowed::Document;
+//!
+//! let input = "[package]\nname = \"foo\"\nversion = '0.1.0' # release\n";
+//! let doc = Document::parse(input).unwrap();
+//! let package = doc.get("package").unwrap();
+//! assert_eq!(package.get("name").and_then(|v| v.as_str()), Some("foo"));
+//! let version = package.get("version").and_then(|v| v.as_value()).unwrap();
+//! assert_eq!(version.as_str(), Some("0.1.0"));
+//! assert_eq!(version.raw(), "'0.1.0'");
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+
+use crate::collections::IndexMap;
+use crate::{ParseOptions, TomlError};
+
+/// A TOML document borrowing from its input
+#[derive(Debug, Clone, Default)]
+pub struct Document<'i> {
+    pub(crate) root: Table<'i>,
+}
+
+impl<'i> Document<'i> {
+    /// Parses a document with the default [`ParseOptions`]
+    pub fn parse(input: &'i str) -> Result<Self, TomlError> {
+        Self::parse_with(input, ParseOptions::new())
+    }
+
+    /// Parses a document, customizing the parser with `options`
+    ///
+    /// The limits and the options for reading integers apply as for [`crate::Document`], while
+    /// datetimes are always kept as their text.  The options tolerating invalid TOML, like
+    /// [`ParseOptions::lax`], [`ParseOptions::decimal_comma`],
+    /// [`ParseOptions::on_duplicate_key`], and [`ParseOptions::lenient_table_headers`], are
+    /// ignored, as the document can only hold what is written in the input.
+    pub fn parse_with(input: &'i str, options: ParseOptions) -> Result<Self, TomlError> {
+        crate::parser::parse_borrowed_document(input, options)
+    }
+
+    /// The root table
+    pub fn as_table(&self) -> &Table<'i> {
+        &self.root
+    }
+
+    /// The root table
+    pub fn into_table(self) -> Table<'i> {
+        self.root
+    }
+}
+
+impl<'i> core::ops::Deref for Document<'i> {
+    type Target = Table<'i>;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_table()
+    }
+}
+
+/// A table, from a header, dotted keys, or an inline table
+#[derive(Debug, Clone, Default)]
+pub struct Table<'i> {
+    pub(crate) items: IndexMap<Key<'i>, Item<'i>>,
+    // Whether the table was only created for a header or dotted key within it
+    pub(crate) implicit: bool,
+    // Whether the table is defined by dotted keys
+    pub(crate) dotted: bool,
+    // The header defining the table, for reporting conflicting headers
+    pub(crate) header: Range<usize>,
+}
+
+/// An iterator over the key/value pairs of a [`Table`]
+pub type Iter<'a, 'i> = Box<dyn Iterator<Item = (&'a Key<'i>, &'a Item<'i>)> + 'a>;
+
+impl<'i> Table<'i> {
+    /// Returns the item for `key`
+    pub fn get<'a>(&'a self, key: &str) -> Option<&'a Item<'i>> {
+        self.items.get(key)
+    }
+
+    /// Returns the key, as it was written, along with the item for `key`
+    pub fn get_key_value<'a>(&'a self, key: &str) -> Option<(&'a Key<'i>, &'a Item<'i>)> {
+        self.items.get_key_value(key)
+    }
+
+    /// Returns whether the table has an item for `key`
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.items.contains_key(key)
+    }
+
+    /// Iterates over the key/value pairs, in the order they were first written
+    pub fn iter(&self) -> Iter<'_, 'i> {
+        Box::new(self.items.iter())
+    }
+
+    /// The number of key/value pairs
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the table has no key/value pairs
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns whether the table was only created for the tables or keys within it, like `a` in
+    /// a `[a.b]` header or a `a.b = 1` dotted key
+    pub fn is_implicit(&self) -> bool {
+        self.implicit
+    }
+
+    /// Returns whether the table is defined by dotted keys
+    pub fn is_dotted(&self) -> bool {
+        self.dotted
+    }
+}
+
+/// A key, as it was written
+///
+/// Keys compare and hash by their value, so tables are looked up with the key without quotes or
+/// escapes.
+#[derive(Debug, Clone)]
+pub struct Key<'i> {
+    key: Cow<'i, str>,
+    raw: &'i str,
+}
+
+impl<'i> Key<'i> {
+    pub(crate) fn new(key: Cow<'i, str>, raw: &'i str) -> Self {
+        Self { key, raw }
+    }
+
+    /// The key, without quotes or escapes
+    pub fn get(&self) -> &str {
+        &self.key
+    }
+
+    /// The key, borrowed from the input unless it has escapes
+    pub fn as_cow(&self) -> &Cow<'i, str> {
+        &self.key
+    }
+
+    /// The key, as it was written
+    pub fn raw(&self) -> &'i str {
+        self.raw
+    }
+}
+
+impl<'i> PartialEq for Key<'i> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<'i> Eq for Key<'i> {}
+
+impl<'i> Hash for Key<'i> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl<'i> Borrow<str> for Key<'i> {
+    fn borrow(&self) -> &str {
+        self.get()
+    }
+}
+
+/// A value along with the text it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formatted<'i, T> {
+    pub(crate) value: T,
+    pub(crate) raw: &'i str,
+}
+
+impl<'i, T> Formatted<'i, T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self { value, raw: "" }
+    }
+
+    /// The value
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The value
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// The text the value was read from
+    pub fn raw(&self) -> &'i str {
+        self.raw
+    }
+}
+
+/// An item within a [`Table`]
+#[derive(Debug, Clone)]
+pub enum Item<'i> {
+    /// A value
+    Value(Value<'i>),
+    /// A table from a header or dotted keys
+    Table(Table<'i>),
+    /// An array of tables
+    ArrayOfTables(Vec<Table<'i>>),
+}
+
+impl<'i> Item<'i> {
+    /// The type of the item, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Item::Value(v) => v.type_name(),
+            Item::Table(_) => "table",
+            Item::ArrayOfTables(_) => "array of tables",
+        }
+    }
+
+    /// Returns the item for `key` when this is a table or inline table
+    pub fn get<'a>(&'a self, key: &str) -> Option<&'a Item<'i>> {
+        self.as_table()?.get(key)
+    }
+
+    /// Returns the value, if this is one
+    pub fn as_value(&self) -> Option<&Value<'i>> {
+        match self {
+            Item::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the table, if this is a table or an inline table
+    pub fn as_table(&self) -> Option<&Table<'i>> {
+        match self {
+            Item::Table(t) => Some(t),
+            Item::Value(v) => v.as_inline_table(),
+            Item::ArrayOfTables(_) => None,
+        }
+    }
+
+    /// Returns the tables, if this is an array of tables
+    pub fn as_array_of_tables(&self) -> Option<&[Table<'i>]> {
+        match self {
+            Item::ArrayOfTables(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if this is one
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_value()?.as_str()
+    }
+
+    /// Returns the integer, if this is one that fits in an `i64`
+    pub fn as_integer(&self) -> Option<i64> {
+        self.as_value()?.as_integer()
+    }
+
+    /// Returns the float, if this is one
+    pub fn as_float(&self) -> Option<f64> {
+        self.as_value()?.as_float()
+    }
+
+    /// Returns the boolean, if this is one
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_value()?.as_bool()
+    }
+
+    /// Returns the values, if this is an array
+    pub fn as_array(&self) -> Option<&[Value<'i>]> {
+        self.as_value()?.as_array()
+    }
+}
+
+/// A value borrowing from the input
+#[derive(Debug, Clone)]
+pub enum Value<'i> {
+    /// A string, borrowed from the input where its value is written as is, like a string without
+    /// escapes
+    String(Formatted<'i, Cow<'i, str>>),
+    /// An integer
+    Integer(Formatted<'i, i64>),
+    /// An integer outside of `i64`, read with
+    /// [`ParseOptions::lenient_prefixed_integers`] or [`ParseOptions::extended_integers`]
+    WideInteger(Formatted<'i, i128>),
+    /// A float
+    Float(Formatted<'i, f64>),
+    /// A boolean
+    Boolean(Formatted<'i, bool>),
+    /// A datetime, kept as its text, which is checked to be a valid TOML datetime
+    Datetime(&'i str),
+    /// An array
+    Array(Formatted<'i, Vec<Value<'i>>>),
+    /// An inline table
+    InlineTable(Formatted<'i, Table<'i>>),
+}
+
+impl<'i> Value<'i> {
+    /// The type of the value, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) | Value::WideInteger(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+            Value::Datetime(_) => "datetime",
+            Value::Array(_) => "array",
+            Value::InlineTable(_) => "inline table",
+        }
+    }
+
+    /// The text the value was read from, without the surrounding whitespace or comments
+    pub fn raw(&self) -> &'i str {
+        match self {
+            Value::String(f) => f.raw(),
+            Value::Integer(f) => f.raw(),
+            Value::WideInteger(f) => f.raw(),
+            Value::Float(f) => f.raw(),
+            Value::Boolean(f) => f.raw(),
+            Value::Datetime(raw) => raw,
+            Value::Array(f) => f.raw(),
+            Value::InlineTable(f) => f.raw(),
+        }
+    }
+
+    pub(crate) fn set_raw(&mut self, raw: &'i str) {
+        match self {
+            Value::String(f) => f.raw = raw,
+            Value::Integer(f) => f.raw = raw,
+            Value::WideInteger(f) => f.raw = raw,
+            Value::Float(f) => f.raw = raw,
+            Value::Boolean(f) => f.raw = raw,
+            Value::Datetime(_) => {}
+            Value::Array(f) => f.raw = raw,
+            Value::InlineTable(f) => f.raw = raw,
+        }
+    }
+
+    pub(crate) fn from_i128(i: i128) -> Self {
+        match i64::try_from(i) {
+            Ok(i) => Value::Integer(Formatted::new(i)),
+            Err(_) => Value::WideInteger(Formatted::new(i)),
+        }
+    }
+
+    /// Returns the string, if this is one
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(f) => Some(f.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if this is one that fits in an `i64`
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(f) => Some(*f.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if this is one
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Integer(f) => Some(i128::from(*f.value())),
+            Value::WideInteger(f) => Some(*f.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the float, if this is one
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(f) => Some(*f.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if this is one
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(f) => Some(*f.value()),
+            _ => None,
+        }
+    }
+
+    /// Interprets the datetime, if this is one
+    #[cfg(feature = "datetime")]
+    pub fn as_datetime(&self) -> Option<crate::Datetime> {
+        match self {
+            Value::Datetime(raw) => {
+                Some(crate::parser::parse_datetime(raw).expect("datetimes are checked when parsed"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the values, if this is an array
+    pub fn as_array(&self) -> Option<&[Value<'i>]> {
+        match self {
+            Value::Array(f) => Some(f.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the table, if this is an inline table
+    pub fn as_inline_table(&self) -> Option<&Table<'i>> {
+        match self {
+            Value::InlineTable(f) => Some(f.value()),
+            _ => None,
+        }
+    }
+}
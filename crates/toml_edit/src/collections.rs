@@ -0,0 +1,14 @@
+//! Hash-based collections, which `alloc` lacks
+
+/// Hasher for keys, randomly seeded with `std`
+///
+/// Without `std`, there is no source of randomness, so keys are hashed with fixed keys, leaving
+/// lookups open to collisions crafted by whoever writes the document.
+#[cfg(feature = "std")]
+pub(crate) type RandomState = std::collections::hash_map::RandomState;
+#[cfg(not(feature = "std"))]
+#[allow(deprecated)]
+pub(crate) type RandomState = core::hash::BuildHasherDefault<core::hash::SipHasher>;
+
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, RandomState>;
+pub(crate) type IndexSet<T> = indexmap::IndexSet<T, RandomState>;
@@ -0,0 +1,78 @@
+#[cfg(feature = "datetime")]
+use alloc::string::ToString;
+use core::str::FromStr;
+
+use crate::InternalString;
+
+/// A TOML datetime, kept as its text without being interpreted
+///
+/// Datetimes are read as these with
+/// [`ParseOptions::opaque_datetimes`][crate::ParseOptions::opaque_datetimes], and always when the
+/// `datetime` feature is disabled.  The text is checked to be a valid TOML datetime, so it is
+/// written back out unquoted, as it was.
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_edit::{OpaqueDatetime, Value};
+///
+/// let released = "1979-05-27 07:32:00Z".parse::<OpaqueDatetime>().unwrap();
+/// assert_eq!(released.as_str(), "1979-05-27 07:32:00Z");
+/// assert_eq!(Value::from(released).to_string(), "1979-05-27 07:32:00Z");
+///
+/// assert!("1979-05-27 07:32".parse::<OpaqueDatetime>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpaqueDatetime {
+    raw: InternalString,
+}
+
+impl OpaqueDatetime {
+    pub(crate) fn new_unchecked(raw: &str) -> Self {
+        Self { raw: raw.into() }
+    }
+
+    /// The text of the datetime
+    pub fn as_str(&self) -> &str {
+        self.raw.as_str()
+    }
+
+    /// Interprets the datetime
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let released = "1979-05-27T07:32:00.999999999999Z"
+    ///     .parse::<toml_edit::OpaqueDatetime>()
+    ///     .unwrap();
+    /// let datetime = released.to_datetime();
+    /// assert_eq!(datetime.time.unwrap().nanosecond, 999_999_999);
+    /// ```
+    #[cfg(feature = "datetime")]
+    pub fn to_datetime(&self) -> crate::Datetime {
+        crate::parser::parse_datetime(self.as_str())
+            .expect("`OpaqueDatetime` is only created from valid datetimes")
+    }
+}
+
+impl FromStr for OpaqueDatetime {
+    type Err = crate::TomlError;
+
+    /// Checks that `s` is a TOML datetime, without interpreting it
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parser::parse_datetime(s).map(|_| Self::new_unchecked(s))
+    }
+}
+
+#[cfg(feature = "datetime")]
+impl From<crate::Datetime> for OpaqueDatetime {
+    fn from(datetime: crate::Datetime) -> Self {
+        Self::new_unchecked(&datetime.to_string())
+    }
+}
+
+impl core::fmt::Display for OpaqueDatetime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -21,7 +21,7 @@ impl<'de> serde::Deserializer<'de> for ArrayDeserializer {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map option unit newtype_struct
         ignored_any unit_struct tuple_struct tuple enum identifier struct
     }
@@ -38,7 +38,7 @@ impl<'de> serde::Deserializer<'de> for crate::Array {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map option unit newtype_struct
         ignored_any unit_struct tuple_struct tuple enum identifier struct
     }
@@ -63,7 +63,7 @@ impl<'de> serde::Deserializer<'de> for crate::ArrayOfTables {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map option unit newtype_struct
         ignored_any unit_struct tuple_struct tuple enum identifier struct
     }
@@ -130,6 +130,24 @@ where
 }
 
 /// Convert an item into `T`.
+///
+/// To deserialize part of a document while leaving the document in place, [`Item`][crate::Item],
+/// [`Table`][crate::Table], [`InlineTable`][crate::InlineTable], and [`Value`][crate::Value]
+/// references are also deserializers.  These deserialize a clone of what they refer to, so
+/// deserializing from a reference costs a deep copy of that part of the document:
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Profile {
+///     lto: bool,
+/// }
+///
+/// let doc = "[profile.release]\nlto = true\n".parse::<toml_edit::Document>().unwrap();
+/// let profile = Profile::deserialize(&doc["profile"]["release"]).unwrap();
+/// assert!(profile.lto);
+/// ```
 pub fn from_item<T>(d: crate::Item) -> Result<T, Error>
 where
     T: DeserializeOwned,
@@ -182,7 +200,7 @@ impl<'de> serde::Deserializer<'de> for Deserializer {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map unit newtype_struct
         ignored_any unit_struct tuple_struct tuple identifier struct
     }
@@ -221,7 +239,7 @@ impl<'de> serde::Deserializer<'de> for crate::Document {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map unit newtype_struct
         ignored_any unit_struct tuple_struct tuple identifier struct
     }
@@ -235,6 +253,74 @@ impl<'de> serde::de::IntoDeserializer<'de, crate::de::Error> for crate::Document
     }
 }
 
+// Deserializes from a reference by cloning what it refers to
+macro_rules! impl_deserializer_for_ref {
+    ($($ty:ty),+) => {
+        $(
+            impl<'de> serde::Deserializer<'de> for &$ty {
+                type Error = Error;
+
+                fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.clone().deserialize_any(visitor)
+                }
+
+                fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.clone().deserialize_option(visitor)
+                }
+
+                fn deserialize_newtype_struct<V>(
+                    self,
+                    name: &'static str,
+                    visitor: V,
+                ) -> Result<V::Value, Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.clone().deserialize_newtype_struct(name, visitor)
+                }
+
+                fn deserialize_struct<V>(
+                    self,
+                    name: &'static str,
+                    fields: &'static [&'static str],
+                    visitor: V,
+                ) -> Result<V::Value, Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.clone().deserialize_struct(name, fields, visitor)
+                }
+
+                fn deserialize_enum<V>(
+                    self,
+                    name: &'static str,
+                    variants: &'static [&'static str],
+                    visitor: V,
+                ) -> Result<V::Value, Error>
+                where
+                    V: serde::de::Visitor<'de>,
+                {
+                    self.clone().deserialize_enum(name, variants, visitor)
+                }
+
+                serde::forward_to_deserialize_any! {
+                    bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
+                    bytes byte_buf map unit
+                    ignored_any unit_struct tuple_struct tuple identifier
+                }
+            }
+        )+
+    };
+}
+
+impl_deserializer_for_ref!(crate::Item, crate::Table, crate::InlineTable, crate::Value);
+
 pub(crate) fn validate_struct_keys(
     table: &crate::table::KeyValuePairs,
     fields: &'static [&'static str],
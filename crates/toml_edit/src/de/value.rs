@@ -12,12 +12,23 @@ impl<'de> serde::Deserializer<'de> for crate::Value {
         match self {
             crate::Value::String(v) => visitor.visit_string(v.into_value()),
             crate::Value::Integer(v) => visitor.visit_i64(v.into_value()),
+            crate::Value::WideInteger(v) => {
+                let i = v.into_value();
+                match u64::try_from(i) {
+                    Ok(u) => visitor.visit_u64(u),
+                    Err(_) => visitor.visit_i128(i),
+                }
+            }
             crate::Value::Float(v) => visitor.visit_f64(v.into_value()),
             crate::Value::Boolean(v) => visitor.visit_bool(v.into_value()),
             crate::Value::Datetime(v) => visitor.visit_map(DatetimeDeserializer {
                 date: v.into_value(),
                 visited: false,
             }),
+            crate::Value::OpaqueDatetime(v) => visitor.visit_map(DatetimeDeserializer {
+                date: v.value().to_datetime(),
+                visited: false,
+            }),
             crate::Value::Array(v) => visitor.visit_seq(crate::de::ArraySeqAccess::with_array(v)),
             crate::Value::InlineTable(v) => {
                 visitor.visit_map(crate::de::InlineTableMapAccess::new(v))
@@ -35,9 +46,14 @@ impl<'de> serde::Deserializer<'de> for crate::Value {
         V: serde::de::Visitor<'de>,
     {
         if name == toml_datetime::__unstable::NAME && fields == [toml_datetime::__unstable::FIELD] {
-            if let crate::Value::Datetime(d) = self {
+            let date = match &self {
+                crate::Value::Datetime(d) => Some(*d.value()),
+                crate::Value::OpaqueDatetime(d) => Some(d.value().to_datetime()),
+                _ => None,
+            };
+            if let Some(date) = date {
                 return visitor.visit_map(DatetimeDeserializer {
-                    date: d.into_value(),
+                    date,
                     visited: false,
                 });
             }
@@ -85,7 +101,7 @@ impl<'de> serde::Deserializer<'de> for crate::Value {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map unit newtype_struct
         ignored_any unit_struct tuple_struct tuple identifier
     }
@@ -139,7 +155,7 @@ impl<'de> serde::de::Deserializer<'de> for DatetimeFieldDeserializer {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 char str string seq
         bytes byte_buf map struct option unit newtype_struct
         ignored_any unit_struct tuple_struct tuple enum identifier
     }
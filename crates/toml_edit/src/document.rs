@@ -1,8 +1,26 @@
-use std::str::FromStr;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::panic::RefUnwindSafe;
+use core::str::FromStr;
 
+use crate::encode::{
+    visit_nested_tables, write_document, EncodingVersion, SortPositions, TableOrder,
+};
+use crate::format::{first_written_table, nth_table_mut};
 use crate::parser;
-use crate::table::Iter;
-use crate::{InternalString, Item, Table};
+use crate::table::{visit_headers_mut, Iter, KeyValuePairs, TableKeyValue, DEFAULT_TABLE_DECOR};
+use crate::visit_mut::{
+    visit_array_mut, visit_inline_table_mut, visit_table_like_kv_mut, visit_table_mut,
+    visit_value_mut, VisitMut,
+};
+use crate::{
+    Array, ArrayOfTables, Decor, InlineTable, InternalString, Item, ItemKind, Key, KeyMut,
+    ParseOptions, ParseWarning, Table, Value,
+};
 
 /// Type representing a TOML document
 #[derive(Debug, Clone)]
@@ -10,6 +28,12 @@ pub struct Document {
     pub(crate) root: Item,
     // Trailing comments and whitespaces
     pub(crate) trailing: InternalString,
+    // Whether the input started with a UTF-8 byte order mark
+    pub(crate) bom: bool,
+    pub(crate) newline: NewlineStyle,
+    pub(crate) default_decor: Option<DecorProvider>,
+    // Where tables without a position are written, see `Document::set_preserve_order`
+    pub(crate) order: Option<TableOrder>,
 }
 
 impl Document {
@@ -18,6 +42,37 @@ impl Document {
         Default::default()
     }
 
+    /// Parses a document, customizing the parser with `options`
+    ///
+    /// [`str::parse`] uses the default [`ParseOptions`].
+    pub fn parse_with(input: &str, options: ParseOptions) -> Result<Self, crate::TomlError> {
+        parser::parse_document(input, options, false).map(|(doc, _)| doc)
+    }
+
+    /// Parses a document like [`Document::parse_with`], also returning the deviations from TOML
+    /// tolerated by [`ParseOptions::lax`]
+    ///
+    /// Valid TOML that likely isn't what was meant, like a key that reads like a date, is reported
+    /// as well, see [`WarningKind`][crate::WarningKind].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use toml_edit::{Document, ParseOptions, WarningKind};
+    /// let input = "version = 1.0\n3.14 = \"pi\"\n";
+    /// let (doc, warnings) = Document::parse_with_warnings(input, ParseOptions::new()).unwrap();
+    /// assert_eq!(doc["3"]["14"].as_str(), Some("pi"));
+    /// assert_eq!(warnings.len(), 1);
+    /// assert_eq!(warnings[0].kind(), WarningKind::AmbiguousKey);
+    /// assert_eq!(&input[warnings[0].span()], "3.14 = \"pi\"");
+    /// ```
+    pub fn parse_with_warnings(
+        input: &str,
+        options: ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), crate::TomlError> {
+        parser::parse_document(input, options, true)
+    }
+
     /// Returns a reference to the root item.
     pub fn as_item(&self) -> &Item {
         &self.root
@@ -45,6 +100,47 @@ impl Document {
         self.as_table().iter()
     }
 
+    /// Returns an iterator over every value in the document along with the keys leading to it,
+    /// in the order they are written.
+    ///
+    /// Tables, dotted keys, inline tables, and arrays of tables are walked into, with each table
+    /// of an array of tables under the same path.  Arrays and empty inline tables are yielded
+    /// whole.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[package]\nname = 'demo'\n\n[[bin]]\npath.main = 'main.rs'\n\n[dependencies]\nserde = { version = '1' }\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let paths = doc
+    ///     .iter_flatten()
+    ///     .map(|(path, _)| path.iter().map(|k| k.get()).collect::<Vec<_>>().join("."))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(paths, ["package.name", "bin.path.main", "dependencies.serde.version"]);
+    /// ```
+    pub fn iter_flatten(&self) -> impl Iterator<Item = (Vec<&Key>, &Item)> + '_ {
+        // Tables are written by position, see `Display`
+        let mut positions = SortPositions::new(self.order.as_ref());
+        let mut tables = Vec::new();
+        visit_nested_tables(
+            self.as_table(),
+            &mut Vec::new(),
+            false,
+            &mut |table, path, _| {
+                tables.push((positions.next(table, path), table, path.clone()));
+                Ok(())
+            },
+        )
+        .expect("the callback never fails");
+        tables.sort_by_key(|&(position, _, _)| position);
+
+        let mut flat = Vec::new();
+        for (_, table, path) in tables {
+            flatten(&table.items, &path, &mut flat);
+        }
+        flat.into_iter()
+    }
+
     /// Set whitespace after last element
     pub fn set_trailing(&mut self, trailing: impl Into<InternalString>) {
         self.trailing = trailing.into();
@@ -54,6 +150,1153 @@ impl Document {
     pub fn trailing(&self) -> &str {
         self.trailing.as_str()
     }
+
+    /// Set whether the document starts with a UTF-8 byte order mark (BOM)
+    pub fn set_bom(&mut self, bom: bool) {
+        self.bom = bom;
+    }
+
+    /// Whether the document starts with a UTF-8 byte order mark (BOM)
+    ///
+    /// A BOM, as written by some Windows editors, is recorded when parsing and written back out
+    /// so the document round-trips unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "\u{FEFF}a = 1\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.has_bom());
+    /// assert_eq!(doc.to_string(), input);
+    /// ```
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Set the line ending of the document, normalizing the line endings of all of its whitespace
+    /// and comments
+    ///
+    /// Values are left as written, so a multi-line string keeps the line endings it was parsed
+    /// with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::NewlineStyle;
+    ///
+    /// let mut doc = "a = 1\n\n[b]\nc = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_newline_style(NewlineStyle::CrLf);
+    /// assert_eq!(doc.to_string(), "a = 1\r\n\r\n[b]\r\nc = 2\r\n");
+    /// ```
+    pub fn set_newline_style(&mut self, newline: NewlineStyle) {
+        self.newline = newline;
+        let mut normalize = NormalizeNewlines(newline);
+        normalize.visit_document_mut(self);
+        if let Some(trailing) = normalize.text(self.trailing()) {
+            self.set_trailing(trailing);
+        }
+    }
+
+    /// The line ending of the lines the document writes itself
+    ///
+    /// This is used for the end of each key/value pair and table header, for the whitespace and
+    /// comments of formatted or newly inserted items, and for newly written strings.  Text kept
+    /// from parsing is written as it is, other than a lone `\n` in whitespace or comments of a
+    /// [`NewlineStyle::CrLf`] document.
+    ///
+    /// When parsing, this is detected from the first line ending of the input, so newly inserted
+    /// items use the same line endings as the rest of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::NewlineStyle;
+    ///
+    /// let mut doc = "a = 1\r\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.newline_style(), NewlineStyle::CrLf);
+    /// doc["b"] = toml_edit::value(2);
+    /// assert_eq!(doc.to_string(), "a = 1\r\nb = 2\r\n");
+    /// ```
+    pub fn newline_style(&self) -> NewlineStyle {
+        self.newline
+    }
+
+    /// Set whether tables are written in the order they are in now, even after tables are
+    /// replaced or added
+    ///
+    /// By default, a table without a position, see [`Table::position`], is written after the
+    /// table before it in the document's tree.  Preserving the order records where every table
+    /// is written when this is called, so call it right after parsing to keep the input's order.
+    /// A table without a position is then written where a table at the same path was, like one
+    /// replacing it, or otherwise at the end of the document, in the order they are found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[a]\n\n[b]\n\n[a.c]\nx = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_preserve_order(true);
+    /// let mut c = toml_edit::Table::new();
+    /// c["x"] = toml_edit::value(2);
+    /// doc["a"]["c"] = toml_edit::Item::Table(c);
+    /// doc["a"]["d"] = toml_edit::table();
+    /// doc["a"]["d"]["y"] = toml_edit::value(3);
+    /// assert_eq!(doc.to_string(), "[a]\n\n[b]\n\n[a.c]\nx = 2\n\n[a.d]\ny = 3\n");
+    /// ```
+    pub fn set_preserve_order(&mut self, yes: bool) {
+        self.order = if yes {
+            Some(TableOrder::record(self.as_table(), self.order.as_ref()))
+        } else {
+            None
+        };
+    }
+
+    /// Whether tables are written in the order recorded by [`Document::set_preserve_order`]
+    pub fn preserve_order(&self) -> bool {
+        self.order.is_some()
+    }
+
+    /// Set a function giving the decor of table headers and key/value pairs that have none of
+    /// their own, like those inserted without explicit decor
+    ///
+    /// The function is called with the path to the table or key and what is written there, each
+    /// time the document is written.  For a header, the returned decor is used in place of the
+    /// header's unset prefix or suffix, except for the prefix of the first table of the document.
+    /// For a key/value pair, the prefix is used in place of the key's unset prefix and the suffix
+    /// in place of the value's unset suffix.  Leaving either unset in the returned decor keeps the
+    /// default format.  Decor within arrays, inline tables, and dotted keys is not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{table, value, Decor, Document, ItemKind};
+    ///
+    /// let mut doc = "[package]\nname = 'demo'\n".parse::<Document>().unwrap();
+    /// doc.set_default_decor_provider(|path, kind| match kind {
+    ///     ItemKind::Table | ItemKind::ArrayOfTables => Decor::new("\n\n", ""),
+    ///     ItemKind::Value if path[0] == "dependencies" => Decor::new("  ", ""),
+    ///     ItemKind::Value => Decor::default(),
+    /// });
+    /// doc["package"]["edition"] = value("2021");
+    /// doc["dependencies"] = table();
+    /// doc["dependencies"]["serde"] = value("1");
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\nname = 'demo'\nedition = \"2021\"\n\n\n[dependencies]\n  serde = \"1\"\n"
+    /// );
+    /// ```
+    pub fn set_default_decor_provider(
+        &mut self,
+        provider: impl Fn(&[&str], ItemKind) -> Decor + Send + Sync + RefUnwindSafe + 'static,
+    ) {
+        self.default_decor = Some(DecorProvider(Arc::new(provider)));
+    }
+
+    /// Go back to the default format for items without decor of their own, see
+    /// [`Document::set_default_decor_provider`].
+    pub fn clear_default_decor_provider(&mut self) {
+        self.default_decor = None;
+    }
+
+    /// Render the document with the default format of `version`.
+    ///
+    /// Pinning a version keeps generated files from changing when a later release improves the
+    /// default format, see [`EncodingVersion`].  Formatting kept from parsing, or set explicitly,
+    /// is written as-is whatever the version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{value, Document, EncodingVersion};
+    ///
+    /// let mut doc = Document::new();
+    /// doc["name"] = value("demo");
+    /// doc["ratio"] = value(0.5);
+    /// assert_eq!(
+    ///     doc.to_string_with(EncodingVersion::V0_17),
+    ///     "name = \"demo\"\nratio = 0.5\n"
+    /// );
+    /// ```
+    pub fn to_string_with(&self, version: EncodingVersion) -> String {
+        let mut output = String::new();
+        write_document(self, &mut output, version).expect("writing to a `String` can't fail");
+        output
+    }
+
+    /// Write the document to `writer`, encoding directly rather than rendering to a `String`.
+    ///
+    /// As this makes many small writes, consider wrapping `writer` in a
+    /// [`BufWriter`][std::io::BufWriter].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "a = 1 # one\n".parse::<toml_edit::Document>().unwrap();
+    /// let mut out = Vec::new();
+    /// doc.write_to(&mut out).unwrap();
+    /// assert_eq!(out, b"a = 1 # one\n");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
+    /// Returns the comment lines heading the document, like a license or a generated-file banner,
+    /// without the `# ` starting each line.
+    ///
+    /// The header is the block of comments at the start of the document that is followed by a
+    /// blank line, or by nothing else.  Comments directly above the first key or table header
+    /// belong to it instead, see [`Key::comment`] and [`Table::comment`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "# Generated by build.rs\n# Do not edit\n\n# The crate\n[package]\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let header = doc.header_comments().collect::<Vec<_>>();
+    /// assert_eq!(header, ["Generated by build.rs", "Do not edit"]);
+    /// assert_eq!(doc["package"].comment().as_deref(), Some("The crate"));
+    ///
+    /// doc.set_header_comments(["SPDX-License-Identifier: MIT"]);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "# SPDX-License-Identifier: MIT\n\n# The crate\n[package]\n"
+    /// );
+    /// ```
+    pub fn header_comments(&self) -> impl Iterator<Item = &str> + '_ {
+        let (raw, ends_document) = self.leading_trivia();
+        split_header(raw, ends_document)
+            .map(|(header, _)| header)
+            .unwrap_or_default()
+            .into_iter()
+    }
+
+    /// Replaces the comment lines heading the document, see [`Document::header_comments`].
+    ///
+    /// Each line is written as a `# ` comment, followed by a blank line separating the header
+    /// from the rest of the document.  Without any lines, the header is removed.
+    pub fn set_header_comments(&mut self, lines: impl IntoIterator<Item = impl AsRef<str>>) {
+        let (raw, ends_document) = self.leading_trivia();
+        let rest = match split_header(raw, ends_document) {
+            Some((_, rest)) => rest,
+            None => skip_blank_lines(raw),
+        };
+        let mut leading = String::new();
+        for line in lines {
+            let line = line.as_ref();
+            leading.push('#');
+            if !line.is_empty() {
+                leading.push(' ');
+                leading.push_str(line);
+            }
+            leading.push('\n');
+        }
+        let blank = !(ends_document && rest.trim().is_empty());
+        if blank && !leading.is_empty() {
+            leading.push('\n');
+        }
+        leading.push_str(rest);
+
+        let first = first_written_table(self.as_table(), self.order.as_ref());
+        let root = self.as_table_mut();
+        match first {
+            Some(0) => first_value_key_mut(root)
+                .expect("the root has values")
+                .decor_mut()
+                .set_prefix(leading),
+            Some(mut n) => nth_table_mut(root, &mut n)
+                .expect("found by `first_written_table`")
+                .decor_mut()
+                .set_prefix(leading),
+            None => self.set_trailing(leading),
+        }
+    }
+
+    // The whitespace and comments starting the document, and whether nothing comes after them
+    fn leading_trivia(&self) -> (&str, bool) {
+        let root = self.as_table();
+        let decor = match first_written_table(root, self.order.as_ref()) {
+            Some(0) => first_value_key(root).expect("the root has values").decor(),
+            Some(first) => {
+                let mut tables = Vec::new();
+                visit_nested_tables(root, &mut Vec::new(), false, &mut |table, _, _| {
+                    tables.push(table);
+                    Ok(())
+                })
+                .expect("the callback never fails");
+                tables[first].decor()
+            }
+            None => return (self.trailing(), true),
+        };
+        (decor.prefix().unwrap_or_default(), false)
+    }
+
+    /// Sorts the key/value pairs of every table in the document, see
+    /// [`Table::sort_keys_recursive`].
+    pub fn sort_keys_recursive(&mut self) {
+        self.as_table_mut().sort_keys_recursive();
+    }
+
+    /// Sorts the key/value pairs of every table in the document using the comparison function
+    /// `compare`, see [`Table::sort_values_by_path`].
+    pub fn sort_values_by_path<F>(&mut self, compare: F)
+    where
+        F: FnMut(&[&str], &Key, &Item, &Key, &Item) -> core::cmp::Ordering,
+    {
+        self.as_table_mut().sort_values_by_path(compare);
+    }
+
+    /// Rewrite dotted keys under the table at `path` as `[header]` tables.
+    ///
+    /// Comments leading the first dotted key of a table are moved to the new header.
+    ///
+    /// Returns `false` if `path` does not refer to a table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a.b = 1\na.c.d = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.convert_dotted_to_headers(&[]);
+    /// assert_eq!(doc.to_string(), "[a]\nb = 1\n\n[a.c]\nd = 2\n");
+    /// ```
+    pub fn convert_dotted_to_headers(&mut self, path: &[&str]) -> bool {
+        match self.table_at_path_mut(path) {
+            Some(table) => {
+                dotted_to_headers(table);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fold `[header]` tables under the table at `path` into dotted keys.
+    ///
+    /// A table is only folded if it and its nested tables span at most `max_depth` levels, so
+    /// the resulting dotted keys name at most `max_depth` tables.  Deeper tables keep their
+    /// headers though their children may still be folded into them.  Tables are left untouched
+    /// when folding would lose or duplicate a comment, when they contain arrays of tables, or
+    /// when they are empty.
+    ///
+    /// Returns `false` if `path` does not refer to a table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[a]\nb = 1\n\n[a.c]\nd = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.convert_headers_to_dotted(&[], 2);
+    /// assert_eq!(doc.to_string(), "a.b = 1\na.c.d = 2\n");
+    /// ```
+    pub fn convert_headers_to_dotted(&mut self, path: &[&str], max_depth: usize) -> bool {
+        match self.table_at_path_mut(path) {
+            Some(table) => {
+                headers_to_dotted(table, max_depth);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rewrite `[[header]]` arrays of tables under the table at `path` as dotted keys indexed by
+    /// the position of each table, like `server.0.name`.
+    ///
+    /// This is for exporting to formats without arrays of tables, like environment variables or
+    /// flat key/value stores.  Nested tables and arrays of tables become dotted keys as well,
+    /// with empty tables written as `{}`.  Comments within the arrays of tables are not kept.
+    /// This is the inverse of [`Document::expand_indexed_tables`].
+    ///
+    /// Returns `false` if `path` does not refer to a table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[[server]]\nname = 'a'\n\n[[server]]\nname = 'b'\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// doc.flatten_arrays_of_tables(&[]);
+    /// assert_eq!(doc.to_string(), "server.0.name = 'a'\nserver.1.name = 'b'\n");
+    /// ```
+    pub fn flatten_arrays_of_tables(&mut self, path: &[&str]) -> bool {
+        match self.table_at_path_mut(path) {
+            Some(table) => {
+                flatten_arrays_of_tables(table);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rewrite tables under the table at `path` whose keys are the indexes `0` to `n - 1` as
+    /// `[[header]]` arrays of tables, in the order of their indexes.
+    ///
+    /// A table is only rewritten when every one of its values is a table, including inline
+    /// tables.  Dotted keys within the indexed tables become `[header]` tables, as by
+    /// [`Document::convert_dotted_to_headers`].  This is the inverse of
+    /// [`Document::flatten_arrays_of_tables`].
+    ///
+    /// Returns `false` if `path` does not refer to a table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "server.1.name = 'b'\nserver.0.name = 'a'\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// doc.expand_indexed_tables(&[]);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[[server]]\nname = 'a'\n\n[[server]]\nname = 'b'\n"
+    /// );
+    /// ```
+    pub fn expand_indexed_tables(&mut self, path: &[&str]) -> bool {
+        match self.table_at_path_mut(path) {
+            Some(table) => {
+                expand_indexed_tables(table);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the `[header]` table at `path`, along with the tables nested under it, to be written
+    /// just before the table at `other` and the tables nested under it.
+    ///
+    /// The positions of every table in the document are renumbered from `0`, see
+    /// [`Table::set_position`], keeping the order of the others.  When the first header changes,
+    /// the old and new first headers trade the blank lines before them.
+    ///
+    /// Returns `false`, leaving the document as is, if `path` or `other` is the root, does not
+    /// refer to a table written with a header, or if `other` is nested under `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[package]\n\n[dependencies]\n\n[dependencies.serde]\n\n[features]\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.move_table_before(&["features"], &["dependencies"]));
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\n\n[features]\n\n[dependencies]\n\n[dependencies.serde]\n"
+    /// );
+    /// ```
+    pub fn move_table_before(&mut self, path: &[&str], other: &[&str]) -> bool {
+        self.move_table(path, other, false)
+    }
+
+    /// Move the `[header]` table at `path`, along with the tables nested under it, to be written
+    /// just after the table at `other` and the tables nested under it.
+    ///
+    /// See [`Document::move_table_before`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[a]\n\n[b]\n\n[b.c]\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.move_table_after(&["a"], &["b"]));
+    /// assert_eq!(doc.to_string(), "[b]\n\n[b.c]\n\n[a]\n");
+    /// ```
+    pub fn move_table_after(&mut self, path: &[&str], other: &[&str]) -> bool {
+        self.move_table(path, other, true)
+    }
+
+    fn move_table(&mut self, path: &[&str], other: &[&str], after: bool) -> bool {
+        fn is_under(path: &[&Key], prefix: &[&str]) -> bool {
+            prefix.len() <= path.len() && path.iter().zip(prefix).all(|(k, p)| k.get() == *p)
+        }
+
+        if path.is_empty() || other.is_empty() || other.starts_with(path) {
+            return false;
+        }
+        let first = first_written_table(self.as_table(), self.order.as_ref());
+        // Tables in the order they are visited, along with where they are written
+        let mut positions = SortPositions::new(self.order.as_ref());
+        let mut tables = Vec::new();
+        visit_nested_tables(
+            self.as_table(),
+            &mut Vec::new(),
+            false,
+            &mut |table, p, _| {
+                let position = positions.next(table, p);
+                let block = if p.is_empty() {
+                    None
+                } else if is_under(p, path) {
+                    Some(true)
+                } else if is_under(p, other) {
+                    Some(false)
+                } else {
+                    None
+                };
+                tables.push((position, block));
+                Ok(())
+            },
+        )
+        .expect("the callback never fails");
+        let mut order = (0..tables.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| tables[i].0);
+
+        let (moved, mut order): (Vec<_>, Vec<_>) =
+            order.into_iter().partition(|&i| tables[i].1 == Some(true));
+        let mut targets = order
+            .iter()
+            .enumerate()
+            .filter(|(_, &i)| tables[i].1 == Some(false))
+            .map(|(rank, _)| rank);
+        let at = if after {
+            targets.next_back().map(|rank| rank + 1)
+        } else {
+            targets.next()
+        };
+        let at = match at {
+            Some(at) if !moved.is_empty() => at,
+            _ => return false,
+        };
+        order.splice(at..at, moved);
+
+        set_positions(self.as_table_mut(), &order);
+        if let (Some(old), Some(new)) = (
+            first,
+            first_written_table(self.as_table(), self.order.as_ref()),
+        ) {
+            if old != new {
+                swap_first_header(self.as_table_mut(), old, new);
+            }
+        }
+        true
+    }
+
+    /// Insert `table` at `path`, to be written just after the table at `other` and the tables
+    /// nested under it, see [`Document::move_table_after`].
+    ///
+    /// Returns `false`, leaving the document as is, if there is already an item at `path`, the
+    /// table it would be nested in does not exist, or `other` does not refer to a table written
+    /// with a header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[package]\nname = 'demo'\n\n[dependencies]\n".parse::<toml_edit::Document>().unwrap();
+    /// let mut lib = toml_edit::Table::new();
+    /// lib["path"] = toml_edit::value("src/lib.rs");
+    /// assert!(doc.insert_table_after(&["package"], &["lib"], lib));
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\nname = 'demo'\n\n[lib]\npath = \"src/lib.rs\"\n\n[dependencies]\n"
+    /// );
+    /// ```
+    pub fn insert_table_after(&mut self, other: &[&str], path: &[&str], table: Table) -> bool {
+        let (key, parent) = match path.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        if other.is_empty() || self.table_at_path_mut(other).is_none() {
+            return false;
+        }
+        match self.table_at_path_mut(parent) {
+            Some(parent) if !parent.contains_key(key) => {
+                parent.insert(key, Item::Table(table));
+            }
+            _ => return false,
+        }
+        if self.move_table(path, other, true) {
+            true
+        } else {
+            if let Some(parent) = self.table_at_path_mut(parent) {
+                parent.remove(key);
+            }
+            false
+        }
+    }
+
+    /// Give every table the position it is written at, counting from `0`, see
+    /// [`Table::set_position`].
+    ///
+    /// Tables created without a position are written after the table before them, so this is
+    /// for choosing positions relative to the other tables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[a]\n\n[b]\n".parse::<toml_edit::Document>().unwrap();
+    /// doc["a"]["c"] = toml_edit::table();
+    /// doc.renumber_positions();
+    /// let positions = ["a", "b"].map(|key| doc[key].as_table().unwrap().position());
+    /// assert_eq!(positions, [Some(1), Some(3)]);
+    /// assert_eq!(doc["a"]["c"].as_table().unwrap().position(), Some(2));
+    /// ```
+    pub fn renumber_positions(&mut self) {
+        let mut sort_positions = SortPositions::new(self.order.as_ref());
+        let mut positions = Vec::new();
+        visit_nested_tables(
+            self.as_table(),
+            &mut Vec::new(),
+            false,
+            &mut |table, path, _| {
+                positions.push(sort_positions.next(table, path));
+                Ok(())
+            },
+        )
+        .expect("the callback never fails");
+        let mut order = (0..positions.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| positions[i]);
+        set_positions(self.as_table_mut(), &order);
+    }
+
+    /// Insert the keys of `template` that are missing from this document, with the template's
+    /// values and comments.
+    ///
+    /// This is for writing back a configuration with every option documented.  Tables found in
+    /// both documents are filled in recursively, including inline tables, while any other value
+    /// already in this document is kept as is.  Arrays of tables are not merged element by
+    /// element.  Tables taken from the template are written after this document's tables, in
+    /// the template's order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let template = "# The name\nname = 'demo'\n# Whether to log\nverbose = false\n\n[server]\nport = 80\n";
+    /// let template = template.parse::<toml_edit::Document>().unwrap();
+    /// let mut doc = "verbose = true\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.fill_defaults_from(&template);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "verbose = true\n# The name\nname = 'demo'\n\n[server]\nport = 80\n"
+    /// );
+    /// ```
+    pub fn fill_defaults_from(&mut self, template: &Document) {
+        // Headers are written by position, so the template's positions are moved past this
+        // document's
+        let mut base = 0;
+        visit_nested_tables(
+            self.as_table(),
+            &mut Vec::new(),
+            false,
+            &mut |table, _, _| {
+                if let Some(position) = table.position() {
+                    base = base.max(position + 1);
+                }
+                Ok(())
+            },
+        )
+        .expect("the callback never fails");
+        let mut positions = Vec::new();
+        visit_nested_tables(
+            template.as_table(),
+            &mut Vec::new(),
+            false,
+            &mut |table, _, _| {
+                positions.extend(table.position());
+                Ok(())
+            },
+        )
+        .expect("the callback never fails");
+        positions.sort_unstable();
+        positions.dedup();
+
+        let mut reposition = |table: &mut Table| {
+            if let Some(position) = table.position() {
+                let rank = positions.binary_search(&position).unwrap_or_else(|i| i);
+                table.set_position(base + rank);
+            }
+        };
+        fill_defaults(
+            &mut self.as_table_mut().items,
+            &template.as_table().items,
+            &mut reposition,
+        );
+    }
+
+    /// Removes every item for which `remove` returns `true`, anywhere in the document.
+    ///
+    /// `remove` is called with the path to the item, ending with its own key, and is not called
+    /// for items under one it removes.  Arrays of tables are walked into, with each table under
+    /// the same path, while inline tables are treated like any other value.  Implicit and dotted
+    /// tables left empty are removed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "name = 'demo'\n\n[build.old]\nflag = true\n\n[run]\nold = 1\nnew = 2\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// doc.prune(|path, _| path.last() == Some(&"old"));
+    /// assert_eq!(doc.to_string(), "name = 'demo'\n\n[run]\nnew = 2\n");
+    /// ```
+    pub fn prune<F>(&mut self, mut remove: F)
+    where
+        F: FnMut(&[&str], &Item) -> bool,
+    {
+        prune(self.as_table_mut(), &mut Vec::new(), &mut remove);
+    }
+
+    fn table_at_path_mut(&mut self, path: &[&str]) -> Option<&mut Table> {
+        let mut table = self.as_table_mut();
+        for key in path {
+            table = table.get_mut(key)?.as_table_mut()?;
+        }
+        Some(table)
+    }
+}
+
+// Numbers the tables in the order of `visit_nested_tables` by their place in `order`
+fn set_positions(root: &mut Table, order: &[usize]) {
+    let mut positions = vec![0; order.len()];
+    for (position, &i) in order.iter().enumerate() {
+        positions[i] = position;
+    }
+    let mut positions = positions.into_iter();
+    visit_headers_mut(root, &mut |table| {
+        table.set_position(positions.next().expect("the same tables are visited"));
+    });
+}
+
+// The first header is usually written without a blank line before it, so a table moved away
+// from the start of the document trades its blank prefix with the one taking its place
+fn swap_first_header(root: &mut Table, old: usize, new: usize) {
+    let mut prefix = |n: usize| {
+        nth_table_mut(root, &mut n.clone())
+            .and_then(|table| table.decor().prefix())
+            .map(str::to_owned)
+    };
+    let (old_prefix, new_prefix) = (prefix(old), prefix(new));
+    let is_blank = |prefix: &Option<String>| match prefix {
+        Some(prefix) => prefix.trim().is_empty(),
+        None => true,
+    };
+    if !is_blank(&old_prefix) || !is_blank(&new_prefix) {
+        return;
+    }
+    if let Some(table) = nth_table_mut(root, &mut old.clone()) {
+        let prefix = new_prefix.unwrap_or_else(|| DEFAULT_TABLE_DECOR.0.to_owned());
+        table.decor_mut().set_prefix(prefix);
+    }
+    if let Some(table) = nth_table_mut(root, &mut new.clone()) {
+        table.decor_mut().set_prefix(old_prefix.unwrap_or_default());
+    }
+}
+
+fn flatten<'d>(
+    items: &'d KeyValuePairs,
+    parent: &[&'d Key],
+    flat: &mut Vec<(Vec<&'d Key>, &'d Item)>,
+) {
+    for kv in items.values() {
+        let mut path = parent.to_vec();
+        path.push(&kv.key);
+        match &kv.value {
+            Item::Table(table) if table.is_dotted() => flatten(&table.items, &path, flat),
+            Item::Value(Value::InlineTable(table)) if !table.is_empty() => {
+                flatten(&table.items, &path, flat);
+            }
+            Item::Value(_) => flat.push((path, &kv.value)),
+            _ => {}
+        }
+    }
+}
+
+// Whether the key/value pair is written under the table's header, possibly as dotted keys
+fn is_written_value(kv: &TableKeyValue) -> bool {
+    match &kv.value {
+        Item::Value(Value::InlineTable(t)) if t.is_dotted() => !t.get_values().is_empty(),
+        Item::Value(_) => true,
+        Item::Table(t) => t.is_dotted() && !t.get_values().is_empty(),
+        Item::None | Item::ArrayOfTables(_) => false,
+    }
+}
+
+// The key starting the first key/value pair written under the table's header
+fn first_value_key(table: &Table) -> Option<&Key> {
+    table
+        .items
+        .values()
+        .find(|kv| is_written_value(kv))
+        .map(|kv| &kv.key)
+}
+
+fn first_value_key_mut(table: &mut Table) -> Option<&mut Key> {
+    table
+        .items
+        .values_mut()
+        .find(|kv| is_written_value(kv))
+        .map(|kv| &mut kv.key)
+}
+
+// Splits whitespace starting the document into its header comments and what follows the blank
+// line ending them
+fn split_header(raw: &str, ends_document: bool) -> Option<(Vec<&str>, &str)> {
+    let mut header = Vec::new();
+    let mut rest = skip_blank_lines(raw);
+    while let Some((line, after)) = rest.split_once('\n') {
+        match line.trim().strip_prefix('#') {
+            Some(comment) => header.push(comment.strip_prefix(' ').unwrap_or(comment)),
+            None => break,
+        }
+        rest = after;
+    }
+    if header.is_empty() {
+        return None;
+    }
+    match rest.split_once('\n') {
+        Some((line, after)) if line.trim().is_empty() => Some((header, after)),
+        _ if ends_document && rest.trim().is_empty() => Some((header, rest)),
+        _ => None,
+    }
+}
+
+fn skip_blank_lines(raw: &str) -> &str {
+    let mut rest = raw;
+    while let Some((line, after)) = rest.split_once('\n') {
+        if !line.trim().is_empty() {
+            break;
+        }
+        rest = after;
+    }
+    rest
+}
+
+fn dotted_to_headers(table: &mut Table) {
+    for kv in table.items.values_mut() {
+        if let Item::Table(child) = &mut kv.value {
+            if child.is_dotted() {
+                child.set_dotted(false);
+                let has_values = child.items.values().any(|kv| kv.value.is_value());
+                child.set_implicit(!has_values);
+                if let Some(prefix) = kv.key.decor.prefix() {
+                    if prefix.contains('#') {
+                        let prefix = format!("\n{}", prefix.trim_start_matches(['\r', '\n']));
+                        child.decor_mut().set_prefix(prefix);
+                    }
+                }
+                kv.key.decor.clear();
+            }
+            dotted_to_headers(child);
+        }
+    }
+}
+
+fn headers_to_dotted(table: &mut Table, max_depth: usize) {
+    for kv in table.items.values_mut() {
+        if let Item::Table(child) = &mut kv.value {
+            if child.is_dotted() {
+                continue;
+            }
+            if !child.is_empty() && is_foldable(child, max_depth) {
+                kv.key.decor.clear();
+                make_dotted(child);
+            } else {
+                headers_to_dotted(child, max_depth);
+            }
+        }
+    }
+}
+
+fn is_foldable(table: &Table, max_depth: usize) -> bool {
+    if max_depth == 0 {
+        return false;
+    }
+    let has_comment = |s: Option<&str>| s.map(|s| s.contains('#')).unwrap_or(false);
+    if !table.is_dotted()
+        && (has_comment(table.decor.prefix()) || has_comment(table.decor.suffix()))
+    {
+        return false;
+    }
+    table.items.values().all(|kv| match &kv.value {
+        Item::None => true,
+        Item::Value(_) => !has_comment(kv.key.decor.prefix()),
+        Item::Table(child) => !child.is_empty() && is_foldable(child, max_depth - 1),
+        Item::ArrayOfTables(_) => false,
+    })
+}
+
+fn make_dotted(table: &mut Table) {
+    table.set_dotted(true);
+    table.set_implicit(true);
+    table.decor_mut().clear();
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::Value(_) => kv.key.decor.set_prefix(""),
+            Item::Table(child) => {
+                kv.key.decor.clear();
+                make_dotted(child);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn flatten_arrays_of_tables(table: &mut Table) {
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::ArrayOfTables(array) => {
+                let array = core::mem::take(array);
+                let mut indexed = Table::new();
+                for (i, child) in array.into_iter().enumerate() {
+                    indexed.insert(&i.to_string(), flat_item(child));
+                }
+                make_dotted(&mut indexed);
+                kv.key.decor.clear();
+                kv.value = Item::Table(indexed);
+            }
+            Item::Table(child) => flatten_arrays_of_tables(child),
+            _ => {}
+        }
+    }
+}
+
+// Converts the table to one that can be written with dotted keys, as empty tables written that
+// way would disappear
+fn flat_item(mut table: Table) -> Item {
+    if table.is_empty() {
+        return Item::Value(Value::InlineTable(InlineTable::new()));
+    }
+    flatten_arrays_of_tables(&mut table);
+    for kv in table.items.values_mut() {
+        if let Item::Table(child) = &mut kv.value {
+            let child = core::mem::take(child);
+            kv.value = flat_item(child);
+        }
+    }
+    make_dotted(&mut table);
+    Item::Table(table)
+}
+
+fn expand_indexed_tables(table: &mut Table) {
+    for kv in table.items.values_mut() {
+        if let Item::Table(child) = &mut kv.value {
+            match indexed_tables(child) {
+                Some(tables) => {
+                    let mut array = ArrayOfTables::new();
+                    for mut indexed in tables {
+                        indexed.set_dotted(false);
+                        indexed.set_implicit(false);
+                        indexed.decor_mut().clear();
+                        dotted_to_headers(&mut indexed);
+                        expand_indexed_tables(&mut indexed);
+                        array.push(indexed);
+                    }
+                    kv.key.decor.clear();
+                    kv.value = Item::ArrayOfTables(array);
+                }
+                None => expand_indexed_tables(child),
+            }
+        }
+    }
+}
+
+// Takes the tables out of a table keyed by `0` to `n - 1`, in the order of their indexes
+fn indexed_tables(table: &mut Table) -> Option<Vec<Table>> {
+    let mut indexes = Vec::new();
+    for (key, item) in table.iter() {
+        let is_table = item.is_table() || item.is_inline_table();
+        let is_index = key == "0" || !key.starts_with('0');
+        match key.parse::<usize>() {
+            Ok(index) if is_table && is_index => indexes.push(index),
+            _ => return None,
+        }
+    }
+    indexes.sort_unstable();
+    if indexes.is_empty() || indexes.iter().enumerate().any(|(i, &index)| i != index) {
+        return None;
+    }
+
+    let mut tables = vec![Table::new(); indexes.len()];
+    for (key, kv) in core::mem::take(&mut table.items) {
+        let index = key.parse::<usize>();
+        match (index, kv.value) {
+            (Ok(index), Item::Table(t)) => tables[index] = t,
+            (Ok(index), Item::Value(Value::InlineTable(t))) => {
+                tables[index] = t.into_table_recursive();
+            }
+            _ => {}
+        }
+    }
+    Some(tables)
+}
+
+fn fill_defaults(
+    items: &mut KeyValuePairs,
+    template: &KeyValuePairs,
+    reposition: &mut dyn FnMut(&mut Table),
+) {
+    for (key, default) in template {
+        if default.value.is_none() {
+            continue;
+        }
+        let kv = match items.get_mut(key) {
+            Some(kv) if !kv.value.is_none() => kv,
+            _ => {
+                let mut default = default.clone();
+                match &mut default.value {
+                    Item::Table(table) => visit_headers_mut(table, reposition),
+                    Item::ArrayOfTables(array) => {
+                        for table in array.iter_mut() {
+                            visit_headers_mut(table, reposition);
+                        }
+                    }
+                    _ => {}
+                }
+                items.insert(key.clone(), default);
+                continue;
+            }
+        };
+        match (&mut kv.value, &default.value) {
+            (Item::Table(table), Item::Table(default)) => {
+                fill_defaults(&mut table.items, &default.items, reposition);
+            }
+            (Item::Value(Value::InlineTable(table)), Item::Value(Value::InlineTable(default))) => {
+                let len = table.items.len();
+                fill_defaults(&mut table.items, &default.items, reposition);
+                // The space before the closing brace moves to the new last value
+                if 0 < len && len < table.items.len() {
+                    let last = table.items.len() - 1;
+                    let old = inline_decor(&mut table.items, len - 1).suffix();
+                    if let Some(old) = old.map(str::to_owned) {
+                        inline_decor(&mut table.items, len - 1).set_suffix("");
+                        inline_decor(&mut table.items, last).set_suffix(old);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn prune<F>(table: &mut Table, path: &mut Vec<String>, remove: &mut F)
+where
+    F: FnMut(&[&str], &Item) -> bool,
+{
+    table.items.retain(|_, kv| {
+        if kv.value.is_none() {
+            return true;
+        }
+        path.push(kv.key.get().to_owned());
+        let keys = path.iter().map(String::as_str).collect::<Vec<_>>();
+        let keep = !remove(&keys, &kv.value)
+            && match &mut kv.value {
+                Item::Table(table) => {
+                    let was_empty = table.is_empty();
+                    prune(table, path, remove);
+                    was_empty || !table.is_empty() || !(table.is_implicit() || table.is_dotted())
+                }
+                Item::ArrayOfTables(array) => {
+                    for table in array.iter_mut() {
+                        prune(table, path, remove);
+                    }
+                    true
+                }
+                _ => true,
+            };
+        path.pop();
+        keep
+    });
+}
+
+// The function set by `Document::set_default_decor_provider`
+#[derive(Clone)]
+pub(crate) struct DecorProvider(Arc<DecorFn>);
+
+type DecorFn = dyn Fn(&[&str], ItemKind) -> Decor + Send + Sync + RefUnwindSafe;
+
+impl DecorProvider {
+    pub(crate) fn decor(&self, path: &[&Key], kind: ItemKind) -> Decor {
+        let path = path.iter().map(|k| k.get()).collect::<Vec<_>>();
+        (self.0)(&path, kind)
+    }
+}
+
+impl core::fmt::Debug for DecorProvider {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("DecorProvider")
+    }
+}
+
+/// The line ending of a [`Document`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NewlineStyle {
+    /// `\n`, as used on Unix
+    Lf,
+    /// `\r\n`, as used on Windows
+    CrLf,
+}
+
+impl NewlineStyle {
+    /// Detect the style of the first line ending in `input`, defaulting to [`NewlineStyle::Lf`]
+    pub fn detect(input: &str) -> Self {
+        match input.find('\n') {
+            Some(i) if input[..i].ends_with('\r') => NewlineStyle::CrLf,
+            _ => NewlineStyle::Lf,
+        }
+    }
+
+    /// The line ending
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NewlineStyle::Lf => "\n",
+            NewlineStyle::CrLf => "\r\n",
+        }
+    }
+}
+
+// Rewrites the line endings of whitespace and comments, leaving values as written
+struct NormalizeNewlines(NewlineStyle);
+
+impl NormalizeNewlines {
+    fn text(&self, text: &str) -> Option<String> {
+        if !text.contains('\n') {
+            return None;
+        }
+        let normalized = text.replace("\r\n", "\n");
+        let normalized = match self.0 {
+            NewlineStyle::Lf => normalized,
+            NewlineStyle::CrLf => normalized.replace('\n', "\r\n"),
+        };
+        if normalized == text {
+            None
+        } else {
+            Some(normalized)
+        }
+    }
+
+    fn decor(&self, decor: &mut Decor) {
+        if let Some(prefix) = decor.prefix().and_then(|p| self.text(p)) {
+            decor.set_prefix(prefix);
+        }
+        if let Some(suffix) = decor.suffix().and_then(|s| self.text(s)) {
+            decor.set_suffix(suffix);
+        }
+    }
+}
+
+impl VisitMut for NormalizeNewlines {
+    fn visit_table_mut(&mut self, node: &mut Table) {
+        self.decor(node.decor_mut());
+        visit_table_mut(self, node);
+    }
+
+    fn visit_table_like_kv_mut(&mut self, mut key: KeyMut<'_>, node: &mut Item) {
+        self.decor(key.decor_mut());
+        visit_table_like_kv_mut(self, key, node);
+    }
+
+    fn visit_value_mut(&mut self, node: &mut Value) {
+        self.decor(node.decor_mut());
+        visit_value_mut(self, node);
+    }
+
+    fn visit_array_mut(&mut self, node: &mut Array) {
+        if let Some(trailing) = self.text(node.trailing()) {
+            node.set_trailing(trailing);
+        }
+        visit_array_mut(self, node);
+    }
+
+    fn visit_inline_table_mut(&mut self, node: &mut InlineTable) {
+        if let Some(trailing) = self.text(node.trailing()) {
+            node.set_trailing(trailing);
+        }
+        visit_inline_table_mut(self, node);
+    }
+}
+
+impl Default for NewlineStyle {
+    fn default() -> Self {
+        NewlineStyle::Lf
+    }
 }
 
 impl Default for Document {
@@ -61,6 +1304,10 @@ impl Default for Document {
         Self {
             root: Item::Table(Table::with_pos(Some(0))),
             trailing: Default::default(),
+            bom: false,
+            newline: NewlineStyle::default(),
+            default_decor: None,
+            order: None,
         }
     }
 }
@@ -70,11 +1317,11 @@ impl FromStr for Document {
 
     /// Parses a document from a &str
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parser::parse_document(s)
+        parser::parse_document(s, ParseOptions::default(), false).map(|(doc, _)| doc)
     }
 }
 
-impl std::ops::Deref for Document {
+impl core::ops::Deref for Document {
     type Target = Table;
 
     fn deref(&self) -> &Self::Target {
@@ -82,7 +1329,7 @@ impl std::ops::Deref for Document {
     }
 }
 
-impl std::ops::DerefMut for Document {
+impl core::ops::DerefMut for Document {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_table_mut()
     }
@@ -96,3 +1343,13 @@ impl From<Table> for Document {
         }
     }
 }
+
+fn inline_decor(items: &mut KeyValuePairs, index: usize) -> &mut Decor {
+    let (_, kv) = items
+        .get_index_mut(index)
+        .expect("index is within the table");
+    kv.value
+        .as_value_mut()
+        .expect("inline tables hold values")
+        .decor_mut()
+}
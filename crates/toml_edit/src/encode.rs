@@ -1,8 +1,14 @@
-use std::fmt::{Display, Formatter, Result, Write};
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result, Write};
 
+#[cfg(feature = "datetime")]
 use toml_datetime::*;
 
-use crate::document::Document;
+use crate::collections::IndexMap;
+use crate::document::{DecorProvider, Document, NewlineStyle};
 use crate::inline_table::DEFAULT_INLINE_KEY_DECOR;
 use crate::key::Key;
 use crate::repr::{Formatted, Repr, ValueRepr};
@@ -10,15 +16,40 @@ use crate::table::{DEFAULT_KEY_DECOR, DEFAULT_KEY_PATH_DECOR, DEFAULT_TABLE_DECO
 use crate::value::{
     DEFAULT_LEADING_VALUE_DECOR, DEFAULT_TRAILING_VALUE_DECOR, DEFAULT_VALUE_DECOR,
 };
-use crate::{Array, InlineTable, Item, Table, Value};
+use crate::{
+    Array, Decor, InlineTable, InternalString, Item, ItemKind, OpaqueDatetime, Table, Value,
+};
 
 pub(crate) trait Encode {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result;
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result;
+}
+
+// A writer of TOML, which may put the line endings written through `Write` in another style
+pub(crate) trait TomlWrite: Write {
+    // Writes text kept from parsing, like a string's repr, exactly as it is
+    fn write_raw(&mut self, raw: &str) -> Result;
+
+    // The default format of what has no repr of its own
+    fn version(&self) -> EncodingVersion {
+        EncodingVersion::LATEST
+    }
+}
+
+impl TomlWrite for Formatter<'_> {
+    fn write_raw(&mut self, raw: &str) -> Result {
+        self.write_str(raw)
+    }
+}
+
+impl TomlWrite for String {
+    fn write_raw(&mut self, raw: &str) -> Result {
+        self.write_str(raw)
+    }
 }
 
 impl Encode for Key {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
-        let repr = self.to_repr();
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
+        let repr = self.to_repr_with(buf.version());
         write!(
             buf,
             "{}{}{}",
@@ -30,7 +61,7 @@ impl Encode for Key {
 }
 
 impl<'k> Encode for &'k [&'k Key] {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
         for (i, key) in self.iter().enumerate() {
             let first = i == 0;
             let last = i + 1 == self.len();
@@ -59,20 +90,19 @@ impl<T> Encode for Formatted<T>
 where
     T: ValueRepr,
 {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
-        let repr = self.to_repr();
-        write!(
-            buf,
-            "{}{}{}",
-            self.decor().prefix().unwrap_or(default_decor.0),
-            repr,
-            self.decor().suffix().unwrap_or(default_decor.1)
-        )
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
+        buf.write_str(self.decor().prefix().unwrap_or(default_decor.0))?;
+        match self.as_repr() {
+            Some(repr) => buf.write_raw(repr.as_raw())?,
+            // A generated string may span lines, which are then in the document's style
+            None => buf.write_str(self.value().to_repr_with(buf.version()).as_raw())?,
+        }
+        buf.write_str(self.decor().suffix().unwrap_or(default_decor.1))
     }
 }
 
 impl Encode for Array {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
         write!(buf, "{}[", self.decor().prefix().unwrap_or(default_decor.0))?;
 
         for (i, elem) in self.iter().enumerate() {
@@ -95,14 +125,12 @@ impl Encode for Array {
 }
 
 impl Encode for InlineTable {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
         write!(
             buf,
             "{}{{",
             self.decor().prefix().unwrap_or(default_decor.0)
         )?;
-        write!(buf, "{}", self.preamble)?;
-
         let children = self.get_values();
         let len = children.len();
         for (i, (key_path, value)) in children.into_iter().enumerate() {
@@ -118,6 +146,10 @@ impl Encode for InlineTable {
             write!(buf, "=")?;
             value.encode(buf, inner_decor)?;
         }
+        if self.trailing_comma() && len != 0 {
+            write!(buf, ",")?;
+        }
+        write!(buf, "{}", self.trailing())?;
 
         write!(
             buf,
@@ -128,13 +160,16 @@ impl Encode for InlineTable {
 }
 
 impl Encode for Value {
-    fn encode(&self, buf: &mut dyn Write, default_decor: (&str, &str)) -> Result {
+    fn encode(&self, buf: &mut dyn TomlWrite, default_decor: (&str, &str)) -> Result {
         match self {
             Value::String(repr) => repr.encode(buf, default_decor),
             Value::Integer(repr) => repr.encode(buf, default_decor),
+            Value::WideInteger(repr) => repr.encode(buf, default_decor),
             Value::Float(repr) => repr.encode(buf, default_decor),
             Value::Boolean(repr) => repr.encode(buf, default_decor),
+            #[cfg(feature = "datetime")]
             Value::Datetime(repr) => repr.encode(buf, default_decor),
+            Value::OpaqueDatetime(repr) => repr.encode(buf, default_decor),
             Value::Array(array) => array.encode(buf, default_decor),
             Value::InlineTable(table) => table.encode(buf, default_decor),
         }
@@ -142,29 +177,105 @@ impl Encode for Value {
 }
 
 impl Display for Document {
+    #[cfg(not(feature = "debug-validate"))]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_document(self, f, EncodingVersion::LATEST)
+    }
+
+    #[cfg(feature = "debug-validate")]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let mut path = Vec::new();
-        let mut last_position = 0;
-        let mut tables = Vec::new();
-        visit_nested_tables(self.as_table(), &mut path, false, &mut |t, p, is_array| {
-            if let Some(pos) = t.position() {
-                last_position = pos;
+        let mut output = String::new();
+        write_document(self, &mut output, EncodingVersion::LATEST)?;
+        crate::validate::validate(self, &output);
+        f.write_str(&output)
+    }
+}
+
+pub(crate) fn write_document(
+    doc: &Document,
+    f: &mut dyn Write,
+    version: EncodingVersion,
+) -> Result {
+    if doc.bom {
+        write!(f, "\u{FEFF}")?;
+    }
+    let mut f = DocumentWriter::new(f, doc.newline, version);
+    let mut path = Vec::new();
+    let mut positions = SortPositions::new(doc.order.as_ref());
+    let mut tables = Vec::new();
+    visit_nested_tables(doc.as_table(), &mut path, false, &mut |t, p, is_array| {
+        tables.push((positions.next(t, p), t, p.clone(), is_array));
+        Ok(())
+    })
+    .unwrap();
+
+    tables.sort_by_key(|&(id, _, _, _)| id);
+    let provider = doc.default_decor.as_ref();
+    let mut first_table = true;
+    for (_, table, path, is_array) in tables {
+        visit_table(&mut f, table, &path, is_array, provider, &mut first_table)?;
+    }
+    f.write_str(doc.trailing())
+}
+
+// Writes the line endings of whitespace, comments, and generated TOML in the document's style,
+// while text kept from parsing is written as it is
+struct DocumentWriter<'w> {
+    inner: &'w mut dyn Write,
+    newline: NewlineStyle,
+    version: EncodingVersion,
+    // Whether the last character written was a `\r`, making a following `\n` part of a `\r\n`
+    after_cr: bool,
+}
+
+impl<'w> DocumentWriter<'w> {
+    fn new(inner: &'w mut dyn Write, newline: NewlineStyle, version: EncodingVersion) -> Self {
+        Self {
+            inner,
+            newline,
+            version,
+            after_cr: false,
+        }
+    }
+}
+
+impl<'w> Write for DocumentWriter<'w> {
+    fn write_str(&mut self, s: &str) -> Result {
+        if self.newline == NewlineStyle::Lf {
+            return self.write_raw(s);
+        }
+        // Only a lone `\n` is rewritten, as a `\r\n` is already in the document's style
+        let mut start = 0;
+        for (i, _) in s.match_indices('\n') {
+            let after_cr = if i == 0 {
+                self.after_cr
+            } else {
+                s.as_bytes()[i - 1] == b'\r'
+            };
+            if !after_cr {
+                self.inner.write_str(&s[start..i])?;
+                self.inner.write_str("\r")?;
+                start = i;
             }
-            tables.push((last_position, t, p.clone(), is_array));
-            Ok(())
-        })
-        .unwrap();
+        }
+        self.write_raw(&s[start..])
+    }
+}
 
-        tables.sort_by_key(|&(id, _, _, _)| id);
-        let mut first_table = true;
-        for (_, table, path, is_array) in tables {
-            visit_table(f, table, &path, is_array, &mut first_table)?;
+impl<'w> TomlWrite for DocumentWriter<'w> {
+    fn write_raw(&mut self, raw: &str) -> Result {
+        if !raw.is_empty() {
+            self.after_cr = raw.ends_with('\r');
         }
-        self.trailing.fmt(f)
+        self.inner.write_str(raw)
+    }
+
+    fn version(&self) -> EncodingVersion {
+        self.version
     }
 }
 
-fn visit_nested_tables<'t, F>(
+pub(crate) fn visit_nested_tables<'t, F>(
     table: &'t Table,
     path: &mut Vec<&'t Key>,
     is_array_of_tables: bool,
@@ -174,12 +285,23 @@ where
     F: FnMut(&'t Table, &Vec<&'t Key>, bool) -> Result,
 {
     callback(table, path, is_array_of_tables)?;
+    visit_child_tables(table, path, callback)
+}
 
+// Visits the tables under `table`, including those under its dotted keys
+fn visit_child_tables<'t, F>(table: &'t Table, path: &mut Vec<&'t Key>, callback: &mut F) -> Result
+where
+    F: FnMut(&'t Table, &Vec<&'t Key>, bool) -> Result,
+{
     for kv in table.items.values() {
         match kv.value {
-            Item::Table(ref t) if !t.is_dotted() => {
+            Item::Table(ref t) => {
                 path.push(&kv.key);
-                visit_nested_tables(t, path, false, callback)?;
+                if t.is_dotted() {
+                    visit_child_tables(t, path, callback)?;
+                } else {
+                    visit_nested_tables(t, path, false, callback)?;
+                }
                 path.pop();
             }
             Item::ArrayOfTables(ref a) => {
@@ -195,11 +317,66 @@ where
     Ok(())
 }
 
+// Where each table was written when `Document::set_preserve_order` was called
+#[derive(Debug, Clone)]
+pub(crate) struct TableOrder(IndexMap<Vec<InternalString>, usize>);
+
+impl TableOrder {
+    pub(crate) fn record(root: &Table, current: Option<&TableOrder>) -> Self {
+        let mut positions = SortPositions::new(current);
+        let mut order = IndexMap::default();
+        visit_nested_tables(root, &mut Vec::new(), false, &mut |table, path, _| {
+            let position = positions.next(table, path);
+            // Each table of an array of tables is recorded at the last one's position
+            let last = order.entry(table_path(path)).or_insert(position);
+            *last = position.max(*last);
+            Ok(())
+        })
+        .expect("the callback never fails");
+        Self(order)
+    }
+
+    fn position(&self, path: &[&Key]) -> Option<usize> {
+        self.0.get(&table_path(path)).copied()
+    }
+}
+
+fn table_path(path: &[&Key]) -> Vec<InternalString> {
+    path.iter().map(|k| InternalString::from(k.get())).collect()
+}
+
+// The positions tables are written in, given in the order of `visit_nested_tables`
+//
+// A table without a position is written after the table visited before it, or, when the
+// document's order is preserved, where a table at the same path was or else at the end.
+pub(crate) struct SortPositions<'o> {
+    last: usize,
+    order: Option<&'o TableOrder>,
+}
+
+impl<'o> SortPositions<'o> {
+    pub(crate) fn new(order: Option<&'o TableOrder>) -> Self {
+        Self { last: 0, order }
+    }
+
+    pub(crate) fn next(&mut self, table: &Table, path: &[&Key]) -> usize {
+        match (table.position(), self.order) {
+            (Some(position), _) => {
+                self.last = position;
+                position
+            }
+            (None, Some(order)) => order.position(path).unwrap_or(usize::MAX),
+            (None, None) => self.last,
+        }
+    }
+}
+
 fn visit_table(
-    buf: &mut dyn Write,
+    buf: &mut dyn TomlWrite,
     table: &Table,
     path: &[&Key],
     is_array_of_tables: bool,
+    provider: Option<&DecorProvider>,
     first_table: &mut bool,
 ) -> Result {
     let children = table.get_values();
@@ -220,46 +397,110 @@ fn visit_table(
             *first_table = false;
         }
     } else if is_array_of_tables {
-        let default_decor = if *first_table {
-            *first_table = false;
-            ("", DEFAULT_TABLE_DECOR.1)
-        } else {
-            DEFAULT_TABLE_DECOR
-        };
-        write!(buf, "{}[[", table.decor.prefix().unwrap_or(default_decor.0))?;
+        let default_decor = default_header_decor(provider, path, true, first_table);
+        let (prefix, suffix) = decor_or(&table.decor, &default_decor);
+        write!(buf, "{}[[", prefix)?;
         path.encode(buf, DEFAULT_KEY_PATH_DECOR)?;
-        writeln!(buf, "]]{}", table.decor.suffix().unwrap_or(default_decor.1))?;
+        writeln!(buf, "]]{}", suffix)?;
     } else if is_visible_std_table {
-        let default_decor = if *first_table {
-            *first_table = false;
-            ("", DEFAULT_TABLE_DECOR.1)
-        } else {
-            DEFAULT_TABLE_DECOR
-        };
-        write!(buf, "{}[", table.decor.prefix().unwrap_or(default_decor.0))?;
+        let default_decor = default_header_decor(provider, path, false, first_table);
+        let (prefix, suffix) = decor_or(&table.decor, &default_decor);
+        write!(buf, "{}[", prefix)?;
         path.encode(buf, DEFAULT_KEY_PATH_DECOR)?;
-        writeln!(buf, "]{}", table.decor.suffix().unwrap_or(default_decor.1))?;
+        writeln!(buf, "]{}", suffix)?;
     }
     // print table body
     for (key_path, value) in children {
-        key_path.as_slice().encode(buf, DEFAULT_KEY_DECOR)?;
+        let provided = default_value_decor(provider, path, &key_path);
+        let (key_decor, value_decor) = default_key_value_decor(&provided);
+        key_path.as_slice().encode(buf, key_decor)?;
         write!(buf, "=")?;
-        value.encode(buf, DEFAULT_VALUE_DECOR)?;
+        value.encode(buf, value_decor)?;
         writeln!(buf)?;
     }
     Ok(())
 }
 
+// The decor of a header whose own decor is unset
+pub(crate) fn default_header_decor(
+    provider: Option<&DecorProvider>,
+    path: &[&Key],
+    is_array_of_tables: bool,
+    first_table: &mut bool,
+) -> Decor {
+    let kind = if is_array_of_tables {
+        ItemKind::ArrayOfTables
+    } else {
+        ItemKind::Table
+    };
+    let provided = provider.map(|p| p.decor(path, kind)).unwrap_or_default();
+    let prefix = if core::mem::take(first_table) {
+        ""
+    } else {
+        provided.prefix().unwrap_or(DEFAULT_TABLE_DECOR.0)
+    };
+    Decor::new(prefix, provided.suffix().unwrap_or(DEFAULT_TABLE_DECOR.1))
+}
+
+// The decor provided for a key/value pair, if any
+pub(crate) fn default_value_decor(
+    provider: Option<&DecorProvider>,
+    table_path: &[&Key],
+    key_path: &[&Key],
+) -> Decor {
+    match provider {
+        Some(provider) => {
+            let mut path = table_path.to_vec();
+            path.extend_from_slice(key_path);
+            provider.decor(&path, ItemKind::Value)
+        }
+        None => Decor::default(),
+    }
+}
+
+// The default decor of the key path and value of a key/value pair, given the provided decor
+pub(crate) fn default_key_value_decor(provided: &Decor) -> ((&str, &str), (&str, &str)) {
+    (
+        (
+            provided.prefix().unwrap_or(DEFAULT_KEY_DECOR.0),
+            DEFAULT_KEY_DECOR.1,
+        ),
+        (
+            DEFAULT_VALUE_DECOR.0,
+            provided.suffix().unwrap_or(DEFAULT_VALUE_DECOR.1),
+        ),
+    )
+}
+
+// Each part of `decor`, or of `default` where it is unset
+pub(crate) fn decor_or<'d>(decor: &'d Decor, default: &'d Decor) -> (&'d str, &'d str) {
+    (
+        decor
+            .prefix()
+            .or_else(|| default.prefix())
+            .unwrap_or_default(),
+        decor
+            .suffix()
+            .or_else(|| default.suffix())
+            .unwrap_or_default(),
+    )
+}
+
 impl ValueRepr for String {
     fn to_repr(&self) -> Repr {
-        to_string_repr(self, None, None)
+        self.to_repr_with(EncodingVersion::LATEST)
+    }
+
+    fn to_repr_with(&self, version: EncodingVersion) -> Repr {
+        to_string_repr(self, None, None, version)
     }
 }
 
 pub(crate) fn to_string_repr(
     value: &str,
-    style: Option<StringStyle>,
+    style: Option<QuoteStyle>,
     literal: Option<bool>,
+    version: EncodingVersion,
 ) -> Repr {
     let (style, literal) = match (style, literal) {
         (Some(style), Some(literal)) => (style, literal),
@@ -280,12 +521,13 @@ pub(crate) fn to_string_repr(
                 '\u{8}' => output.push_str("\\b"),
                 '\u{9}' => output.push_str("\\t"),
                 '\u{a}' => match style {
-                    StringStyle::NewlineTripple => output.push('\n'),
-                    StringStyle::OnelineSingle => output.push_str("\\n"),
+                    QuoteStyle::NewlineTripple => output.push('\n'),
+                    QuoteStyle::OnelineSingle => output.push_str("\\n"),
                     _ => unreachable!(),
                 },
                 '\u{c}' => output.push_str("\\f"),
                 '\u{d}' => output.push_str("\\r"),
+                '\u{1b}' if version.is_toml_1_1() => output.push_str("\\e"),
                 '\u{22}' => output.push_str("\\\""),
                 '\u{5c}' => output.push_str("\\\\"),
                 c if c <= '\u{1f}' || c == '\u{7f}' => {
@@ -300,14 +542,112 @@ pub(crate) fn to_string_repr(
     Repr::new_unchecked(output)
 }
 
+/// A version of the default format, for keeping output byte-for-byte stable across upgrades, see
+/// [`Document::to_string_with`]
+///
+/// When a release changes how values, keys, or tables without formatting of their own are
+/// written, it adds a variant for the new format and moves [`EncodingVersion::LATEST`] to it.
+/// Superseded variants keep producing their format for at least one more breaking release.
+///
+/// Every version only writes TOML 1.0, except for the `toml-1.1` feature's `V0_17Toml1_1`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum EncodingVersion {
+    /// The default format of `toml_edit` 0.17
+    V0_17,
+    /// The default format of `toml_edit` 0.17 with the TOML 1.1 syntax of the `toml-1.1`
+    /// feature: the `\e` escape and Unicode letters and digits in bare keys
+    #[cfg(feature = "toml-1.1")]
+    V0_17Toml1_1,
+}
+
+impl EncodingVersion {
+    /// The default format of this release, as written by [`Display`]
+    #[cfg(not(feature = "toml-1.1"))]
+    pub const LATEST: Self = Self::V0_17;
+    /// The default format of this release, as written by [`Display`]
+    #[cfg(feature = "toml-1.1")]
+    pub const LATEST: Self = Self::V0_17Toml1_1;
+
+    // Whether the format may use TOML 1.1 syntax
+    pub(crate) fn is_toml_1_1(self) -> bool {
+        match self {
+            Self::V0_17 => false,
+            #[cfg(feature = "toml-1.1")]
+            Self::V0_17Toml1_1 => true,
+        }
+    }
+}
+
+impl Default for EncodingVersion {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+/// How to quote a string, see [`escape_str`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StringStyle {
+    /// `"basic"`, with escapes for quotes, backslashes, and control characters
+    Basic,
+    /// `"""multi-line basic"""`, like [`StringStyle::Basic`] but with literal newlines
+    MultilineBasic,
+    /// `'literal'`, without escapes
+    Literal,
+    /// `'''multi-line literal'''`, without escapes
+    MultilineLiteral,
+}
+
+/// Quote `value` as a TOML string in `style`, escaping it as needed
+///
+/// Literal strings can't have escapes, so `None` is returned if `value` can't be written as one:
+/// it contains a control character other than tab (or newline, for
+/// [`StringStyle::MultilineLiteral`]), a `'` in a [`StringStyle::Literal`], or `'''` in a
+/// [`StringStyle::MultilineLiteral`].
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_edit::{escape_str, StringStyle};
+///
+/// assert_eq!(escape_str("say \"hi\"", StringStyle::Basic).unwrap(), r#""say \"hi\"""#);
+/// assert_eq!(escape_str(r"C:\dir", StringStyle::Literal).unwrap(), r"'C:\dir'");
+/// assert_eq!(escape_str("it's", StringStyle::Literal), None);
+/// ```
+pub fn escape_str(value: &str, style: StringStyle) -> Option<String> {
+    let is_control = |c: char| (c <= '\u{1f}' && c != '\t') || c == '\u{7f}';
+    let representable = match style {
+        StringStyle::Basic | StringStyle::MultilineBasic => true,
+        StringStyle::Literal => !value.contains(|c| is_control(c) || c == '\''),
+        StringStyle::MultilineLiteral => {
+            !value.contains(|c| is_control(c) && c != '\n') && !value.contains("'''")
+        }
+    };
+    if !representable {
+        return None;
+    }
+
+    let (quote, literal) = match style {
+        StringStyle::Basic => (QuoteStyle::OnelineSingle, false),
+        StringStyle::MultilineBasic => (QuoteStyle::NewlineTripple, false),
+        StringStyle::Literal => (QuoteStyle::OnelineSingle, true),
+        StringStyle::MultilineLiteral => (QuoteStyle::NewlineTripple, true),
+    };
+    Some(
+        to_string_repr(value, Some(quote), Some(literal), EncodingVersion::LATEST)
+            .as_raw()
+            .to_owned(),
+    )
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum StringStyle {
+pub(crate) enum QuoteStyle {
     NewlineTripple,
     OnelineTripple,
     OnelineSingle,
 }
 
-impl StringStyle {
+impl QuoteStyle {
     fn literal_start(self) -> &'static str {
         match self {
             Self::NewlineTripple => "'''\n",
@@ -344,7 +684,7 @@ impl StringStyle {
     }
 }
 
-fn infer_style(value: &str) -> (StringStyle, bool) {
+fn infer_style(value: &str) -> (QuoteStyle, bool) {
     // For doing pretty prints we store in a new String
     // because there are too many cases where pretty cannot
     // work. We need to determine:
@@ -355,7 +695,7 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
     // Doing it any other way would require multiple passes
     // to determine if a pretty string works or not.
     let mut out = String::with_capacity(value.len() * 2);
-    let mut ty = StringStyle::OnelineSingle;
+    let mut ty = QuoteStyle::OnelineSingle;
     // found consecutive single quotes
     let mut max_found_singles = 0;
     let mut found_singles = 0;
@@ -380,7 +720,7 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
                 '\\' => {
                     prefer_literal = true;
                 }
-                '\n' => ty = StringStyle::NewlineTripple,
+                '\n' => ty = QuoteStyle::NewlineTripple,
                 // Escape codes are needed if any ascii control
                 // characters are present, including \b \f \r.
                 c if c <= '\u{1f}' || c == '\u{7f}' => can_be_pretty = false,
@@ -391,7 +731,7 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
             // the string cannot be represented as pretty,
             // still check if it should be multiline
             if ch == '\n' {
-                ty = StringStyle::NewlineTripple;
+                ty = QuoteStyle::NewlineTripple;
             }
         }
     }
@@ -403,16 +743,16 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
         can_be_pretty = false;
     }
     if !can_be_pretty {
-        debug_assert!(ty != StringStyle::OnelineTripple);
+        debug_assert!(ty != QuoteStyle::OnelineTripple);
         return (ty, false);
     }
     if found_singles > max_found_singles {
         max_found_singles = found_singles;
     }
     debug_assert!(max_found_singles < 3);
-    if ty == StringStyle::OnelineSingle && max_found_singles >= 1 {
+    if ty == QuoteStyle::OnelineSingle && max_found_singles >= 1 {
         // no newlines, but must use ''' because it has ' in it
-        ty = StringStyle::OnelineTripple;
+        ty = QuoteStyle::OnelineTripple;
     }
     (ty, true)
 }
@@ -423,12 +763,20 @@ impl ValueRepr for i64 {
     }
 }
 
+impl ValueRepr for i128 {
+    fn to_repr(&self) -> Repr {
+        Repr::new_unchecked(self.to_string())
+    }
+}
+
 impl ValueRepr for f64 {
     fn to_repr(&self) -> Repr {
         to_f64_repr(*self)
     }
 }
 
+// `Display` writes the fewest digits that parse back to the same `f64`, so every float but NaN
+// payloads round-trips bit-for-bit
 fn to_f64_repr(f: f64) -> Repr {
     let repr = match (f.is_sign_negative(), f.is_nan(), f == 0.0) {
         (true, true, _) => "-nan".to_owned(),
@@ -446,14 +794,139 @@ fn to_f64_repr(f: f64) -> Repr {
     Repr::new_unchecked(repr)
 }
 
+// Writes `f` in the notation of `like`, a float's representation
+pub(crate) fn to_f64_repr_like(f: f64, like: &str) -> Repr {
+    let digits = like.trim_start_matches(['+', '-']);
+    if !f.is_finite() || digits.starts_with("inf") || digits.starts_with("nan") {
+        return to_f64_repr(f);
+    }
+
+    let (mantissa_like, exponent_like) = match digits.find(['e', 'E']) {
+        Some(i) => (&digits[..i], Some(&digits[i..])),
+        None => (digits, None),
+    };
+    let (int_like, fraction_like) = match mantissa_like.split_once('.') {
+        Some((int, fraction)) => (int, Some(fraction)),
+        None => (mantissa_like, None),
+    };
+
+    let mut repr = String::new();
+    if f.is_sign_negative() {
+        repr.push('-');
+    } else if like.starts_with('+') {
+        repr.push('+');
+    }
+    let abs = f.abs();
+    let (mantissa, exponent) = match exponent_like {
+        Some(exponent_like) => {
+            let formatted = format!("{:e}", abs);
+            let (mantissa, exponent) = formatted.split_once('e').expect("`{:e}` has an exponent");
+            (
+                mantissa.to_owned(),
+                Some((exponent.to_owned(), exponent_like)),
+            )
+        }
+        None => (format!("{}", abs), None),
+    };
+    let (int, fraction) = match mantissa.split_once('.') {
+        Some((int, fraction)) => (int, Some(fraction)),
+        None => (mantissa.as_str(), None),
+    };
+    repr.push_str(&group_digits(int, int_like, true));
+    match (fraction, fraction_like, exponent.is_some()) {
+        (Some(fraction), _, _) => {
+            repr.push('.');
+            repr.push_str(&group_digits(
+                fraction,
+                fraction_like.unwrap_or_default(),
+                false,
+            ));
+        }
+        (None, Some(_), _) | (None, None, false) => repr.push_str(".0"),
+        (None, None, true) => {}
+    }
+    if let Some((exponent, exponent_like)) = exponent {
+        repr.push_str(&exponent_like[..1]);
+        if !exponent.starts_with('-') && exponent_like[1..].starts_with('+') {
+            repr.push('+');
+        }
+        repr.push_str(&exponent);
+    }
+    Repr::new_unchecked(repr)
+}
+
+// Separates `digits` with `_` into groups the size of the last group of `like`, counting from
+// the decimal point
+fn group_digits(digits: &str, like: &str, from_right: bool) -> String {
+    let group = if from_right {
+        like.rsplit('_').next()
+    } else {
+        like.split('_').next()
+    };
+    let size = match group {
+        Some(group) if like.contains('_') && !group.is_empty() => group.len(),
+        _ => return digits.to_owned(),
+    };
+
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        let position = if from_right { digits.len() - i } else { i };
+        if i != 0 && position % size == 0 {
+            grouped.push('_');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
 impl ValueRepr for bool {
     fn to_repr(&self) -> Repr {
         Repr::new_unchecked(self.to_string())
     }
 }
 
+#[cfg(feature = "datetime")]
 impl ValueRepr for Datetime {
     fn to_repr(&self) -> Repr {
         Repr::new_unchecked(self.to_string())
     }
+
+    fn to_repr_like(&self, like: &Repr) -> Repr {
+        to_datetime_repr_like(self, like.as_raw())
+    }
+}
+
+// Digits past nanoseconds are truncated when parsing, so they are carried over from `like` as
+// long as the nanoseconds they were truncated to still match
+#[cfg(feature = "datetime")]
+pub(crate) fn to_datetime_repr_like(datetime: &Datetime, like: &str) -> Repr {
+    let mut repr = datetime.to_string();
+    if let (Some(time), Some(secfrac)) = (datetime.time, datetime_secfrac(like)) {
+        let truncated = secfrac.get(..9).and_then(|n| n.parse::<u32>().ok());
+        if 9 < secfrac.len() && truncated == Some(time.nanosecond) {
+            // `hh:mm:ss`, after `yyyy-mm-ddT` for date-times
+            let seconds_end = if datetime.date.is_some() { 19 } else { 8 };
+            let fraction_end = repr[seconds_end..]
+                .find(|c: char| c != '.' && !c.is_ascii_digit())
+                .map_or(repr.len(), |i| seconds_end + i);
+            repr.replace_range(seconds_end..fraction_end, &format!(".{}", secfrac));
+        }
+    }
+    Repr::new_unchecked(repr)
+}
+
+// Only times have a `.`, dates and offsets don't
+#[cfg(feature = "datetime")]
+pub(crate) fn datetime_secfrac(raw: &str) -> Option<&str> {
+    let (_, fraction) = raw.split_once('.')?;
+    let end = fraction
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(fraction.len());
+    Some(&fraction[..end])
+}
+
+impl ValueRepr for OpaqueDatetime {
+    fn to_repr(&self) -> Repr {
+        Repr::new_unchecked(self.as_str())
+    }
 }
@@ -0,0 +1,153 @@
+//! A pull parser for processing TOML in a single pass.
+//!
+//! [`Events`] yields each piece of a document as it is parsed, without building a
+//! [`Document`][crate::Document].  Concatenating the source of every event reproduces the input
+//! (minus any byte order mark).
+//!
+//! Only syntax is checked.  Semantic errors, like a key being defined twice, are left to the
+//! consumer.
+//!
+//! ```
+//! use toml_edit::events::{Event, Events};
+//!
+//! let input = "# config\n[package]\nname = \"foo\"\n";
+//! let mut keys = Vec::new();
+//! for event in Events::new(input) {
+//!     if let Event::KeyValue { path, .. } = event.unwrap() {
+//!         keys.push(path.last().unwrap().get().to_owned());
+//!     }
+//! }
+//! assert_eq!(keys, ["name"]);
+//! ```
+
+use alloc::vec::Vec;
+
+use nom8::bytes::any;
+use nom8::combinator::peek;
+
+use crate::key::Key;
+use crate::parser::events::{header, keyval};
+use crate::parser::prelude::*;
+use crate::parser::trivia::{comment, line_trailing, ws_newline};
+use crate::parser::TomlError;
+use crate::Value;
+
+/// A piece of a TOML document.
+#[derive(Debug, Clone)]
+pub enum Event<'i> {
+    /// Spaces, tabs, and newlines
+    Whitespace(&'i str),
+    /// A comment, including the leading `#` but not the line ending
+    Comment(&'i str),
+    /// A `[table]` or `[[array.of.tables]]` header
+    TableHeader {
+        /// The (possibly dotted) key in the header
+        path: Vec<Key>,
+        /// Whether this is an array of tables header
+        is_array: bool,
+    },
+    /// A `key = value` line, without trailing whitespace or comment
+    KeyValue {
+        /// The (possibly dotted) key, relative to the most recent table header
+        path: Vec<Key>,
+        /// The value, decorated with the whitespace following `=`
+        value: Value,
+    },
+}
+
+/// An iterator over the [`Event`]s of a TOML document.
+///
+/// Iteration stops after the first error.
+#[derive(Debug, Clone)]
+pub struct Events<'i> {
+    original: &'i str,
+    offset: usize,
+    line_start: bool,
+    done: bool,
+}
+
+impl<'i> Events<'i> {
+    /// Start parsing `input`.
+    pub fn new(input: &'i str) -> Self {
+        let offset = if input.starts_with('\u{feff}') { 3 } else { 0 };
+        Self {
+            original: input,
+            offset,
+            line_start: true,
+            done: false,
+        }
+    }
+
+    /// The byte offset in the input of the next event.
+    ///
+    /// Read before and after calling `next` to get the span of an event.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn parse_next(&mut self) -> Result<Option<Event<'i>>, ParserError<'i>> {
+        let original = new_input(self.original);
+        let rest = &original[self.offset..];
+        if rest.is_empty() {
+            return Ok(None);
+        }
+
+        let (after, event) = match ws_newline(rest)? {
+            (after, whitespace) if !whitespace.is_empty() => {
+                if whitespace.contains('\n') {
+                    self.line_start = true;
+                }
+                (after, Event::Whitespace(whitespace))
+            }
+            _ => match peek(any)(rest)?.1 {
+                crate::parser::trivia::COMMENT_START_SYMBOL => {
+                    let (after, c) = comment(rest)?;
+                    let c = core::str::from_utf8(c).expect("comments are checked for UTF-8");
+                    self.line_start = false;
+                    (after, Event::Comment(c))
+                }
+                _ if !self.line_start => {
+                    // Everything else must start a line, so report what should have come instead
+                    line_trailing
+                        .context(Context::Expected(ParserValue::CharLiteral('\n')))
+                        .context(Context::Expected(ParserValue::CharLiteral('#')))
+                        .parse(rest)?;
+                    unreachable!("`line_trailing` fails on anything but a comment or newline")
+                }
+                crate::parser::table::STD_TABLE_OPEN => {
+                    let (after, (path, is_array)) = header(rest)?;
+                    self.line_start = false;
+                    (after, Event::TableHeader { path, is_array })
+                }
+                _ => {
+                    let (after, (path, value)) = keyval(rest)?;
+                    self.line_start = false;
+                    (after, Event::KeyValue { path, value })
+                }
+            },
+        };
+        self.offset += rest.len() - after.len();
+        Ok(Some(event))
+    }
+}
+
+impl<'i> Iterator for Events<'i> {
+    type Item = Result<Event<'i>, TomlError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.parse_next() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(TomlError::new(err, new_input(self.original))))
+            }
+        }
+    }
+}
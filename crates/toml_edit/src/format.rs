@@ -0,0 +1,630 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::encode::{visit_nested_tables, SortPositions, TableOrder};
+use crate::{Array, Decor, Document, InlineTable, Item, Table, Value};
+
+/// Options for [`Document::format`]
+///
+/// ```
+/// use toml_edit::{Document, FormatOptions};
+///
+/// let mut doc = "[a]\nx=1\n[a.b]\ny    =    [ 1,2 ]\n".parse::<Document>().unwrap();
+/// doc.format(&FormatOptions::new().indent(2).indent_tables(true));
+/// assert_eq!(doc.to_string(), "[a]\nx = 1\n\n  [a.b]\n  y = [1, 2]\n");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    indent: usize,
+    indent_tables: bool,
+    space_around_eq: bool,
+    array_width: usize,
+    trailing_comma: bool,
+    trailing_newline: bool,
+    shortest_floats: bool,
+}
+
+impl FormatOptions {
+    /// Default formatting options
+    pub fn new() -> Self {
+        Self {
+            indent: 4,
+            indent_tables: false,
+            space_around_eq: true,
+            array_width: 80,
+            trailing_comma: true,
+            trailing_newline: true,
+            shortest_floats: false,
+        }
+    }
+
+    /// Options whose output never changes between releases, for generated files that are
+    /// committed
+    ///
+    /// [`FormatOptions::new`] follows the preferred style of each release, while these stay the
+    /// defaults of `toml_edit` 0.17.  Formatting with them, and writing the document with
+    /// [`Document::to_string_with`] and an [`EncodingVersion`][crate::EncodingVersion] for the
+    /// items without formatting of their own, gives the same bytes on every platform and version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, EncodingVersion, FormatOptions};
+    ///
+    /// let mut doc = "b={x=1}\n[a]\ny=[ 1,2 ]\n".parse::<Document>().unwrap();
+    /// doc.format(&FormatOptions::deterministic());
+    /// assert_eq!(
+    ///     doc.to_string_with(EncodingVersion::V0_17),
+    ///     "b = { x = 1 }\n\n[a]\ny = [1, 2]\n"
+    /// );
+    /// ```
+    pub fn deterministic() -> Self {
+        Self {
+            indent: 4,
+            indent_tables: false,
+            space_around_eq: true,
+            array_width: 80,
+            trailing_comma: true,
+            trailing_newline: true,
+            shortest_floats: false,
+        }
+    }
+
+    /// Indent by `indent` spaces per level, defaulting to 4
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Indent tables, along with their key/value pairs, by how deeply they are nested within
+    /// other tables
+    ///
+    /// Top-level tables are not indented.  Defaults to `false`.
+    pub fn indent_tables(mut self, yes: bool) -> Self {
+        self.indent_tables = yes;
+        self
+    }
+
+    /// Surround the `=` of key/value pairs with spaces, defaulting to `true`
+    pub fn space_around_eq(mut self, yes: bool) -> Self {
+        self.space_around_eq = yes;
+        self
+    }
+
+    /// Wrap arrays that would make their line longer than `width` characters, defaulting to 80
+    ///
+    /// Wrapped arrays put each element on its own line, indented one level, like
+    /// [`Array::fmt_multiline`].  Arrays within inline tables are never wrapped.
+    pub fn array_width(mut self, width: usize) -> Self {
+        self.array_width = width;
+        self
+    }
+
+    /// End the last element of wrapped arrays with a comma, defaulting to `true`
+    pub fn trailing_comma(mut self, yes: bool) -> Self {
+        self.trailing_comma = yes;
+        self
+    }
+
+    /// End a non-empty document with a newline, defaulting to `true`
+    ///
+    /// Key/value pairs and headers always end their line, so this only affects documents ending
+    /// in a comment.
+    pub fn trailing_newline(mut self, yes: bool) -> Self {
+        self.trailing_newline = yes;
+        self
+    }
+
+    /// Rewrite floats with the fewest digits that parse back to the same value, defaulting to
+    /// `false`
+    ///
+    /// Floats written by `toml_edit` already have their shortest digits, while parsed ones keep
+    /// the digits they were written with, like `3.14159265358979323846`.  Their notation is kept,
+    /// including an exponent or `_` separators.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, FormatOptions};
+    ///
+    /// let mut doc = "pi = 3.14159265358979323846\nc = 2.99792458000e8\n"
+    ///     .parse::<Document>()
+    ///     .unwrap();
+    /// doc.format(&FormatOptions::new().shortest_floats(true));
+    /// assert_eq!(doc.to_string(), "pi = 3.141592653589793\nc = 2.99792458e8\n");
+    /// ```
+    pub fn shortest_floats(mut self, yes: bool) -> Self {
+        self.shortest_floats = yes;
+        self
+    }
+
+    fn eq(&self) -> &'static str {
+        if self.space_around_eq {
+            " "
+        } else {
+            ""
+        }
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Document {
+    /// Lay out the whole document according to `options`.
+    ///
+    /// Unlike [`Table::fmt_recursive`], which resets formatting to the defaults, this keeps
+    /// comments, single blank lines between key/value pairs, and the representation of keys and
+    /// values, like a string's quotes or an integer's base, while normalizing everything else:
+    /// whitespace, indentation, blank lines before headers, and the wrapping of arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, FormatOptions};
+    ///
+    /// let input = r#"
+    ///
+    /// [package]   # the crate
+    /// name="demo"
+    /// authors=[ "first author", "second author" ]
+    ///
+    ///
+    /// ## more
+    /// version =   0x1
+    /// [dependencies]
+    /// serde={version="1",features=["derive"]}
+    /// "#;
+    /// let mut doc = input.parse::<Document>().unwrap();
+    /// doc.format(&FormatOptions::new().array_width(40));
+    /// assert_eq!(doc.to_string(), r#"[package] # the crate
+    /// name = "demo"
+    /// authors = [
+    ///     "first author",
+    ///     "second author",
+    /// ]
+    ///
+    /// ## more
+    /// version = 0x1
+    ///
+    /// [dependencies]
+    /// serde = { version = "1", features = ["derive"] }
+    /// "#);
+    /// ```
+    pub fn format(&mut self, options: &FormatOptions) {
+        format_table(self.as_table_mut(), 0, options);
+        let order = self.order.clone();
+        remove_leading_blank_line(self.as_table_mut(), order.as_ref());
+
+        let trailing = own_line_comments(self.trailing())
+            .map(|comment| format!("{}\n", comment))
+            .collect::<String>();
+        let blank = if has_blank_line(self.trailing()) && !trailing.is_empty() {
+            "\n"
+        } else {
+            ""
+        };
+        let mut trailing = format!("{}{}", blank, trailing);
+        if !options.trailing_newline {
+            trailing.pop();
+        }
+        self.set_trailing(trailing);
+    }
+}
+
+impl Table {
+    /// Lay out the table and everything nested within it according to `options`.
+    ///
+    /// Like [`Document::format`], but limited to this table's subtree, so the rest of a document
+    /// keeps its formatting.  The table is laid out as if it were at the top level, indenting the
+    /// tables nested within it relative to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, FormatOptions};
+    ///
+    /// let mut doc = "[a]\nx   =   1\n\n[b]\ny=[ 1,2 ]\n".parse::<Document>().unwrap();
+    /// let options = FormatOptions::new().space_around_eq(false);
+    /// doc["b"].as_table_mut().unwrap().fmt_with(&options);
+    /// assert_eq!(doc.to_string(), "[a]\nx   =   1\n\n[b]\ny=[1, 2]\n");
+    /// ```
+    pub fn fmt_with(&mut self, options: &FormatOptions) {
+        format_table(self, 1, options);
+    }
+}
+
+impl Item {
+    /// Lay out the item and everything nested within it according to `options`.
+    ///
+    /// See [`Table::fmt_with`] and [`Value::fmt_with`].
+    pub fn fmt_with(&mut self, options: &FormatOptions) {
+        match self {
+            Item::None => {}
+            Item::Value(value) => value.fmt_with(options),
+            Item::Table(table) => table.fmt_with(options),
+            Item::ArrayOfTables(array) => {
+                for table in array.iter_mut() {
+                    table.fmt_with(options);
+                }
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Lay out the inside of the value, and everything nested within it, according to `options`.
+    ///
+    /// Like [`Document::format`], this keeps representations and comments.  The value's own decor
+    /// is left alone, as it belongs to its parent's layout, and arrays are wrapped as if the value
+    /// started a line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{FormatOptions, Value};
+    ///
+    /// let mut value = "{a=0x10,b=[ 'x' ]}".parse::<Value>().unwrap();
+    /// value.fmt_with(&FormatOptions::new());
+    /// assert_eq!(value.to_string(), "{ a = 0x10, b = ['x'] }");
+    /// ```
+    pub fn fmt_with(&mut self, options: &FormatOptions) {
+        format_value(self, 0, "", true, options);
+    }
+}
+
+impl Array {
+    /// Lay out the array and everything nested within it according to `options`.
+    ///
+    /// See [`Value::fmt_with`].
+    pub fn fmt_with(&mut self, options: &FormatOptions) {
+        format_array(self, 0, "", true, options);
+    }
+}
+
+impl InlineTable {
+    /// Lay out the table and everything nested within it according to `options`.
+    ///
+    /// See [`Value::fmt_with`].
+    pub fn fmt_with(&mut self, options: &FormatOptions) {
+        format_inline_table(self, options);
+    }
+}
+
+fn format_table(table: &mut Table, depth: usize, options: &FormatOptions) {
+    let indent = if options.indent_tables {
+        " ".repeat(options.indent * depth.saturating_sub(1))
+    } else {
+        String::new()
+    };
+    if depth != 0 && !table.is_dotted() {
+        // The blank line is removed from the first table of the document
+        let prefix = line_prefix(table.decor.prefix(), &indent, false);
+        let suffix = same_line_comment(table.decor.suffix().unwrap_or_default());
+        table.decor = Decor::new(format!("\n{}", prefix), suffix);
+    }
+
+    let mut first = true;
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::None => {}
+            Item::Table(child) if !child.is_dotted() => {
+                kv.key.decor.clear();
+                format_table(child, depth + 1, options);
+            }
+            Item::ArrayOfTables(array) => {
+                kv.key.decor.clear();
+                for child in array.iter_mut() {
+                    format_table(child, depth + 1, options);
+                }
+            }
+            _ => {
+                let prefix = line_prefix(kv.key.decor.prefix(), &indent, !first);
+                kv.key.decor.set_prefix(prefix);
+                let column = indent.len();
+                format_entry(&mut kv.key, &mut kv.value, column, &indent, depth, options);
+                first = false;
+            }
+        }
+    }
+}
+
+// Formats a key/value pair, or dotted keys, given the key's prefix and the column of the key
+fn format_entry(
+    key: &mut crate::Key,
+    item: &mut Item,
+    column: usize,
+    indent: &str,
+    depth: usize,
+    options: &FormatOptions,
+) {
+    let column = column + key.to_repr().as_raw().chars().count();
+    match item {
+        Item::Value(value) => {
+            key.decor.set_suffix(options.eq());
+            let suffix = same_line_comment(value.decor().suffix().unwrap_or_default());
+            *value.decor_mut() = Decor::new(options.eq(), suffix);
+            let column = column + 2 * options.eq().len() + 1;
+            format_value(value, column, indent, true, options);
+        }
+        Item::Table(table) if table.is_dotted() => {
+            key.decor.set_suffix("");
+            for kv in table.items.values_mut() {
+                kv.key.decor.set_prefix("");
+                format_entry(
+                    &mut kv.key,
+                    &mut kv.value,
+                    column + 1,
+                    indent,
+                    depth,
+                    options,
+                );
+            }
+        }
+        Item::Table(table) => format_table(table, depth + 1, options),
+        Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                format_table(table, depth + 1, options);
+            }
+        }
+        Item::None => {}
+    }
+}
+
+// Formats the inside of `value`, starting at `column` of a line indented by `indent`
+fn format_value(
+    value: &mut Value,
+    column: usize,
+    indent: &str,
+    wrap: bool,
+    options: &FormatOptions,
+) {
+    match value {
+        Value::Array(array) => format_array(array, column, indent, wrap, options),
+        Value::InlineTable(table) => format_inline_table(table, options),
+        Value::Float(f) if options.shortest_floats => {
+            let value = *f.value();
+            f.set_value_preserving_repr_style(value);
+        }
+        _ => {}
+    }
+}
+
+fn format_array(
+    array: &mut Array,
+    column: usize,
+    indent: &str,
+    wrap: bool,
+    options: &FormatOptions,
+) {
+    // Comments before each element, and then before the closing bracket, each split into one
+    // ending the previous line and those on their own lines
+    let mut comments = Vec::with_capacity(array.len() + 1);
+    let mut previous_suffix = String::new();
+    for value in array.iter() {
+        let prefix = value.decor().prefix().unwrap_or_default();
+        comments.push(split_comments(prefix, &previous_suffix));
+        previous_suffix = value.decor().suffix().unwrap_or_default().to_owned();
+    }
+    comments.push(split_comments(array.trailing(), &previous_suffix));
+    let has_comments = comments
+        .iter()
+        .any(|(same_line, own_lines)| same_line.is_some() || !own_lines.is_empty());
+    if has_comments && !wrap {
+        return;
+    }
+
+    if !has_comments {
+        array.fmt();
+        for value in array.iter_mut() {
+            format_value(value, 0, indent, false, options);
+        }
+        let mut single_line = array.clone();
+        single_line.decor_mut().clear();
+        let width = single_line.to_string().chars().count();
+        if !wrap || column + width <= options.array_width || array.is_empty() {
+            return;
+        }
+    }
+
+    let inner = format!("{}{}", indent, " ".repeat(options.indent));
+    let mut comments = comments.into_iter();
+    for value in array.iter_mut() {
+        let (same_line, own_lines) = comments.next().expect("one per element");
+        let prefix = comment_lines(same_line.as_deref(), &own_lines, &inner, &inner);
+        *value.decor_mut() = Decor::new(prefix, "");
+        format_value(value, inner.len(), &inner, true, options);
+    }
+    let (same_line, own_lines) = comments.next().expect("one for the closing bracket");
+    let trailing = comment_lines(same_line.as_deref(), &own_lines, &inner, indent);
+    array.set_trailing_comma(options.trailing_comma && !array.is_empty());
+    array.set_trailing(trailing);
+}
+
+fn format_inline_table(table: &mut InlineTable, options: &FormatOptions) {
+    // Comments are only allowed by TOML 1.1, requiring the table to span lines
+    if has_inline_comments(table) {
+        return;
+    }
+    table.set_trailing_comma(false);
+    table.set_trailing("");
+    let len = table.get_values().len();
+    format_inline_entries(table, true, &mut 0, len, options);
+}
+
+fn format_inline_entries(
+    table: &mut InlineTable,
+    first_key: bool,
+    count: &mut usize,
+    len: usize,
+    options: &FormatOptions,
+) {
+    let prefix = if first_key { " " } else { "" };
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::Value(Value::InlineTable(child)) if child.is_dotted() => {
+                kv.key.decor = Decor::new(prefix, "");
+                format_inline_entries(child, false, count, len, options);
+            }
+            Item::Value(value) => {
+                kv.key.decor = Decor::new(prefix, options.eq());
+                *count += 1;
+                let suffix = if *count == len { " " } else { "" };
+                *value.decor_mut() = Decor::new(options.eq(), suffix);
+                format_value(value, 0, "", false, options);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn has_inline_comments(table: &InlineTable) -> bool {
+    let has_comment = |decor: &Decor| {
+        [decor.prefix(), decor.suffix()]
+            .iter()
+            .any(|s| s.unwrap_or_default().contains('#'))
+    };
+    table.trailing().contains('#')
+        || table.items.values().any(|kv| {
+            has_comment(&kv.key.decor)
+                || match &kv.value {
+                    Item::Value(Value::InlineTable(child)) if child.is_dotted() => {
+                        has_inline_comments(child)
+                    }
+                    Item::Value(value) => has_comment(value.decor()),
+                    _ => false,
+                }
+        })
+}
+
+// Removes the blank line before the first header when nothing comes before it
+fn remove_leading_blank_line(root: &mut Table, order: Option<&TableOrder>) {
+    let first = match first_written_table(root, order) {
+        Some(first) if first != 0 => first,
+        _ => return,
+    };
+    let mut n = first;
+    let table = nth_table_mut(root, &mut n).expect("found by `first_written_table`");
+    let prefix = table.decor.prefix().unwrap_or_default();
+    let prefix = prefix.strip_prefix('\n').unwrap_or(prefix).to_owned();
+    table.decor.set_prefix(prefix);
+}
+
+// The index of the first table written, in the order of `visit_nested_tables`: the root if it has
+// values, otherwise the first header by position
+pub(crate) fn first_written_table(root: &Table, order: Option<&TableOrder>) -> Option<usize> {
+    let mut tables = Vec::new();
+    let mut positions = SortPositions::new(order);
+    visit_nested_tables(
+        root,
+        &mut Vec::new(),
+        false,
+        &mut |table, path, is_array| {
+            let position = positions.next(table, path);
+            let has_values = !table.get_values().is_empty();
+            let visible = if tables.is_empty() {
+                has_values
+            } else {
+                is_array || !table.is_implicit() || has_values
+            };
+            tables.push((position, visible));
+            Ok(())
+        },
+    )
+    .expect("the callback never fails");
+    (0..tables.len())
+        .filter(|i| tables[*i].1)
+        .min_by_key(|i| tables[*i].0)
+}
+
+// The table `n` steps into the order of `visit_nested_tables`
+pub(crate) fn nth_table_mut<'t>(table: &'t mut Table, n: &mut usize) -> Option<&'t mut Table> {
+    if *n == 0 {
+        return Some(table);
+    }
+    *n -= 1;
+    for kv in table.items.values_mut() {
+        let found = match &mut kv.value {
+            Item::Table(child) if !child.is_dotted() => nth_table_mut(child, n),
+            Item::ArrayOfTables(array) => {
+                array.iter_mut().find_map(|child| nth_table_mut(child, n))
+            }
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+// The prefix of a line starting with `indent`, keeping comments on the lines before it and, if
+// `blank`, one blank line
+fn line_prefix(raw: Option<&str>, indent: &str, blank: bool) -> String {
+    let raw = raw.unwrap_or_default();
+    let mut prefix = String::new();
+    if blank && has_blank_line(raw) {
+        prefix.push('\n');
+    }
+    for comment in own_line_comments(raw) {
+        prefix.push_str(indent);
+        prefix.push_str(comment);
+        prefix.push('\n');
+    }
+    prefix.push_str(indent);
+    prefix
+}
+
+// Whether whitespace starting a line contains a blank line
+fn has_blank_line(raw: &str) -> bool {
+    let mut lines = raw.split('\n');
+    lines.next_back();
+    lines.any(|line| line.trim().is_empty())
+}
+
+pub(crate) fn own_line_comments(raw: &str) -> impl Iterator<Item = &str> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+}
+
+// A comment at the end of a line, after a key/value pair or header
+fn same_line_comment(raw: &str) -> String {
+    own_line_comments(raw)
+        .next()
+        .map(|comment| format!(" {}", comment))
+        .unwrap_or_default()
+}
+
+// Splits the comments between array elements into the one ending the line of the comma and
+// those on their own lines, with comments before the comma moved after it
+fn split_comments(raw: &str, before_comma: &str) -> (Option<String>, Vec<String>) {
+    let (first_line, rest) = raw.split_once('\n').unwrap_or((raw, ""));
+    let same_line = own_line_comments(first_line).next().map(str::to_owned);
+    let own_lines = own_line_comments(before_comma)
+        .chain(own_line_comments(rest))
+        .map(str::to_owned)
+        .collect();
+    (same_line, own_lines)
+}
+
+fn comment_lines(same_line: Option<&str>, own_lines: &[String], inner: &str, end: &str) -> String {
+    let mut lines = String::new();
+    if let Some(comment) = same_line {
+        lines.push(' ');
+        lines.push_str(comment);
+    }
+    for comment in own_lines {
+        lines.push('\n');
+        lines.push_str(inner);
+        lines.push_str(comment);
+    }
+    lines.push('\n');
+    lines.push_str(end);
+    lines
+}
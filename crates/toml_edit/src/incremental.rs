@@ -0,0 +1,182 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::collections::IndexMap;
+use crate::events::{Event, Events};
+use crate::{Document, Item, Table, TomlError};
+
+impl Document {
+    /// Update the document for a text edit, where `input` is the text it was parsed from.
+    ///
+    /// `range` is the byte range in `input` being replaced with `replacement`.  When the edit is
+    /// confined to the key/value pairs of a single table, only that table's lines are reparsed
+    /// and the rest of the document is left as-is.  Otherwise, this falls back to reparsing the
+    /// whole edited text.
+    ///
+    /// On error, the document is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of bounds or does not fall on `char` boundaries of `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "[a]\nb = 1\n\n[c]\nd = 2\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// doc.reparse_edit(input, 8..9, "10").unwrap();
+    /// assert_eq!(doc.to_string(), "[a]\nb = 10\n\n[c]\nd = 2\n");
+    /// ```
+    pub fn reparse_edit(
+        &mut self,
+        input: &str,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Result<(), TomlError> {
+        let mut edited = String::with_capacity(input.len() - range.len() + replacement.len());
+        edited.push_str(&input[..range.start]);
+        edited.push_str(replacement);
+        edited.push_str(&input[range.end..]);
+
+        if let Some(block) = find_block(input, &range) {
+            let new_end = block.lines.end - range.len() + replacement.len();
+            let block_text = &edited[block.lines.start..new_end];
+            let at_line_end = block_text.ends_with('\n') || new_end == edited.len();
+            if at_line_end && self.patch_block(&block, block_text) {
+                return Ok(());
+            }
+        }
+
+        *self = edited.parse::<Document>()?;
+        Ok(())
+    }
+
+    // Returns `false` if the block can't be patched in isolation
+    fn patch_block(&mut self, block: &Block, text: &str) -> bool {
+        let fragment = match text.parse::<Document>() {
+            Ok(fragment) => fragment,
+            Err(_) => return false,
+        };
+        // Text after the last key/value pair belongs to the next header, and headers start
+        // their own block
+        let fragment_has_headers = fragment.as_table().iter().any(|(_, item)| match item {
+            Item::Table(t) => !t.is_dotted(),
+            Item::ArrayOfTables(_) => true,
+            _ => false,
+        });
+        if !fragment.trailing().is_empty() || fragment_has_headers {
+            return false;
+        }
+
+        let table = match block_table(self.as_table_mut(), block) {
+            Some(table) => table,
+            None => return false,
+        };
+        // Keep the children defined by headers elsewhere in the document
+        let mut items = fragment.root.into_table().expect("root is a table").items;
+        for (key, kv) in table.items.iter() {
+            let from_header = match &kv.value {
+                Item::Table(t) => !t.is_dotted(),
+                Item::ArrayOfTables(_) => true,
+                _ => false,
+            };
+            if from_header {
+                if items.contains_key(key) {
+                    return false;
+                }
+                items.insert(key.clone(), kv.clone());
+            }
+        }
+        table.items = items;
+        true
+    }
+}
+
+/// The key/value lines following a header, or before the first header
+struct Block {
+    header: Vec<String>,
+    // For each key in `header` naming an array of tables, the index of the table in use
+    indices: Vec<Option<usize>>,
+    // From after the header line through the end of the last key/value line
+    lines: Range<usize>,
+}
+
+fn find_block(input: &str, edit: &Range<usize>) -> Option<Block> {
+    let mut counts = IndexMap::<Vec<String>, usize>::default();
+    let mut events = Events::new(input);
+    let mut current = Block {
+        header: Vec::new(),
+        indices: Vec::new(),
+        lines: events.offset()..events.offset(),
+    };
+    let mut has_values = false;
+    loop {
+        let start = events.offset();
+        let event = match events.next() {
+            Some(Ok(event)) => event,
+            Some(Err(_)) => return None,
+            None => break,
+        };
+        let end = events.offset();
+        match event {
+            Event::TableHeader { path, is_array } => {
+                if has_values && contains(&current.lines, edit) {
+                    return Some(current);
+                }
+                if start < edit.end {
+                    has_values = false;
+                    let header = path.iter().map(|k| k.get().to_owned()).collect::<Vec<_>>();
+                    if is_array {
+                        // Arrays of tables nested in the new element start over
+                        counts.retain(|path, _| {
+                            path.len() <= header.len() || !path.starts_with(&header)
+                        });
+                        *counts.entry(header.clone()).or_default() += 1;
+                    }
+                    let indices = (1..=header.len())
+                        .map(|i| counts.get(&header[..i]).map(|c| c - 1))
+                        .collect();
+                    let line_end = line_end(input, end);
+                    current = Block {
+                        header,
+                        indices,
+                        lines: line_end..line_end,
+                    };
+                } else {
+                    break;
+                }
+            }
+            Event::KeyValue { .. } => {
+                has_values = true;
+                current.lines.end = line_end(input, end);
+            }
+            Event::Whitespace(_) | Event::Comment(_) => {}
+        }
+    }
+    (has_values && contains(&current.lines, edit)).then(|| current)
+}
+
+fn contains(lines: &Range<usize>, edit: &Range<usize>) -> bool {
+    lines.start <= edit.start && edit.end <= lines.end
+}
+
+// The remainder of a line is whitespace and comments, so the line ends at the next newline
+fn line_end(input: &str, offset: usize) -> usize {
+    input[offset..]
+        .find('\n')
+        .map(|i| offset + i + 1)
+        .unwrap_or(input.len())
+}
+
+fn block_table<'t>(mut table: &'t mut Table, block: &Block) -> Option<&'t mut Table> {
+    for (key, index) in block.header.iter().zip(block.indices.iter()) {
+        table = match (table.get_mut(key)?, index) {
+            (Item::Table(t), _) => t,
+            (Item::ArrayOfTables(a), Some(index)) => a.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    Some(table)
+}
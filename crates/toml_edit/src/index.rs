@@ -1,4 +1,5 @@
-use std::ops;
+use alloc::string::String;
+use core::ops;
 
 use crate::document::Document;
 use crate::key::Key;
@@ -1,16 +1,20 @@
-use std::iter::FromIterator;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
 
 use crate::key::Key;
-use crate::repr::Decor;
+use crate::repr::{Decor, Repr};
 use crate::table::{Iter, IterMut, KeyValuePairs, TableKeyValue, TableLike};
-use crate::{InternalString, Item, KeyMut, Table, Value};
+use crate::{ArrayOfTables, InternalString, Item, KeyMut, Table, Value};
 
 /// Type representing a TOML inline table,
 /// payload of the `Value::InlineTable` variant
 #[derive(Debug, Default, Clone)]
 pub struct InlineTable {
-    // `preamble` represents whitespaces in an empty table
-    pub(crate) preamble: InternalString,
+    // whitespace after the last key/value pair or its trailing comma, or within an empty table
+    trailing: InternalString,
+    trailing_comma: bool,
     // prefix before `{` and suffix after `}`
     decor: Decor,
     // whether this is a proxy for dotted keys
@@ -40,6 +44,26 @@ impl InlineTable {
         t.fmt();
         t
     }
+
+    /// Convert to a table, along with every inline table nested within it
+    ///
+    /// Nested inline tables become subtables and non-empty arrays of only inline tables become
+    /// arrays of tables, while dotted keys stay dotted.  Within dotted keys, where headers can't
+    /// be written, nested inline tables become dotted keys as well and arrays are left as-is.
+    /// This is the inverse of [`Table::into_inline_table_recursive`].
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "a = { b.c = 1, d = { e = 2 }, f = [{ g = 3 }] }\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let a = doc.remove("a").and_then(|a| a.as_inline_table().cloned()).unwrap();
+    /// doc["a"] = toml_edit::Item::Table(a.into_table_recursive());
+    /// assert_eq!(doc.to_string(), "[a]\nb.c = 1\n\n[a.d]\ne = 2\n\n[[a.f]]\ng = 3\n");
+    /// ```
+    pub fn into_table_recursive(self) -> Table {
+        into_table_recursive(self, false)
+    }
 }
 
 /// Formatting
@@ -79,6 +103,39 @@ impl InlineTable {
         decorate_inline_table(self);
     }
 
+    /// Set whether the table will use a trailing comma
+    ///
+    /// Trailing commas are only allowed by the TOML 1.1 draft, see the `toml-1.1` feature.
+    pub fn set_trailing_comma(&mut self, yes: bool) {
+        self.trailing_comma = yes;
+    }
+
+    /// Whether the table will use a trailing comma
+    pub fn trailing_comma(&self) -> bool {
+        self.trailing_comma
+    }
+
+    /// Set whitespace after the last key/value pair, or within an empty table
+    ///
+    /// Newlines and comments are only allowed by the TOML 1.1 draft, see the `toml-1.1` feature.
+    pub fn set_trailing(&mut self, trailing: impl Into<InternalString>) {
+        self.trailing = trailing.into();
+    }
+
+    /// Whitespace after the last key/value pair, or within an empty table
+    pub fn trailing(&self) -> &str {
+        self.trailing.as_str()
+    }
+
+    /// Auto formats the table, its keys, and everything nested within it.
+    pub fn fmt_recursive(&mut self) {
+        for kv in self.items.values_mut() {
+            kv.key.fmt();
+            kv.value.fmt_recursive();
+        }
+        decorate_inline_table(self);
+    }
+
     /// Sorts the key/value pairs by key.
     pub fn sort_values(&mut self) {
         // Assuming standard tables have their position set and this won't negatively impact them
@@ -99,25 +156,25 @@ impl InlineTable {
     /// values or their combination as needed).
     pub fn sort_values_by<F>(&mut self, mut compare: F)
     where
-        F: FnMut(&Key, &Value, &Key, &Value) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Value, &Key, &Value) -> core::cmp::Ordering,
     {
         self.sort_values_by_internal(&mut compare);
     }
 
     fn sort_values_by_internal<F>(&mut self, compare: &mut F)
     where
-        F: FnMut(&Key, &Value, &Key, &Value) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Value, &Key, &Value) -> core::cmp::Ordering,
     {
         let modified_cmp = |_: &InternalString,
                             val1: &TableKeyValue,
                             _: &InternalString,
                             val2: &TableKeyValue|
-         -> std::cmp::Ordering {
+         -> core::cmp::Ordering {
             match (val1.value.as_value(), val2.value.as_value()) {
                 (Some(v1), Some(v2)) => compare(&val1.key, v1, &val2.key, v2),
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (None, Some(_)) => std::cmp::Ordering::Less,
-                (None, None) => std::cmp::Ordering::Equal,
+                (Some(_), None) => core::cmp::Ordering::Greater,
+                (None, Some(_)) => core::cmp::Ordering::Less,
+                (None, None) => core::cmp::Ordering::Equal,
             }
         };
 
@@ -161,6 +218,13 @@ impl InlineTable {
     pub fn key_decor(&self, key: &str) -> Option<&Decor> {
         self.items.get(key).map(|kv| &kv.key.decor)
     }
+
+    /// Returns the raw representation of a given key of the table, as it appeared when parsed.
+    ///
+    /// Returns `None` if the key is absent or was not parsed or explicitly formatted.
+    pub fn get_original_repr(&self, key: &str) -> Option<&Repr> {
+        self.items.get(key).and_then(|kv| kv.key.as_repr())
+    }
 }
 
 impl InlineTable {
@@ -204,7 +268,7 @@ impl InlineTable {
         match self.items.entry(key.into()) {
             indexmap::map::Entry::Occupied(mut entry) => {
                 // Ensure it is a `Value` to simplify `InlineOccupiedEntry`'s code.
-                let scratch = std::mem::take(&mut entry.get_mut().value);
+                let scratch = core::mem::take(&mut entry.get_mut().value);
                 let scratch = Item::Value(
                     scratch
                         .into_value()
@@ -228,7 +292,7 @@ impl InlineTable {
         match self.items.entry(key.get().into()) {
             indexmap::map::Entry::Occupied(mut entry) => {
                 // Ensure it is a `Value` to simplify `InlineOccupiedEntry`'s code.
-                let scratch = std::mem::take(&mut entry.get_mut().value);
+                let scratch = core::mem::take(&mut entry.get_mut().value);
                 let scratch = Item::Value(
                     scratch
                         .into_value()
@@ -337,10 +401,27 @@ impl InlineTable {
             kv.value.into_value().ok().map(|value| (key, value))
         })
     }
+
+    /// Renames the key `old` to `new` in place, see [`Table::rename_key`].
+    pub fn rename_key(&mut self, old: &str, new: &str) -> bool {
+        crate::table::rename_key(&mut self.items, old, new)
+    }
+
+    /// Retains only the key/value pairs for which `keep` returns `true`, keeping their order and
+    /// decor.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str, &mut Value) -> bool,
+    {
+        self.items.retain(|key, kv| match kv.value.as_value_mut() {
+            Some(value) => keep(key, value),
+            None => true,
+        });
+    }
 }
 
-impl std::fmt::Display for InlineTable {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InlineTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, ("", ""))
     }
 }
@@ -391,6 +472,38 @@ impl<'s> IntoIterator for &'s InlineTable {
     }
 }
 
+fn into_table_recursive(table: InlineTable, dotted: bool) -> Table {
+    let mut items = table.items;
+    for kv in items.values_mut() {
+        kv.value = match core::mem::take(&mut kv.value) {
+            Item::Value(Value::InlineTable(table)) => {
+                kv.key.decor.clear();
+                let dotted = dotted || table.is_dotted();
+                let mut table = into_table_recursive(table, dotted);
+                table.set_dotted(dotted);
+                Item::Table(table)
+            }
+            Item::Value(Value::Array(array))
+                if !dotted && !array.is_empty() && array.iter().all(Value::is_inline_table) =>
+            {
+                kv.key.decor.clear();
+                let array = array
+                    .into_iter()
+                    .filter_map(|value| match value {
+                        Value::InlineTable(table) => Some(into_table_recursive(table, false)),
+                        _ => None,
+                    })
+                    .collect::<ArrayOfTables>();
+                Item::ArrayOfTables(array)
+            }
+            item => item,
+        };
+    }
+    let mut t = Table::with_pairs(items);
+    t.fmt();
+    t
+}
+
 fn decorate_inline_table(table: &mut InlineTable) {
     for (key_decor, value) in table
         .items
@@ -401,6 +514,8 @@ fn decorate_inline_table(table: &mut InlineTable) {
         key_decor.clear();
         value.decor_mut().clear();
     }
+    table.set_trailing_comma(false);
+    table.set_trailing("");
 }
 
 /// An owned iterator type over key/value pairs of an inline table.
@@ -492,6 +607,9 @@ impl TableLike for InlineTable {
     fn key_decor(&self, key: &str) -> Option<&Decor> {
         self.key_decor(key)
     }
+    fn get_original_repr(&self, key: &str) -> Option<&Repr> {
+        self.get_original_repr(key)
+    }
 }
 
 // `{ key1 = value1, ... }`
@@ -524,6 +642,15 @@ impl<'a> InlineEntry<'a> {
         }
     }
 
+    /// Returns the index of the entry among the table's key/value pairs, where a vacant entry
+    /// will be inserted
+    pub fn position(&self) -> usize {
+        match self {
+            InlineEntry::Occupied(e) => e.position(),
+            InlineEntry::Vacant(e) => e.position(),
+        }
+    }
+
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     pub fn or_insert(self, default: Value) -> &'a mut Value {
@@ -569,6 +696,11 @@ impl<'a> InlineOccupiedEntry<'a> {
         self.entry.get_mut().key.as_mut()
     }
 
+    /// Returns the index of the entry among the table's key/value pairs
+    pub fn position(&self) -> usize {
+        self.entry.index()
+    }
+
     /// Gets a reference to the value in the entry.
     pub fn get(&self) -> &Value {
         self.entry.get().value.as_value().unwrap()
@@ -588,7 +720,7 @@ impl<'a> InlineOccupiedEntry<'a> {
     /// Sets the value of the entry, and returns the entry's old value
     pub fn insert(&mut self, value: Value) -> Value {
         let mut value = Item::Value(value);
-        std::mem::swap(&mut value, &mut self.entry.get_mut().value);
+        core::mem::swap(&mut value, &mut self.entry.get_mut().value);
         value.into_value().unwrap()
     }
 
@@ -620,6 +752,28 @@ impl<'a> InlineVacantEntry<'a> {
         self.entry.key().as_str()
     }
 
+    /// Gets a mutable reference to the key that will be inserted, to set its decor or
+    /// representation beforehand
+    pub fn key_mut(&mut self) -> KeyMut<'_> {
+        let entry = &self.entry;
+        self.key
+            .get_or_insert_with(|| Key::new(entry.key().as_str()))
+            .as_mut()
+    }
+
+    /// Returns the index the entry will have among the table's key/value pairs, after the
+    /// existing ones
+    pub fn position(&self) -> usize {
+        self.entry.index()
+    }
+
+    /// Sets the value of the entry with the VacantEntry's key written with `decor`, and returns
+    /// a mutable reference to the value
+    pub fn insert_with_decor(mut self, value: Value, decor: Decor) -> &'a mut Value {
+        *self.key_mut().decor_mut() = decor;
+        self.insert(value)
+    }
+
     /// Sets the value of the entry with the VacantEntry's key,
     /// and returns a mutable reference to it
     pub fn insert(self, value: Value) -> &'a mut Value {
@@ -1,29 +1,52 @@
-use std::borrow::Borrow;
-use std::str::FromStr;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::str::FromStr;
 
 /// Opaque string storage internal to `toml_edit`
-#[derive(Default, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// The storage is picked by feature flag, with the first enabled one taking precedence:
+/// * `perf`: [`kstring`](https://docs.rs/kstring), storing short strings inline
+/// * `compact_str`: [`compact_str`](https://docs.rs/compact_str), storing strings of up to 24
+///   bytes inline
+/// * `arc_str`: `Arc<str>`, making clones cheap for documents that are copied often
+/// * otherwise, `String`
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InternalString(Inner);
 
-#[cfg(feature = "kstring")]
+#[cfg(feature = "perf")]
 type Inner = kstring::KString;
-#[cfg(not(feature = "kstring"))]
+#[cfg(all(feature = "compact_str", not(feature = "perf")))]
+type Inner = compact_str::CompactString;
+#[cfg(all(
+    feature = "arc_str",
+    not(any(feature = "perf", feature = "compact_str"))
+))]
+type Inner = alloc::sync::Arc<str>;
+#[cfg(not(any(feature = "perf", feature = "compact_str", feature = "arc_str")))]
 type Inner = String;
 
 impl InternalString {
     /// Create an empty string
     pub fn new() -> Self {
-        InternalString(Inner::new())
+        InternalString::from("")
     }
 
     /// Access the underlying string
     #[inline]
     pub fn as_str(&self) -> &str {
-        self.0.as_str()
+        &self.0
     }
 }
 
-impl std::ops::Deref for InternalString {
+impl Default for InternalString {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::ops::Deref for InternalString {
     type Target = str;
 
     #[inline]
@@ -49,10 +72,10 @@ impl AsRef<str> for InternalString {
 impl From<&str> for InternalString {
     #[inline]
     fn from(s: &str) -> Self {
-        #[cfg(feature = "kstring")]
+        #[cfg(feature = "perf")]
         let inner = kstring::KString::from_ref(s);
-        #[cfg(not(feature = "kstring"))]
-        let inner = String::from(s);
+        #[cfg(not(feature = "perf"))]
+        let inner = Inner::from(s);
 
         InternalString(inner)
     }
@@ -69,7 +92,7 @@ impl From<String> for InternalString {
 impl From<&String> for InternalString {
     #[inline]
     fn from(s: &String) -> Self {
-        InternalString(s.into())
+        InternalString::from(s.as_str())
     }
 }
 
@@ -95,9 +118,9 @@ impl FromStr for InternalString {
     }
 }
 
-impl std::fmt::Display for InternalString {
+impl core::fmt::Display for InternalString {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.as_str().fmt(f)
     }
 }
@@ -130,7 +153,7 @@ struct StringVisitor;
 impl<'de> serde::de::Visitor<'de> for StringVisitor {
     type Value = InternalString;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         formatter.write_str("a string")
     }
 
@@ -152,7 +175,7 @@ impl<'de> serde::de::Visitor<'de> for StringVisitor {
     where
         E: serde::de::Error,
     {
-        match std::str::from_utf8(v) {
+        match core::str::from_utf8(v) {
             Ok(s) => Ok(InternalString::from(s)),
             Err(_) => Err(serde::de::Error::invalid_value(
                 serde::de::Unexpected::Bytes(v),
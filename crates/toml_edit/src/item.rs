@@ -1,10 +1,14 @@
-use std::str::FromStr;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
 
+#[cfg(feature = "datetime")]
 use toml_datetime::*;
 
 use crate::array_of_tables::ArrayOfTables;
 use crate::table::TableLike;
-use crate::{Array, InlineTable, Table, Value};
+use crate::{Array, InlineTable, OpaqueDatetime, Repr, Table, Value};
 
 /// Type representing either a value, a table, an array of tables, or none.
 #[derive(Debug, Clone)]
@@ -19,6 +23,18 @@ pub enum Item {
     ArrayOfTables(ArrayOfTables),
 }
 
+/// What an [`Item`] is written as, see
+/// [`Document::set_default_decor_provider`][crate::Document::set_default_decor_provider]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    /// A key/value pair
+    Value,
+    /// A `[table]` header
+    Table,
+    /// An `[[array.of.tables]]` header
+    ArrayOfTables,
+}
+
 impl Item {
     /// Sets `self` to the given item iff `self` is none and
     /// returns a mutable reference to `self`.
@@ -130,7 +146,7 @@ impl Item {
     }
     /// In-place convert to a value
     pub fn make_value(&mut self) {
-        let other = std::mem::take(self);
+        let other = core::mem::take(self);
         let other = other.into_value().map(Item::Value).unwrap_or(Item::None);
         *self = other;
     }
@@ -146,26 +162,16 @@ impl Item {
     pub fn into_array_of_tables(self) -> Result<ArrayOfTables, Self> {
         match self {
             Item::ArrayOfTables(a) => Ok(a),
-            Item::Value(Value::Array(a)) => {
-                if a.is_empty() {
-                    Err(Item::Value(Value::Array(a)))
-                } else if a.iter().all(|v| v.is_inline_table()) {
-                    let mut aot = ArrayOfTables::new();
-                    aot.values = a.values;
-                    for value in aot.values.iter_mut() {
-                        value.make_item();
-                    }
-                    Ok(aot)
-                } else {
-                    Err(Item::Value(Value::Array(a)))
-                }
-            }
+            Item::Value(Value::Array(a)) => a
+                .into_array_of_tables()
+                .map_err(|a| Item::Value(Value::Array(a))),
             _ => Err(self),
         }
     }
     // Starting private because the name is unclear
+    #[cfg(feature = "serde")]
     pub(crate) fn make_item(&mut self) {
-        let other = std::mem::take(self);
+        let other = core::mem::take(self);
         let other = match other.into_table().map(crate::Item::Table) {
             Ok(i) => i,
             Err(i) => i,
@@ -176,6 +182,22 @@ impl Item {
         };
         *self = other;
     }
+    /// Auto formats the item and everything nested within it.
+    ///
+    /// See [`Table::fmt_recursive`].  Formatting is limited to this item, so it can be used to
+    /// clean up just the part of a document that was edited.
+    pub fn fmt_recursive(&mut self) {
+        match self {
+            Item::None => {}
+            Item::Value(v) => v.fmt_recursive(),
+            Item::Table(t) => t.fmt_recursive(),
+            Item::ArrayOfTables(a) => {
+                for t in a.iter_mut() {
+                    t.fmt_recursive();
+                }
+            }
+        }
+    }
     /// Returns true iff `self` is a value.
     pub fn is_value(&self) -> bool {
         self.as_value().is_some()
@@ -202,7 +224,115 @@ impl Item {
 
     /// Returns true iff `self` is an integer.
     pub fn is_integer(&self) -> bool {
-        self.as_integer().is_some()
+        self.as_value().map(Value::is_integer).unwrap_or(false)
+    }
+
+    /// Returns the comment on the lines above a table header, see [`Table::comment`].
+    ///
+    /// For an array of tables, this is the comment above its first header.  The lines above a
+    /// value belong to its key, see [`Key::comment`][crate::Key::comment].
+    pub fn comment(&self) -> Option<String> {
+        match self {
+            Item::Table(t) => t.comment(),
+            Item::ArrayOfTables(a) => a.get(0).and_then(Table::comment),
+            Item::None | Item::Value(_) => None,
+        }
+    }
+
+    /// Replaces the comment on the lines above a table header, see [`Table::set_comment`].
+    ///
+    /// For an array of tables, this is the comment above its first header.  Values have no lines
+    /// of their own above them, so this does nothing for them; use
+    /// [`KeyMut::set_comment`][crate::KeyMut::set_comment] instead.
+    pub fn set_comment(&mut self, comment: &str) {
+        match self {
+            Item::Table(t) => t.set_comment(comment),
+            Item::ArrayOfTables(a) => {
+                if let Some(t) = a.get_mut(0) {
+                    t.set_comment(comment);
+                }
+            }
+            Item::None | Item::Value(_) => {}
+        }
+    }
+
+    /// Returns the comment after a value or table header, on the same line, without its `# `, see
+    /// [`Value::trailing_comment`].
+    ///
+    /// For an array of tables, this is the comment after its first header.
+    pub fn trailing_comment(&self) -> Option<String> {
+        match self {
+            Item::Value(v) => v.trailing_comment(),
+            Item::Table(t) => t.trailing_comment(),
+            Item::ArrayOfTables(a) => a.get(0).and_then(Table::trailing_comment),
+            Item::None => None,
+        }
+    }
+
+    /// Replaces the comment after a value or table header with the first line of `comment`, see
+    /// [`Value::set_trailing_comment`].
+    ///
+    /// For an array of tables, this is the comment after its first header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "port = 80  # default\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc["port"].trailing_comment().as_deref(), Some("default"));
+    /// doc["port"].set_trailing_comment("override");
+    /// assert_eq!(doc.to_string(), "port = 80  # override\n");
+    /// ```
+    pub fn set_trailing_comment(&mut self, comment: &str) {
+        match self {
+            Item::Value(v) => v.set_trailing_comment(comment),
+            Item::Table(t) => t.set_trailing_comment(comment),
+            Item::ArrayOfTables(a) => {
+                if let Some(t) = a.get_mut(0) {
+                    t.set_trailing_comment(comment);
+                }
+            }
+            Item::None => {}
+        }
+    }
+
+    /// Returns the raw representation of the item, without its decor.
+    ///
+    /// For a value, see [`Value::to_repr`].  For a table, this is the key/value pairs written
+    /// under its header, without the header itself or its subtables.  Returns `None` for
+    /// [`Item::None`] and for arrays of tables, whose tables are each written under their own
+    /// header.
+    ///
+    /// ```rust
+    /// let input = "[package]\nname = 'demo'  # crate\nversion = \"1.0\"\n\n[package.metadata]\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(
+    ///     doc["package"].to_repr().unwrap().as_raw(),
+    ///     "name = 'demo'  # crate\nversion = \"1.0\"\n"
+    /// );
+    /// assert_eq!(doc["package"]["name"].to_repr().unwrap().as_raw(), "'demo'");
+    /// ```
+    pub fn to_repr(&self) -> Option<Cow<'_, Repr>> {
+        match self {
+            Item::Value(v) => Some(v.to_repr()),
+            Item::Table(t) => Some(Cow::Owned(Repr::new_unchecked(t.to_string()))),
+            Item::ArrayOfTables(_) | Item::None => None,
+        }
+    }
+
+    /// Returns the raw representation of a value as it appeared in the source, see
+    /// [`Value::as_repr`].
+    pub fn as_repr(&self) -> Option<&Repr> {
+        self.as_value().and_then(Value::as_repr)
+    }
+
+    /// Casts `self` to an unsigned integer, see [`Value::as_u64`].
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_value().and_then(Value::as_u64)
+    }
+
+    /// Casts `self` to a 128-bit integer, see [`Value::as_i128`].
+    pub fn as_i128(&self) -> Option<i128> {
+        self.as_value().and_then(Value::as_i128)
     }
 
     /// Casts `self` to float.
@@ -236,13 +366,19 @@ impl Item {
     }
 
     /// Casts `self` to date-time.
+    #[cfg(feature = "datetime")]
     pub fn as_datetime(&self) -> Option<&Datetime> {
         self.as_value().and_then(Value::as_datetime)
     }
 
-    /// Returns true iff `self` is a date-time.
+    /// Casts `self` to a date-time kept as its text, see [`Value::as_opaque_datetime`].
+    pub fn as_opaque_datetime(&self) -> Option<&OpaqueDatetime> {
+        self.as_value().and_then(Value::as_opaque_datetime)
+    }
+
+    /// Returns true iff `self` is a date-time, see [`Value::is_datetime`].
     pub fn is_datetime(&self) -> bool {
-        self.as_datetime().is_some()
+        matches!(self.as_value(), Some(v) if v.is_datetime())
     }
 
     /// Casts `self` to array.
@@ -297,6 +433,142 @@ impl Item {
     }
 }
 
+/// Panicking accessors
+///
+/// These are intended for tests and prototypes, where a panic is the desired way to report an
+/// unexpected document.  Items don't know where they are within a document, so the panic message
+/// includes the item's type and a snippet of it while the location reported is the caller's.
+/// Look items up with [`Document::expect_path`][crate::Document::expect_path] to also have their
+/// path in the message.
+impl Item {
+    /// Casts `self` to value, panicking if it is not one.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a value, with a message like:
+    ///
+    /// ```text
+    /// expected a value, found table: "b = 1"
+    /// ```
+    #[track_caller]
+    pub fn expect_value(&self) -> &Value {
+        match self.as_value() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a value"),
+        }
+    }
+
+    /// Casts `self` to table, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_table(&self) -> &Table {
+        match self.as_table() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a table"),
+        }
+    }
+
+    /// Casts `self` to array of tables, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_array_of_tables(&self) -> &ArrayOfTables {
+        match self.as_array_of_tables() {
+            Some(v) => v,
+            None => expected(Some(self), None, "an array of tables"),
+        }
+    }
+
+    /// Casts `self` to integer, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_integer(&self) -> i64 {
+        match self.as_integer() {
+            Some(v) => v,
+            None => expected(Some(self), None, "an integer"),
+        }
+    }
+
+    /// Casts `self` to float, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_float(&self) -> f64 {
+        match self.as_float() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a float"),
+        }
+    }
+
+    /// Casts `self` to boolean, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_bool(&self) -> bool {
+        match self.as_bool() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a boolean"),
+        }
+    }
+
+    /// Casts `self` to string, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_str(&self) -> &str {
+        match self.as_str() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a string"),
+        }
+    }
+
+    /// Casts `self` to datetime, panicking if it is not one.
+    #[cfg(feature = "datetime")]
+    #[track_caller]
+    pub fn expect_datetime(&self) -> &Datetime {
+        match self.as_datetime() {
+            Some(v) => v,
+            None => expected(Some(self), None, "a datetime"),
+        }
+    }
+
+    /// Casts `self` to array, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_array(&self) -> &Array {
+        match self.as_array() {
+            Some(v) => v,
+            None => expected(Some(self), None, "an array"),
+        }
+    }
+
+    /// Casts `self` to inline table, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_inline_table(&self) -> &InlineTable {
+        match self.as_inline_table() {
+            Some(v) => v,
+            None => expected(Some(self), None, "an inline table"),
+        }
+    }
+}
+
+/// Panics for an item that is not what was `expected`, naming the item's `path` when known
+#[cold]
+#[track_caller]
+pub(crate) fn expected(item: Option<&Item>, path: Option<&str>, expected: &str) -> ! {
+    let at = path.map(|p| format!(" at `{}`", p)).unwrap_or_default();
+    let item = match item {
+        Some(item) if !item.is_none() => item,
+        _ => panic!("expected {}{}, found none", expected, at),
+    };
+    const MAX_SNIPPET: usize = 60;
+    let mut snippet = item.to_string().trim().to_owned();
+    if MAX_SNIPPET < snippet.len() {
+        let mut end = MAX_SNIPPET;
+        while !snippet.is_char_boundary(end) {
+            end -= 1;
+        }
+        snippet.truncate(end);
+        snippet.push_str("...");
+    }
+    panic!(
+        "expected {}{}, found {}: {:?}",
+        expected,
+        at,
+        item.type_name(),
+        snippet
+    );
+}
+
 impl Default for Item {
     fn default() -> Self {
         Item::None
@@ -313,8 +585,8 @@ impl FromStr for Item {
     }
 }
 
-impl std::fmt::Display for Item {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Item {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self {
             Item::None => Ok(()),
             Item::Value(v) => v.fmt(f),
@@ -324,6 +596,73 @@ impl std::fmt::Display for Item {
     }
 }
 
+/// Types an [`Item`] can be cast to, see [`Table::values_of`]
+pub trait FromItem<'a>: Sized + crate::private::Sealed {
+    /// Casts `item`, returning `None` if it is of another type
+    fn from_item(item: &'a Item) -> Option<Self>;
+}
+
+impl<'a> FromItem<'a> for i64 {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_integer()
+    }
+}
+
+impl<'a> FromItem<'a> for f64 {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_float()
+    }
+}
+
+impl<'a> FromItem<'a> for bool {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_bool()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a str {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_str()
+    }
+}
+
+#[cfg(feature = "datetime")]
+impl<'a> FromItem<'a> for &'a Datetime {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_datetime()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a Array {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_array()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a InlineTable {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_inline_table()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a Value {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_value()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a Table {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_table()
+    }
+}
+
+impl<'a> FromItem<'a> for &'a ArrayOfTables {
+    fn from_item(item: &'a Item) -> Option<Self> {
+        item.as_array_of_tables()
+    }
+}
+
 /// Returns a formatted value.
 ///
 /// Since formatting is part of a `Value`, the right hand side of the
@@ -0,0 +1,308 @@
+use serde_json::{Map, Number, Value as Json};
+use toml_datetime::{Datetime, Offset};
+
+use crate::ser::ErrorKind;
+use crate::{Array, ArrayOfTables, Document, InlineTable, Item, Table, Value};
+
+impl Document {
+    /// Convert the document into a [`serde_json::Value`], discarding formatting.
+    ///
+    /// Datetimes become RFC 3339 strings.  Since JSON has no representation for them, `nan`,
+    /// `inf`, and `-inf` floats become strings as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "a = 1\n[b]\nc = 1979-05-27T07:32:00Z\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     doc.to_json(),
+    ///     serde_json::json!({"a": 1, "b": {"c": "1979-05-27T07:32:00Z"}})
+    /// );
+    /// ```
+    pub fn to_json(&self) -> Json {
+        self.to_json_with(JsonDatetime::Rfc3339)
+    }
+
+    /// Convert the document into a [`serde_json::Value`], representing datetimes as `datetimes`.
+    ///
+    /// Otherwise the same as [`Document::to_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::JsonDatetime;
+    ///
+    /// let doc = "a = 1979-05-27T07:32:00Z\nb = 07:32:00\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     doc.to_json_with(JsonDatetime::Tagged),
+    ///     serde_json::json!({
+    ///         "a": {"$__toml_private_datetime": "1979-05-27T07:32:00Z"},
+    ///         "b": {"$__toml_private_datetime": "07:32:00"},
+    ///     })
+    /// );
+    /// assert_eq!(
+    ///     doc.to_json_with(JsonDatetime::UnixSeconds),
+    ///     serde_json::json!({"a": 296638320, "b": "07:32:00"})
+    /// );
+    /// ```
+    pub fn to_json_with(&self, datetimes: JsonDatetime) -> Json {
+        table_to_json(self.as_table(), datetimes)
+    }
+
+    /// Build a document from a [`serde_json::Value`] with default formatting.
+    ///
+    /// Object keys keep their order, as the `json` feature enables `serde_json`'s
+    /// `preserve_order`.  Objects that render compactly become inline tables while larger ones
+    /// become `[header]` tables.  Likewise for arrays of objects and arrays of tables.
+    ///
+    /// Datetimes tagged as by [`JsonDatetime::Tagged`] become datetimes again, while other
+    /// representations are left as strings and numbers.
+    ///
+    /// Fails if `json` is not an object or contains a `null` or a number out of range for TOML.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let json = serde_json::json!({
+    ///     "name": "foo",
+    ///     "dependencies": {
+    ///         "serde": {"version": "1", "features": ["derive"]},
+    ///     },
+    /// });
+    /// let doc = toml_edit::Document::from_json(&json).unwrap();
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     r#"name = "foo"
+    ///
+    /// [dependencies]
+    /// serde = { version = "1", features = ["derive"] }
+    /// "#
+    /// );
+    /// ```
+    pub fn from_json(json: &Json) -> Result<Self, crate::ser::Error> {
+        let root = match json {
+            Json::Object(map) => object_from_json(map)?,
+            _ => return Err(ErrorKind::UnsupportedType.into()),
+        };
+        let mut doc = Document::from(root.into_table());
+        layout_table(doc.as_table_mut());
+        Ok(doc)
+    }
+}
+
+/// How datetimes are represented by [`Document::to_json_with`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum JsonDatetime {
+    /// An RFC 3339 string, like `"1979-05-27T07:32:00Z"`
+    Rfc3339,
+    /// An object holding the RFC 3339 string under the key `toml` uses when deserializing a
+    /// [`Datetime`] with serde, like `{"$__toml_private_datetime": "1979-05-27T07:32:00Z"}`
+    ///
+    /// Unlike the other representations, [`Document::from_json`] reads these back as datetimes.
+    Tagged,
+    /// The number of seconds since the Unix epoch, with a fraction for sub-second precision
+    ///
+    /// Local dates and datetimes are taken as UTC, while local times, without a day to count
+    /// from, stay RFC 3339 strings.
+    UnixSeconds,
+}
+
+fn datetime_to_json(datetime: &Datetime, datetimes: JsonDatetime) -> Json {
+    match datetimes {
+        JsonDatetime::Rfc3339 => Json::String(datetime.to_string()),
+        JsonDatetime::Tagged => {
+            let mut map = Map::new();
+            map.insert(
+                toml_datetime::__unstable::FIELD.to_owned(),
+                Json::String(datetime.to_string()),
+            );
+            Json::Object(map)
+        }
+        JsonDatetime::UnixSeconds => match unix_seconds(datetime) {
+            Some((seconds, 0)) => Json::Number(seconds.into()),
+            Some((seconds, nanoseconds)) => {
+                let seconds = seconds as f64 + f64::from(nanoseconds) / 1e9;
+                Number::from_f64(seconds).map_or(Json::Null, Json::Number)
+            }
+            None => Json::String(datetime.to_string()),
+        },
+    }
+}
+
+// Seconds and nanoseconds since 1970-01-01T00:00:00Z, or `None` for a local time
+fn unix_seconds(datetime: &Datetime) -> Option<(i64, u32)> {
+    let date = datetime.date?;
+    // Days from the civil calendar, counting years from March so leap days end each year
+    let year = i64::from(date.year) - i64::from(date.month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (i64::from(date.month) + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(date.day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let (mut seconds, nanoseconds) = match datetime.time {
+        Some(time) => (
+            i64::from(time.hour) * 3600 + i64::from(time.minute) * 60 + i64::from(time.second),
+            time.nanosecond,
+        ),
+        None => (0, 0),
+    };
+    if let Some(Offset::Custom { hours, minutes }) = datetime.offset {
+        let sign = if hours < 0 { -1 } else { 1 };
+        seconds -= (i64::from(hours) * 60 + sign * i64::from(minutes)) * 60;
+    }
+    Some((days * 86_400 + seconds, nanoseconds))
+}
+
+// Walks the entries of `map` in their order in the JSON
+fn object_from_json(map: &Map<String, Json>) -> Result<InlineTable, crate::ser::Error> {
+    let mut table = InlineTable::new();
+    for (key, value) in map {
+        table.insert(key, value_from_json(value)?);
+    }
+    table.fmt();
+    Ok(table)
+}
+
+fn value_from_json(json: &Json) -> Result<Value, crate::ser::Error> {
+    let value = match json {
+        Json::Null => return Err(ErrorKind::UnsupportedNone.into()),
+        Json::Bool(b) => Value::from(*b),
+        Json::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => Value::from(i),
+            (None, _) if n.is_u64() => return Err(ErrorKind::OutOfRange("u64").into()),
+            (None, Some(f)) => Value::from(f),
+            (None, None) => return Err(ErrorKind::UnsupportedType.into()),
+        },
+        Json::String(s) => Value::from(s.as_str()),
+        Json::Array(values) => {
+            let mut array = values
+                .iter()
+                .map(value_from_json)
+                .collect::<Result<Array, _>>()?;
+            array.fmt();
+            Value::Array(array)
+        }
+        // Objects tagged as by `JsonDatetime::Tagged` become datetimes again
+        Json::Object(map) => match tagged_datetime(map) {
+            Some(datetime) => Value::from(datetime),
+            None => Value::InlineTable(object_from_json(map)?),
+        },
+    };
+    Ok(value)
+}
+
+fn tagged_datetime(map: &Map<String, Json>) -> Option<Datetime> {
+    match map.get(toml_datetime::__unstable::FIELD) {
+        Some(Json::String(s)) if map.len() == 1 => s.parse().ok(),
+        _ => None,
+    }
+}
+
+// Longest rendering of an inline table, or array of them, before switching to headers
+const MAX_INLINE_WIDTH: usize = 60;
+
+fn layout_table(table: &mut Table) {
+    for (_, item) in table.iter_mut() {
+        let expand = match &*item {
+            Item::Value(Value::InlineTable(t)) => !fits_inline(t),
+            Item::Value(Value::Array(a)) => {
+                !a.is_empty()
+                    && a.iter().all(Value::is_inline_table)
+                    && (a.to_string().len() > MAX_INLINE_WIDTH
+                        || a.iter()
+                            .filter_map(Value::as_inline_table)
+                            .any(|t| !fits_inline(t)))
+            }
+            _ => false,
+        };
+        if expand {
+            item.make_item();
+        }
+
+        match item {
+            Item::Table(t) => layout_child(t),
+            Item::ArrayOfTables(a) => a.iter_mut().for_each(layout_child),
+            _ => {}
+        }
+    }
+}
+
+fn layout_child(table: &mut Table) {
+    // Empty tables could be semantically meaningful, so make sure they are not implicit
+    if !table.is_empty() {
+        table.set_implicit(true);
+    }
+    layout_table(table);
+}
+
+fn fits_inline(table: &InlineTable) -> bool {
+    table.iter().all(|(_, v)| !v.is_inline_table()) && table.to_string().len() <= MAX_INLINE_WIDTH
+}
+
+fn item_to_json(item: &Item, datetimes: JsonDatetime) -> Json {
+    match item {
+        Item::None => Json::Null,
+        Item::Value(v) => value_to_json(v, datetimes),
+        Item::Table(t) => table_to_json(t, datetimes),
+        Item::ArrayOfTables(a) => array_of_tables_to_json(a, datetimes),
+    }
+}
+
+fn value_to_json(value: &Value, datetimes: JsonDatetime) -> Json {
+    match value {
+        Value::String(v) => Json::String(v.value().clone()),
+        Value::Integer(v) => Json::Number((*v.value()).into()),
+        Value::WideInteger(v) => {
+            let i = *v.value();
+            match u64::try_from(i) {
+                Ok(u) => Json::Number(u.into()),
+                // Beyond what JSON numbers are read as, so kept exact as a string
+                Err(_) => Json::String(i.to_string()),
+            }
+        }
+        Value::Float(v) => {
+            let f = *v.value();
+            match Number::from_f64(f) {
+                Some(n) => Json::Number(n),
+                None if f.is_nan() => Json::String("nan".to_owned()),
+                None if f.is_sign_negative() => Json::String("-inf".to_owned()),
+                None => Json::String("inf".to_owned()),
+            }
+        }
+        Value::Boolean(v) => Json::Bool(*v.value()),
+        Value::Datetime(v) => datetime_to_json(v.value(), datetimes),
+        Value::OpaqueDatetime(v) => datetime_to_json(&v.value().to_datetime(), datetimes),
+        Value::Array(v) => array_to_json(v, datetimes),
+        Value::InlineTable(v) => inline_table_to_json(v, datetimes),
+    }
+}
+
+fn array_to_json(array: &Array, datetimes: JsonDatetime) -> Json {
+    Json::Array(array.iter().map(|v| value_to_json(v, datetimes)).collect())
+}
+
+fn array_of_tables_to_json(array: &ArrayOfTables, datetimes: JsonDatetime) -> Json {
+    Json::Array(array.iter().map(|t| table_to_json(t, datetimes)).collect())
+}
+
+fn table_to_json(table: &Table, datetimes: JsonDatetime) -> Json {
+    let map = table
+        .iter()
+        .map(|(k, v)| (k.to_owned(), item_to_json(v, datetimes)))
+        .collect::<Map<_, _>>();
+    Json::Object(map)
+}
+
+fn inline_table_to_json(table: &InlineTable, datetimes: JsonDatetime) -> Json {
+    let map = table
+        .iter()
+        .map(|(k, v)| (k.to_owned(), value_to_json(v, datetimes)))
+        .collect::<Map<_, _>>();
+    Json::Object(map)
+}
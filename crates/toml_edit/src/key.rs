@@ -1,7 +1,10 @@
-use std::borrow::Cow;
-use std::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
 
-use crate::encode::{to_string_repr, StringStyle};
+use crate::encode::{to_string_repr, EncodingVersion, QuoteStyle};
+use crate::lint::KeyCase;
 use crate::parser;
 use crate::parser::key::is_unquoted_char;
 use crate::repr::{Decor, Repr};
@@ -83,7 +86,23 @@ impl Key {
         self.repr
             .as_ref()
             .map(Cow::Borrowed)
-            .unwrap_or_else(|| Cow::Owned(to_key_repr(&self.key)))
+            .unwrap_or_else(|| Cow::Owned(to_key_repr(&self.key, EncodingVersion::LATEST)))
+    }
+
+    // The raw representation, or else the default one of `version`
+    pub(crate) fn to_repr_with(&self, version: EncodingVersion) -> Cow<'_, Repr> {
+        self.repr
+            .as_ref()
+            .map(Cow::Borrowed)
+            .unwrap_or_else(|| Cow::Owned(to_key_repr(&self.key, version)))
+    }
+
+    /// Returns the key raw representation, if available.
+    ///
+    /// For parsed keys, this is exactly how the key appeared in the source, including quotes and
+    /// escapes.
+    pub fn as_repr(&self) -> Option<&Repr> {
+        self.repr.as_ref()
     }
 
     /// Returns the surrounding whitespace
@@ -96,12 +115,87 @@ impl Key {
         &self.decor
     }
 
+    /// Returns the comment on the lines directly above this key's key/value pair, without the `# `
+    /// starting each line.
+    pub fn comment(&self) -> Option<String> {
+        self.decor.comment()
+    }
+
+    /// Replaces the comment on the lines above this key's key/value pair.
+    ///
+    /// Each line of `comment` is written as a `# ` comment line, keeping the indentation of the
+    /// pair and any blank line and comments before them.  An empty `comment` removes the comment
+    /// lines.
+    ///
+    /// Comments can't be written within inline tables, so this is only for keys of a
+    /// [`Table`][crate::Table].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a = 1\n\n# old\nb = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// let (mut key, _) = doc.get_key_value_mut("b").unwrap();
+    /// assert_eq!(key.comment().as_deref(), Some("old"));
+    /// key.set_comment("new\nlines");
+    /// assert_eq!(doc.to_string(), "a = 1\n\n# new\n# lines\nb = 2\n");
+    /// ```
+    pub fn set_comment(&mut self, comment: &str) {
+        self.decor.set_comment(comment, "");
+    }
+
     /// Auto formats the key.
     pub fn fmt(&mut self) {
-        self.repr = Some(to_key_repr(&self.key));
+        self.repr = Some(to_key_repr(&self.key, EncodingVersion::LATEST));
         self.decor.clear();
     }
 
+    /// The key renamed to `snake_case`, keeping its decor.
+    ///
+    /// Words are split at punctuation, whitespace, and lower-to-upper case changes.  Returns
+    /// `None` if the key can't be written in `snake_case`, like when it has non-ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let key = toml_edit::Key::new("rustVersion");
+    /// assert_eq!(key.to_snake_case().unwrap().get(), "rust_version");
+    /// assert!(toml_edit::Key::new("café").to_snake_case().is_none());
+    /// ```
+    pub fn to_snake_case(&self) -> Option<Key> {
+        self.to_case(KeyCase::Snake)
+    }
+
+    /// The key renamed to `kebab-case`, keeping its decor, see [`Key::to_snake_case`].
+    pub fn to_kebab_case(&self) -> Option<Key> {
+        self.to_case(KeyCase::Kebab)
+    }
+
+    /// The key renamed to follow the naming convention `case`, keeping its decor, see
+    /// [`Key::to_snake_case`].
+    pub fn to_case(&self, case: KeyCase) -> Option<Key> {
+        case.convert(&self.key).map(|key| Key {
+            key: key.into(),
+            repr: None,
+            decor: self.decor.clone(),
+        })
+    }
+
+    // Normalizes the key to NFC, keeping how it was written
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) fn normalize(&mut self) {
+        use unicode_normalization::UnicodeNormalization;
+
+        if self.key.is_ascii() {
+            return;
+        }
+        let normalized = self.key.nfc().collect::<String>();
+        if normalized != self.key.as_str() {
+            self.repr
+                .get_or_insert_with(|| to_key_repr(&self.key, EncodingVersion::LATEST));
+            self.key = normalized.into();
+        }
+    }
+
     fn try_parse_simple(s: &str) -> Result<Key, crate::TomlError> {
         parser::parse_key(s)
     }
@@ -111,7 +205,7 @@ impl Key {
     }
 }
 
-impl std::ops::Deref for Key {
+impl core::ops::Deref for Key {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -119,20 +213,20 @@ impl std::ops::Deref for Key {
     }
 }
 
-impl std::hash::Hash for Key {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl core::hash::Hash for Key {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.get().hash(state);
     }
 }
 
 impl Ord for Key {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.get().cmp(other.get())
     }
 }
 
 impl PartialOrd for Key {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -167,8 +261,8 @@ impl PartialEq<String> for Key {
     }
 }
 
-impl std::fmt::Display for Key {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, ("", ""))
     }
 }
@@ -184,11 +278,18 @@ impl FromStr for Key {
     }
 }
 
-fn to_key_repr(key: &str) -> Repr {
-    if key.as_bytes().iter().copied().all(is_unquoted_char) && !key.is_empty() {
+/// Whether `key` can be written without quotes
+// Whether `key` can be written unquoted in `version`
+pub(crate) fn is_bare_key(key: &str, version: EncodingVersion) -> bool {
+    let unquoted = |c: char| (c.is_ascii() || version.is_toml_1_1()) && is_unquoted_char(c);
+    key.chars().all(unquoted) && !key.is_empty()
+}
+
+fn to_key_repr(key: &str, version: EncodingVersion) -> Repr {
+    if is_bare_key(key, version) {
         Repr::new_unchecked(key)
     } else {
-        to_string_repr(key, Some(StringStyle::OnelineSingle), Some(false))
+        to_string_repr(key, Some(QuoteStyle::OnelineSingle), Some(false), version)
     }
 }
 
@@ -240,6 +341,11 @@ impl<'k> KeyMut<'k> {
         self.key.to_repr()
     }
 
+    /// Returns the key raw representation, if available.
+    pub fn as_repr(&self) -> Option<&Repr> {
+        self.key.as_repr()
+    }
+
     /// Returns the surrounding whitespace
     pub fn decor_mut(&mut self) -> &mut Decor {
         self.key.decor_mut()
@@ -250,13 +356,24 @@ impl<'k> KeyMut<'k> {
         self.key.decor()
     }
 
+    /// Returns the comment on the lines above this key's key/value pair, see [`Key::comment`].
+    pub fn comment(&self) -> Option<String> {
+        self.key.comment()
+    }
+
+    /// Replaces the comment on the lines above this key's key/value pair, see
+    /// [`Key::set_comment`].
+    pub fn set_comment(&mut self, comment: &str) {
+        self.key.set_comment(comment)
+    }
+
     /// Auto formats the key.
     pub fn fmt(&mut self) {
         self.key.fmt()
     }
 }
 
-impl<'k> std::ops::Deref for KeyMut<'k> {
+impl<'k> core::ops::Deref for KeyMut<'k> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -285,8 +402,8 @@ impl<'s> PartialEq<String> for KeyMut<'s> {
     }
 }
 
-impl<'k> std::fmt::Display for KeyMut<'k> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.key, f)
+impl<'k> core::fmt::Display for KeyMut<'k> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.key, f)
     }
 }
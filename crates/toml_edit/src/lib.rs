@@ -1,4 +1,6 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
+#![cfg_attr(feature = "forbid-unsafe", forbid(unsafe_code))]
 // https://github.com/Marwes/combine/issues/172
 #![recursion_limit = "256"]
 
@@ -11,6 +13,7 @@
 //! It is primarily tailored to the needs of [cargo-edit](https://github.com/killercup/cargo-edit/).
 //!
 //! If you also need the ease of a more traditional API, see the [`easy`] module.
+//! For documents that are only read, [`borrowed`] parses them without copying their text.
 //!
 //! # Example
 //!
@@ -55,6 +58,63 @@
 //! assert_eq!(doc.to_string(), expected);
 //! ```
 //!
+//! ## Feature flags
+//!
+//! Only `std` and `datetime` are enabled by default.  Without them, only the format-preserving
+//! parser and editor are left, with no dependencies beyond the parser's.
+//!
+//! * `std`: implement `std::error::Error` for errors, and write documents to a `std::io::Write`
+//!   with [`Document::write_to`].  Without it, this crate is `no_std` and only requires `alloc`,
+//!   though tables then hash their keys without a random seed, so documents from untrusted
+//!   sources can be crafted to make lookups slow.  `serde`, `easy`, `json`, `regex`, `perf`, and
+//!   `compact_str` require it
+//! * `datetime`: interpret datetimes as [`toml_datetime`](https://docs.rs/toml_datetime)'s
+//!   `Datetime`.  Without it, datetimes are still checked but kept as their text, see
+//!   [`OpaqueDatetime`].  `serde`, and so `easy` and `json`, require it
+//! * `serde`: the `de` and `ser` modules for converting between documents and Rust types
+//! * `easy`: the `easy` module, a `serde`-based API modeled after the `toml` crate
+//! * `json`: `Document::to_json` and `Document::from_json` for converting between documents and
+//!   `serde_json::Value`
+//! * `regex`: `Document::find_string_values` for searching string values with a
+//!   `regex::Regex`
+//! * `unicode-normalization`: `ParseOptions::normalize_keys` for treating keys that are equal
+//!   after Unicode NFC normalization as the same key
+//! * `perf`: a small-string optimization for keys
+//! * `forbid-unsafe`: build with `#![forbid(unsafe_code)]`, re-checking the UTF-8 the parser
+//!   has already validated at a small cost to parsing speed
+//! * `compact_str`, `arc_str`: alternative storage for keys, see [`InternalString`]
+//! * `unbounded`: parse arbitrarily deeply nested documents without a recursion limit, unless one
+//!   is set with [`ParseOptions::max_depth`]
+//! * `toml-1.1`: syntax from the TOML 1.1 draft, which TOML 1.0 parsers reject:
+//!   * the `\e` escape for U+001B, which is also used when writing strings containing it
+//!   * newlines, comments, and a trailing comma within inline tables, preserved by
+//!     [`InlineTable::trailing_comma`] and the decor of its keys and values
+//!   * Unicode letters and digits in bare keys, which are then also written unquoted
+//! * `debug-validate`: check that every [`Document`] written with `Display` parses back to the
+//!   same document, panicking with the path of the first difference.  This re-parses all
+//!   output, so it is meant for tests and debug builds
+//!
+//! With the `datetime` feature, tools passing datetimes through uninterpreted can still keep them
+//! as their text with [`ParseOptions::opaque_datetimes`].  Both `toml_datetime` and `toml_edit`
+//! support `no_std` with their `std` feature disabled.
+//!
+//! ## Output stability
+//!
+//! Rendering is deterministic: the output depends only on the document, not on the platform,
+//! hashing, or locale, and new documents use `\n` line endings while parsed documents keep the
+//! line ending of their input, see [`Document::newline_style`].  Parsed formatting is
+//! reproduced as-is, while values, keys, and tables without formatting of their own get a
+//! default format.  Changes to the default format are only made in breaking releases, so
+//! generated files can be committed without spurious diffs between versions.  To also keep them
+//! stable across breaking releases, pin the format with [`Document::to_string_with`] and an
+//! [`EncodingVersion`], which stays available for at least one breaking release after it is
+//! superseded, and lay them out with [`FormatOptions::deterministic`] rather than
+//! [`FormatOptions::new`].
+//!
+//! Floats are written with the fewest digits that parse back to the same value, so rendering and
+//! reparsing a float reproduces it bit-for-bit, except for the payload of a NaN.  Parsed floats
+//! keep their digits unless formatted with [`FormatOptions::shortest_floats`].
+//!
 //! ## Limitations
 //!
 //! Things it does not preserve:
@@ -64,18 +124,34 @@
 //!
 //! [test]: https://github.com/ordian/toml_edit/blob/f09bd5d075fdb7d2ef8d9bb3270a34506c276753/tests/test_valid.rs#L84
 
+extern crate alloc;
+
 mod array;
 mod array_of_tables;
+mod collections;
+mod datetime;
 mod document;
 mod encode;
+mod format;
+mod incremental;
 mod index;
 mod inline_table;
 mod internal_string;
 mod item;
+#[cfg(feature = "json")]
+mod json;
 mod key;
+mod metrics;
 mod parser;
+mod path;
 mod repr;
+mod search;
+mod symbols;
 mod table;
+mod template;
+mod trivia;
+#[cfg(feature = "debug-validate")]
+mod validate;
 mod value;
 
 #[cfg(feature = "easy")]
@@ -86,27 +162,45 @@ pub mod de;
 #[cfg(feature = "serde")]
 pub mod ser;
 
+pub mod borrowed;
+pub mod events;
+pub mod lint;
 pub mod visit;
 pub mod visit_mut;
 
-pub use crate::array::{Array, ArrayIntoIter, ArrayIter, ArrayIterMut};
+pub use crate::array::{Array, ArrayIntoIter, ArrayIter, ArrayIterMut, ArrayStyle};
 pub use crate::array_of_tables::{
     ArrayOfTables, ArrayOfTablesIntoIter, ArrayOfTablesIter, ArrayOfTablesIterMut,
 };
-pub use crate::document::Document;
+pub use crate::datetime::OpaqueDatetime;
+pub use crate::document::{Document, NewlineStyle};
+pub use crate::encode::{escape_str, EncodingVersion, StringStyle};
+pub use crate::format::FormatOptions;
 pub use crate::inline_table::{
     InlineEntry, InlineOccupiedEntry, InlineTable, InlineTableIntoIter, InlineTableIter,
     InlineTableIterMut, InlineVacantEntry,
 };
 pub use crate::internal_string::InternalString;
-pub use crate::item::{array, table, value, Item};
+pub use crate::item::{array, table, value, FromItem, Item, ItemKind};
+#[cfg(feature = "json")]
+pub use crate::json::JsonDatetime;
 pub use crate::key::{Key, KeyMut};
-pub use crate::parser::TomlError;
+pub use crate::parser::{
+    DuplicateKeyPolicy, Expected, Limit, ParseOptions, ParseWarning, SourceDisplay, TableConflict,
+    TableKind, TomlError, WarningKind,
+};
+pub use crate::path::{ItemAt, PathError};
 pub use crate::repr::{Decor, Formatted, Repr};
+#[cfg(feature = "regex")]
+pub use crate::search::StringMatch;
+pub use crate::symbols::{Symbol, SymbolKind};
 pub use crate::table::{
-    Entry, IntoIter, Iter, IterMut, OccupiedEntry, Table, TableLike, VacantEntry,
+    Entry, IntoIter, Iter, IterMut, OccupiedEntry, Table, TableCursor, TableLike, VacantEntry,
 };
-pub use crate::value::Value;
+pub use crate::template::Bindings;
+pub use crate::trivia::{Trivia, TriviaKind};
+pub use crate::value::{Value, ValueKind};
+#[cfg(feature = "datetime")]
 pub use toml_datetime::*;
 
 // Prevent users from some traits.
@@ -114,12 +208,18 @@ pub(crate) mod private {
     pub trait Sealed {}
     impl Sealed for usize {}
     impl Sealed for str {}
-    impl Sealed for String {}
+    impl Sealed for alloc::string::String {}
     impl Sealed for i64 {}
+    impl Sealed for i128 {}
     impl Sealed for f64 {}
     impl Sealed for bool {}
+    #[cfg(feature = "datetime")]
     impl Sealed for crate::Datetime {}
+    impl Sealed for crate::OpaqueDatetime {}
     impl<'a, T: ?Sized> Sealed for &'a T where T: Sealed {}
     impl Sealed for crate::Table {}
     impl Sealed for crate::InlineTable {}
+    impl Sealed for crate::Array {}
+    impl Sealed for crate::ArrayOfTables {}
+    impl Sealed for crate::Value {}
 }
@@ -0,0 +1,1012 @@
+//! Checks for TOML that is commonly confusing to read, and for keys against naming policies with
+//! [`KeyPolicy`] and [`Document::audit_keys`].  Keys can be renamed to follow a naming convention
+//! with [`Document::convert_key_case`], and [`StyleLints`] checks that a document is written in
+//! one consistent style.
+//!
+//! ```
+//! let input = "[fruit]\napple.color = \"red\"\n\n[fruit.apple.texture]\nsmooth = true\n";
+//! let lints = toml_edit::lint::mixed_table_definitions(input).unwrap();
+//! assert_eq!(lints.len(), 1);
+//! assert_eq!(lints[0].path(), ["fruit", "apple"]);
+//! assert_eq!(&input[lints[0].dotted_span()], "apple.color = \"red\"");
+//! assert_eq!(&input[lints[0].header_span()], "[fruit.apple.texture]");
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::collections::{IndexMap, IndexSet};
+use crate::events::{Event, Events};
+use crate::key::is_bare_key;
+use crate::table::{KeyValuePairs, TableKeyValue};
+use crate::{Document, EncodingVersion, Item, Key, Table, TomlError, Value};
+
+/// A table that is given entries by dotted keys while also having a sub-table defined by a
+/// `[header]`.
+///
+/// For example, `fruit.apple` in
+/// ```toml
+/// [fruit]
+/// apple.color = "red"
+///
+/// [fruit.apple.texture]
+/// smooth = true
+/// ```
+/// is defined across two places using different syntax, hiding that `color` and `texture` are
+/// siblings.  While the TOML specification allows this, defining the table with a
+/// `[fruit.apple]` header instead keeps its definition in one form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixedTableDefinition {
+    path: Vec<String>,
+    dotted: Range<usize>,
+    header: Range<usize>,
+}
+
+impl MixedTableDefinition {
+    /// The keys of the table defined both ways.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The byte range of the first dotted key/value pair adding to the table.
+    pub fn dotted_span(&self) -> Range<usize> {
+        self.dotted.clone()
+    }
+
+    /// The byte range of the first header defining a sub-table of the table.
+    pub fn header_span(&self) -> Range<usize> {
+        self.header.clone()
+    }
+
+    /// A suggested fix.
+    pub fn suggestion(&self) -> String {
+        format!(
+            "define `{}` with a `[{}]` header instead of dotted keys",
+            self.dotted_path(),
+            self.dotted_path()
+        )
+    }
+
+    fn dotted_path(&self) -> String {
+        dotted_path(&self.path)
+    }
+}
+
+fn dotted_path(path: &[String]) -> String {
+    path.iter()
+        .map(|k| Key::new(k.as_str()).to_repr().as_raw().to_owned())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+impl core::fmt::Display for MixedTableDefinition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "table `{}` is defined by both dotted keys and a sub-table header; {}",
+            self.dotted_path(),
+            self.suggestion()
+        )
+    }
+}
+
+/// Find tables in `input` given entries by dotted keys while also having a sub-table header.
+///
+/// Only syntax is checked, returning an error if `input` is not valid TOML syntax.  See
+/// [`MixedTableDefinition`].
+pub fn mixed_table_definitions(input: &str) -> Result<Vec<MixedTableDefinition>, TomlError> {
+    // Tables are identified by their keys along with, for arrays of tables, which element
+    type TablePath = Vec<(String, Option<usize>)>;
+    let mut counts = IndexMap::<Vec<String>, usize>::default();
+    let mut current = TablePath::new();
+    let mut dotted = IndexMap::<TablePath, Range<usize>>::default();
+    let mut headers = IndexMap::<TablePath, Range<usize>>::default();
+
+    let mut events = Events::new(input);
+    loop {
+        let start = events.offset();
+        let event = match events.next() {
+            Some(event) => event?,
+            None => break,
+        };
+        let span = start..events.offset();
+        match event {
+            Event::TableHeader { path, is_array } => {
+                let keys = path.iter().map(|k| k.get().to_owned()).collect::<Vec<_>>();
+                if is_array {
+                    *counts.entry(keys.clone()).or_default() += 1;
+                }
+                current = (1..=keys.len())
+                    .map(|i| {
+                        let index = counts.get(&keys[..i]).map(|c| c - 1);
+                        (keys[i - 1].clone(), index)
+                    })
+                    .collect();
+                for i in 1..current.len() {
+                    headers
+                        .entry(current[..i].to_vec())
+                        .or_insert_with(|| span.clone());
+                }
+            }
+            Event::KeyValue { path, .. } => {
+                let mut table = current.clone();
+                for key in &path[..path.len() - 1] {
+                    table.push((key.get().to_owned(), None));
+                    dotted.entry(table.clone()).or_insert_with(|| span.clone());
+                }
+            }
+            Event::Whitespace(_) | Event::Comment(_) => {}
+        }
+    }
+
+    let mut mixed = dotted
+        .into_iter()
+        .filter_map(|(path, dotted)| {
+            let header = headers.get(&path)?.clone();
+            let path = path.into_iter().map(|(k, _)| k).collect();
+            Some(MixedTableDefinition {
+                path,
+                dotted,
+                header,
+            })
+        })
+        .collect::<Vec<_>>();
+    mixed.sort_by(|a, b| (a.dotted.start, &a.path).cmp(&(b.dotted.start, &b.path)));
+    Ok(mixed)
+}
+
+/// A key that may go against a key-naming policy, see [`Document::audit_keys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAnomaly {
+    path: Vec<String>,
+    needs_quotes: bool,
+    non_ascii: bool,
+    too_long: bool,
+}
+
+impl KeyAnomaly {
+    /// The keys leading to and including the anomalous key.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The anomalous key.
+    pub fn key(&self) -> &str {
+        self.path.last().expect("paths are non-empty")
+    }
+
+    /// Whether the key can only be written quoted.
+    pub fn needs_quotes(&self) -> bool {
+        self.needs_quotes
+    }
+
+    /// Whether the key contains non-ASCII characters.
+    pub fn is_non_ascii(&self) -> bool {
+        self.non_ascii
+    }
+
+    /// Whether the key is longer than the maximum length given to [`Document::audit_keys`].
+    pub fn is_too_long(&self) -> bool {
+        self.too_long
+    }
+}
+
+impl Document {
+    /// Find keys that require quoting, contain non-ASCII characters, or are longer than `max_len`
+    /// bytes.
+    ///
+    /// Keys are checked throughout the document, including within inline tables and arrays, and
+    /// are returned in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "ok = 1\n\"needs space\" = 2\n\n[table]\n\"café\" = 3\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let anomalies = doc.audit_keys(64);
+    /// assert_eq!(anomalies.len(), 2);
+    /// assert_eq!(anomalies[0].key(), "needs space");
+    /// assert!(anomalies[0].needs_quotes());
+    /// assert_eq!(anomalies[1].path(), ["table", "café"]);
+    /// assert!(anomalies[1].is_non_ascii());
+    /// ```
+    pub fn audit_keys(&self, max_len: usize) -> Vec<KeyAnomaly> {
+        let policy = KeyPolicy::new().max_len(max_len);
+        let mut anomalies = Vec::new();
+        walk_item(self.as_item(), &mut Vec::new(), &mut |path| {
+            let key = path.last().expect("paths are non-empty");
+            let needs_quotes = !is_bare_key(key, EncodingVersion::LATEST);
+            let non_ascii = !key.is_ascii();
+            let too_long = policy.too_long(key).is_some();
+            if needs_quotes || non_ascii || too_long {
+                anomalies.push(KeyAnomaly {
+                    path: path.to_vec(),
+                    needs_quotes,
+                    non_ascii,
+                    too_long,
+                });
+            }
+        });
+        anomalies
+    }
+
+    /// Rename the keys of every table in the document to follow the naming convention `case`,
+    /// see [`KeyCase::convert`].
+    ///
+    /// Keys keep their decor and their place in the table, with the new names written as bare
+    /// keys.  Keys within inline tables and arrays are renamed as well.  A key is left as it was
+    /// when it can't be written following `case` or when renaming it would clash with another
+    /// key of its table; the paths of these keys are returned in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::lint::KeyCase;
+    ///
+    /// let input = "[package]\nname = 'demo'\nrust_version = '1.60' # MSRV\n\n[lib]\n'café' = 1\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let skipped = doc.convert_key_case(KeyCase::Kebab);
+    /// assert_eq!(skipped, [["lib", "café"]]);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\nname = 'demo'\nrust-version = '1.60' # MSRV\n\n[lib]\n'café' = 1\n"
+    /// );
+    /// ```
+    pub fn convert_key_case(&mut self, case: KeyCase) -> Vec<Vec<String>> {
+        let mut skipped = Vec::new();
+        let mut path = Vec::new();
+        convert_item(self.as_item_mut(), case, &mut path, &mut skipped);
+        skipped
+    }
+}
+
+fn convert_item(
+    item: &mut Item,
+    case: KeyCase,
+    path: &mut Vec<String>,
+    skipped: &mut Vec<Vec<String>>,
+) {
+    match item {
+        Item::None => {}
+        Item::Value(value) => convert_value(value, case, path, skipped),
+        Item::Table(table) => convert_table(table, case, path, skipped),
+        Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                convert_table(table, case, path, skipped);
+            }
+        }
+    }
+}
+
+fn convert_table(
+    table: &mut Table,
+    case: KeyCase,
+    path: &mut Vec<String>,
+    skipped: &mut Vec<Vec<String>>,
+) {
+    convert_keys(&mut table.items, case, path, skipped);
+    for kv in table.items.values_mut() {
+        path.push(kv.key.get().to_owned());
+        convert_item(&mut kv.value, case, path, skipped);
+        path.pop();
+    }
+}
+
+fn convert_value(
+    value: &mut Value,
+    case: KeyCase,
+    path: &mut Vec<String>,
+    skipped: &mut Vec<Vec<String>>,
+) {
+    match value {
+        Value::InlineTable(table) => {
+            convert_keys(&mut table.items, case, path, skipped);
+            for kv in table.items.values_mut() {
+                if let Item::Value(value) = &mut kv.value {
+                    path.push(kv.key.get().to_owned());
+                    convert_value(value, case, path, skipped);
+                    path.pop();
+                }
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter_mut() {
+                convert_value(value, case, path, skipped);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Renames the keys of one table in place, keeping the names that would clash
+fn convert_keys(
+    items: &mut KeyValuePairs,
+    case: KeyCase,
+    path: &[String],
+    skipped: &mut Vec<Vec<String>>,
+) {
+    let mut renamed = items
+        .values()
+        .map(|kv| kv.key.to_case(case).filter(|key| key.get() != kv.key.get()))
+        .collect::<Vec<_>>();
+    // Keeping a key's name can clash with another key renamed to it, so repeat until settled
+    loop {
+        let mut names = IndexMap::default();
+        for (kv, renamed) in items.values().zip(&renamed) {
+            let name = renamed.as_ref().unwrap_or(&kv.key).get();
+            *names.entry(name.to_owned()).or_insert(0) += 1;
+        }
+        let mut changed = false;
+        for renamed in renamed.iter_mut() {
+            if matches!(renamed, Some(key) if 1 < names[key.get()]) {
+                *renamed = None;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    for (kv, renamed) in items.values().zip(&renamed) {
+        if renamed.is_none() && !case.matches(kv.key.get()) {
+            let mut path = path.to_vec();
+            path.push(kv.key.get().to_owned());
+            skipped.push(path);
+        }
+    }
+    *items = core::mem::take(items)
+        .into_iter()
+        .zip(renamed)
+        .map(|((name, kv), renamed)| match renamed {
+            Some(key) => (key.get().into(), TableKeyValue::new(key, kv.value)),
+            None => (name, kv),
+        })
+        .collect();
+}
+
+// Calls `f` with the path to each key within `item`, in document order
+fn walk_item(item: &Item, path: &mut Vec<String>, f: &mut dyn FnMut(&[String])) {
+    match item {
+        Item::None => {}
+        Item::Value(value) => walk_value(value, path, f),
+        Item::Table(table) => walk_table(table, path, f),
+        Item::ArrayOfTables(array) => {
+            for table in array.iter() {
+                walk_table(table, path, f);
+            }
+        }
+    }
+}
+
+fn walk_table(table: &Table, path: &mut Vec<String>, f: &mut dyn FnMut(&[String])) {
+    for (key, item) in table.iter() {
+        path.push(key.to_owned());
+        f(path);
+        walk_item(item, path, f);
+        path.pop();
+    }
+}
+
+fn walk_value(value: &Value, path: &mut Vec<String>, f: &mut dyn FnMut(&[String])) {
+    match value {
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter() {
+                path.push(key.to_owned());
+                f(path);
+                walk_value(value, path, f);
+                path.pop();
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter() {
+                walk_value(value, path, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A naming convention for keys, see [`KeyPolicy::case`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyCase {
+    /// Lowercase ASCII letters, digits, and underscores, like `snake_case`
+    Snake,
+    /// Lowercase ASCII letters, digits, and hyphens, like `kebab-case`
+    Kebab,
+}
+
+impl KeyCase {
+    fn separator(self) -> char {
+        match self {
+            KeyCase::Snake => '_',
+            KeyCase::Kebab => '-',
+        }
+    }
+
+    fn matches(self, key: &str) -> bool {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == self.separator())
+    }
+
+    /// Rename `key` to follow the convention, splitting it into words at punctuation,
+    /// whitespace, and lower-to-upper case changes.
+    ///
+    /// Returns `None` if the key can't be written following the convention, like when it has
+    /// non-ASCII letters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::lint::KeyCase;
+    ///
+    /// assert_eq!(KeyCase::Kebab.convert("max_HTTPRetries").unwrap(), "max-httpretries");
+    /// assert_eq!(KeyCase::Snake.convert("Rust Version").unwrap(), "rust_version");
+    /// assert_eq!(KeyCase::Snake.convert("--"), None);
+    /// ```
+    pub fn convert(self, key: &str) -> Option<String> {
+        let mut converted = String::with_capacity(key.len());
+        let mut prev_lower = false;
+        let mut word_break = false;
+        for c in key.chars() {
+            if !c.is_alphanumeric() {
+                word_break = true;
+                prev_lower = false;
+                continue;
+            }
+            if (word_break || prev_lower && c.is_uppercase()) && !converted.is_empty() {
+                converted.push(self.separator());
+            }
+            word_break = false;
+            prev_lower = c.is_lowercase() || c.is_numeric();
+            converted.extend(c.to_lowercase());
+        }
+        Some(converted).filter(|converted| self.matches(converted))
+    }
+}
+
+impl core::fmt::Display for KeyCase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyCase::Snake => write!(f, "snake_case"),
+            KeyCase::Kebab => write!(f, "kebab-case"),
+        }
+    }
+}
+
+/// Rules for naming keys, checked over a whole document.
+///
+/// ```
+/// use toml_edit::lint::{KeyCase, KeyPolicy};
+///
+/// let input = "[package]\nname = \"demo\"\nrustVersion = \"1.60\"\n";
+/// let violations = KeyPolicy::new().case(KeyCase::Kebab).check(input).unwrap();
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].path(), ["package", "rustVersion"]);
+/// assert_eq!(&input[violations[0].span()], "rustVersion");
+/// assert_eq!(violations[0].suggestion(), Some("rust-version"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyPolicy {
+    case: Option<KeyCase>,
+    max_len: Option<usize>,
+}
+
+impl KeyPolicy {
+    /// A policy allowing any key.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Require keys to follow a naming convention.
+    pub fn case(mut self, case: KeyCase) -> Self {
+        self.case = Some(case);
+        self
+    }
+
+    /// Require keys to be at most `max_len` bytes long.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Find keys in `input` that go against the policy.
+    ///
+    /// Each key is reported once, at its first occurrence, and only syntax is checked, returning
+    /// an error if `input` is not valid TOML syntax.  Keys within inline tables are located by
+    /// the key/value pair containing the inline table.
+    pub fn check(&self, input: &str) -> Result<Vec<KeyViolation>, TomlError> {
+        let mut violations = Vec::new();
+        let mut seen = IndexSet::default();
+        let mut table = Vec::new();
+
+        let mut events = Events::new(input);
+        loop {
+            let start = events.offset();
+            let event = match events.next() {
+                Some(event) => event?,
+                None => break,
+            };
+            let span = start..events.offset();
+            match event {
+                Event::TableHeader { path, is_array } => {
+                    table.clear();
+                    let bracket = if is_array { 2 } else { 1 };
+                    self.check_keys(
+                        &path,
+                        span.start + bracket,
+                        &mut table,
+                        &mut seen,
+                        &mut violations,
+                    );
+                }
+                Event::KeyValue { path, value } => {
+                    let mut path_ = table.clone();
+                    self.check_keys(&path, span.start, &mut path_, &mut seen, &mut violations);
+                    walk_value(&value, &mut path_, &mut |path| {
+                        self.check_key(path, span.clone(), &mut seen, &mut violations)
+                    });
+                }
+                Event::Whitespace(_) | Event::Comment(_) => {}
+            }
+        }
+        Ok(violations)
+    }
+
+    // Pushes each of `keys`, starting at `offset` in the input, onto `path`
+    fn check_keys(
+        &self,
+        keys: &[Key],
+        mut offset: usize,
+        path: &mut Vec<String>,
+        seen: &mut IndexSet<Vec<String>>,
+        violations: &mut Vec<KeyViolation>,
+    ) {
+        for key in keys {
+            let repr = key.to_repr();
+            let raw = repr.as_raw();
+            offset += key.decor().prefix().unwrap_or_default().len();
+            let span = offset..offset + raw.len();
+            offset = span.end + key.decor().suffix().unwrap_or_default().len() + 1;
+
+            path.push(key.get().to_owned());
+            self.check_key(path, span, seen, violations);
+        }
+    }
+
+    fn check_key(
+        &self,
+        path: &[String],
+        span: Range<usize>,
+        seen: &mut IndexSet<Vec<String>>,
+        violations: &mut Vec<KeyViolation>,
+    ) {
+        let key = path.last().expect("paths are non-empty");
+        let wrong_case = self.wrong_case(key);
+        let too_long = self.too_long(key);
+        if (wrong_case.is_some() || too_long.is_some()) && seen.insert(path.to_vec()) {
+            violations.push(KeyViolation {
+                path: path.to_vec(),
+                span,
+                wrong_case,
+                too_long,
+                suggestion: wrong_case.and_then(|case| case.convert(key)),
+            });
+        }
+    }
+
+    fn wrong_case(&self, key: &str) -> Option<KeyCase> {
+        self.case.filter(|case| !case.matches(key))
+    }
+
+    // The maximum length `key` exceeds
+    fn too_long(&self, key: &str) -> Option<usize> {
+        self.max_len.filter(|max_len| *max_len < key.len())
+    }
+}
+
+/// A key going against a [`KeyPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyViolation {
+    path: Vec<String>,
+    span: Range<usize>,
+    wrong_case: Option<KeyCase>,
+    too_long: Option<usize>,
+    suggestion: Option<String>,
+}
+
+impl KeyViolation {
+    /// The keys leading to and including the key.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The byte range of the key, or of the key/value pair for keys within inline tables.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The naming convention the key doesn't follow.
+    pub fn wrong_case(&self) -> Option<KeyCase> {
+        self.wrong_case
+    }
+
+    /// The maximum length the key exceeds.
+    pub fn too_long(&self) -> Option<usize> {
+        self.too_long
+    }
+
+    /// The key renamed to follow the naming convention, if it can be.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl core::fmt::Display for KeyViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let key = Key::new(self.path.last().expect("paths are non-empty").as_str());
+        write!(f, "key `{}`", key.to_repr().as_raw())?;
+        if let Some(case) = self.wrong_case {
+            write!(f, " is not {}", case)?;
+        }
+        if let Some(max_len) = self.too_long {
+            let and = if self.wrong_case.is_some() {
+                " and"
+            } else {
+                ""
+            };
+            write!(f, "{} is longer than {} bytes", and, max_len)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; rename it to `{}`", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks for a document written in an inconsistent style.
+///
+/// Checks are enabled one at a time, or all at once with [`StyleLints::all`].
+///
+/// ```
+/// use toml_edit::lint::{StyleLintKind, StyleLints};
+///
+/// let input = "[dependencies]\nserde = \"1\"\nlog = '0.4' \n";
+/// let lints = StyleLints::all().check(input).unwrap();
+/// assert_eq!(lints.len(), 3);
+/// assert_eq!(lints[0].kind(), StyleLintKind::UnsortedKeys);
+/// assert_eq!(&input[lints[0].span()], "log = '0.4'");
+/// assert_eq!(lints[1].kind(), StyleLintKind::MixedStringStyle);
+/// assert_eq!(&input[lints[1].span()], "'0.4'");
+/// assert_eq!(lints[2].kind(), StyleLintKind::TrailingWhitespace);
+/// assert_eq!(&input[lints[2].span()], " ");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleLints {
+    indentation: bool,
+    string_styles: bool,
+    sorted_keys: bool,
+    trailing_whitespace: bool,
+}
+
+impl StyleLints {
+    /// Checks enabling nothing.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Checks enabling everything.
+    pub fn all() -> Self {
+        Self {
+            indentation: true,
+            string_styles: true,
+            sorted_keys: true,
+            trailing_whitespace: true,
+        }
+    }
+
+    /// Check that lines are indented with one of tabs or spaces, and that the key/value pairs
+    /// of a table are indented alike, see [`StyleLintKind::InconsistentIndentation`].
+    pub fn indentation(mut self, yes: bool) -> Self {
+        self.indentation = yes;
+        self
+    }
+
+    /// Check that strings are quoted alike, see [`StyleLintKind::MixedStringStyle`].
+    pub fn string_styles(mut self, yes: bool) -> Self {
+        self.string_styles = yes;
+        self
+    }
+
+    /// Check that the keys of dependency-like tables are sorted, see
+    /// [`StyleLintKind::UnsortedKeys`].
+    pub fn sorted_keys(mut self, yes: bool) -> Self {
+        self.sorted_keys = yes;
+        self
+    }
+
+    /// Check for spaces and tabs ending a line, see [`StyleLintKind::TrailingWhitespace`].
+    pub fn trailing_whitespace(mut self, yes: bool) -> Self {
+        self.trailing_whitespace = yes;
+        self
+    }
+
+    /// Find where `input` strays from its own style.
+    ///
+    /// The first indented line and the first single-line string set the style the rest of the
+    /// document is checked against.  Only syntax is checked, returning an error if `input` is
+    /// not valid TOML syntax.  Lints are returned in document order.
+    pub fn check(&self, input: &str) -> Result<Vec<StyleLint>, TomlError> {
+        let mut lints = Vec::new();
+        let mut table = Vec::new();
+        let mut style = DocumentStyle::default();
+        let mut table_indent = None;
+        let mut last_key: Option<String> = None;
+
+        let mut events = Events::new(input);
+        loop {
+            let start = events.offset();
+            let event = match events.next() {
+                Some(event) => event?,
+                None => break,
+            };
+            let span = start..events.offset();
+            match event {
+                Event::Whitespace(whitespace) if self.trailing_whitespace => {
+                    let at_end = span.end == input.len();
+                    for trailing in trailing_whitespace(whitespace, start, at_end) {
+                        lints.push(StyleLint::new(
+                            StyleLintKind::TrailingWhitespace,
+                            &table,
+                            trailing,
+                        ));
+                    }
+                }
+                Event::Comment(comment) if self.trailing_whitespace => {
+                    let trimmed = comment.trim_end_matches([' ', '\t']);
+                    if trimmed.len() < comment.len() {
+                        let trailing = start + trimmed.len()..span.end;
+                        lints.push(StyleLint::new(
+                            StyleLintKind::TrailingWhitespace,
+                            &table,
+                            trailing,
+                        ));
+                    }
+                }
+                Event::Whitespace(_) | Event::Comment(_) => {}
+                Event::TableHeader { path, .. } => {
+                    table = path.iter().map(|k| k.get().to_owned()).collect();
+                    table_indent = None;
+                    last_key = None;
+                    if self.indentation {
+                        let indent = indentation(input, start);
+                        if !style.indented_alike(indent) {
+                            lints.push(StyleLint::new(
+                                StyleLintKind::InconsistentIndentation,
+                                &table,
+                                start - indent.len()..span.end,
+                            ));
+                        }
+                    }
+                }
+                Event::KeyValue { path, value } => {
+                    let mut kv_path = table.clone();
+                    kv_path.extend(path.iter().map(|k| k.get().to_owned()));
+                    if self.indentation {
+                        let indent = indentation(input, start);
+                        let table_indent = *table_indent.get_or_insert(indent);
+                        if !style.indented_alike(indent) || indent != table_indent {
+                            lints.push(StyleLint::new(
+                                StyleLintKind::InconsistentIndentation,
+                                &kv_path,
+                                start - indent.len()..span.end,
+                            ));
+                        }
+                    }
+                    if self.sorted_keys && is_dependency_like(&table) {
+                        let key = path[0].get();
+                        if matches!(&last_key, Some(last) if key < last.as_str()) {
+                            lints.push(StyleLint::new(
+                                StyleLintKind::UnsortedKeys,
+                                &kv_path,
+                                span.clone(),
+                            ));
+                        }
+                        last_key = Some(key.to_owned());
+                    }
+                    if self.string_styles {
+                        let suffix = value.decor().suffix().unwrap_or_default().len();
+                        let value_end = span.end - suffix;
+                        check_strings(
+                            &value,
+                            &span,
+                            Some(value_end),
+                            &mut kv_path,
+                            &mut style,
+                            &mut lints,
+                        );
+                    }
+                }
+            }
+        }
+        Ok(lints)
+    }
+}
+
+// The style set by the first indented line and the first single-line string
+#[derive(Default)]
+struct DocumentStyle {
+    indent_char: Option<char>,
+    quote: Option<char>,
+}
+
+impl DocumentStyle {
+    fn indented_alike(&mut self, indent: &str) -> bool {
+        let mut chars = indent.chars();
+        match chars.next() {
+            Some(c) if chars.all(|other| other == c) => *self.indent_char.get_or_insert(c) == c,
+            Some(_) => false,
+            None => true,
+        }
+    }
+}
+
+// Tables listing packages by name, like `[dependencies]` or `[workspace.dev-dependencies]`
+fn is_dependency_like(table: &[String]) -> bool {
+    matches!(table.last(), Some(key) if key.ends_with("dependencies"))
+}
+
+// The spaces and tabs at the start of the line, when the event at `start` begins the line
+fn indentation(input: &str, start: usize) -> &str {
+    let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = input[line_start..start].trim_start_matches('\u{feff}');
+    if indent.chars().all(|c| c == ' ' || c == '\t') {
+        indent
+    } else {
+        ""
+    }
+}
+
+// The spans of spaces and tabs before each line ending in `whitespace`, and at its end when it
+// ends the input
+fn trailing_whitespace(whitespace: &str, start: usize, at_end: bool) -> Vec<Range<usize>> {
+    let mut trailing = Vec::new();
+    let mut offset = start;
+    let lines = whitespace.split('\n').collect::<Vec<_>>();
+    for (i, line) in lines.iter().enumerate() {
+        let last = i == lines.len() - 1;
+        let line_ = line.strip_suffix('\r').unwrap_or(line);
+        if !line_.is_empty() && (!last || at_end) {
+            trailing.push(offset..offset + line_.len());
+        }
+        offset += line.len() + 1;
+    }
+    trailing
+}
+
+// A string directly after the `=` is located exactly, ending at `value_end`, others by their
+// key/value pair
+fn check_strings(
+    value: &Value,
+    span: &Range<usize>,
+    value_end: Option<usize>,
+    path: &mut Vec<String>,
+    style: &mut DocumentStyle,
+    lints: &mut Vec<StyleLint>,
+) {
+    match value {
+        Value::String(s) => {
+            let repr = s.to_repr();
+            let raw = repr.as_raw();
+            if raw.starts_with("\"\"\"") || raw.starts_with("\'\'\'") {
+                return;
+            }
+            let quote = raw.chars().next().unwrap_or('"');
+            let expected = *style.quote.get_or_insert(quote);
+            // A string can be left in the other style when it would need escapes otherwise
+            let needs_other = match expected {
+                '"' => s.value().contains(['"', '\\']),
+                _ => s.value().contains(|c: char| c == '\'' || c.is_control()),
+            };
+            if quote != expected && !needs_other {
+                let value_span = match value_end {
+                    Some(end) => end - raw.len()..end,
+                    None => span.clone(),
+                };
+                lints.push(StyleLint::new(
+                    StyleLintKind::MixedStringStyle,
+                    path,
+                    value_span,
+                ));
+            }
+        }
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter() {
+                path.push(key.to_owned());
+                check_strings(value, span, None, path, style, lints);
+                path.pop();
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter() {
+                check_strings(value, span, None, path, style, lints);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Where a document strays from its own style, see [`StyleLints`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleLint {
+    kind: StyleLintKind,
+    path: Vec<String>,
+    span: Range<usize>,
+}
+
+impl StyleLint {
+    fn new(kind: StyleLintKind, path: &[String], span: Range<usize>) -> Self {
+        Self {
+            kind,
+            path: path.to_vec(),
+            span,
+        }
+    }
+
+    /// What the lint is about.
+    pub fn kind(&self) -> StyleLintKind {
+        self.kind
+    }
+
+    /// The keys of the key/value pair with the lint, or of the table it is in.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The byte range of the input with the lint.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl core::fmt::Display for StyleLint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let path = dotted_path(&self.path);
+        match self.kind {
+            StyleLintKind::InconsistentIndentation => {
+                write!(f, "`{}` is indented differently from the rest", path)
+            }
+            StyleLintKind::MixedStringStyle => {
+                write!(
+                    f,
+                    "string in `{}` is quoted differently from the rest",
+                    path
+                )
+            }
+            StyleLintKind::UnsortedKeys => write!(f, "key `{}` is out of order", path),
+            StyleLintKind::TrailingWhitespace => write!(f, "trailing whitespace"),
+        }
+    }
+}
+
+/// The kind of a [`StyleLint`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StyleLintKind {
+    /// A line indented with a mix of tabs and spaces, with tabs where the document is indented
+    /// with spaces or the other way around, or unlike the other key/value pairs of its table
+    InconsistentIndentation,
+    /// A single-line string quoted with `'` in a document using `"`, or the other way around,
+    /// when it can be written in either without escapes
+    MixedStringStyle,
+    /// A key sorting before the key above it, in a table whose name ends with `dependencies`
+    UnsortedKeys,
+    /// Spaces or tabs ending a line
+    TrailingWhitespace,
+}
@@ -0,0 +1,91 @@
+use crate::visit::{visit_table_like, visit_value, Visit};
+use crate::{Document, Table, TableLike, Value, ValueKind};
+
+impl Table {
+    /// Returns the number of non-empty items in the table and every table nested within it.
+    ///
+    /// Nested tables include subtables, inline tables, and the tables of arrays of tables, at any
+    /// depth.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "a = 1\nb = { c = 2, d = [{ e = 3 }] }\n\n[f]\ng = 4\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// assert_eq!(doc.len(), 3);
+    /// assert_eq!(doc.len_recursive(), 7);
+    /// ```
+    pub fn len_recursive(&self) -> usize {
+        let mut metrics = Metrics::default();
+        metrics.visit_table(self);
+        metrics.len
+    }
+}
+
+impl Document {
+    /// Returns how deeply tables are nested within the document.
+    ///
+    /// Top-level values are at depth 0 and each table they're nested within, whether a subtable,
+    /// dotted key, inline table, or array of tables, adds a level.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "a = 1\n\n[b]\nc.d = { e = 2 }\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.max_depth(), 3);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        let mut metrics = Metrics::default();
+        metrics.visit_document(self);
+        metrics.max_depth
+    }
+
+    /// Returns the number of values of `kind` in the document, including those nested within
+    /// arrays and inline tables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::ValueKind;
+    ///
+    /// let doc = "a = 1\nb = [2, 3.0]\n\n[c]\nd = { e = 4 }\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.count_kind(ValueKind::Integer), 3);
+    /// assert_eq!(doc.count_kind(ValueKind::Float), 1);
+    /// assert_eq!(doc.count_kind(ValueKind::InlineTable), 1);
+    /// ```
+    pub fn count_kind(&self, kind: ValueKind) -> usize {
+        let mut metrics = Metrics {
+            kind: Some(kind),
+            ..Default::default()
+        };
+        metrics.visit_document(self);
+        metrics.kind_count
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    depth: usize,
+    max_depth: usize,
+    len: usize,
+    kind: Option<ValueKind>,
+    kind_count: usize,
+}
+
+impl<'doc> Visit<'doc> for Metrics {
+    fn visit_table_like(&mut self, node: &'doc dyn TableLike) {
+        self.max_depth = self.max_depth.max(self.depth);
+        self.len += node.len();
+        self.depth += 1;
+        visit_table_like(self, node);
+        self.depth -= 1;
+    }
+
+    fn visit_value(&mut self, node: &'doc Value) {
+        if Some(node.kind()) == self.kind {
+            self.kind_count += 1;
+        }
+        visit_value(self, node);
+    }
+}
@@ -1,10 +1,13 @@
+use alloc::vec::Vec;
+
 use nom8::combinator::cut;
 use nom8::combinator::opt;
 use nom8::multi::separated_list1;
 use nom8::sequence::delimited;
 
+use crate::parser::errors::{CustomError, WarningKind};
 use crate::parser::trivia::ws_comment_newline;
-use crate::parser::value::value;
+use crate::parser::value::warned_value;
 use crate::{Array, Item, Value};
 
 use crate::parser::prelude::*;
@@ -14,7 +17,7 @@ use crate::parser::prelude::*;
 // array = array-open array-values array-close
 pub(crate) fn array(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, Array, ParserError<'_>> {
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Array, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
         delimited(
             ARRAY_OPEN,
@@ -23,6 +26,10 @@ pub(crate) fn array(
                 .context(Context::Expression("array"))
                 .context(Context::Expected(ParserValue::CharLiteral(']'))),
         )
+        .map_res(|(array, warnings): (Array, _)| {
+            check.check_array_len(array.len())?;
+            Ok::<_, CustomError>((array, warnings))
+        })
         .parse(input)
     }
 }
@@ -32,16 +39,16 @@ pub(crate) fn array(
 // array-open  = %x5B ws-newline  ; [
 pub(crate) const ARRAY_OPEN: u8 = b'[';
 // array-close = ws-newline %x5D  ; ]
-const ARRAY_CLOSE: u8 = b']';
+pub(crate) const ARRAY_CLOSE: u8 = b']';
 // array-sep = ws %x2C ws  ; , Comma
-const ARRAY_SEP: u8 = b',';
+pub(crate) const ARRAY_SEP: u8 = b',';
 
 // note: this rule is modified
 // array-values = [ ( array-value array-sep array-values ) /
 //                  array-value / ws-comment-newline ]
 pub(crate) fn array_values(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, Array, ParserError<'_>> {
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Array, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
         let check = check.recursing(input)?;
         (
@@ -49,19 +56,26 @@ pub(crate) fn array_values(
                 separated_list1(ARRAY_SEP, array_value(check)),
                 opt(ARRAY_SEP),
             )
-                .map(|(v, trailing): (Vec<Value>, Option<u8>)| {
-                    (
-                        Array::with_vec(v.into_iter().map(Item::Value).collect()),
-                        trailing.is_some(),
-                    )
-                })),
+                .map(
+                    |(v, trailing): (Vec<(Value, Vec<WarningKind>)>, Option<u8>)| {
+                        let mut warnings = Vec::new();
+                        let values = v
+                            .into_iter()
+                            .map(|(v, w)| {
+                                warnings.extend(w);
+                                Item::Value(v)
+                            })
+                            .collect();
+                        (Array::with_vec(values), trailing.is_some(), warnings)
+                    },
+                )),
             ws_comment_newline,
         )
-            .map_res::<_, _, std::str::Utf8Error>(|(array, trailing)| {
-                let (mut array, comma) = array.unwrap_or_default();
+            .map_res::<_, _, core::str::Utf8Error>(|(array, trailing)| {
+                let (mut array, comma, warnings) = array.unwrap_or_default();
                 array.set_trailing_comma(comma);
-                array.set_trailing(std::str::from_utf8(trailing)?);
-                Ok(array)
+                array.set_trailing(core::str::from_utf8(trailing)?);
+                Ok((array, warnings))
             })
             .parse(input)
     }
@@ -69,12 +83,12 @@ pub(crate) fn array_values(
 
 pub(crate) fn array_value(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, Value, ParserError<'_>> {
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Value, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
-        (ws_comment_newline, value(check), ws_comment_newline)
-            .map_res::<_, _, std::str::Utf8Error>(|(ws1, v, ws2)| {
-                let v = v.decorated(std::str::from_utf8(ws1)?, std::str::from_utf8(ws2)?);
-                Ok(v)
+        (ws_comment_newline, warned_value(check), ws_comment_newline)
+            .map_res::<_, _, core::str::Utf8Error>(|(ws1, (v, warnings), ws2)| {
+                let v = v.decorated(core::str::from_utf8(ws1)?, core::str::from_utf8(ws2)?);
+                Ok((v, warnings))
             })
             .parse(input)
     }
@@ -123,7 +137,7 @@ mod test {
         ];
         for input in inputs {
             let parsed = array(Default::default()).parse(new_input(input)).finish();
-            assert_eq!(parsed.map(|a| a.to_string()), Ok(input.to_owned()));
+            assert_eq!(parsed.map(|(a, _)| a.to_string()), Ok(input.to_owned()));
         }
 
         let invalid_inputs = [r#"["#, r#"[,]"#, r#"[,2]"#, r#"[1e165,,]"#];
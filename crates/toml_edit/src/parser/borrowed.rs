@@ -0,0 +1,560 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::ops::Range;
+
+use nom8::branch::alt;
+use nom8::bytes::any;
+use nom8::bytes::one_of;
+use nom8::bytes::take;
+use nom8::combinator::cut;
+use nom8::combinator::eof;
+use nom8::combinator::fail;
+use nom8::combinator::opt;
+use nom8::combinator::peek;
+use nom8::multi::many0_count;
+use nom8::multi::separated_list0;
+use nom8::multi::separated_list1;
+use nom8::sequence::delimited;
+use nom8::sequence::terminated;
+
+use crate::borrowed::{Formatted, Item, Key, Table, Value};
+use crate::parser::array::{ARRAY_CLOSE, ARRAY_OPEN, ARRAY_SEP};
+use crate::parser::datetime::date_time;
+use crate::parser::errors::{CustomError, TableConflict, TableKind};
+use crate::parser::inline_table::{
+    inline_ws, INLINE_TABLE_CLOSE, INLINE_TABLE_OPEN, INLINE_TABLE_SEP, KEYVAL_SEP,
+};
+use crate::parser::key::{unquoted_key, DOT_SEP};
+use crate::parser::numbers::{float, integer, integer_in_range};
+use crate::parser::prelude::*;
+use crate::parser::strings::{basic_string, literal_string, string};
+use crate::parser::table::{ARRAY_TABLE_CLOSE, ARRAY_TABLE_OPEN, STD_TABLE_CLOSE, STD_TABLE_OPEN};
+use crate::parser::trivia::{comment, line_ending, line_trailing, newline, ws, ws_comment_newline};
+use crate::parser::{Limit, ParseOptions};
+use crate::repr::Repr;
+
+// Parsers for `crate::borrowed`, which follow the grammar of the owned parsers while keeping
+// slices of the input rather than building `Decor` and `Repr`.
+
+// toml = expression *( newline expression )
+pub(crate) fn document<'i>(
+    options: ParseOptions,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Table<'i>, ParserError<'i>> {
+    move |input| {
+        let check = RecursionCheck::new(options);
+        let state = RefCell::new(ParseState::new(options, input.len()));
+        let state_ref = &state;
+
+        let (i, _) = (
+            opt(b"\xEF\xBB\xBF"),
+            ws,
+            many0_count((
+                dispatch! {peek(any);
+                    crate::parser::trivia::COMMENT_START_SYMBOL => {
+                        cut((comment, line_ending)).value(())
+                    },
+                    STD_TABLE_OPEN => cut(header(state_ref, check)),
+                    crate::parser::trivia::LF |
+                    crate::parser::trivia::CR => newline.value(()),
+                    _ => cut(keyval(state_ref, check)),
+                },
+                ws,
+            )),
+            eof,
+        )
+            .parse(input)?;
+        Ok((i, state.into_inner().root))
+    }
+}
+
+// table = std-table / array-table
+fn header<'s, 'i>(
+    state: &'s RefCell<ParseState<'i>>,
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
+    move |i| {
+        (
+            (dispatch!(peek::<_, &[u8],_,_>(take(2usize));
+                b"[[" => delimited(
+                    ARRAY_TABLE_OPEN,
+                    cut(key(check)),
+                    cut(ARRAY_TABLE_CLOSE)
+                        .context(Context::Expected(ParserValue::CharLiteral('.')))
+                        .context(Context::Expected(ParserValue::StringLiteral("]]"))),
+                ).map(|path| (path, true)),
+                _ => delimited(
+                    STD_TABLE_OPEN,
+                    cut(key(check)),
+                    cut(STD_TABLE_CLOSE)
+                        .context(Context::Expected(ParserValue::CharLiteral('.')))
+                        .context(Context::Expected(ParserValue::StringLiteral("]"))),
+                ).map(|path| (path, false)),
+            ))
+            .with_recognized(),
+            cut(line_trailing)
+                .context(Context::Expected(ParserValue::CharLiteral('\n')))
+                .context(Context::Expected(ParserValue::CharLiteral('#'))),
+        )
+            .map_res(|(((path, is_array), header), _)| {
+                let mut state = state.borrow_mut();
+                let start = state.offset(i);
+                state.on_header(path, is_array, start..start + header.len())
+            })
+            .context(Context::Expression("table header"))
+            .parse(i)
+    }
+}
+
+// keyval = key keyval-sep val
+fn keyval<'s, 'i>(
+    state: &'s RefCell<ParseState<'i>>,
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
+    move |i| {
+        (
+            key(check),
+            cut((
+                one_of(KEYVAL_SEP)
+                    .context(Context::Expected(ParserValue::CharLiteral('.')))
+                    .context(Context::Expected(ParserValue::CharLiteral('='))),
+                ws,
+                value(check),
+                line_trailing
+                    .context(Context::Expected(ParserValue::CharLiteral('\n')))
+                    .context(Context::Expected(ParserValue::CharLiteral('#'))),
+            )),
+        )
+            .map_res(|(path, (_, _, v, _))| state.borrow_mut().on_keyval(path, v))
+            .parse(i)
+    }
+}
+
+// key = simple-key / dotted-key
+// dotted-key = simple-key 1*( dot-sep simple-key )
+fn key<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Vec<Key<'i>>, ParserError<'i>> {
+    move |input| {
+        separated_list1(DOT_SEP, delimited(ws, simple_key, ws))
+            .context(Context::Expression("key"))
+            .map_res(|k: Vec<Key<'_>>| {
+                check.check_depth(k.len())?;
+                for key in &k {
+                    check.check_string_len(key.get().len())?;
+                }
+                Ok::<_, CustomError>(k)
+            })
+            .parse(input)
+    }
+}
+
+// simple-key = quoted-key / unquoted-key
+// quoted-key = basic-string / literal-string
+fn simple_key(input: Input<'_>) -> IResult<Input<'_>, Key<'_>, ParserError<'_>> {
+    (dispatch! {peek(any);
+        crate::parser::strings::QUOTATION_MARK => basic_string,
+        crate::parser::strings::APOSTROPHE => literal_string.map(Cow::Borrowed),
+        _ => unquoted_key.map(Cow::Borrowed),
+    })
+    .with_recognized()
+    .map(|(k, raw)| {
+        let raw = from_utf8_unchecked!(
+            raw,
+            "If `quoted_key` or `unquoted_key` are valid, then their `recognize`d value is valid"
+        );
+        Key::new(k, raw)
+    })
+    .parse(input)
+}
+
+// val = string / boolean / array / inline-table / date-time / float / integer
+fn value<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Value<'i>, ParserError<'i>> {
+    move |input| {
+        (dispatch! {peek(any);
+            crate::parser::strings::QUOTATION_MARK |
+            crate::parser::strings::APOSTROPHE => string.map_res(|s| {
+                check.check_string_len(s.len())?;
+                Ok::<_, CustomError>(Value::String(Formatted::new(s)))
+            }),
+            ARRAY_OPEN => array(check).map(|a| Value::Array(Formatted::new(a))),
+            INLINE_TABLE_OPEN => inline_table(check).map(|t| Value::InlineTable(Formatted::new(t))),
+            // Date/number starts
+            b'+' | b'-' | b'0'..=b'9' => {
+                alt((
+                    date_time.recognize().map(|raw| {
+                        Value::Datetime(from_utf8_unchecked!(raw, "`date_time()` filters out non-ASCII"))
+                    }),
+                    float.map(|f| Value::Float(Formatted::new(f))),
+                    integer_in_range(check.integer_range()).map(Value::from_i128),
+                ))
+            },
+            // Report as if they were numbers because its most likely a typo
+            b'_' => {
+                integer
+                    .map(|i| Value::Integer(Formatted::new(i)))
+                    .context(Context::Expected(ParserValue::Description("leading digit")))
+            },
+            // Report as if they were numbers because its most likely a typo
+            b'.' => {
+                float
+                    .map(|f| Value::Float(Formatted::new(f)))
+                    .context(Context::Expected(ParserValue::Description("leading digit")))
+            },
+            b't' => {
+                crate::parser::numbers::true_.map(|b| Value::Boolean(Formatted::new(b)))
+                    .context(Context::Expression("string"))
+                    .context(Context::Expected(ParserValue::CharLiteral('"')))
+                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
+            },
+            b'f' => {
+                crate::parser::numbers::false_.map(|b| Value::Boolean(Formatted::new(b)))
+                    .context(Context::Expression("string"))
+                    .context(Context::Expected(ParserValue::CharLiteral('"')))
+                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
+            },
+            b'i' => {
+                crate::parser::numbers::inf.map(|f| Value::Float(Formatted::new(f)))
+                    .context(Context::Expression("string"))
+                    .context(Context::Expected(ParserValue::CharLiteral('"')))
+                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
+            },
+            b'n' => {
+                crate::parser::numbers::nan.map(|f| Value::Float(Formatted::new(f)))
+                    .context(Context::Expression("string"))
+                    .context(Context::Expected(ParserValue::CharLiteral('"')))
+                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
+            },
+            _ => {
+                fail
+                    .context(Context::Expression("string"))
+                    .context(Context::Expected(ParserValue::CharLiteral('"')))
+                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
+            },
+        })
+        .with_recognized()
+        .map_res(|(mut value, raw)| {
+            value.set_raw(core::str::from_utf8(raw)?);
+            Ok::<_, core::str::Utf8Error>(value)
+        })
+        .parse(input)
+    }
+}
+
+// array = array-open array-values array-close
+fn array<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Vec<Value<'i>>, ParserError<'i>> {
+    move |input| {
+        delimited(
+            ARRAY_OPEN,
+            cut(array_values(check)),
+            cut(ARRAY_CLOSE)
+                .context(Context::Expression("array"))
+                .context(Context::Expected(ParserValue::CharLiteral(']'))),
+        )
+        .map_res(|array: Vec<_>| {
+            check.check_array_len(array.len())?;
+            Ok::<_, CustomError>(array)
+        })
+        .parse(input)
+    }
+}
+
+// array-values = [ ( array-value array-sep array-values ) /
+//                  array-value / ws-comment-newline ]
+fn array_values<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Vec<Value<'i>>, ParserError<'i>> {
+    move |input| {
+        let check = check.recursing(input)?;
+        terminated(
+            opt(terminated(
+                separated_list1(
+                    ARRAY_SEP,
+                    delimited(ws_comment_newline, value(check), ws_comment_newline),
+                ),
+                opt(ARRAY_SEP),
+            ))
+            .map(Option::unwrap_or_default),
+            ws_comment_newline,
+        )
+        .parse(input)
+    }
+}
+
+// inline-table = inline-table-open inline-table-keyvals inline-table-close
+fn inline_table<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Table<'i>, ParserError<'i>> {
+    move |input| {
+        delimited(
+            INLINE_TABLE_OPEN,
+            cut(inline_table_keyvals(check).map_res(table_from_pairs)),
+            cut(INLINE_TABLE_CLOSE)
+                .context(Context::Expression("inline table"))
+                .context(Context::Expected(ParserValue::CharLiteral('}'))),
+        )
+        .parse(input)
+    }
+}
+
+fn table_from_pairs<'i>(pairs: Vec<(Vec<Key<'i>>, Value<'i>)>) -> Result<Table<'i>, CustomError> {
+    let mut root = Table::default();
+    for (mut path, value) in pairs {
+        let key = path.pop().expect("grammar ensures at least 1");
+        let table = descend_path(&mut root, &path, true)?;
+        if table.items.contains_key(key.get()) {
+            return Err(CustomError::DuplicateKey {
+                key: key.get().into(),
+                table: None,
+            });
+        }
+        table.items.insert(key, Item::Value(value));
+    }
+    Ok(root)
+}
+
+// inline-table-keyvals = [ inline-table-keyvals-non-empty ]
+// inline-table-keyvals-non-empty =
+// ( key keyval-sep val inline-table-sep inline-table-keyvals-non-empty ) /
+// ( key keyval-sep val )
+//
+// TOML 1.1 allows a trailing `inline-table-sep` and replaces `ws` with `ws-comment-newline`
+// around key/value pairs.
+fn inline_table_keyvals<'i>(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, Vec<(Vec<Key<'i>>, Value<'i>)>, ParserError<'i>> {
+    move |input| {
+        let check = check.recursing(input)?;
+        let (input, pairs) = separated_list0(
+            INLINE_TABLE_SEP,
+            (
+                inline_ws,
+                key(check),
+                cut((
+                    one_of(KEYVAL_SEP)
+                        .context(Context::Expected(ParserValue::CharLiteral('.')))
+                        .context(Context::Expected(ParserValue::CharLiteral('='))),
+                    ws,
+                    value(check),
+                    inline_ws,
+                )),
+            )
+                .map(|(_, path, (_, _, v, _))| (path, v)),
+        )
+        .parse(input)?;
+        let (input, _) = if cfg!(feature = "toml-1.1") && !pairs.is_empty() {
+            opt(INLINE_TABLE_SEP).parse(input)?
+        } else {
+            (input, None)
+        };
+        let (input, _) = inline_ws.parse(input)?;
+        Ok((input, pairs))
+    }
+}
+
+struct ParseState<'i> {
+    root: Table<'i>,
+    current_table_path: Vec<Key<'i>>,
+    input_len: usize,
+    keys: usize,
+    options: ParseOptions,
+}
+
+impl<'i> ParseState<'i> {
+    fn new(options: ParseOptions, input_len: usize) -> Self {
+        Self {
+            root: Table::default(),
+            current_table_path: Vec::new(),
+            input_len,
+            keys: 0,
+            options,
+        }
+    }
+
+    fn offset(&self, rest: Input<'_>) -> usize {
+        self.input_len - rest.len()
+    }
+
+    fn count_keys(&mut self, keys: usize) -> Result<(), CustomError> {
+        self.keys = self.keys.saturating_add(keys);
+        if self.keys <= self.options.max_keys {
+            Ok(())
+        } else {
+            Err(CustomError::LimitExceeded(
+                Limit::Keys,
+                self.options.max_keys,
+            ))
+        }
+    }
+
+    fn on_keyval(&mut self, mut path: Vec<Key<'i>>, value: Value<'i>) -> Result<(), CustomError> {
+        self.count_keys(1 + nested_keys(&value))?;
+        let key = path.pop().expect("grammar ensures at least 1");
+
+        let table = descend_path(&mut self.root, &self.current_table_path, false)?;
+        let table = descend_path(table, &path, true)?;
+
+        // "Likewise, using dotted keys to redefine tables already defined in [table] form is not allowed"
+        if table.dotted == path.is_empty() {
+            return Err(CustomError::DuplicateKey {
+                key: key.get().into(),
+                table: None,
+            });
+        }
+
+        if table.items.contains_key(key.get()) {
+            return Err(CustomError::DuplicateKey {
+                key: key.get().into(),
+                table: Some(owned_path(&self.current_table_path)),
+            });
+        }
+        table.items.insert(key, Item::Value(value));
+
+        Ok(())
+    }
+
+    fn on_header(
+        &mut self,
+        path: Vec<Key<'i>>,
+        is_array: bool,
+        span: Range<usize>,
+    ) -> Result<(), CustomError> {
+        debug_assert!(!path.is_empty());
+
+        self.count_keys(1)?;
+        let max_array_len = self.options.max_array_len;
+        let (key, parent_path) = path.split_last().expect("grammar ensures at least 1");
+        let parent = descend_path(&mut self.root, parent_path, false)?;
+        let table = Table {
+            header: span.clone(),
+            ..Default::default()
+        };
+        match (parent.items.get_mut(key.get()), is_array) {
+            (None, false) => {
+                parent.items.insert(key.clone(), Item::Table(table));
+            }
+            (None, true) => {
+                check_array_len(0, max_array_len)?;
+                parent
+                    .items
+                    .insert(key.clone(), Item::ArrayOfTables(vec![table]));
+            }
+            (Some(Item::Table(t)), false) if t.implicit && !t.dotted => {
+                t.implicit = false;
+                t.header = span;
+            }
+            (Some(Item::ArrayOfTables(array)), true) => {
+                check_array_len(array.len(), max_array_len)?;
+                array.push(table);
+            }
+            (Some(Item::ArrayOfTables(array)), false) => {
+                return Err(CustomError::TableConflict(TableConflict::new(
+                    &owned_path(&path),
+                    TableKind::ArrayOfTables,
+                    array[0].header.clone(),
+                    span,
+                )));
+            }
+            (Some(Item::Table(t)), true) if !t.implicit => {
+                return Err(CustomError::TableConflict(TableConflict::new(
+                    &owned_path(&path),
+                    TableKind::Table,
+                    t.header.clone(),
+                    span,
+                )));
+            }
+            // Since tables cannot be defined more than once, redefining such tables using a [table] header is not allowed. Likewise, using dotted keys to redefine tables already defined in [table] form is not allowed.
+            (Some(_), _) => {
+                return Err(CustomError::duplicate_key(
+                    &owned_path(&path),
+                    path.len() - 1,
+                ))
+            }
+        }
+        self.current_table_path = path;
+
+        Ok(())
+    }
+}
+
+// Arrays of tables are limited like arrays, before adding each table
+fn check_array_len(len: usize, max: usize) -> Result<(), CustomError> {
+    if len < max {
+        Ok(())
+    } else {
+        Err(CustomError::LimitExceeded(Limit::ArrayLength, max))
+    }
+}
+
+fn descend_path<'t, 'i>(
+    mut table: &'t mut Table<'i>,
+    path: &[Key<'i>],
+    dotted: bool,
+) -> Result<&'t mut Table<'i>, CustomError> {
+    for (i, key) in path.iter().enumerate() {
+        let entry = table.items.entry(key.clone()).or_insert_with(|| {
+            Item::Table(Table {
+                implicit: true,
+                dotted,
+                ..Default::default()
+            })
+        });
+        table = match entry {
+            Item::Value(v) => {
+                return Err(CustomError::extend_wrong_type(
+                    &owned_path(path),
+                    i,
+                    v.type_name(),
+                ));
+            }
+            Item::ArrayOfTables(array) => array
+                .last_mut()
+                .expect("arrays of tables are created with a table"),
+            Item::Table(sweet_child_of_mine) => {
+                // Using dotted keys to redefine tables already defined in [table] form is not
+                // allowed.  A [table] header may still add sub-tables to a table defined by
+                // dotted keys.
+                if dotted && !sweet_child_of_mine.implicit {
+                    return Err(CustomError::DuplicateKey {
+                        key: key.get().into(),
+                        table: None,
+                    });
+                }
+                sweet_child_of_mine
+            }
+        };
+    }
+    Ok(table)
+}
+
+// Keys within inline tables in `value`
+fn nested_keys(value: &Value<'_>) -> usize {
+    match value {
+        Value::InlineTable(t) => table_keys(t.value()),
+        Value::Array(a) => a.value().iter().map(nested_keys).sum(),
+        _ => 0,
+    }
+}
+
+fn table_keys(table: &Table<'_>) -> usize {
+    table
+        .iter()
+        .map(|(_, item)| match item {
+            Item::Value(v) => 1 + nested_keys(v),
+            Item::Table(t) => 1 + table_keys(t),
+            Item::ArrayOfTables(_) => 1,
+        })
+        .sum()
+}
+
+// Errors name keys as they were written, so only they are copied
+fn owned_path(path: &[Key<'_>]) -> Vec<crate::Key> {
+    path.iter()
+        .map(|k| crate::Key::new(k.get()).with_repr_unchecked(Repr::new_unchecked(k.raw())))
+        .collect()
+}
@@ -1,8 +1,7 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 use crate::parser::errors::CustomError;
 use crate::parser::prelude::*;
-use crate::parser::trivia::from_utf8_unchecked;
 
 use nom8::branch::alt;
 use nom8::bytes::one_of;
@@ -10,7 +9,11 @@ use nom8::bytes::take_while_m_n;
 use nom8::combinator::cut;
 use nom8::combinator::opt;
 use nom8::sequence::preceded;
-use toml_datetime::*;
+use nom8::sequence::terminated;
+#[cfg(feature = "datetime")]
+pub(crate) use toml_datetime::*;
+#[cfg(not(feature = "datetime"))]
+pub(crate) use uninterpreted::*;
 
 // ;; Date and Time (as defined in RFC 3339)
 
@@ -49,9 +52,17 @@ pub(crate) fn date_time(input: Input<'_>) -> IResult<Input<'_>, Datetime, Parser
 
 // full-date      = date-fullyear "-" date-month "-" date-mday
 pub(crate) fn full_date(input: Input<'_>) -> IResult<Input<'_>, Date, ParserError<'_>> {
-    (date_fullyear, b'-', cut((date_month, b'-', date_mday)))
-        .map(|(year, _, (month, _, day))| Date { year, month, day })
-        .parse(input)
+    let (input, year) = terminated(date_fullyear, b'-').parse(input)?;
+    let (input, month) = cut(terminated(date_month, b'-')).parse(input)?;
+    cut(date_mday.map_res(move |day| {
+        let days = days_in_month(year, month);
+        if day <= days {
+            Ok(Date { year, month, day })
+        } else {
+            Err(CustomError::DayOutOfRange { year, month, days })
+        }
+    }))
+    .parse(input)
 }
 
 // partial-time   = time-hour ":" time-minute ":" time-second [time-secfrac]
@@ -126,6 +137,15 @@ pub(crate) fn date_mday(input: Input<'_>) -> IResult<Input<'_>, u8, ParserError<
         .parse(input)
 }
 
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
 // time-delim     = "T" / %x20 ; T, t, or space
 pub(crate) fn time_delim(input: Input<'_>) -> IResult<Input<'_>, u8, ParserError<'_>> {
     one_of(TIME_DELIM).parse(input)
@@ -215,13 +235,57 @@ pub(crate) fn unsigned_digits<const MIN: usize, const MAX: usize>(
     input: Input<'_>,
 ) -> IResult<Input<'_>, &str, ParserError<'_>> {
     take_while_m_n(MIN, MAX, DIGIT)
-        .map(|b: &[u8]| unsafe { from_utf8_unchecked(b, "`is_ascii_digit` filters out on-ASCII") })
+        .map(|b: &[u8]| from_utf8_unchecked!(b, "`is_ascii_digit` filters out on-ASCII"))
         .parse(input)
 }
 
 // DIGIT = %x30-39 ; 0-9
 const DIGIT: RangeInclusive<u8> = b'0'..=b'9';
 
+// Stand-ins for the `toml_datetime` types, so datetimes are still checked without the `datetime`
+// feature
+#[cfg(not(feature = "datetime"))]
+#[allow(dead_code)] // only checked, never read
+mod uninterpreted {
+    #[derive(Copy, Clone, Debug)]
+    pub(crate) struct Datetime {
+        pub(crate) date: Option<Date>,
+        pub(crate) time: Option<Time>,
+        pub(crate) offset: Option<Offset>,
+    }
+
+    impl From<Time> for Datetime {
+        fn from(time: Time) -> Self {
+            Datetime {
+                date: None,
+                time: Some(time),
+                offset: None,
+            }
+        }
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub(crate) struct Date {
+        pub(crate) year: u16,
+        pub(crate) month: u8,
+        pub(crate) day: u8,
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub(crate) struct Time {
+        pub(crate) hour: u8,
+        pub(crate) minute: u8,
+        pub(crate) second: u8,
+        pub(crate) nanosecond: u32,
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub(crate) enum Offset {
+        Z,
+        Custom { hours: i8, minutes: u8 },
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -262,6 +326,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn calendar_dates() {
+        let inputs = ["2023-02-28", "2024-02-29", "2000-02-29", "2023-04-30"];
+        for input in inputs {
+            date_time.parse(new_input(input)).finish().unwrap();
+        }
+        let inputs = ["2023-02-29", "1900-02-29", "2023-02-30", "2023-04-31"];
+        for input in inputs {
+            date_time.parse(new_input(input)).finish().unwrap_err();
+        }
+    }
+
     #[test]
     fn time_fraction_truncated() {
         let input = "1987-07-05T17:45:00.123456789012345Z";
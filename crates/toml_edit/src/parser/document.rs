@@ -1,4 +1,7 @@
-use std::cell::RefCell;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
 
 use nom8::bytes::any;
 use nom8::bytes::one_of;
@@ -8,18 +11,23 @@ use nom8::combinator::opt;
 use nom8::combinator::peek;
 use nom8::error::FromExternalError;
 use nom8::multi::many0_count;
+use nom8::sequence::terminated;
 
-use crate::document::Document;
+use crate::document::{Document, NewlineStyle};
 use crate::key::Key;
+use crate::parser::errors::{ParseWarning, WarningKind};
 use crate::parser::inline_table::KEYVAL_SEP;
 use crate::parser::key::key;
+use crate::parser::numbers::decimal_comma_float;
 use crate::parser::prelude::*;
 use crate::parser::state::ParseState;
 use crate::parser::table::table;
 use crate::parser::trivia::{comment, line_ending, line_trailing, newline, ws};
-use crate::parser::value::value;
+use crate::parser::value::warned_value;
+use crate::parser::ParseOptions;
+use crate::repr::{Formatted, Repr};
 use crate::table::TableKeyValue;
-use crate::Item;
+use crate::{Item, Value};
 
 // ;; TOML
 
@@ -29,38 +37,49 @@ use crate::Item;
 //                ( ws keyval ws [ comment ] ) /
 //                ( ws table ws [ comment ] ) /
 //                  ws )
-pub(crate) fn document(input: Input<'_>) -> IResult<Input<'_>, Document, ParserError<'_>> {
-    let state = RefCell::new(ParseState::default());
-    let state_ref = &state;
-
-    let (i, _o) = (
-        // Remove BOM if present
-        opt(b"\xEF\xBB\xBF"),
-        parse_ws(state_ref),
-        many0_count((
-            dispatch! {peek(any);
-                crate::parser::trivia::COMMENT_START_SYMBOL => cut(parse_comment(state_ref)),
-                crate::parser::table::STD_TABLE_OPEN => cut(table(state_ref)),
-                crate::parser::trivia::LF |
-                crate::parser::trivia::CR => parse_newline(state_ref),
-                _ => cut(keyval(state_ref)),
-            },
+pub(crate) fn document(
+    options: ParseOptions,
+    want_warnings: bool,
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Document, Vec<ParseWarning>), ParserError<'_>> {
+    move |input| {
+        let check = RecursionCheck::new(options);
+        let state = RefCell::new(ParseState::new(options, input.len(), want_warnings));
+        let state_ref = &state;
+
+        let (i, (bom, _, _, _)) = (
+            // Remove BOM if present, recording it on the document
+            opt(b"\xEF\xBB\xBF").map(|bom| bom.is_some()),
             parse_ws(state_ref),
-        )),
-        eof,
-    )
-        .parse(input)?;
-    state
-        .into_inner()
-        .into_document()
-        .map(|document| (i, document))
-        .map_err(|err| {
-            nom8::Err::Error(ParserError::from_external_error(
-                i,
-                nom8::error::ErrorKind::MapRes,
-                err,
-            ))
-        })
+            many0_count((
+                dispatch! {peek(any);
+                    crate::parser::trivia::COMMENT_START_SYMBOL => cut(parse_comment(state_ref)),
+                    crate::parser::table::STD_TABLE_OPEN => cut(table(state_ref, check)),
+                    crate::parser::trivia::LF |
+                    crate::parser::trivia::CR => parse_newline(state_ref),
+                    _ => cut(keyval(state_ref, check)),
+                },
+                parse_ws(state_ref),
+            )),
+            eof,
+        )
+            .parse(input)?;
+        state
+            .into_inner()
+            .into_document()
+            .map(|(mut document, warnings)| {
+                document.bom = bom;
+                let input = from_utf8_unchecked!(input, "`input` comes from a `&str`");
+                document.newline = NewlineStyle::detect(input);
+                (i, (document, warnings))
+            })
+            .map_err(|err| {
+                nom8::Err::Error(ParserError::from_external_error(
+                    i,
+                    nom8::error::ErrorKind::MapRes,
+                    err,
+                ))
+            })
+    }
 }
 
 pub(crate) fn parse_comment<'s, 'i>(
@@ -68,8 +87,8 @@ pub(crate) fn parse_comment<'s, 'i>(
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'_>> + 's {
     move |i| {
         (comment, line_ending)
-            .map_res::<_, _, std::str::Utf8Error>(|(c, e)| {
-                let c = std::str::from_utf8(c)?;
+            .map_res::<_, _, core::str::Utf8Error>(|(c, e)| {
+                let c = core::str::from_utf8(c)?;
                 state.borrow_mut().on_comment(c, e);
                 Ok(())
             })
@@ -91,49 +110,138 @@ pub(crate) fn parse_newline<'s, 'i>(
 
 pub(crate) fn keyval<'s, 'i>(
     state: &'s RefCell<ParseState>,
+    check: RecursionCheck,
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
     move |i| {
-        parse_keyval
-            .map_res(|(p, kv)| state.borrow_mut().on_keyval(p, kv))
+        parse_keyval(check)
+            .with_recognized()
+            .map_res(|((p, kv, warnings), raw)| {
+                let mut state = state.borrow_mut();
+                let start = state.offset(i);
+                // Exclude the trailing whitespace, comment, and newline
+                let newline = raw.iter().rev().take_while(|b| b"\r\n".contains(b)).count();
+                let suffix = kv.value.as_value().and_then(|v| v.decor().suffix());
+                let end = start + raw.len() - newline - suffix.unwrap_or_default().len();
+                state.on_keyval(p, kv, warnings, start..end)
+            })
             .parse(i)
     }
 }
 
 // keyval = key keyval-sep val
+//
+// Also returns the deviations tolerated by `ParseOptions::lax`
 pub(crate) fn parse_keyval(
-    input: Input<'_>,
-) -> IResult<Input<'_>, (Vec<Key>, TableKeyValue), ParserError<'_>> {
-    (
-        key,
-        cut((
-            one_of(KEYVAL_SEP)
-                .context(Context::Expected(ParserValue::CharLiteral('.')))
-                .context(Context::Expected(ParserValue::CharLiteral('='))),
-            (
-                ws,
-                value(RecursionCheck::default()),
-                line_trailing
-                    .context(Context::Expected(ParserValue::CharLiteral('\n')))
-                    .context(Context::Expected(ParserValue::CharLiteral('#'))),
-            ),
-        )),
-    )
-        .map_res::<_, _, std::str::Utf8Error>(|(key, (_, v))| {
-            let mut path = key;
-            let key = path.pop().expect("grammar ensures at least 1");
-
-            let (pre, v, suf) = v;
-            let suf = std::str::from_utf8(suf)?;
-            let v = v.decorated(pre, suf);
-            Ok((
-                path,
-                TableKeyValue {
-                    key,
-                    value: Item::Value(v),
-                },
-            ))
-        })
-        .parse(input)
+    check: RecursionCheck,
+) -> impl FnMut(
+    Input<'_>,
+) -> IResult<Input<'_>, (Vec<Key>, TableKeyValue, Vec<WarningKind>), ParserError<'_>> {
+    move |input| {
+        (
+            key(check),
+            cut((
+                one_of(KEYVAL_SEP)
+                    .context(Context::Expected(ParserValue::CharLiteral('.')))
+                    .context(Context::Expected(ParserValue::CharLiteral('='))),
+                (
+                    ws,
+                    lax_value(check),
+                    line_trailing
+                        .context(Context::Expected(ParserValue::CharLiteral('\n')))
+                        .context(Context::Expected(ParserValue::CharLiteral('#'))),
+                ),
+            )),
+        )
+            .map_res::<_, _, core::str::Utf8Error>(|(key, (_, v))| {
+                let mut path = key;
+                let key = path.pop().expect("grammar ensures at least 1");
+
+                let (pre, (v, mut warnings), suf) = v;
+                let suf = core::str::from_utf8(suf)?;
+                let mut v = match v {
+                    Some(v) => v.decorated(pre, suf),
+                    None => {
+                        warnings.push(WarningKind::MissingValue);
+                        let mut v = Value::from("");
+                        v.decor_mut().set_suffix(format!("{}{}", pre, suf));
+                        v
+                    }
+                };
+                if check.is_lax() && !cfg!(feature = "toml-1.1") {
+                    remove_trailing_commas(&mut v, &mut warnings);
+                }
+                Ok((
+                    path,
+                    TableKeyValue {
+                        key,
+                        value: Item::Value(v),
+                    },
+                    warnings,
+                ))
+            })
+            .parse(input)
+    }
+}
+
+// val, which `ParseOptions::lax` allows to be missing or followed by a stray comma and
+// `ParseOptions::decimal_comma` allows to be a float with a decimal comma
+fn lax_value(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Option<Value>, Vec<WarningKind>), ParserError<'_>>
+{
+    move |input| {
+        if check.allows_decimal_comma() {
+            // Only where a comma couldn't be separating values
+            let mut decimal_comma = terminated(decimal_comma_float, peek(line_trailing));
+            if let Ok((input, raw)) = decimal_comma.parse(input) {
+                let fixed = raw.replace(',', ".");
+                let mut f = Formatted::new(
+                    fixed
+                        .replace('_', "")
+                        .parse()
+                        .expect("`decimal_comma_float` is a valid float after the fix"),
+                );
+                f.set_repr_unchecked(Repr::new_unchecked(fixed));
+                let warnings = vec![WarningKind::DecimalComma];
+                return Ok((input, (Some(Value::Float(f)), warnings)));
+            }
+        }
+        if !check.is_lax() {
+            return warned_value(check)
+                .map(|(v, warnings)| (Some(v), warnings))
+                .parse(input);
+        }
+        if peek(line_trailing).parse(input).is_ok() {
+            return Ok((input, (None, Vec::new())));
+        }
+        (warned_value(check), opt((ws, b',')))
+            .map(|((v, mut warnings), comma)| {
+                warnings.extend(comma.map(|_| WarningKind::TrailingComma));
+                (Some(v), warnings)
+            })
+            .parse(input)
+    }
+}
+
+// Trailing commas in inline tables are only valid with TOML 1.1
+fn remove_trailing_commas(value: &mut Value, warnings: &mut Vec<WarningKind>) {
+    match value {
+        Value::InlineTable(t) => {
+            if t.trailing_comma() {
+                t.set_trailing_comma(false);
+                warnings.push(WarningKind::TrailingComma);
+            }
+            for (_, v) in t.iter_mut() {
+                remove_trailing_commas(v, warnings);
+            }
+        }
+        Value::Array(a) => {
+            for v in a.iter_mut() {
+                remove_trailing_commas(v, warnings);
+            }
+        }
+        _ => {}
+    }
 }
 
 #[cfg(test)]
@@ -199,11 +307,19 @@ key = "value"
 "#,
             r#"foo = 1979-05-27 # Comment
 "#,
+            "\u{FEFF}
+[package]
+name = \"foo\"
+version = \"0.0.1\"
+authors = []
+",
         ];
         for input in documents {
-            let parsed = document.parse(new_input(input)).finish();
+            let parsed = document(Default::default(), true)
+                .parse(new_input(input))
+                .finish();
             let doc = match parsed {
-                Ok(doc) => doc,
+                Ok((doc, _)) => doc,
                 Err(err) => {
                     panic!(
                         "Parse error: {:?}\nFailed to parse:\n```\n{}\n```",
@@ -215,29 +331,12 @@ key = "value"
             snapbox::assert_eq(input, doc.to_string());
         }
 
-        let parse_only = ["\u{FEFF}
-[package]
-name = \"foo\"
-version = \"0.0.1\"
-authors = []
-"];
-        for input in parse_only {
-            let parsed = document.parse(new_input(input)).finish();
-            match parsed {
-                Ok(_) => (),
-                Err(err) => {
-                    panic!(
-                        "Parse error: {:?}\nFailed to parse:\n```\n{}\n```",
-                        err, input
-                    )
-                }
-            }
-        }
-
         let invalid_inputs = [r#" hello = 'darkness' # my old friend
 $"#];
         for input in invalid_inputs {
-            let parsed = document.parse(new_input(input)).finish();
+            let parsed = document(Default::default(), true)
+                .parse(new_input(input))
+                .finish();
             assert!(parsed.is_err(), "Input: {:?}", input);
         }
     }
@@ -1,5 +1,10 @@
-use std::error::Error as StdError;
-use std::fmt::{Display, Formatter, Result};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt::{Display, Formatter, Result};
+use core::ops::Range;
 
 use itertools::Itertools;
 
@@ -11,6 +16,13 @@ use crate::Key;
 pub struct TomlError {
     message: String,
     line_col: Option<(usize, usize)>,
+    // The byte range underlined by `display_with`, and where the message continues after the
+    // source lines
+    span: Option<Range<usize>>,
+    description_start: usize,
+    expected: Box<[Expected]>,
+    table_conflict: Option<Box<TableConflict>>,
+    limit_exceeded: Option<Limit>,
 }
 
 impl TomlError {
@@ -25,7 +37,38 @@ impl TomlError {
         }
         .to_string();
         let line_col = Some(position);
-        Self { message, line_col }
+        let description_start = message.len() - ErrorDescription(&error).to_string().len();
+        let expected = error
+            .context
+            .iter()
+            .filter_map(|c| match c {
+                Context::Expected(c) => Some(Expected::from(*c)),
+                _ => None,
+            })
+            .collect();
+        let cause = error.cause.as_deref().and_then(Cause::as_custom);
+        let table_conflict = match cause {
+            Some(CustomError::TableConflict(conflict)) => Some(Box::new(conflict.clone())),
+            _ => None,
+        };
+        let span = match &table_conflict {
+            Some(conflict) => conflict.conflicting_span(),
+            None => offset..offset + token_len(&original[offset.min(original.len())..]),
+        };
+        let limit_exceeded = match cause {
+            Some(CustomError::LimitExceeded(limit, _)) => Some(*limit),
+            Some(CustomError::RecursionLimitExceeded) => Some(Limit::Depth),
+            _ => None,
+        };
+        Self {
+            message,
+            line_col,
+            span: Some(span),
+            description_start,
+            expected,
+            table_conflict,
+            limit_exceeded,
+        }
     }
 
     #[cfg(feature = "serde")]
@@ -33,6 +76,11 @@ impl TomlError {
         Self {
             message,
             line_col: None,
+            span: None,
+            description_start: 0,
+            expected: Box::default(),
+            table_conflict: None,
+            limit_exceeded: None,
         }
     }
 
@@ -42,6 +90,381 @@ impl TomlError {
     pub fn line_col(&self) -> Option<(usize, usize)> {
         self.line_col
     }
+
+    /// The conflicting headers, if the error is from a `[table]` and an `[[array of tables]]`
+    /// sharing a name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::TableKind;
+    ///
+    /// let input = "[a]\nb = 1\n\n[[a]]\nb = 2\n";
+    /// let err = input.parse::<toml_edit::Document>().unwrap_err();
+    /// let conflict = err.table_conflict().unwrap();
+    /// assert_eq!(conflict.path(), ["a"]);
+    /// assert_eq!(conflict.existing_kind(), TableKind::Table);
+    /// assert_eq!(&input[conflict.existing_span()], "[a]");
+    /// assert_eq!(conflict.conflicting_kind(), TableKind::ArrayOfTables);
+    /// assert_eq!(&input[conflict.conflicting_span()], "[[a]]");
+    /// ```
+    pub fn table_conflict(&self) -> Option<&TableConflict> {
+        self.table_conflict.as_deref()
+    }
+
+    /// The limit from [`ParseOptions`][crate::ParseOptions], if the error is from exceeding one
+    pub fn limit_exceeded(&self) -> Option<Limit> {
+        self.limit_exceeded
+    }
+
+    /// What the parser expected to find at the position of the error, if known
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::Expected;
+    ///
+    /// let err = "a = [1, 2".parse::<toml_edit::Document>().unwrap_err();
+    /// assert_eq!(
+    ///     err.expected().collect::<Vec<_>>(),
+    ///     [Expected::Char(']')]
+    /// );
+    /// ```
+    pub fn expected(&self) -> impl Iterator<Item = Expected> + '_ {
+        self.expected.iter().copied()
+    }
+
+    /// Displays the error with the offending token of `source` underlined and lines of context
+    /// around it, see [`SourceDisplay`].
+    ///
+    /// `source` must be the input that failed to parse.  Errors without a position, like those
+    /// from deserializing, are displayed as with [`Display`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::SourceDisplay;
+    ///
+    /// let input = "[package]\nname = 'demo'\nversion = bogus\nedition = '2021'\n";
+    /// let err = input.parse::<toml_edit::Document>().unwrap_err();
+    /// let display = SourceDisplay::new(input).context_lines(1);
+    /// assert_eq!(
+    ///     err.display_with(display).to_string(),
+    ///     "\
+    /// TOML parse error at line 3, column 11
+    ///   |
+    /// 2 | name = 'demo'
+    /// 3 | version = bogus
+    ///   |           ^^^^^
+    /// 4 | edition = '2021'
+    ///   |
+    /// Invalid string
+    /// Expected `\"`, `'`
+    /// "
+    /// );
+    /// ```
+    pub fn display_with<'a>(&'a self, display: SourceDisplay<'a>) -> impl Display + 'a {
+        SourceErrorDisplay {
+            error: self,
+            display,
+        }
+    }
+}
+
+/// A token or expression the parser expected, see [`TomlError::expected`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Expected {
+    /// A character, like `=`
+    Char(char),
+    /// A string of characters, like `]]`
+    Literal(&'static str),
+    /// A kind of token, like `digit`
+    Description(&'static str),
+}
+
+impl From<ParserValue> for Expected {
+    fn from(value: ParserValue) -> Self {
+        match value {
+            ParserValue::CharLiteral(c) => Expected::Char(c),
+            ParserValue::StringLiteral(s) => Expected::Literal(s),
+            ParserValue::Description(s) => Expected::Description(s),
+        }
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Expected::Char('\n') => "newline".fmt(f),
+            Expected::Char('`') => "'`'".fmt(f),
+            Expected::Char(c) if c.is_ascii_control() => write!(f, "`{}`", c.escape_debug()),
+            Expected::Char(c) => write!(f, "`{}`", c),
+            Expected::Literal(s) => write!(f, "`{}`", s),
+            Expected::Description(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// How [`TomlError::display_with`] shows the source around an error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SourceDisplay<'s> {
+    source: &'s str,
+    context_lines: usize,
+}
+
+impl<'s> SourceDisplay<'s> {
+    /// Show the line of `source` with the error, without context
+    pub fn new(source: &'s str) -> Self {
+        Self {
+            source,
+            context_lines: 0,
+        }
+    }
+
+    /// The number of lines to show before and after the line with the error
+    pub fn context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+}
+
+struct SourceErrorDisplay<'a> {
+    error: &'a TomlError,
+    display: SourceDisplay<'a>,
+}
+
+impl<'a> Display for SourceErrorDisplay<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let source = self.display.source.as_bytes();
+        let span = match &self.error.span {
+            Some(span) if span.start <= source.len() => span,
+            _ => return write!(f, "{}", self.error),
+        };
+        let position = translate_position(source, span.start);
+        let end = span.end.min(source.len());
+        let width = String::from_utf8_lossy(&source[span.start..end])
+            .lines()
+            .next()
+            .map_or(0, |line| line.chars().count());
+        writeln!(
+            f,
+            "TOML parse error at line {}, column {}",
+            position.0 + 1,
+            position.1 + 1
+        )?;
+        write_snippet(
+            f,
+            source,
+            position,
+            width.max(1),
+            self.display.context_lines,
+        )?;
+        write!(f, "{}", &self.error.message[self.error.description_start..])
+    }
+}
+
+/// A resource limit from [`ParseOptions`][crate::ParseOptions]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// See [`ParseOptions::max_depth`][crate::ParseOptions::max_depth]
+    Depth,
+    /// See [`ParseOptions::max_size`][crate::ParseOptions::max_size]
+    Size,
+    /// See [`ParseOptions::max_keys`][crate::ParseOptions::max_keys]
+    Keys,
+    /// See [`ParseOptions::max_array_len`][crate::ParseOptions::max_array_len]
+    ArrayLength,
+    /// See [`ParseOptions::max_string_len`][crate::ParseOptions::max_string_len]
+    StringLength,
+}
+
+/// A `[table]` header and an `[[array of tables]]` header defining the same key
+///
+/// See [`TomlError::table_conflict`] and
+/// [`ParseOptions::lenient_table_headers`][crate::ParseOptions::lenient_table_headers].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TableConflict {
+    path: Vec<String>,
+    existing_kind: TableKind,
+    existing_span: Range<usize>,
+    conflicting_span: Range<usize>,
+}
+
+impl TableConflict {
+    pub(crate) fn new(
+        path: &[Key],
+        existing_kind: TableKind,
+        existing_span: Range<usize>,
+        conflicting_span: Range<usize>,
+    ) -> Self {
+        Self {
+            path: path.iter().map(|k| k.get().to_owned()).collect(),
+            existing_kind,
+            existing_span,
+            conflicting_span,
+        }
+    }
+
+    /// The keys of the conflicting headers.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// How the key was first defined.
+    pub fn existing_kind(&self) -> TableKind {
+        self.existing_kind
+    }
+
+    /// The byte range of the first header defining the key.
+    pub fn existing_span(&self) -> Range<usize> {
+        self.existing_span.clone()
+    }
+
+    /// How the key was redefined.
+    pub fn conflicting_kind(&self) -> TableKind {
+        match self.existing_kind {
+            TableKind::Table => TableKind::ArrayOfTables,
+            TableKind::ArrayOfTables => TableKind::Table,
+        }
+    }
+
+    /// The byte range of the header redefining the key.
+    pub fn conflicting_span(&self) -> Range<usize> {
+        self.conflicting_span.clone()
+    }
+}
+
+/// The kind of table a header defines
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TableKind {
+    /// A `[table]`
+    Table,
+    /// An `[[array of tables]]`
+    ArrayOfTables,
+}
+
+impl Display for TableKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            TableKind::Table => write!(f, "table"),
+            TableKind::ArrayOfTables => write!(f, "array of tables"),
+        }
+    }
+}
+
+/// A deviation from TOML tolerated by [`ParseOptions::lax`][crate::ParseOptions::lax],
+/// [`ParseOptions::decimal_comma`][crate::ParseOptions::decimal_comma], or
+/// [`ParseOptions::on_duplicate_key`][crate::ParseOptions::on_duplicate_key], or valid TOML that
+/// likely isn't what was meant
+///
+/// See [`Document::parse_with_warnings`][crate::Document::parse_with_warnings].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ParseWarning {
+    kind: WarningKind,
+    key: String,
+    span: Range<usize>,
+    fix: Option<(Range<usize>, String)>,
+}
+
+impl ParseWarning {
+    pub(crate) fn new(kind: WarningKind, path: &[Key], key: &Key, span: Range<usize>) -> Self {
+        let key = path
+            .iter()
+            .chain(core::iter::once(key))
+            .map(|k| k.to_repr().as_raw().to_owned())
+            .join(".");
+        Self {
+            kind,
+            key,
+            span,
+            fix: None,
+        }
+    }
+
+    pub(crate) fn with_fix(mut self, span: Range<usize>, replacement: String) -> Self {
+        self.fix = Some((span, replacement));
+        self
+    }
+
+    /// The deviation tolerated, or the suspicious construct.
+    pub fn kind(&self) -> WarningKind {
+        self.kind
+    }
+
+    /// The key of the key/value pair with the deviation, as written in the input.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The byte range of the key/value pair with the deviation.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// A byte range of the input and its replacement correcting the deviation, when it can be
+    /// corrected in place.
+    pub fn fix(&self) -> Option<(Range<usize>, &str)> {
+        self.fix
+            .as_ref()
+            .map(|(span, replacement)| (span.clone(), replacement.as_str()))
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.kind {
+            WarningKind::DuplicateKey => {
+                write!(f, "duplicate key `{}`, keeping the last value", self.key)
+            }
+            WarningKind::DroppedDuplicateKey => {
+                write!(f, "duplicate key `{}`, keeping the first value", self.key)
+            }
+            WarningKind::MissingValue => write!(
+                f,
+                "missing value for key `{}`, reading it as an empty string",
+                self.key
+            ),
+            WarningKind::TrailingComma => {
+                write!(f, "stray trailing comma in `{}`, removed", self.key)
+            }
+            WarningKind::DecimalComma => {
+                let fixed = self.fix.as_ref().map_or("", |(_, fixed)| fixed.as_str());
+                write!(f, "decimal comma in `{}`, read as `{}`", self.key, fixed)
+            }
+            WarningKind::AmbiguousKey => {
+                write!(
+                    f,
+                    "key `{}` looks like a value, but is read as a key",
+                    self.key
+                )
+            }
+        }
+    }
+}
+
+/// The kind of a [`ParseWarning`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// A key/value pair redefining a key, replacing the earlier value
+    DuplicateKey,
+    /// A key/value pair redefining a key, dropped by
+    /// [`DuplicateKeyPolicy::FirstWins`][crate::DuplicateKeyPolicy::FirstWins]
+    DroppedDuplicateKey,
+    /// A key with nothing after the `=`, read as an empty string
+    MissingValue,
+    /// A comma after the last value of a line or of an inline table, dropped
+    TrailingComma,
+    /// A float written with a decimal comma, see
+    /// [`ParseOptions::decimal_comma`][crate::ParseOptions::decimal_comma]
+    DecimalComma,
+    /// A bare key that reads like a date, like `1979-05-27 = true`, or a dotted key that reads
+    /// like a float, like `3.14 = "pi"`, which is the key `14` of a table `3`
+    ///
+    /// This is valid TOML, and reported even without any [`ParseOptions`][crate::ParseOptions].
+    AmbiguousKey,
 }
 
 /// Displays a TOML parse error
@@ -62,7 +485,8 @@ impl Display for TomlError {
     }
 }
 
-impl StdError for TomlError {
+#[cfg(feature = "std")]
+impl std::error::Error for TomlError {
     fn description(&self) -> &'static str {
         "TOML parse error"
     }
@@ -72,7 +496,18 @@ impl StdError for TomlError {
 pub(crate) struct ParserError<'b> {
     input: Input<'b>,
     context: Vec<Context>,
-    cause: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    cause: Option<Box<dyn Cause>>,
+}
+
+/// The error a `map_res` failed with, without requiring `std::error::Error`
+pub(crate) trait Cause: core::fmt::Debug + Display + Send + Sync + 'static {
+    fn as_custom(&self) -> Option<&CustomError>;
+}
+
+impl<E: core::fmt::Debug + Display + Send + Sync + 'static> Cause for E {
+    fn as_custom(&self) -> Option<&CustomError> {
+        (self as &dyn Any).downcast_ref()
+    }
 }
 
 impl<'b> nom8::error::ParseError<Input<'b>> for ParserError<'b> {
@@ -104,9 +539,7 @@ impl<'b> nom8::error::ContextError<Input<'b>, Context> for ParserError<'b> {
     }
 }
 
-impl<'b, E: std::error::Error + Send + Sync + 'static> nom8::error::FromExternalError<Input<'b>, E>
-    for ParserError<'b>
-{
+impl<'b, E: Cause> nom8::error::FromExternalError<Input<'b>, E> for ParserError<'b> {
     fn from_external_error(input: Input<'b>, _kind: nom8::error::ErrorKind, e: E) -> Self {
         Self {
             input,
@@ -116,8 +549,17 @@ impl<'b, E: std::error::Error + Send + Sync + 'static> nom8::error::FromExternal
     }
 }
 
+impl<'b> From<nom8::Err<ParserError<'b>>> for ParserError<'b> {
+    fn from(err: nom8::Err<ParserError<'b>>) -> Self {
+        match err {
+            nom8::Err::Error(e) | nom8::Err::Failure(e) => e,
+            nom8::Err::Incomplete(_) => unreachable!("input is always complete"),
+        }
+    }
+}
+
 // For tests
-impl<'b> std::cmp::PartialEq for ParserError<'b> {
+impl<'b> core::cmp::PartialEq for ParserError<'b> {
     fn eq(&self, other: &Self) -> bool {
         self.input == other.input
             && self.context == other.context
@@ -132,25 +574,31 @@ struct ParserErrorDisplay<'a> {
     position: (usize, usize),
 }
 
-impl<'a> std::fmt::Display for ParserErrorDisplay<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<'a> core::fmt::Display for ParserErrorDisplay<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let (line, column) = self.position;
-        let line_num = line + 1;
-        let col_num = column + 1;
-        let gutter = line_num.to_string().len();
-        let content = self
-            .original
-            .split(|b| *b == b'\n')
-            .nth(line)
-            .expect("valid line number");
-        let content = String::from_utf8_lossy(content);
+        writeln!(
+            f,
+            "TOML parse error at line {}, column {}",
+            line + 1,
+            column + 1
+        )?;
+        write_snippet(f, self.original, self.position, 1, 0)?;
+        write!(f, "{}", ErrorDescription(self.error))
+    }
+}
+
+// What went wrong, written after the source lines
+struct ErrorDescription<'a, 'b>(&'a ParserError<'b>);
 
-        let expression = self.error.context.iter().find_map(|c| match c {
+impl<'a, 'b> core::fmt::Display for ErrorDescription<'a, 'b> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let expression = self.0.context.iter().find_map(|c| match c {
             Context::Expression(c) => Some(c),
             _ => None,
         });
         let expected = self
-            .error
+            .0
             .context
             .iter()
             .filter_map(|c| match c {
@@ -159,31 +607,6 @@ impl<'a> std::fmt::Display for ParserErrorDisplay<'a> {
             })
             .collect::<Vec<_>>();
 
-        writeln!(
-            f,
-            "TOML parse error at line {}, column {}",
-            line_num, col_num
-        )?;
-        //   |
-        for _ in 0..=gutter {
-            write!(f, " ")?;
-        }
-        writeln!(f, "|")?;
-
-        // 1 | 00:32:00.a999999
-        write!(f, "{} | ", line_num)?;
-        writeln!(f, "{}", content)?;
-
-        //   |          ^
-        for _ in 0..=gutter {
-            write!(f, " ")?;
-        }
-        write!(f, "|")?;
-        for _ in 0..=column {
-            write!(f, " ")?;
-        }
-        writeln!(f, "^")?;
-
         if let Some(expression) = expression {
             writeln!(f, "Invalid {}", expression)?;
         }
@@ -198,7 +621,7 @@ impl<'a> std::fmt::Display for ParserErrorDisplay<'a> {
             }
             writeln!(f)?;
         }
-        if let Some(cause) = &self.error.cause {
+        if let Some(cause) = &self.0.cause {
             write!(f, "{}", cause)?;
         }
 
@@ -206,6 +629,75 @@ impl<'a> std::fmt::Display for ParserErrorDisplay<'a> {
     }
 }
 
+// Writes the line at `position` with `width` characters underlined from the position, and up to
+// `context_lines` lines around it
+fn write_snippet(
+    f: &mut dyn core::fmt::Write,
+    source: &[u8],
+    position: (usize, usize),
+    width: usize,
+    context_lines: usize,
+) -> core::fmt::Result {
+    let (line, column) = position;
+    let lines = source.split(|b| *b == b'\n').collect::<Vec<_>>();
+    // A final newline ends the last line rather than starting another
+    let count = lines.len() - usize::from(source.ends_with(b"\n"));
+    let first = line.saturating_sub(context_lines);
+    let last = (line + context_lines)
+        .min(count.saturating_sub(1))
+        .max(line);
+    let gutter = (last + 1).to_string().len();
+
+    //   |
+    for _ in 0..=gutter {
+        write!(f, " ")?;
+    }
+    writeln!(f, "|")?;
+
+    for (i, content) in lines.iter().enumerate().take(last + 1).skip(first) {
+        // 1 | 00:32:00.a999999
+        let content = String::from_utf8_lossy(content);
+        writeln!(f, "{:>gutter$} | {}", i + 1, content, gutter = gutter)?;
+
+        if i == line {
+            //   |          ^
+            for _ in 0..=gutter {
+                write!(f, " ")?;
+            }
+            write!(f, "|")?;
+            for _ in 0..=column {
+                write!(f, " ")?;
+            }
+            for _ in 0..width {
+                write!(f, "^")?;
+            }
+            writeln!(f)?;
+        }
+    }
+
+    if 0 < context_lines {
+        for _ in 0..=gutter {
+            write!(f, " ")?;
+        }
+        writeln!(f, "|")?;
+    }
+    Ok(())
+}
+
+// The length in bytes of the token starting `input`, at least one character unless at the end
+fn token_len(input: &[u8]) -> usize {
+    let is_bare =
+        |b: &u8| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.' | b':' | b'+');
+    match input.iter().position(|b| !is_bare(b)) {
+        Some(0) => core::str::from_utf8(input)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map_or(1, |c| c.len_utf8()),
+        Some(len) => len,
+        None => input.len(),
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub(crate) enum Context {
     Expression(&'static str),
@@ -219,18 +711,9 @@ pub(crate) enum ParserValue {
     Description(&'static str),
 }
 
-impl std::fmt::Display for ParserValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParserValue::CharLiteral('\n') => "newline".fmt(f),
-            ParserValue::CharLiteral('`') => "'`'".fmt(f),
-            ParserValue::CharLiteral(c) if c.is_ascii_control() => {
-                write!(f, "`{}`", c.escape_debug())
-            }
-            ParserValue::CharLiteral(c) => write!(f, "`{}`", c),
-            ParserValue::StringLiteral(c) => write!(f, "`{}`", c),
-            ParserValue::Description(c) => write!(f, "{}", c),
-        }
+impl core::fmt::Display for ParserValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Expected::from(*self).fmt(f)
     }
 }
 
@@ -256,7 +739,7 @@ fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
     let line = input[0..line_start].iter().filter(|b| **b == b'\n').count();
     let line = line;
 
-    let column = std::str::from_utf8(&input[line_start..=index])
+    let column = core::str::from_utf8(&input[line_start..=index])
         .map(|s| s.chars().count() - 1)
         .unwrap_or_else(|_| index - line_start);
     let column = column + column_offset;
@@ -343,8 +826,18 @@ pub(crate) enum CustomError {
         key: Vec<Key>,
         actual: &'static str,
     },
+    TableConflict(TableConflict),
+    LimitExceeded(Limit, usize),
     OutOfRange,
-    #[cfg_attr(feature = "unbounded", allow(dead_code))]
+    IntegerOutOfRange {
+        radix: u32,
+        max: u128,
+    },
+    DayOutOfRange {
+        year: u16,
+        month: u8,
+        days: u8,
+    },
     RecursionLimitExceeded,
 }
 
@@ -366,7 +859,8 @@ impl CustomError {
     }
 }
 
-impl StdError for CustomError {
+#[cfg(feature = "std")]
+impl std::error::Error for CustomError {
     fn description(&self) -> &'static str {
         "TOML parse error"
     }
@@ -395,7 +889,59 @@ impl Display for CustomError {
                     path, actual
                 )
             }
+            CustomError::TableConflict(conflict) => {
+                let path = conflict
+                    .path
+                    .iter()
+                    .map(|k| Key::new(k.as_str()).to_repr().as_raw().to_owned())
+                    .join(".");
+                match conflict.existing_kind {
+                    TableKind::Table => {
+                        writeln!(
+                            f,
+                            "Array of tables `{}` was already defined as a table",
+                            path
+                        )
+                    }
+                    TableKind::ArrayOfTables => writeln!(
+                        f,
+                        "Table `{}` was already defined as an array of tables",
+                        path
+                    ),
+                }
+            }
+            CustomError::LimitExceeded(limit, max) => match limit {
+                Limit::Depth => writeln!(f, "Recursion limit exceded"),
+                Limit::Size => writeln!(f, "Document is larger than the limit of {} bytes", max),
+                Limit::Keys => writeln!(f, "Document has more than the limit of {} keys", max),
+                Limit::ArrayLength => {
+                    writeln!(f, "Array is longer than the limit of {} elements", max)
+                }
+                Limit::StringLength => {
+                    writeln!(f, "String is longer than the limit of {} bytes", max)
+                }
+            },
             CustomError::OutOfRange => writeln!(f, "Value is out of range"),
+            CustomError::IntegerOutOfRange { radix, max } => match radix {
+                16 => writeln!(
+                    f,
+                    "Hexadecimal integer is larger than the maximum of {:#x}",
+                    max
+                ),
+                8 => writeln!(f, "Octal integer is larger than the maximum of {:#o}", max),
+                2 => writeln!(f, "Binary integer is larger than the maximum of {:#b}", max),
+                _ => writeln!(
+                    f,
+                    "Integer is outside of the range {} to {}",
+                    -(*max as i128) - 1,
+                    max
+                ),
+            },
+            CustomError::DayOutOfRange { year, month, days } => writeln!(
+                f,
+                "Day is out of range, {:04}-{:02} has {} days",
+                year, month, days
+            ),
             CustomError::RecursionLimitExceeded => writeln!(f, "Recursion limit exceded"),
         }
     }
@@ -0,0 +1,57 @@
+use alloc::vec::Vec;
+
+use nom8::bytes::one_of;
+use nom8::bytes::take;
+use nom8::combinator::cut;
+use nom8::combinator::peek;
+use nom8::sequence::delimited;
+
+use crate::key::Key;
+use crate::parser::inline_table::KEYVAL_SEP;
+use crate::parser::key::key;
+use crate::parser::prelude::*;
+use crate::parser::trivia::ws;
+use crate::parser::value::value;
+use crate::Value;
+
+// Parsers for `crate::events`, which emits whitespace and comments as their own events rather
+// than attaching them to the surrounding items.
+
+// std-table = std-table-open key *( table-key-sep key) std-table-close
+// array-table = array-table-open key *( table-key-sep key) array-table-close
+pub(crate) fn header(input: Input<'_>) -> IResult<Input<'_>, (Vec<Key>, bool), ParserError<'_>> {
+    dispatch!(peek::<_, &[u8],_,_>(take(2usize));
+        b"[[" => delimited(
+            b"[[",
+            cut(key(RecursionCheck::default())),
+            cut(b"]]")
+                .context(Context::Expected(ParserValue::CharLiteral('.')))
+                .context(Context::Expected(ParserValue::StringLiteral("]]"))),
+        ).map(|path| (path, true)),
+        _ => delimited(
+            b"[",
+            cut(key(RecursionCheck::default())),
+            cut(b"]")
+                .context(Context::Expected(ParserValue::CharLiteral('.')))
+                .context(Context::Expected(ParserValue::StringLiteral("]"))),
+        ).map(|path| (path, false)),
+    )
+    .context(Context::Expression("table header"))
+    .parse(input)
+}
+
+// keyval = key keyval-sep val
+pub(crate) fn keyval(input: Input<'_>) -> IResult<Input<'_>, (Vec<Key>, Value), ParserError<'_>> {
+    (
+        key(RecursionCheck::default()),
+        cut((
+            one_of(KEYVAL_SEP)
+                .context(Context::Expected(ParserValue::CharLiteral('.')))
+                .context(Context::Expected(ParserValue::CharLiteral('='))),
+            ws,
+            value(RecursionCheck::default()),
+        )),
+    )
+        .map(|(path, (_, pre, v))| (path, v.decorated(pre, "")))
+        .parse(input)
+}
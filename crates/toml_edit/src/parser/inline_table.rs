@@ -1,29 +1,34 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+
 use nom8::bytes::one_of;
 use nom8::combinator::cut;
+use nom8::combinator::opt;
 use nom8::multi::separated_list0;
 use nom8::sequence::delimited;
 
 use crate::key::Key;
-use crate::parser::errors::CustomError;
+use crate::parser::errors::{CustomError, WarningKind};
 use crate::parser::key::key;
 use crate::parser::prelude::*;
-use crate::parser::trivia::ws;
-use crate::parser::value::value;
+use crate::parser::state::insert_keyval;
+use crate::parser::trivia::{ws, ws_comment_newline};
+use crate::parser::value::warned_value;
 use crate::table::TableKeyValue;
-use crate::{InlineTable, InternalString, Item, Value};
-
-use indexmap::map::Entry;
+use crate::{InlineTable, Item, Value};
 
 // ;; Inline Table
 
 // inline-table = inline-table-open inline-table-keyvals inline-table-close
 pub(crate) fn inline_table(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, InlineTable, ParserError<'_>> {
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (InlineTable, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
         delimited(
             INLINE_TABLE_OPEN,
-            cut(inline_table_keyvals(check).map_res(|(kv, p)| table_from_pairs(kv, p))),
+            cut(inline_table_keyvals(check)
+                .map_res(|(kv, comma, trailing)| table_from_pairs(kv, comma, trailing, check))),
             cut(INLINE_TABLE_CLOSE)
                 .context(Context::Expression("inline table"))
                 .context(Context::Expected(ParserValue::CharLiteral('}'))),
@@ -33,30 +38,59 @@ pub(crate) fn inline_table(
 }
 
 fn table_from_pairs(
-    v: Vec<(Vec<Key>, TableKeyValue)>,
-    preamble: &str,
-) -> Result<InlineTable, CustomError> {
+    v: Vec<(Vec<Key>, TableKeyValue, Vec<WarningKind>)>,
+    trailing_comma: bool,
+    trailing: &str,
+    check: RecursionCheck,
+) -> Result<(InlineTable, Vec<WarningKind>), CustomError> {
     let mut root = InlineTable::new();
-    root.preamble = preamble.into();
+    root.set_trailing_comma(trailing_comma);
+    root.set_trailing(trailing);
     // Assuming almost all pairs will be directly in `root`
     root.items.reserve(v.len());
 
-    for (path, kv) in v {
+    let mut warnings = Vec::new();
+    let last = v.len().saturating_sub(1);
+    let mut closing = None;
+    for (i, (path, kv, kv_warnings)) in v.into_iter().enumerate() {
+        warnings.extend(kv_warnings);
         let table = descend_path(&mut root, &path)?;
-        let key: InternalString = kv.key.get_internal().into();
-        match table.items.entry(key) {
-            Entry::Vacant(o) => {
-                o.insert(kv);
-            }
-            Entry::Occupied(o) => {
-                return Err(CustomError::DuplicateKey {
-                    key: o.key().as_str().into(),
-                    table: None,
-                });
+        // The whitespace before the closing brace, in case the last pair is dropped
+        let suffix = if i == last {
+            kv.value
+                .as_value()
+                .and_then(|v| v.decor().suffix())
+                .map(str::to_owned)
+        } else {
+            None
+        };
+        match insert_keyval(&mut table.items, kv, check.duplicate_keys()) {
+            Ok(Some((kind, _))) => {
+                if kind == WarningKind::DroppedDuplicateKey {
+                    closing = suffix;
+                }
+                warnings.push(kind);
             }
+            Ok(None) => {}
+            Err(key) => return Err(CustomError::DuplicateKey { key, table: None }),
         }
     }
-    Ok(root)
+    if let (Some(closing), Some(value)) = (closing, last_value_mut(&mut root)) {
+        value.decor_mut().set_suffix(closing);
+    }
+    Ok((root, warnings))
+}
+
+fn last_value_mut(table: &mut InlineTable) -> Option<&mut Value> {
+    let (_, kv) = table
+        .items
+        .get_index_mut(table.items.len().checked_sub(1)?)?;
+    let value = kv.value.as_value_mut()?;
+    if matches!(value, Value::InlineTable(child) if child.is_dotted()) {
+        last_value_mut(value.as_inline_table_mut()?)
+    } else {
+        Some(value)
+    }
 }
 
 fn descend_path<'a>(
@@ -85,45 +119,80 @@ fn descend_path<'a>(
 // inline-table-open  = %x7B ws     ; {
 pub(crate) const INLINE_TABLE_OPEN: u8 = b'{';
 // inline-table-close = ws %x7D     ; }
-const INLINE_TABLE_CLOSE: u8 = b'}';
+pub(crate) const INLINE_TABLE_CLOSE: u8 = b'}';
 // inline-table-sep   = ws %x2C ws  ; , Comma
-const INLINE_TABLE_SEP: u8 = b',';
+pub(crate) const INLINE_TABLE_SEP: u8 = b',';
 // keyval-sep = ws %x3D ws ; =
 pub(crate) const KEYVAL_SEP: u8 = b'=';
 
+// Whitespace within an inline table, allowing newlines and comments with TOML 1.1
+pub(crate) fn inline_ws(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
+    if cfg!(feature = "toml-1.1") {
+        ws_comment_newline
+            .map_res(core::str::from_utf8)
+            .parse(input)
+    } else {
+        ws.parse(input)
+    }
+}
+
 // inline-table-keyvals = [ inline-table-keyvals-non-empty ]
 // inline-table-keyvals-non-empty =
 // ( key keyval-sep val inline-table-sep inline-table-keyvals-non-empty ) /
 // ( key keyval-sep val )
-
+//
+// TOML 1.1 allows a trailing `inline-table-sep` and replaces `ws` with `ws-comment-newline`
+// around key/value pairs.  `ParseOptions::lax` also allows the trailing `inline-table-sep`.
 fn inline_table_keyvals(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Vec<(Vec<Key>, TableKeyValue)>, &str), ParserError<'_>>
-{
+) -> impl FnMut(
+    Input<'_>,
+) -> IResult<
+    Input<'_>,
+    (Vec<(Vec<Key>, TableKeyValue, Vec<WarningKind>)>, bool, &str),
+    ParserError<'_>,
+> {
     move |input| {
         let check = check.recursing(input)?;
-        (separated_list0(INLINE_TABLE_SEP, keyval(check)), ws).parse(input)
+        let (input, pairs) = separated_list0(INLINE_TABLE_SEP, keyval(check)).parse(input)?;
+        let allow_comma = cfg!(feature = "toml-1.1") || check.is_lax();
+        let (input, trailing_comma) = if allow_comma && !pairs.is_empty() {
+            opt(INLINE_TABLE_SEP).map(|c| c.is_some()).parse(input)?
+        } else {
+            (input, false)
+        };
+        let (input, trailing) = inline_ws.parse(input)?;
+        Ok((input, (pairs, trailing_comma, trailing)))
     }
 }
 
 fn keyval(
     check: RecursionCheck,
-) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (Vec<Key>, TableKeyValue), ParserError<'_>> {
+) -> impl FnMut(
+    Input<'_>,
+) -> IResult<Input<'_>, (Vec<Key>, TableKeyValue, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
         (
-            key,
+            inline_ws,
+            key(check),
             cut((
                 one_of(KEYVAL_SEP)
                     .context(Context::Expected(ParserValue::CharLiteral('.')))
                     .context(Context::Expected(ParserValue::CharLiteral('='))),
-                (ws, value(check), ws),
+                (ws, warned_value(check), inline_ws),
             )),
         )
-            .map(|(key, (_, v))| {
+            .map(|(leading, mut key, (_, v))| {
+                let first_key = &mut key[0];
+                let prefix = first_key.decor.prefix().unwrap_or_default();
+                if !leading.is_empty() {
+                    first_key.decor.set_prefix(format!("{}{}", leading, prefix));
+                }
+
                 let mut path = key;
                 let key = path.pop().expect("grammar ensures at least 1");
 
-                let (pre, v, suf) = v;
+                let (pre, (v, warnings), suf) = v;
                 let v = v.decorated(pre, suf);
                 (
                     path,
@@ -131,6 +200,7 @@ fn keyval(
                         key,
                         value: Item::Value(v),
                     },
+                    warnings,
                 )
             })
             .parse(input)
@@ -154,7 +224,7 @@ mod test {
             let parsed = inline_table(Default::default())
                 .parse(new_input(input))
                 .finish();
-            assert_eq!(parsed.map(|a| a.to_string()), Ok(input.to_owned()));
+            assert_eq!(parsed.map(|(a, _)| a.to_string()), Ok(input.to_owned()));
         }
         let invalid_inputs = [r#"{a = 1e165"#, r#"{ hello = "world", a = 2, hello = 1}"#];
         for input in invalid_inputs {
@@ -1,4 +1,5 @@
-use std::ops::RangeInclusive;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
 
 use nom8::bytes::any;
 use nom8::bytes::take_while1;
@@ -9,28 +10,35 @@ use crate::key::Key;
 use crate::parser::errors::CustomError;
 use crate::parser::prelude::*;
 use crate::parser::strings::{basic_string, literal_string};
-use crate::parser::trivia::{from_utf8_unchecked, ws};
+use crate::parser::trivia::{ws, NON_ASCII};
 use crate::repr::{Decor, Repr};
 use crate::InternalString;
 
 // key = simple-key / dotted-key
 // dotted-key = simple-key 1*( dot-sep simple-key )
-pub(crate) fn key(input: Input<'_>) -> IResult<Input<'_>, Vec<Key>, ParserError<'_>> {
-    separated_list1(
-        DOT_SEP,
-        (ws, simple_key, ws).map(|(pre, (raw, key), suffix)| {
-            Key::new(key)
-                .with_repr_unchecked(Repr::new_unchecked(raw))
-                .with_decor(Decor::new(pre, suffix))
-        }),
-    )
-    .context(Context::Expression("key"))
-    .map_res(|k| {
-        // Inserting the key will require recursion down the line
-        RecursionCheck::check_depth(k.len())?;
-        Ok::<_, CustomError>(k)
-    })
-    .parse(input)
+pub(crate) fn key(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, Vec<Key>, ParserError<'_>> {
+    move |input| {
+        separated_list1(
+            DOT_SEP,
+            (ws, simple_key, ws).map(|(pre, (raw, key), suffix)| {
+                Key::new(key)
+                    .with_repr_unchecked(Repr::new_unchecked(raw))
+                    .with_decor(Decor::new(pre, suffix))
+            }),
+        )
+        .context(Context::Expression("key"))
+        .map_res(|k| {
+            // Inserting the key will require recursion down the line
+            check.check_depth(k.len())?;
+            for key in &k {
+                check.check_string_len(key.get().len())?;
+            }
+            Ok::<_, CustomError>(k)
+        })
+        .parse(input)
+    }
 }
 
 // simple-key = quoted-key / unquoted-key
@@ -40,28 +48,50 @@ pub(crate) fn simple_key(
 ) -> IResult<Input<'_>, (&str, InternalString), ParserError<'_>> {
     dispatch! {peek(any);
         crate::parser::strings::QUOTATION_MARK => basic_string
-            .map(|s: std::borrow::Cow<'_, str>| s.as_ref().into()),
+            .map(|s: alloc::borrow::Cow<'_, str>| s.as_ref().into()),
         crate::parser::strings::APOSTROPHE => literal_string.map(|s: &str| s.into()),
         _ => unquoted_key.map(|s: &str| s.into()),
     }
-        .with_recognized()
-        .map(|(k, b)| {
-            let s = unsafe { from_utf8_unchecked(b, "If `quoted_key` or `unquoted_key` are valid, then their `recognize`d value is valid") };
-            (s, k)
-        })
-        .parse(input)
+    .with_recognized()
+    .map(|(k, b)| {
+        let s = from_utf8_unchecked!(
+            b,
+            "If `quoted_key` or `unquoted_key` are valid, then their `recognize`d value is valid"
+        );
+        (s, k)
+    })
+    .parse(input)
 }
 
 // unquoted-key = 1*( ALPHA / DIGIT / %x2D / %x5F ) ; A-Z / a-z / 0-9 / - / _
-fn unquoted_key(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
-    take_while1(UNQUOTED_CHAR)
-        .map(|b| unsafe { from_utf8_unchecked(b, "`is_unquoted_char` filters out on-ASCII") })
-        .parse(input)
+//
+// With `toml-1.1`, the TOML 1.1 draft also allows Unicode letters and digits:
+// unquoted-key-char = ALPHA / DIGIT / %x2D / %x5F / %xB2 / %xB3 / %xB9 / %xBC-BE / ...
+pub(crate) fn unquoted_key(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
+    if cfg!(feature = "toml-1.1") {
+        take_while1((UNQUOTED_CHAR, NON_ASCII))
+            .map(|b| {
+                from_utf8_unchecked!(
+                    b,
+                    "`NON_ASCII` only stops on ASCII, after a whole character",
+                )
+            })
+            .verify(|s: &str| s.chars().all(is_unquoted_char))
+            .parse(input)
+    } else {
+        take_while1(UNQUOTED_CHAR)
+            .map(|b| from_utf8_unchecked!(b, "`is_unquoted_char` filters out on-ASCII"))
+            .parse(input)
+    }
 }
 
-pub(crate) fn is_unquoted_char(c: u8) -> bool {
+pub(crate) fn is_unquoted_char(c: char) -> bool {
     use nom8::input::FindToken;
-    UNQUOTED_CHAR.find_token(c)
+    if c.is_ascii() {
+        UNQUOTED_CHAR.find_token(c as u8)
+    } else {
+        cfg!(feature = "toml-1.1") && UNQUOTED_NON_ASCII.iter().any(|r| r.contains(&c))
+    }
 }
 
 const UNQUOTED_CHAR: (
@@ -72,8 +102,27 @@ const UNQUOTED_CHAR: (
     u8,
 ) = (b'A'..=b'Z', b'a'..=b'z', b'0'..=b'9', b'-', b'_');
 
-// dot-sep   = ws %x2E ws  ; . Period
-const DOT_SEP: u8 = b'.';
+// The non-ASCII `unquoted-key-char`s of the TOML 1.1 draft
+const UNQUOTED_NON_ASCII: [RangeInclusive<char>; 16] = [
+    '\u{B2}'..='\u{B3}',
+    '\u{B9}'..='\u{B9}',
+    '\u{BC}'..='\u{BE}',
+    '\u{C0}'..='\u{D6}',
+    '\u{D8}'..='\u{F6}',
+    '\u{F8}'..='\u{37D}',
+    '\u{37F}'..='\u{1FFF}',
+    '\u{200C}'..='\u{200D}',
+    '\u{203F}'..='\u{2040}',
+    '\u{2070}'..='\u{218F}',
+    '\u{2460}'..='\u{24FF}',
+    '\u{2C00}'..='\u{2FEF}',
+    '\u{3001}'..='\u{D7FF}',
+    '\u{F900}'..='\u{FDCF}',
+    '\u{FDF0}'..='\u{FFFD}',
+    '\u{10000}'..='\u{EFFFF}',
+];
+
+pub(crate) const DOT_SEP: u8 = b'.';
 
 #[cfg(test)]
 mod test {
@@ -11,3 +11,19 @@ macro_rules! dispatch {
         }
     }
 }
+
+/// Converts bytes the parser has already checked to be UTF-8, given why they are valid
+#[cfg(not(feature = "forbid-unsafe"))]
+macro_rules! from_utf8_unchecked {
+    ($bytes: expr, $safety_justification: expr $(,)?) => {
+        unsafe { crate::parser::trivia::from_utf8_unchecked($bytes, $safety_justification) }
+    };
+}
+
+/// Converts bytes the parser has already checked to be UTF-8, checking them again
+#[cfg(feature = "forbid-unsafe")]
+macro_rules! from_utf8_unchecked {
+    ($bytes: expr, $safety_justification: expr $(,)?) => {
+        core::str::from_utf8($bytes).expect($safety_justification)
+    };
+}
@@ -4,9 +4,11 @@
 pub(crate) mod macros;
 
 pub(crate) mod array;
+pub(crate) mod borrowed;
 pub(crate) mod datetime;
 pub(crate) mod document;
 pub(crate) mod errors;
+pub(crate) mod events;
 pub(crate) mod inline_table;
 pub(crate) mod key;
 pub(crate) mod numbers;
@@ -16,18 +18,421 @@ pub(crate) mod table;
 pub(crate) mod trivia;
 pub(crate) mod value;
 
-pub use errors::TomlError;
+pub use errors::{
+    Expected, Limit, ParseWarning, SourceDisplay, TableConflict, TableKind, TomlError, WarningKind,
+};
 
-pub(crate) fn parse_document(raw: &str) -> Result<crate::Document, TomlError> {
+use alloc::vec::Vec;
+
+/// Options for parsing a [`Document`][crate::Document]
+///
+/// # Examples
+///
+/// ```rust
+/// use toml_edit::{Document, ParseOptions};
+///
+/// let input = "a = [[[1]]]";
+/// assert!(Document::parse_with(input, ParseOptions::new().max_depth(3)).is_err());
+/// assert!(Document::parse_with(input, ParseOptions::new().max_depth(4)).is_ok());
+/// ```
+///
+/// Services parsing untrusted input can bound the resources used with the `max_*` limits,
+/// reported by [`TomlError::limit_exceeded`] when exceeded:
+///
+/// ```rust
+/// use toml_edit::{Document, Limit, ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .max_size(64 * 1024)
+///     .max_keys(1000)
+///     .max_array_len(100)
+///     .max_string_len(1024);
+/// let err = Document::parse_with("a = [1, 2, 3]", options.max_array_len(2)).unwrap_err();
+/// assert_eq!(err.limit_exceeded(), Some(Limit::ArrayLength));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    max_depth: usize,
+    max_size: usize,
+    max_keys: usize,
+    max_array_len: usize,
+    max_string_len: usize,
+    lenient_table_headers: bool,
+    lenient_prefixed_integers: bool,
+    extended_integers: bool,
+    opaque_datetimes: bool,
+    lax: bool,
+    decimal_comma: bool,
+    duplicate_keys: DuplicateKeyPolicy,
+    #[cfg(feature = "unicode-normalization")]
+    normalize_keys: bool,
+}
+
+impl ParseOptions {
+    /// Default parsing options
+    pub fn new() -> Self {
+        let max_depth = if cfg!(feature = "unbounded") {
+            usize::MAX
+        } else {
+            128
+        };
+        Self {
+            max_depth,
+            max_size: usize::MAX,
+            max_keys: usize::MAX,
+            max_array_len: usize::MAX,
+            max_string_len: usize::MAX,
+            lenient_table_headers: false,
+            lenient_prefixed_integers: false,
+            extended_integers: false,
+            opaque_datetimes: false,
+            lax: false,
+            decimal_comma: false,
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_keys: false,
+        }
+    }
+
+    /// Limit how deeply arrays, inline tables, and dotted keys may be nested
+    ///
+    /// Parsing recurses for each level of nesting, so this guards against stack overflows from
+    /// untrusted input.  Defaults to 128, or no limit with the `unbounded` feature.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Limit the length of the input in bytes
+    ///
+    /// Defaults to no limit.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Limit the number of keys, counting each table header and key/value pair, including those
+    /// within inline tables
+    ///
+    /// Defaults to no limit.
+    pub fn max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys;
+        self
+    }
+
+    /// Limit the number of values in an array and of tables in an array of tables
+    ///
+    /// Defaults to no limit.
+    pub fn max_array_len(mut self, max_array_len: usize) -> Self {
+        self.max_array_len = max_array_len;
+        self
+    }
+
+    /// Limit the length in bytes of each string and key, after processing escapes
+    ///
+    /// Defaults to no limit.
+    pub fn max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = max_string_len;
+        self
+    }
+
+    /// Convert between `[table]` and `[[array of tables]]` headers of the same name rather than
+    /// reporting a [`TableConflict`]
+    ///
+    /// A table followed by an array of tables header becomes the first table in the array, while
+    /// a table header following an array of tables appends to the array.  Conflicts with tables
+    /// that were only implied or defined with dotted keys are still reported as errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// let input = "[a]\nb = 1\n\n[[a]]\nb = 2\n";
+    /// let doc = Document::parse_with(input, ParseOptions::new().lenient_table_headers(true));
+    /// let doc = doc.unwrap();
+    /// assert_eq!(doc["a"].as_array_of_tables().unwrap().len(), 2);
+    /// assert_eq!(doc.to_string(), "[[a]]\nb = 1\n\n[[a]]\nb = 2\n");
+    /// ```
+    pub fn lenient_table_headers(mut self, lenient: bool) -> Self {
+        self.lenient_table_headers = lenient;
+        self
+    }
+
+    /// Read hexadecimal, octal, and binary integers up to `u64::MAX`, like the unsigned IDs and
+    /// masks of some configs, rather than reporting those above `i64::MAX` as out of range
+    ///
+    /// Those above `i64::MAX` are a [`Value::WideInteger`][crate::Value::WideInteger], read with
+    /// [`Value::as_u64`][crate::Value::as_u64] rather than
+    /// [`Value::as_integer`][crate::Value::as_integer].  They are written back out unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// let input = "mask = 0xffff_ffff_ffff_ff00\n";
+    /// let err = Document::parse_with(input, ParseOptions::new()).unwrap_err();
+    /// assert!(err.to_string().contains("maximum of 0x7fffffffffffffff"));
+    ///
+    /// let options = ParseOptions::new().lenient_prefixed_integers(true);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// assert_eq!(doc["mask"].as_u64(), Some(0xffff_ffff_ffff_ff00));
+    /// assert_eq!(doc["mask"].as_integer(), None);
+    /// assert_eq!(doc.to_string(), input);
+    /// ```
+    pub fn lenient_prefixed_integers(mut self, lenient: bool) -> Self {
+        self.lenient_prefixed_integers = lenient;
+        self
+    }
+
+    /// Read integers up to the range of `i128`, like the `u64` counters of some configs, rather
+    /// than reporting those outside of `i64` as out of range
+    ///
+    /// This includes [`ParseOptions::lenient_prefixed_integers`].  Integers outside of `i64` are
+    /// a [`Value::WideInteger`][crate::Value::WideInteger], so read them with
+    /// [`Value::as_i128`][crate::Value::as_i128] or [`Value::as_u64`][crate::Value::as_u64]
+    /// rather than [`Value::as_integer`][crate::Value::as_integer].  They keep their
+    /// representation, so they are written back out unchanged, and are deserialized losslessly
+    /// into `u64` and `i128` fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// let input = "count = 18_446_744_073_709_551_615\n";
+    /// assert!(Document::parse_with(input, ParseOptions::new()).is_err());
+    ///
+    /// let options = ParseOptions::new().extended_integers(true);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// assert_eq!(doc["count"].as_u64(), Some(u64::MAX));
+    /// assert_eq!(doc["count"].as_i128(), Some(u64::MAX as i128));
+    /// assert_eq!(doc.to_string(), input);
+    /// ```
+    pub fn extended_integers(mut self, extended: bool) -> Self {
+        self.extended_integers = extended;
+        self
+    }
+
+    /// Read datetimes as an [`OpaqueDatetime`][crate::OpaqueDatetime] of their text, for tools
+    /// that pass them through without interpreting them
+    ///
+    /// A datetime still has to be valid TOML, but becomes a
+    /// [`Value::OpaqueDatetime`][crate::Value::OpaqueDatetime] rather than a `Value::Datetime`.
+    /// It is written back out unchanged, including after formatting.  Without the `datetime`
+    /// feature, datetimes are always read this way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// let input = "released = 1979-05-27 07:32:00Z\n";
+    /// let options = ParseOptions::new().opaque_datetimes(true);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// let released = doc["released"].as_opaque_datetime().unwrap();
+    /// assert_eq!(released.as_str(), "1979-05-27 07:32:00Z");
+    /// assert!(doc["released"].is_datetime());
+    /// assert_eq!(doc.to_string(), input);
+    /// ```
+    pub fn opaque_datetimes(mut self, opaque: bool) -> Self {
+        self.opaque_datetimes = opaque;
+        self
+    }
+
+    /// Tolerate deviations from TOML common in hand-written legacy files, for migrating them
+    ///
+    /// * A key/value pair redefining a key replaces the earlier value, rather than being an error
+    /// * A key with no value, like `key =`, is read as an empty string
+    /// * A stray comma after a value, like `key = 1,`, or at the end of an inline table is dropped
+    ///
+    /// Each deviation is reported as a [`ParseWarning`] by
+    /// [`Document::parse_with_warnings`][crate::Document::parse_with_warnings], while the
+    /// document is corrected to be valid TOML when written back out.  Redefining a table, like
+    /// with a duplicate table header, is still reported as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions, WarningKind};
+    ///
+    /// let input = "a = 1\nb =\nc = [1, 2],\na = 2\n";
+    /// let (doc, warnings) = Document::parse_with_warnings(input, ParseOptions::new().lax(true))
+    ///     .unwrap();
+    /// let kinds = warnings.iter().map(|w| w.kind()).collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     kinds,
+    ///     [WarningKind::MissingValue, WarningKind::TrailingComma, WarningKind::DuplicateKey]
+    /// );
+    /// assert_eq!(doc.to_string(), "b = \"\"\nc = [1, 2]\na = 2\n");
+    /// ```
+    pub fn lax(mut self, lax: bool) -> Self {
+        self.lax = lax;
+        self
+    }
+
+    /// Read numbers written with a decimal comma, like `3,14`, as floats
+    ///
+    /// Only a key/value pair's whole value is read this way, as within arrays and inline tables
+    /// the comma could be separating values, and exactly three digits after the comma are
+    /// rejected as they could be a thousands separator.  Each is reported as a [`ParseWarning`]
+    /// by [`Document::parse_with_warnings`][crate::Document::parse_with_warnings], with a
+    /// [`ParseWarning::fix`] replacing the number with a decimal point, which is also how it is
+    /// written back out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// let input = "pi = 3,14\n";
+    /// let options = ParseOptions::new().decimal_comma(true);
+    /// let (doc, warnings) = Document::parse_with_warnings(input, options).unwrap();
+    /// assert_eq!(doc["pi"].as_float(), Some(3.14));
+    /// assert_eq!(doc.to_string(), "pi = 3.14\n");
+    /// assert_eq!(warnings[0].fix(), Some((5..9, "3.14")));
+    ///
+    /// assert!(Document::parse_with("thousands = 1,000\n", options).is_err());
+    /// ```
+    pub fn decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Choose what to do with a key/value pair redefining a key, rather than always reporting a
+    /// duplicate key error
+    ///
+    /// This is for tools ingesting machine-generated TOML.  Only a key whose earlier value is a
+    /// value is redefined, including within inline tables, while redefining a table is still an
+    /// error.  Each key/value pair replaced or dropped is reported as a
+    /// [`WarningKind::DuplicateKey`] or [`WarningKind::DroppedDuplicateKey`] by
+    /// [`Document::parse_with_warnings`][crate::Document::parse_with_warnings], against the
+    /// key/value pair holding the inline table for keys within one.  Defaults to
+    /// [`DuplicateKeyPolicy::Error`], though [`ParseOptions::lax`] replaces redefined values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, DuplicateKeyPolicy, ParseOptions};
+    ///
+    /// let input = "a = 1\nb = { c = 1, c = 2 }\na = 2\n";
+    /// assert!(Document::parse_with(input, ParseOptions::new()).is_err());
+    ///
+    /// let options = ParseOptions::new().on_duplicate_key(DuplicateKeyPolicy::FirstWins);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// assert_eq!(doc.to_string(), "a = 1\nb = { c = 1 }\n");
+    ///
+    /// let options = ParseOptions::new().on_duplicate_key(DuplicateKeyPolicy::LastWins);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// assert_eq!(doc.to_string(), "b = { c = 2 }\na = 2\n");
+    /// ```
+    pub fn on_duplicate_key(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    pub(crate) fn duplicate_keys(&self) -> DuplicateKeyPolicy {
+        match self.duplicate_keys {
+            DuplicateKeyPolicy::Error if self.lax => DuplicateKeyPolicy::LastWins,
+            policy => policy,
+        }
+    }
+
+    /// Compare keys after Unicode normalization to NFC, so keys written in decomposed form, as
+    /// by some macOS tools, match the composed form used on other systems
+    ///
+    /// Keys are stored normalized, so look them up with NFC strings.  Each key keeps its
+    /// representation, so it is written back out as it was in the input, though headers naming
+    /// the same table share the key as it was first written.  Keys that are only distinct before
+    /// normalization are reported as duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions};
+    ///
+    /// // `e` followed by a combining acute accent
+    /// let input = "\"cafe\u{301}\" = 1\n";
+    /// assert!(Document::parse_with(input, ParseOptions::new()).unwrap().get("caf\u{e9}").is_none());
+    ///
+    /// let options = ParseOptions::new().normalize_keys(true);
+    /// let doc = Document::parse_with(input, options).unwrap();
+    /// assert_eq!(doc["caf\u{e9}"].as_integer(), Some(1));
+    /// assert_eq!(doc.to_string(), input);
+    ///
+    /// let input = "\"cafe\u{301}\" = 1\n\"caf\u{e9}\" = 2\n";
+    /// assert!(Document::parse_with(input, options).is_err());
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalize_keys(mut self, normalize: bool) -> Self {
+        self.normalize_keys = normalize;
+        self
+    }
+}
+
+/// What to do with a key/value pair redefining a key, see [`ParseOptions::on_duplicate_key`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DuplicateKeyPolicy {
+    /// Report a duplicate key error
+    Error,
+    /// Keep the earlier key/value pair, dropping the later one
+    FirstWins,
+    /// Keep the later key/value pair, where it is written, dropping the earlier one
+    LastWins,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn parse_document(
+    raw: &str,
+    options: ParseOptions,
+    want_warnings: bool,
+) -> Result<(crate::Document, Vec<ParseWarning>), TomlError> {
+    use prelude::*;
+
+    let b = new_input(raw);
+    check_size(b, options)?;
+    document::document(options, want_warnings)
+        .parse(b)
+        .finish()
+        .map_err(|e| TomlError::new(e, b))
+}
+
+pub(crate) fn parse_borrowed_document(
+    raw: &str,
+    options: ParseOptions,
+) -> Result<crate::borrowed::Document<'_>, TomlError> {
     use prelude::*;
 
     let b = new_input(raw);
-    document::document
+    check_size(b, options)?;
+    borrowed::document(options)
         .parse(b)
         .finish()
+        .map(|root| crate::borrowed::Document { root })
         .map_err(|e| TomlError::new(e, b))
 }
 
+fn check_size(b: prelude::Input<'_>, options: ParseOptions) -> Result<(), TomlError> {
+    if options.max_size < b.len() {
+        let error = errors::CustomError::LimitExceeded(Limit::Size, options.max_size);
+        return Err(TomlError::new(
+            nom8::error::FromExternalError::from_external_error(
+                b,
+                nom8::error::ErrorKind::Eof,
+                error,
+            ),
+            b,
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn parse_key(raw: &str) -> Result<crate::Key, TomlError> {
     use prelude::*;
 
@@ -41,17 +446,43 @@ pub(crate) fn parse_key(raw: &str) -> Result<crate::Key, TomlError> {
     }
 }
 
+pub(crate) fn parse_decor(raw: &str) -> Result<(), TomlError> {
+    use prelude::*;
+
+    let b = new_input(raw);
+    let result = nom8::sequence::terminated(trivia::ws_comment_newline, nom8::combinator::eof)
+        .parse(b)
+        .finish();
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(TomlError::new(e, b)),
+    }
+}
+
 pub(crate) fn parse_key_path(raw: &str) -> Result<Vec<crate::Key>, TomlError> {
     use prelude::*;
 
     let b = new_input(raw);
-    let result = key::key.parse(b).finish();
+    let result = key::key(RecursionCheck::default()).parse(b).finish();
     match result {
         Ok(keys) => Ok(keys),
         Err(e) => Err(TomlError::new(e, b)),
     }
 }
 
+pub(crate) fn parse_datetime(raw: &str) -> Result<datetime::Datetime, TomlError> {
+    use prelude::*;
+
+    let b = new_input(raw);
+    let result = nom8::sequence::terminated(datetime::date_time, nom8::combinator::eof)
+        .parse(b)
+        .finish();
+    match result {
+        Ok(datetime) => Ok(datetime),
+        Err(e) => Err(TomlError::new(e, b)),
+    }
+}
+
 pub(crate) fn parse_value(raw: &str) -> Result<crate::Value, TomlError> {
     use prelude::*;
 
@@ -90,23 +521,24 @@ pub(crate) mod prelude {
         }
     }
 
+    #[cfg(feature = "std")]
     #[allow(dead_code)]
-    pub(crate) fn trace<I: std::fmt::Debug, O: std::fmt::Debug, E: std::fmt::Debug>(
-        context: impl std::fmt::Display,
+    pub(crate) fn trace<I: core::fmt::Debug, O: core::fmt::Debug, E: core::fmt::Debug>(
+        context: impl core::fmt::Display,
         mut parser: impl nom8::Parser<I, O, E>,
     ) -> impl FnMut(I) -> IResult<I, O, E> {
-        static DEPTH: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        static DEPTH: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
         move |input: I| {
-            let depth = DEPTH.fetch_add(1, std::sync::atomic::Ordering::SeqCst) * 2;
+            let depth = DEPTH.fetch_add(1, core::sync::atomic::Ordering::SeqCst) * 2;
             eprintln!("{:depth$}--> {} {:?}", "", context, input);
             match parser.parse(input) {
                 Ok((i, o)) => {
-                    DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                    DEPTH.fetch_sub(1, core::sync::atomic::Ordering::SeqCst);
                     eprintln!("{:depth$}<-- {} {:?}", "", context, i);
                     Ok((i, o))
                 }
                 Err(err) => {
-                    DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                    DEPTH.fetch_sub(1, core::sync::atomic::Ordering::SeqCst);
                     eprintln!("{:depth$}<-- {} {:?}", "", context, err);
                     Err(err)
                 }
@@ -114,16 +546,49 @@ pub(crate) mod prelude {
         }
     }
 
-    #[cfg(not(feature = "unbounded"))]
-    #[derive(Copy, Clone, Debug, Default)]
+    #[derive(Copy, Clone, Debug)]
     pub(crate) struct RecursionCheck {
         current: usize,
+        options: super::ParseOptions,
     }
 
-    #[cfg(not(feature = "unbounded"))]
     impl RecursionCheck {
-        pub(crate) fn check_depth(depth: usize) -> Result<(), super::errors::CustomError> {
-            if depth < 128 {
+        pub(crate) fn new(options: super::ParseOptions) -> Self {
+            Self {
+                current: 0,
+                options,
+            }
+        }
+
+        pub(crate) fn is_lax(self) -> bool {
+            self.options.lax
+        }
+
+        pub(crate) fn duplicate_keys(self) -> super::DuplicateKeyPolicy {
+            self.options.duplicate_keys()
+        }
+
+        #[cfg(feature = "datetime")]
+        pub(crate) fn opaque_datetimes(self) -> bool {
+            self.options.opaque_datetimes
+        }
+
+        pub(crate) fn allows_decimal_comma(self) -> bool {
+            self.options.decimal_comma
+        }
+
+        pub(crate) fn integer_range(self) -> super::numbers::IntegerRange {
+            if self.options.extended_integers {
+                super::numbers::IntegerRange::I128
+            } else if self.options.lenient_prefixed_integers {
+                super::numbers::IntegerRange::U64Prefixed
+            } else {
+                super::numbers::IntegerRange::I64
+            }
+        }
+
+        pub(crate) fn check_depth(self, depth: usize) -> Result<(), super::errors::CustomError> {
+            if depth < self.options.max_depth {
                 Ok(())
             } else {
                 Err(super::errors::CustomError::RecursionLimitExceeded)
@@ -135,7 +600,7 @@ pub(crate) mod prelude {
             input: Input<'_>,
         ) -> Result<Self, nom8::Err<ParserError<'_>>> {
             self.current += 1;
-            if self.current < 128 {
+            if self.current < self.options.max_depth {
                 Ok(self)
             } else {
                 Err(nom8::Err::Error(
@@ -147,23 +612,35 @@ pub(crate) mod prelude {
                 ))
             }
         }
-    }
 
-    #[cfg(feature = "unbounded")]
-    #[derive(Copy, Clone, Debug, Default)]
-    pub(crate) struct RecursionCheck {}
+        pub(crate) fn check_array_len(self, len: usize) -> Result<(), super::errors::CustomError> {
+            let max = self.options.max_array_len;
+            if len <= max {
+                Ok(())
+            } else {
+                Err(super::errors::CustomError::LimitExceeded(
+                    super::Limit::ArrayLength,
+                    max,
+                ))
+            }
+        }
 
-    #[cfg(feature = "unbounded")]
-    impl RecursionCheck {
-        pub(crate) fn check_depth(_depth: usize) -> Result<(), super::errors::CustomError> {
-            Ok(())
+        pub(crate) fn check_string_len(self, len: usize) -> Result<(), super::errors::CustomError> {
+            let max = self.options.max_string_len;
+            if len <= max {
+                Ok(())
+            } else {
+                Err(super::errors::CustomError::LimitExceeded(
+                    super::Limit::StringLength,
+                    max,
+                ))
+            }
         }
+    }
 
-        pub(crate) fn recursing(
-            self,
-            _input: Input<'_>,
-        ) -> Result<Self, nom8::Err<ParserError<'_>>> {
-            Ok(self)
+    impl Default for RecursionCheck {
+        fn default() -> Self {
+            Self::new(Default::default())
         }
     }
 }
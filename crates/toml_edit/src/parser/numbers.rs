@@ -1,17 +1,18 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 use nom8::branch::alt;
 use nom8::bytes::any;
 use nom8::bytes::one_of;
 use nom8::bytes::tag;
+use nom8::bytes::take_while1;
 use nom8::combinator::cut;
 use nom8::combinator::opt;
 use nom8::combinator::peek;
 use nom8::multi::many0_count;
 use nom8::sequence::preceded;
 
+use crate::parser::errors::CustomError;
 use crate::parser::prelude::*;
-use crate::parser::trivia::from_utf8_unchecked;
 
 // ;; Boolean
 
@@ -35,13 +36,65 @@ const FALSE: &[u8] = b"false";
 
 // integer = dec-int / hex-int / oct-int / bin-int
 pub(crate) fn integer(input: Input<'_>) -> IResult<Input<'_>, i64, ParserError<'_>> {
-    dispatch! {peek(opt((any, any)));
-        Some((b'0', b'x')) => hex_int.map_res(|s| i64::from_str_radix(&s.replace('_', ""), 16)),
-        Some((b'0', b'o')) => oct_int.map_res(|s| i64::from_str_radix(&s.replace('_', ""), 8)),
-        Some((b'0', b'b')) => bin_int.map_res(|s| i64::from_str_radix(&s.replace('_', ""), 2)),
-        _ => dec_int.map_res(|s| s.replace('_', "").parse()),
+    integer_in_range(IntegerRange::I64)
+        .map(|value| value as i64)
+        .parse(input)
+}
+
+// Which integers are read, with those outside of `i64` becoming a `Value::WideInteger`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum IntegerRange {
+    I64,
+    // `ParseOptions::lenient_prefixed_integers` allows hex-int, oct-int, and bin-int up to
+    // `u64::MAX`
+    U64Prefixed,
+    // `ParseOptions::extended_integers` allows any integer within `i128`
+    I128,
+}
+
+pub(crate) fn integer_in_range(
+    range: IntegerRange,
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, i128, ParserError<'_>> {
+    move |input| {
+        dispatch! {peek(opt((any, any)));
+            Some((b'0', b'x')) => hex_int.map_res(|s| prefixed_int(s, 16, range)),
+            Some((b'0', b'o')) => oct_int.map_res(|s| prefixed_int(s, 8, range)),
+            Some((b'0', b'b')) => bin_int.map_res(|s| prefixed_int(s, 2, range)),
+            _ => dec_int.map_res(|s| dec_int_in_range(s, range)),
+        }
+        .parse(input)
+    }
+}
+
+fn dec_int_in_range(digits: &str, range: IntegerRange) -> Result<i128, CustomError> {
+    let digits = digits.replace('_', "");
+    match range {
+        IntegerRange::I64 | IntegerRange::U64Prefixed => digits
+            .parse::<i64>()
+            .map(i128::from)
+            .map_err(|_| CustomError::IntegerOutOfRange {
+                radix: 10,
+                max: i64::MAX as u128,
+            }),
+        IntegerRange::I128 => digits
+            .parse::<i128>()
+            .map_err(|_| CustomError::IntegerOutOfRange {
+                radix: 10,
+                max: i128::MAX as u128,
+            }),
+    }
+}
+
+fn prefixed_int(digits: &str, radix: u32, range: IntegerRange) -> Result<i128, CustomError> {
+    let max = match range {
+        IntegerRange::I64 => i64::MAX as u128,
+        IntegerRange::U64Prefixed => u64::MAX as u128,
+        IntegerRange::I128 => i128::MAX as u128,
+    };
+    match u128::from_str_radix(&digits.replace('_', ""), radix) {
+        Ok(value) if value <= max => Ok(value as i128),
+        _ => Err(CustomError::IntegerOutOfRange { radix, max }),
     }
-    .parse(input)
 }
 
 // dec-int = [ minus / plus ] unsigned-dec-int
@@ -66,7 +119,7 @@ pub(crate) fn dec_int(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<
         )),
     )
         .recognize()
-        .map(|b: &[u8]| unsafe { from_utf8_unchecked(b, "`digit` and `_` filter out non-ASCII") })
+        .map(|b: &[u8]| from_utf8_unchecked!(b, "`digit` and `_` filter out non-ASCII"))
         .context(Context::Expression("integer"))
         .parse(input)
 }
@@ -90,7 +143,7 @@ pub(crate) fn hex_int(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<
         ))
         .recognize(),
     )
-    .map(|b| unsafe { from_utf8_unchecked(b, "`hexdig` and `_` filter out non-ASCII") })
+    .map(|b| from_utf8_unchecked!(b, "`hexdig` and `_` filter out non-ASCII"))
     .context(Context::Expression("hexadecimal integer"))
     .parse(input)
 }
@@ -115,7 +168,7 @@ pub(crate) fn oct_int(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<
         ))
         .recognize(),
     )
-    .map(|b| unsafe { from_utf8_unchecked(b, "`DIGIT0_7` and `_` filter out non-ASCII") })
+    .map(|b| from_utf8_unchecked!(b, "`DIGIT0_7` and `_` filter out non-ASCII"))
     .context(Context::Expression("octal integer"))
     .parse(input)
 }
@@ -141,7 +194,7 @@ pub(crate) fn bin_int(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<
         ))
         .recognize(),
     )
-    .map(|b| unsafe { from_utf8_unchecked(b, "`DIGIT0_1` and `_` filter out non-ASCII") })
+    .map(|b| from_utf8_unchecked!(b, "`DIGIT0_1` and `_` filter out non-ASCII"))
     .context(Context::Expression("binary integer"))
     .parse(input)
 }
@@ -165,8 +218,8 @@ pub(crate) fn float(input: Input<'_>) -> IResult<Input<'_>, f64, ParserError<'_>
 pub(crate) fn float_(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
     (dec_int, alt((exp, (frac, opt(exp)).map(|_| ""))))
         .recognize()
-        .map(|b: &[u8]| unsafe {
-            from_utf8_unchecked(
+        .map(|b: &[u8]| {
+            from_utf8_unchecked!(
                 b,
                 "`dec_int`, `one_of`, `exp`, and `frac` filter out non-ASCII",
             )
@@ -174,6 +227,21 @@ pub(crate) fn float_(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'
         .parse(input)
 }
 
+// Not part of TOML: a float written with a decimal comma, for `ParseOptions::decimal_comma`.
+// Exactly three digits after the comma may instead be a thousands separator, so are rejected.
+//
+// decimal-comma-float = dec-int %x2C 1*DIGIT
+pub(crate) fn decimal_comma_float(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
+    (
+        dec_int,
+        b',',
+        take_while1(DIGIT).verify(|d: &[u8]| d.len() != 3),
+    )
+        .recognize()
+        .map(|b: &[u8]| from_utf8_unchecked!(b, "`dec_int`, `,`, and `DIGIT` filter out non-ASCII"))
+        .parse(input)
+}
+
 // frac = decimal-point zero-prefixable-int
 // decimal-point = %x2E               ; .
 pub(crate) fn frac(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
@@ -182,8 +250,8 @@ pub(crate) fn frac(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>
         cut(zero_prefixable_int).context(Context::Expected(ParserValue::Description("digit"))),
     )
         .recognize()
-        .map(|b: &[u8]| unsafe {
-            from_utf8_unchecked(
+        .map(|b: &[u8]| {
+            from_utf8_unchecked!(
                 b,
                 "`.` and `parse_zero_prefixable_int` filter out non-ASCII",
             )
@@ -205,7 +273,7 @@ pub(crate) fn zero_prefixable_int(input: Input<'_>) -> IResult<Input<'_>, &str,
         ))),
     )
         .recognize()
-        .map(|b: &[u8]| unsafe { from_utf8_unchecked(b, "`digit` and `_` filter out non-ASCII") })
+        .map(|b: &[u8]| from_utf8_unchecked!(b, "`digit` and `_` filter out non-ASCII"))
         .parse(input)
 }
 
@@ -218,8 +286,8 @@ pub(crate) fn exp(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>>
         cut(zero_prefixable_int),
     )
         .recognize()
-        .map(|b: &[u8]| unsafe {
-            from_utf8_unchecked(
+        .map(|b: &[u8]| {
+            from_utf8_unchecked!(
                 b,
                 "`one_of` and `parse_zero_prefixable_int` filter out non-ASCII",
             )
@@ -278,8 +346,8 @@ mod test {
             ("0xF", 15),
             ("0o0_755", 493),
             ("0b1_0_1", 5),
-            (&std::i64::MIN.to_string()[..], std::i64::MIN),
-            (&std::i64::MAX.to_string()[..], std::i64::MAX),
+            (&core::i64::MIN.to_string()[..], core::i64::MIN),
+            (&core::i64::MAX.to_string()[..], core::i64::MAX),
         ];
         for &(input, expected) in &cases {
             let parsed = integer.parse(new_input(input)).finish();
@@ -289,6 +357,68 @@ mod test {
         let overflow = "1000000000000000000000000000000000";
         let parsed = integer.parse(new_input(overflow)).finish();
         assert!(parsed.is_err());
+
+        let bin_overflow = format!("0b{}", "1".repeat(64));
+        for input in [
+            "0x8000000000000000",
+            "0o1000000000000000000000",
+            &bin_overflow,
+        ] {
+            let parsed = integer.parse(new_input(input)).finish();
+            assert!(parsed.is_err(), "Parsing {input:?}");
+        }
+    }
+
+    #[test]
+    fn u64_prefixed_integers() {
+        let cases = [
+            ("0xF", 15),
+            ("0x7fff_ffff_ffff_ffff", i64::MAX as i128),
+            ("0x8000000000000000", 1 << 63),
+            ("0xffff_ffff_ffff_ffff", u64::MAX as i128),
+            ("0o1777777777777777777777", u64::MAX as i128),
+            ("-17", -17),
+        ];
+        for &(input, expected) in &cases {
+            let parsed = integer_in_range(IntegerRange::U64Prefixed)
+                .parse(new_input(input))
+                .finish();
+            assert_eq!(parsed, Ok(expected), "Parsing {input:?}");
+        }
+
+        for input in ["0x1_0000_0000_0000_0000", "9223372036854775808"] {
+            let parsed = integer_in_range(IntegerRange::U64Prefixed)
+                .parse(new_input(input))
+                .finish();
+            assert!(parsed.is_err(), "Parsing {input:?}");
+        }
+    }
+
+    #[test]
+    fn i128_integers() {
+        let cases = [
+            ("9223372036854775808", 1 << 63),
+            ("18_446_744_073_709_551_615", u64::MAX as i128),
+            ("-9223372036854775809", i64::MIN as i128 - 1),
+            ("0x1_0000_0000_0000_0001", (1 << 64) + 1),
+            ("170141183460469231731687303715884105727", i128::MAX),
+        ];
+        for &(input, expected) in &cases {
+            let parsed = integer_in_range(IntegerRange::I128)
+                .parse(new_input(input))
+                .finish();
+            assert_eq!(parsed, Ok(expected), "Parsing {input:?}");
+        }
+
+        for input in [
+            "170141183460469231731687303715884105728",
+            "0x8000_0000_0000_0000_0000_0000_0000_0000",
+        ] {
+            let parsed = integer_in_range(IntegerRange::I128)
+                .parse(new_input(input))
+                .finish();
+            assert!(parsed.is_err(), "Parsing {input:?}");
+        }
     }
 
     #[track_caller]
@@ -301,7 +431,7 @@ mod test {
         } else {
             dbg!(expected);
             dbg!(actual);
-            assert!((expected - actual).abs() < std::f64::EPSILON);
+            assert!((expected - actual).abs() < core::f64::EPSILON);
         }
     }
 
@@ -316,8 +446,8 @@ mod test {
             ("-2E-2", -2E-2),
             ("6.626e-34", 6.626e-34),
             ("9_224_617.445_991_228_313", 9_224_617.445_991_227),
-            ("-1.7976931348623157e+308", std::f64::MIN),
-            ("1.7976931348623157e+308", std::f64::MAX),
+            ("-1.7976931348623157e+308", core::f64::MIN),
+            ("1.7976931348623157e+308", core::f64::MAX),
             ("nan", f64::NAN),
             ("+nan", f64::NAN),
             ("-nan", f64::NAN),
@@ -1,8 +1,15 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
 use crate::key::Key;
-use crate::parser::errors::CustomError;
+use crate::parser::errors::{CustomError, ParseWarning, TableConflict, TableKind, WarningKind};
+use crate::parser::prelude::*;
+use crate::parser::{DuplicateKeyPolicy, Limit, ParseOptions};
 use crate::repr::Decor;
-use crate::table::TableKeyValue;
-use crate::{ArrayOfTables, Document, InternalString, Item, Table};
+use crate::table::{KeyValuePairs, TableKeyValue};
+use crate::{ArrayOfTables, Document, InternalString, Item, Table, Value};
 
 pub(crate) struct ParseState {
     document: Document,
@@ -11,14 +18,66 @@ pub(crate) struct ParseState {
     current_table: Table,
     current_is_array: bool,
     current_table_path: Vec<Key>,
+    // Indexed by table position, less one for the root table
+    header_spans: Vec<Range<usize>>,
+    input_len: usize,
+    keys: usize,
+    options: ParseOptions,
+    // Whether the caller reads the warnings, as they are otherwise not worth finding
+    want_warnings: bool,
+    warnings: Vec<ParseWarning>,
 }
 
 impl ParseState {
-    pub(crate) fn into_document(mut self) -> Result<Document, CustomError> {
+    pub(crate) fn new(options: ParseOptions, input_len: usize, want_warnings: bool) -> Self {
+        Self {
+            document: Document::new(),
+            trailing: String::new(),
+            current_table_position: 0,
+            current_table: Table::new(),
+            current_is_array: false,
+            current_table_path: Vec::new(),
+            header_spans: Vec::new(),
+            input_len,
+            keys: 0,
+            options,
+            want_warnings,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn count_keys(&mut self, keys: usize) -> Result<(), CustomError> {
+        self.keys = self.keys.saturating_add(keys);
+        if self.keys <= self.options.max_keys {
+            Ok(())
+        } else {
+            Err(CustomError::LimitExceeded(
+                Limit::Keys,
+                self.options.max_keys,
+            ))
+        }
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    fn normalize_path(&self, mut path: Vec<Key>) -> Vec<Key> {
+        if self.options.normalize_keys {
+            for key in &mut path {
+                key.normalize();
+            }
+        }
+        path
+    }
+
+    /// The offset of `rest` within the input being parsed
+    pub(crate) fn offset(&self, rest: Input<'_>) -> usize {
+        self.input_len - rest.len()
+    }
+
+    pub(crate) fn into_document(mut self) -> Result<(Document, Vec<ParseWarning>), CustomError> {
         self.finalize_table()?;
         let trailing = self.trailing.as_str().into();
         self.document.trailing = trailing;
-        Ok(self.document)
+        Ok((self.document, self.warnings))
     }
 
     pub(crate) fn on_ws(&mut self, w: &str) {
@@ -33,9 +92,35 @@ impl ParseState {
         &mut self,
         mut path: Vec<Key>,
         mut kv: TableKeyValue,
+        mut warnings: Vec<WarningKind>,
+        span: Range<usize>,
     ) -> Result<(), CustomError> {
+        if self.want_warnings && looks_like_value(&path, &kv.key) {
+            warnings.push(WarningKind::AmbiguousKey);
+        }
+        #[cfg(feature = "unicode-normalization")]
+        if self.options.normalize_keys {
+            for key in path.iter_mut().chain(core::iter::once(&mut kv.key)) {
+                key.normalize();
+            }
+            if let Item::Value(value) = &mut kv.value {
+                normalize_value(value)?;
+            }
+        }
+        self.count_keys(1 + kv.value.as_value().map_or(0, nested_keys))?;
+        for kind in warnings.into_iter().filter(|_| self.want_warnings) {
+            let mut warning = ParseWarning::new(kind, &path, &kv.key, span.clone());
+            if let (WarningKind::DecimalComma, Some(Value::Float(f))) = (kind, kv.value.as_value())
+            {
+                // The fixed number is as long as the original, which ends the key/value pair
+                let fixed = f.to_repr().as_raw().to_owned();
+                warning = warning.with_fix(span.end - fixed.len()..span.end, fixed);
+            }
+            self.warnings.push(warning);
+        }
+
         {
-            let prefix = std::mem::take(&mut self.trailing);
+            let prefix = core::mem::take(&mut self.trailing);
             let first_key = if path.is_empty() {
                 &mut kv.key
             } else {
@@ -58,15 +143,16 @@ impl ParseState {
             });
         }
 
-        let key: InternalString = kv.key.get_internal().into();
-        match table.items.entry(key) {
-            indexmap::map::Entry::Vacant(o) => {
-                o.insert(kv);
+        match insert_keyval(&mut table.items, kv, self.options.duplicate_keys()) {
+            Ok(Some((kind, key))) if self.want_warnings => {
+                self.warnings
+                    .push(ParseWarning::new(kind, &path, &key, span));
             }
-            indexmap::map::Entry::Occupied(o) => {
+            Ok(_) => {}
+            Err(key) => {
                 // "Since tables cannot be defined more than once, redefining such tables using a [table] header is not allowed"
                 return Err(CustomError::DuplicateKey {
-                    key: o.key().as_str().into(),
+                    key,
                     table: Some(self.current_table_path.clone()),
                 });
             }
@@ -91,9 +177,33 @@ impl ParseState {
         let entry = parent_table
             .entry_format(key)
             .or_insert(Item::ArrayOfTables(ArrayOfTables::new()));
-        entry
+        match entry {
+            Item::Table(t) if !t.is_implicit() && !t.is_dotted() => {
+                if !self.options.lenient_table_headers {
+                    return Err(CustomError::TableConflict(TableConflict::new(
+                        &path,
+                        TableKind::Table,
+                        header_span(&self.header_spans, t.position()),
+                        self.header_spans.last().cloned().unwrap_or_default(),
+                    )));
+                }
+                let mut array = ArrayOfTables::new();
+                if let Item::Table(t) = core::mem::take(entry) {
+                    array.push(t);
+                }
+                *entry = Item::ArrayOfTables(array);
+            }
+            _ => {}
+        }
+        let array = entry
             .as_array_of_tables()
             .ok_or_else(|| CustomError::duplicate_key(&path, path.len() - 1))?;
+        if self.options.max_array_len <= array.len() {
+            return Err(CustomError::LimitExceeded(
+                Limit::ArrayLength,
+                self.options.max_array_len,
+            ));
+        }
 
         self.current_table_position += 1;
         self.current_table.decor = decor;
@@ -114,6 +224,17 @@ impl ParseState {
         let root = self.document.as_table_mut();
         let parent_table = Self::descend_path(root, &path[..path.len() - 1], false)?;
         let key = &path[path.len() - 1];
+        if let Some(Item::ArrayOfTables(array)) = parent_table.get(key.get()) {
+            if !self.options.lenient_table_headers {
+                return Err(CustomError::TableConflict(TableConflict::new(
+                    &path,
+                    TableKind::ArrayOfTables,
+                    header_span(&self.header_spans, array.get(0).and_then(Table::position)),
+                    self.header_spans.last().cloned().unwrap_or_default(),
+                )));
+            }
+            return self.start_aray_table(path, decor);
+        }
         if let Some(entry) = parent_table.remove(key.get()) {
             match entry {
                 Item::Table(t) if t.implicit && !t.is_dotted() => {
@@ -134,13 +255,13 @@ impl ParseState {
     }
 
     pub(crate) fn finalize_table(&mut self) -> Result<(), CustomError> {
-        let mut table = std::mem::take(&mut self.current_table);
-        let path = std::mem::take(&mut self.current_table_path);
+        let mut table = core::mem::take(&mut self.current_table);
+        let path = core::mem::take(&mut self.current_table_path);
 
         let root = self.document.as_table_mut();
         if path.is_empty() {
             assert!(root.is_empty());
-            std::mem::swap(&mut table, root);
+            core::mem::swap(&mut table, root);
         } else if self.current_is_array {
             let parent_table = Self::descend_path(root, &path[..path.len() - 1], false)?;
             let key = &path[path.len() - 1];
@@ -162,7 +283,7 @@ impl ParseState {
                     match entry.into_mut() {
                         // if [a.b.c] header preceded [a.b]
                         Item::Table(ref mut t) if t.implicit => {
-                            std::mem::swap(t, &mut table);
+                            core::mem::swap(t, &mut table);
                         }
                         _ => return Err(CustomError::duplicate_key(&path, path.len() - 1)),
                     }
@@ -203,15 +324,9 @@ impl ParseState {
                     table = last_child;
                 }
                 Item::Table(ref mut sweet_child_of_mine) => {
-                    // Since tables cannot be defined more than once, redefining such tables using a
-                    // [table] header is not allowed. Likewise, using dotted keys to redefine tables
-                    // already defined in [table] form is not allowed.
-                    if sweet_child_of_mine.is_dotted() && !dotted {
-                        return Err(CustomError::DuplicateKey {
-                            key: key.get().into(),
-                            table: None,
-                        });
-                    }
+                    // Using dotted keys to redefine tables already defined in [table] form is not
+                    // allowed.  A [table] header may still add sub-tables to a table defined by
+                    // dotted keys, while `start_table` rejects redefining the table itself.
                     if dotted && !sweet_child_of_mine.is_implicit() {
                         return Err(CustomError::DuplicateKey {
                             key: key.get().into(),
@@ -230,11 +345,16 @@ impl ParseState {
         &mut self,
         path: Vec<Key>,
         trailing: &str,
+        span: Range<usize>,
     ) -> Result<(), CustomError> {
         debug_assert!(!path.is_empty());
 
+        #[cfg(feature = "unicode-normalization")]
+        let path = self.normalize_path(path);
+        self.count_keys(1)?;
         self.finalize_table()?;
-        let leading = std::mem::take(&mut self.trailing);
+        self.header_spans.push(span);
+        let leading = core::mem::take(&mut self.trailing);
         self.start_table(path, Decor::new(leading, trailing))?;
 
         Ok(())
@@ -244,26 +364,123 @@ impl ParseState {
         &mut self,
         path: Vec<Key>,
         trailing: &str,
+        span: Range<usize>,
     ) -> Result<(), CustomError> {
         debug_assert!(!path.is_empty());
 
+        #[cfg(feature = "unicode-normalization")]
+        let path = self.normalize_path(path);
+        self.count_keys(1)?;
         self.finalize_table()?;
-        let leading = std::mem::take(&mut self.trailing);
+        self.header_spans.push(span);
+        let leading = core::mem::take(&mut self.trailing);
         self.start_aray_table(path, Decor::new(leading, trailing))?;
 
         Ok(())
     }
 }
 
-impl Default for ParseState {
-    fn default() -> Self {
-        Self {
-            document: Document::new(),
-            trailing: String::new(),
-            current_table_position: 0,
-            current_table: Table::new(),
-            current_is_array: false,
-            current_table_path: Vec::new(),
+/// Inserts `kv`, resolving a redefined value according to `policy`
+///
+/// Returns the warning to report, with the key of the replaced or dropped pair, or the key when it
+/// can't be redefined.  Shared by tables and inline tables so both follow the same policy.
+pub(crate) fn insert_keyval(
+    items: &mut KeyValuePairs,
+    kv: TableKeyValue,
+    policy: DuplicateKeyPolicy,
+) -> Result<Option<(WarningKind, Key)>, String> {
+    let key: InternalString = kv.key.get_internal().into();
+    match items.entry(key) {
+        indexmap::map::Entry::Vacant(o) => {
+            o.insert(kv);
+            Ok(None)
+        }
+        indexmap::map::Entry::Occupied(o)
+            if policy != DuplicateKeyPolicy::Error && o.get().value.is_value() =>
+        {
+            if policy == DuplicateKeyPolicy::LastWins {
+                let key = o.key().clone();
+                o.shift_remove();
+                let replacement = kv.key.clone();
+                items.insert(key, kv);
+                Ok(Some((WarningKind::DuplicateKey, replacement)))
+            } else {
+                Ok(Some((WarningKind::DroppedDuplicateKey, kv.key)))
+            }
+        }
+        indexmap::map::Entry::Occupied(o) => Err(o.key().as_str().into()),
+    }
+}
+
+// Normalizes the keys of inline tables in `value`, which were checked for duplicates as written
+#[cfg(feature = "unicode-normalization")]
+fn normalize_value(value: &mut Value) -> Result<(), CustomError> {
+    match value {
+        Value::InlineTable(table) => {
+            for (_, mut kv) in core::mem::take(&mut table.items) {
+                kv.key.normalize();
+                if let Item::Value(value) = &mut kv.value {
+                    normalize_value(value)?;
+                }
+                let key: InternalString = kv.key.get_internal().into();
+                match table.items.entry(key) {
+                    indexmap::map::Entry::Vacant(o) => {
+                        o.insert(kv);
+                    }
+                    indexmap::map::Entry::Occupied(o) => {
+                        return Err(CustomError::DuplicateKey {
+                            key: o.key().as_str().into(),
+                            table: None,
+                        });
+                    }
+                }
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter_mut() {
+                normalize_value(value)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// Keys within inline tables in `value`
+fn nested_keys(value: &Value) -> usize {
+    match value {
+        Value::InlineTable(t) => t.len() + t.iter().map(|(_, v)| nested_keys(v)).sum::<usize>(),
+        Value::Array(a) => a.iter().map(nested_keys).sum(),
+        _ => 0,
+    }
+}
+
+fn header_span(header_spans: &[Range<usize>], position: Option<usize>) -> Range<usize> {
+    position
+        .and_then(|p| header_spans.get(p.checked_sub(1)?))
+        .cloned()
+        .unwrap_or_default()
+}
+
+// A bare key that reads like a date, like `1979-05-27`, or a dotted key of two bare numbers that
+// reads like a float, like `3.14`, which is the key `14` of a table `3`
+fn looks_like_value(path: &[Key], key: &Key) -> bool {
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let date = |s: &str| {
+        let mut parts = s.split('-');
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(y), Some(m), Some(d), None) => {
+                y.len() == 4 && m.len() == 2 && d.len() == 2 && digits(y) && digits(m) && digits(d)
+            }
+            _ => false,
         }
+    };
+    let mut any_date = false;
+    let mut all_digits = true;
+    for k in path.iter().chain(core::iter::once(key)) {
+        let repr = k.to_repr();
+        any_date |= date(repr.as_raw());
+        all_digits &= digits(repr.as_raw());
     }
+    any_date || path.len() == 1 && all_digits
 }
@@ -1,14 +1,13 @@
-use std::borrow::Cow;
-use std::char;
-use std::ops::RangeInclusive;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::char;
+use core::ops::RangeInclusive;
 
 use nom8::branch::alt;
 use nom8::bytes::any;
 use nom8::bytes::none_of;
 use nom8::bytes::one_of;
 use nom8::bytes::tag;
-use nom8::bytes::take_while;
-use nom8::bytes::take_while1;
 use nom8::bytes::take_while_m_n;
 use nom8::combinator::cut;
 use nom8::combinator::fail;
@@ -24,7 +23,7 @@ use nom8::sequence::terminated;
 use crate::parser::errors::CustomError;
 use crate::parser::numbers::HEXDIG;
 use crate::parser::prelude::*;
-use crate::parser::trivia::{from_utf8_unchecked, newline, ws, ws_newlines, NON_ASCII, WSCHAR};
+use crate::parser::trivia::{newline, take_text, take_text1, ws, ws_newlines, NON_ASCII, WSCHAR};
 
 // ;; String
 
@@ -70,8 +69,8 @@ fn basic_chars(input: Input<'_>) -> IResult<Input<'_>, Cow<'_, str>, ParserError
     alt((
         // Deviate from the official grammar by batching the unescaped chars so we build a string a
         // chunk at a time, rather than a `char` at a time.
-        take_while1(BASIC_UNESCAPED)
-            .map_res(std::str::from_utf8)
+        take_text1(BASIC_UNESCAPED, b"\"\\")
+            .map_res(core::str::from_utf8)
             .map(Cow::Borrowed),
         escaped.map(|c| Cow::Owned(String::from(c))),
     ))
@@ -102,6 +101,7 @@ pub(crate) const ESCAPE: u8 = b'\\';
 // escape-seq-char =/ %x6E         ; n    line feed       U+000A
 // escape-seq-char =/ %x72         ; r    carriage return U+000D
 // escape-seq-char =/ %x74         ; t    tab             U+0009
+// escape-seq-char =/ %x65         ; e    escape          U+001B (TOML 1.1)
 // escape-seq-char =/ %x75 4HEXDIG ; uXXXX                U+XXXX
 // escape-seq-char =/ %x55 8HEXDIG ; UXXXXXXXX            U+XXXXXXXX
 fn escape_seq_char(input: Input<'_>) -> IResult<Input<'_>, char, ParserError<'_>> {
@@ -111,6 +111,7 @@ fn escape_seq_char(input: Input<'_>) -> IResult<Input<'_>, char, ParserError<'_>
         b'n' => success('\n'),
         b'r' => success('\r'),
         b't' => success('\t'),
+        b'e' if cfg!(feature = "toml-1.1") => success('\u{1b}'),
         b'u' => cut(hexescape::<4>).context(Context::Expression("unicode 4-digit hex code")),
         b'U' => cut(hexescape::<8>).context(Context::Expression("unicode 8-digit hex code")),
         b'\\' => success('\\'),
@@ -137,7 +138,7 @@ pub(crate) fn hexescape<const N: usize>(
 ) -> IResult<Input<'_>, char, ParserError<'_>> {
     take_while_m_n(0, N, HEXDIG)
         .verify(|b: &[u8]| b.len() == N)
-        .map(|b: &[u8]| unsafe { from_utf8_unchecked(b, "`is_ascii_digit` filters out on-ASCII") })
+        .map(|b: &[u8]| from_utf8_unchecked!(b, "`is_ascii_digit` filters out on-ASCII"))
         .map_opt(|s| u32::from_str_radix(s, 16).ok())
         .map_res(|h| char::from_u32(h).ok_or(CustomError::OutOfRange))
         .parse(input)
@@ -201,8 +202,8 @@ fn mlb_content(input: Input<'_>) -> IResult<Input<'_>, Cow<'_, str>, ParserError
     alt((
         // Deviate from the official grammar by batching the unescaped chars so we build a string a
         // chunk at a time, rather than a `char` at a time.
-        take_while1(MLB_UNESCAPED)
-            .map_res(std::str::from_utf8)
+        take_text1(MLB_UNESCAPED, b"\"\\")
+            .map_res(core::str::from_utf8)
             .map(Cow::Borrowed),
         // Order changed fromg grammar so `escaped` can more easily `cut` on bad escape sequences
         mlb_escaped_nl.map(|_| Cow::Borrowed("")),
@@ -218,12 +219,12 @@ fn mlb_quotes<'i>(
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, &str, ParserError<'i>> {
     move |input| {
         let res = terminated(b"\"\"", peek(term.by_ref()))
-            .map(|b| unsafe { from_utf8_unchecked(b, "`bytes` out non-ASCII") })
+            .map(|b| from_utf8_unchecked!(b, "`bytes` out non-ASCII"))
             .parse(input);
 
         match res {
             Err(nom8::Err::Error(_)) => terminated(b"\"", peek(term.by_ref()))
-                .map(|b| unsafe { from_utf8_unchecked(b, "`bytes` out non-ASCII") })
+                .map(|b| from_utf8_unchecked!(b, "`bytes` out non-ASCII"))
                 .parse(input),
             res => res,
         }
@@ -254,10 +255,14 @@ fn mlb_escaped_nl(input: Input<'_>) -> IResult<Input<'_>, (), ParserError<'_>> {
 
 // literal-string = apostrophe *literal-char apostrophe
 pub(crate) fn literal_string(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
-    delimited(APOSTROPHE, cut(take_while(LITERAL_CHAR)), cut(APOSTROPHE))
-        .map_res(std::str::from_utf8)
-        .context(Context::Expression("literal string"))
-        .parse(input)
+    delimited(
+        APOSTROPHE,
+        cut(take_text(LITERAL_CHAR, b"'")),
+        cut(APOSTROPHE),
+    )
+    .map_res(core::str::from_utf8)
+    .context(Context::Expression("literal string"))
+    .parse(input)
 }
 
 // apostrophe = %x27 ; ' apostrophe
@@ -305,13 +310,18 @@ fn ml_literal_body(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>
         opt(mll_quotes(tag(ML_LITERAL_STRING_DELIM).value(()))),
     )
         .recognize()
-        .map_res(std::str::from_utf8)
+        .map_res(core::str::from_utf8)
         .parse(input)
 }
 
 // mll-content = mll-char / newline
-fn mll_content(input: Input<'_>) -> IResult<Input<'_>, u8, ParserError<'_>> {
-    alt((one_of(MLL_CHAR), newline)).parse(input)
+fn mll_content(input: Input<'_>) -> IResult<Input<'_>, (), ParserError<'_>> {
+    alt((
+        // Deviate from the official grammar by batching the chars for speed
+        take_text1(MLL_CHAR, b"'").value(()),
+        newline.value(()),
+    ))
+    .parse(input)
 }
 
 // mll-char = %x09 / %x20-26 / %x28-7E / non-ascii
@@ -328,12 +338,12 @@ fn mll_quotes<'i>(
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, &str, ParserError<'i>> {
     move |input| {
         let res = terminated(b"''", peek(term.by_ref()))
-            .map(|b| unsafe { from_utf8_unchecked(b, "`bytes` out non-ASCII") })
+            .map(|b| from_utf8_unchecked!(b, "`bytes` out non-ASCII"))
             .parse(input);
 
         match res {
             Err(nom8::Err::Error(_)) => terminated(b"'", peek(term.by_ref()))
-                .map(|b| unsafe { from_utf8_unchecked(b, "`bytes` out non-ASCII") })
+                .map(|b| from_utf8_unchecked!(b, "`bytes` out non-ASCII"))
                 .parse(input),
             res => res,
         }
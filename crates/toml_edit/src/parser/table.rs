@@ -1,6 +1,6 @@
-use std::cell::RefCell;
+use core::cell::RefCell;
 #[allow(unused_imports)]
-use std::ops::DerefMut;
+use core::ops::DerefMut;
 
 use nom8::bytes::take;
 use nom8::combinator::cut;
@@ -16,32 +16,39 @@ use crate::parser::trivia::line_trailing;
 // std-table-open  = %x5B ws     ; [ Left square bracket
 pub(crate) const STD_TABLE_OPEN: u8 = b'[';
 // std-table-close = ws %x5D     ; ] Right square bracket
-const STD_TABLE_CLOSE: u8 = b']';
+pub(crate) const STD_TABLE_CLOSE: u8 = b']';
 // array-table-open  = %x5B.5B ws  ; [[ Double left square bracket
-const ARRAY_TABLE_OPEN: &[u8] = b"[[";
+pub(crate) const ARRAY_TABLE_OPEN: &[u8] = b"[[";
 // array-table-close = ws %x5D.5D  ; ]] Double right quare bracket
-const ARRAY_TABLE_CLOSE: &[u8] = b"]]";
+pub(crate) const ARRAY_TABLE_CLOSE: &[u8] = b"]]";
 
 // ;; Standard Table
 
 // std-table = std-table-open key *( table-key-sep key) std-table-close
 pub(crate) fn std_table<'s, 'i>(
     state: &'s RefCell<ParseState>,
+    check: RecursionCheck,
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
     move |i| {
         (
             delimited(
                 STD_TABLE_OPEN,
-                cut(key),
+                cut(key(check)),
                 cut(STD_TABLE_CLOSE)
                     .context(Context::Expected(ParserValue::CharLiteral('.')))
                     .context(Context::Expected(ParserValue::StringLiteral("]"))),
-            ),
-            cut(line_trailing.map_res(std::str::from_utf8))
+            )
+            .with_recognized(),
+            cut(line_trailing.map_res(core::str::from_utf8))
                 .context(Context::Expected(ParserValue::CharLiteral('\n')))
                 .context(Context::Expected(ParserValue::CharLiteral('#'))),
         )
-            .map_res(|(h, t)| state.borrow_mut().deref_mut().on_std_header(h, t))
+            .map_res(|((h, header), t)| {
+                let mut state = state.borrow_mut();
+                let start = state.offset(i);
+                let span = start..start + header.len();
+                state.deref_mut().on_std_header(h, t, span)
+            })
             .parse(i)
     }
 }
@@ -51,21 +58,28 @@ pub(crate) fn std_table<'s, 'i>(
 // array-table = array-table-open key *( table-key-sep key) array-table-close
 pub(crate) fn array_table<'s, 'i>(
     state: &'s RefCell<ParseState>,
+    check: RecursionCheck,
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
     move |i| {
         (
             delimited(
                 ARRAY_TABLE_OPEN,
-                cut(key),
+                cut(key(check)),
                 cut(ARRAY_TABLE_CLOSE)
                     .context(Context::Expected(ParserValue::CharLiteral('.')))
                     .context(Context::Expected(ParserValue::StringLiteral("]]"))),
-            ),
-            cut(line_trailing.map_res(std::str::from_utf8))
+            )
+            .with_recognized(),
+            cut(line_trailing.map_res(core::str::from_utf8))
                 .context(Context::Expected(ParserValue::CharLiteral('\n')))
                 .context(Context::Expected(ParserValue::CharLiteral('#'))),
         )
-            .map_res(|(h, t)| state.borrow_mut().deref_mut().on_array_header(h, t))
+            .map_res(|((h, header), t)| {
+                let mut state = state.borrow_mut();
+                let start = state.offset(i);
+                let span = start..start + header.len();
+                state.deref_mut().on_array_header(h, t, span)
+            })
             .parse(i)
     }
 }
@@ -75,11 +89,12 @@ pub(crate) fn array_table<'s, 'i>(
 // table = std-table / array-table
 pub(crate) fn table<'s, 'i>(
     state: &'s RefCell<ParseState>,
+    check: RecursionCheck,
 ) -> impl FnMut(Input<'i>) -> IResult<Input<'i>, (), ParserError<'i>> + 's {
     move |i| {
         dispatch!(peek::<_, &[u8],_,_>(take(2usize));
-            b"[[" => array_table(state),
-            _ => std_table(state),
+            b"[[" => array_table(state, check),
+            _ => std_table(state, check),
         )
         .context(Context::Expression("table header"))
         .parse(i)
@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 use nom8::branch::alt;
 use nom8::bytes::one_of;
@@ -13,15 +13,18 @@ use nom8::sequence::terminated;
 
 use crate::parser::prelude::*;
 
+/// Debug builds, like those of the `toml_edit_fuzz` targets run with `--debug-assertions`,
+/// validate `bytes` anyway and panic with `safety_justification` if they are not UTF-8.
+#[cfg(not(feature = "forbid-unsafe"))]
 pub(crate) unsafe fn from_utf8_unchecked<'b>(
     bytes: &'b [u8],
     safety_justification: &'static str,
 ) -> &'b str {
     if cfg!(debug_assertions) {
         // Catch problems more quickly when testing
-        std::str::from_utf8(bytes).expect(safety_justification)
+        core::str::from_utf8(bytes).expect(safety_justification)
     } else {
-        std::str::from_utf8_unchecked(bytes)
+        core::str::from_utf8_unchecked(bytes)
     }
 }
 
@@ -32,7 +35,7 @@ pub(crate) const WSCHAR: (u8, u8) = (b' ', b'\t');
 // ws = *wschar
 pub(crate) fn ws(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
     take_while(WSCHAR)
-        .map(|b| unsafe { from_utf8_unchecked(b, "`is_wschar` filters out on-ASCII") })
+        .map(|b| from_utf8_unchecked!(b, "`is_wschar` filters out on-ASCII"))
         .parse(input)
 }
 
@@ -46,12 +49,67 @@ pub(crate) const NON_ASCII: RangeInclusive<u8> = 0x80..=0xff;
 pub(crate) const NON_EOL: (u8, RangeInclusive<u8>, RangeInclusive<u8>) =
     (0x09, 0x20..=0x7E, NON_ASCII);
 
+/// `take_while(set)`, skipping ahead a word at a time through the common case of printable text
+///
+/// `set` must accept `%x20-7E / non-ascii` other than the bytes in `stops`.
+pub(crate) fn take_text<'i, T>(
+    set: T,
+    stops: &'static [u8],
+) -> impl Fn(Input<'i>) -> IResult<Input<'i>, &'i [u8], ParserError<'i>>
+where
+    T: nom8::input::FindToken<u8> + Clone,
+{
+    move |input| {
+        let fast = text_len(input, stops);
+        let (rest, slow) = take_while(set.clone())(&input[fast..])?;
+        Ok((rest, &input[..fast + slow.len()]))
+    }
+}
+
+/// [`take_text`] for `take_while1(set)`
+pub(crate) fn take_text1<'i, T>(
+    set: T,
+    stops: &'static [u8],
+) -> impl Fn(Input<'i>) -> IResult<Input<'i>, &'i [u8], ParserError<'i>>
+where
+    T: nom8::input::FindToken<u8> + Clone,
+{
+    move |input| {
+        let fast = text_len(input, stops);
+        if fast == 0 {
+            return take_while1(set.clone())(input);
+        }
+        let (rest, slow) = take_while(set.clone())(&input[fast..])?;
+        Ok((rest, &input[..fast + slow.len()]))
+    }
+}
+
+// Length of the leading 8-byte words without control characters or `stops`, checking all bytes of
+// a word at once.  See "Determine if a word has a byte less than n" in Bit Twiddling Hacks.
+fn text_len(input: &[u8], stops: &[u8]) -> usize {
+    const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGHS: u64 = u64::from_ne_bytes([0x80; 8]);
+    let has_less = |word: u64, n: u8| word.wrapping_sub(ONES * n as u64) & !word & HIGHS != 0;
+    let has_byte = |word: u64, b: u8| has_less(word ^ (ONES * b as u64), 1);
+
+    let mut len = 0;
+    for chunk in input.chunks_exact(8) {
+        let word = u64::from_ne_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+        if has_less(word, 0x20) || has_byte(word, 0x7F) || stops.iter().any(|&b| has_byte(word, b))
+        {
+            break;
+        }
+        len += 8;
+    }
+    len
+}
+
 // comment-start-symbol = %x23 ; #
 pub(crate) const COMMENT_START_SYMBOL: u8 = b'#';
 
 // comment = comment-start-symbol *non-eol
 pub(crate) fn comment(input: Input<'_>) -> IResult<Input<'_>, &[u8], ParserError<'_>> {
-    (COMMENT_START_SYMBOL, take_while(NON_EOL))
+    (COMMENT_START_SYMBOL, take_text(NON_EOL, b""))
         .recognize()
         .parse(input)
 }
@@ -72,9 +130,7 @@ pub(crate) const CR: u8 = b'\r';
 pub(crate) fn ws_newline(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
     many0_count(alt((newline.value(&b"\n"[..]), take_while1(WSCHAR))))
         .recognize()
-        .map(|b| unsafe {
-            from_utf8_unchecked(b, "`is_wschar` and `newline` filters out on-ASCII")
-        })
+        .map(|b| from_utf8_unchecked!(b, "`is_wschar` and `newline` filters out on-ASCII"))
         .parse(input)
 }
 
@@ -82,9 +138,7 @@ pub(crate) fn ws_newline(input: Input<'_>) -> IResult<Input<'_>, &str, ParserErr
 pub(crate) fn ws_newlines(input: Input<'_>) -> IResult<Input<'_>, &str, ParserError<'_>> {
     (newline, ws_newline)
         .recognize()
-        .map(|b| unsafe {
-            from_utf8_unchecked(b, "`is_wschar` and `newline` filters out on-ASCII")
-        })
+        .map(|b| from_utf8_unchecked!(b, "`is_wschar` and `newline` filters out on-ASCII"))
         .parse(input)
 }
 
@@ -144,4 +198,28 @@ mod test {
             assert_eq!(parsed, input.as_bytes());
         }
     }
+
+    #[test]
+    fn take_text_matches_take_while() {
+        let text = "a comment with ünïcödé and\ttabs ";
+        for stop in ["", "\x00", "\x07", "\t", "\n", "\x7F", "\"", "'"] {
+            for i in 0..=text.len() {
+                if !text.is_char_boundary(i) {
+                    continue;
+                }
+                let input = format!("{}{}{}", &text[..i], stop, text);
+                let input = new_input(&input);
+                let expected: IResult<_, _, ParserError<'_>> = take_while(NON_EOL)(input);
+                assert_eq!(take_text(NON_EOL, b"")(input), expected, "{:?}", input);
+                let expected: IResult<_, _, ParserError<'_>> =
+                    take_while((0x09, 0x20..=0x21, 0x23..=0x7E, NON_ASCII))(input);
+                assert_eq!(
+                    take_text((0x09, 0x20..=0x21, 0x23..=0x7E, NON_ASCII), b"\"")(input),
+                    expected,
+                    "{:?}",
+                    input
+                );
+            }
+        }
+    }
 }
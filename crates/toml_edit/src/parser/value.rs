@@ -1,114 +1,163 @@
+use alloc::vec::Vec;
+
 use nom8::branch::alt;
 use nom8::bytes::any;
 use nom8::combinator::fail;
 use nom8::combinator::peek;
 
+use core::cell::RefCell;
+
 use crate::parser::array::array;
-use crate::parser::datetime::date_time;
+use crate::parser::datetime::{self, date_time};
+use crate::parser::errors::{CustomError, WarningKind};
 use crate::parser::inline_table::inline_table;
-use crate::parser::numbers::{float, integer};
+use crate::parser::numbers::{float, integer, integer_in_range};
 use crate::parser::prelude::*;
 use crate::parser::strings::string;
-use crate::parser::trivia::from_utf8_unchecked;
 use crate::repr::{Formatted, Repr};
 use crate::value as v;
-use crate::Value;
+use crate::{OpaqueDatetime, Value};
 
 // val = string / boolean / array / inline-table / date-time / float / integer
 pub(crate) fn value(
     check: RecursionCheck,
 ) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, v::Value, ParserError<'_>> {
+    move |input| warned_value(check).map(|(v, _)| v).parse(input)
+}
+
+// `val`, with the warnings from within arrays and inline tables
+pub(crate) fn warned_value(
+    check: RecursionCheck,
+) -> impl FnMut(Input<'_>) -> IResult<Input<'_>, (v::Value, Vec<WarningKind>), ParserError<'_>> {
     move |input| {
-        dispatch!{peek(any);
-            crate::parser::strings::QUOTATION_MARK |
-            crate::parser::strings::APOSTROPHE => string.map(|s| {
-                v::Value::String(Formatted::new(
-                    s.into_owned()
-                ))
-            }),
-            crate::parser::array::ARRAY_OPEN => array(check).map(v::Value::Array),
-            crate::parser::inline_table::INLINE_TABLE_OPEN => inline_table(check).map(v::Value::InlineTable),
-            // Date/number starts
-            b'+' | b'-' | b'0'..=b'9' => {
-                // Uncommon enough not to be worth optimizing at this time
-                alt((
-                    date_time
-                        .map(v::Value::from),
-                    float
-                        .map(v::Value::from),
-                    integer
-                        .map(v::Value::from),
-                ))
-            },
-            // Report as if they were numbers because its most likely a typo
-            b'_' => {
-                    integer
-                        .map(v::Value::from)
-                .context(Context::Expected(ParserValue::Description("leading digit")))
-            },
-            // Report as if they were numbers because its most likely a typo
-            b'.' =>  {
-                    float
-                        .map(v::Value::from)
-                .context(Context::Expected(ParserValue::Description("leading digit")))
-            },
-            b't' => {
-                crate::parser::numbers::true_.map(v::Value::from)
-                    .context(Context::Expression("string"))
-                    .context(Context::Expected(ParserValue::CharLiteral('"')))
-                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
-            },
-            b'f' => {
-                crate::parser::numbers::false_.map(v::Value::from)
-                    .context(Context::Expression("string"))
-                    .context(Context::Expected(ParserValue::CharLiteral('"')))
-                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
-            },
-            b'i' => {
-                crate::parser::numbers::inf.map(v::Value::from)
-                    .context(Context::Expression("string"))
-                    .context(Context::Expected(ParserValue::CharLiteral('"')))
-                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
-            },
-            b'n' => {
-                crate::parser::numbers::nan.map(v::Value::from)
-                    .context(Context::Expression("string"))
-                    .context(Context::Expected(ParserValue::CharLiteral('"')))
-                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
-            },
-            _ => {
-                fail
-                    .context(Context::Expression("string"))
-                    .context(Context::Expected(ParserValue::CharLiteral('"')))
-                    .context(Context::Expected(ParserValue::CharLiteral('\'')))
-            },
-    }
+        let warnings = RefCell::new(Vec::new());
+        let nested = &warnings;
+        dispatch! {peek(any);
+                crate::parser::strings::QUOTATION_MARK |
+                crate::parser::strings::APOSTROPHE => string.map_res(|s| {
+                    check.check_string_len(s.len())?;
+                    Ok::<_, CustomError>(v::Value::String(Formatted::new(
+                        s.into_owned()
+                    )))
+                }),
+                crate::parser::array::ARRAY_OPEN => array(check).map(|(a, w)| {
+                    nested.borrow_mut().extend(w);
+                    v::Value::Array(a)
+                }),
+                crate::parser::inline_table::INLINE_TABLE_OPEN => inline_table(check).map(|(t, w)| {
+                    nested.borrow_mut().extend(w);
+                    v::Value::InlineTable(t)
+                }),
+                // Date/number starts
+                b'+' | b'-' | b'0'..=b'9' => {
+                    // Uncommon enough not to be worth optimizing at this time
+                    alt((
+                        date_time
+                            .with_recognized()
+                            .map(|(dt, raw)| datetime_value(dt, raw, check)),
+                        float
+                            .map(v::Value::from),
+                        integer_in_range(check.integer_range())
+                            .map(v::Value::from_i128),
+                    ))
+                },
+                // Report as if they were numbers because its most likely a typo
+                b'_' => {
+                        integer
+                            .map(v::Value::from)
+                    .context(Context::Expected(ParserValue::Description("leading digit")))
+                },
+                // Report as if they were numbers because its most likely a typo
+                b'.' =>  {
+                        float
+                            .map(v::Value::from)
+                    .context(Context::Expected(ParserValue::Description("leading digit")))
+                },
+                b't' => {
+                    crate::parser::numbers::true_.map(v::Value::from)
+                        .context(Context::Expression("string"))
+                        .context(Context::Expected(ParserValue::CharLiteral('"')))
+                        .context(Context::Expected(ParserValue::CharLiteral('\'')))
+                },
+                b'f' => {
+                    crate::parser::numbers::false_.map(v::Value::from)
+                        .context(Context::Expression("string"))
+                        .context(Context::Expected(ParserValue::CharLiteral('"')))
+                        .context(Context::Expected(ParserValue::CharLiteral('\'')))
+                },
+                b'i' => {
+                    crate::parser::numbers::inf.map(v::Value::from)
+                        .context(Context::Expression("string"))
+                        .context(Context::Expected(ParserValue::CharLiteral('"')))
+                        .context(Context::Expected(ParserValue::CharLiteral('\'')))
+                },
+                b'n' => {
+                    crate::parser::numbers::nan.map(v::Value::from)
+                        .context(Context::Expression("string"))
+                        .context(Context::Expected(ParserValue::CharLiteral('"')))
+                        .context(Context::Expected(ParserValue::CharLiteral('\'')))
+                },
+                _ => {
+                    fail
+                        .context(Context::Expression("string"))
+                        .context(Context::Expected(ParserValue::CharLiteral('"')))
+                        .context(Context::Expected(ParserValue::CharLiteral('\'')))
+                },
+        }
         .with_recognized()
         .map_res(|(value, raw)| apply_raw(value, raw))
+        .map(|value| (value, nested.take()))
         .parse(input)
     }
 }
 
-fn apply_raw(mut val: Value, raw: &[u8]) -> Result<Value, std::str::Utf8Error> {
+// Datetimes are only interpreted with the `datetime` feature, and without
+// `ParseOptions::opaque_datetimes`
+#[cfg(feature = "datetime")]
+fn datetime_value(datetime: datetime::Datetime, raw: &[u8], check: RecursionCheck) -> v::Value {
+    if check.opaque_datetimes() {
+        let raw = from_utf8_unchecked!(raw, "`date_time()` filters out non-ASCII");
+        v::Value::from(OpaqueDatetime::new_unchecked(raw))
+    } else {
+        v::Value::from(datetime)
+    }
+}
+
+#[cfg(not(feature = "datetime"))]
+fn datetime_value(_datetime: datetime::Datetime, raw: &[u8], _check: RecursionCheck) -> v::Value {
+    let raw = from_utf8_unchecked!(raw, "`date_time()` filters out non-ASCII");
+    v::Value::from(OpaqueDatetime::new_unchecked(raw))
+}
+
+fn apply_raw(mut val: Value, raw: &[u8]) -> Result<Value, core::str::Utf8Error> {
     match val {
         Value::String(ref mut f) => {
-            let raw = std::str::from_utf8(raw)?;
+            let raw = core::str::from_utf8(raw)?;
             f.set_repr_unchecked(Repr::new_unchecked(raw));
         }
         Value::Integer(ref mut f) => {
-            let raw = unsafe { from_utf8_unchecked(raw, "`integer()` filters out non-ASCII") };
+            let raw = from_utf8_unchecked!(raw, "`integer()` filters out non-ASCII");
+            f.set_repr_unchecked(Repr::new_unchecked(raw));
+        }
+        Value::WideInteger(ref mut f) => {
+            let raw = from_utf8_unchecked!(raw, "`integer()` filters out non-ASCII");
             f.set_repr_unchecked(Repr::new_unchecked(raw));
         }
         Value::Float(ref mut f) => {
-            let raw = unsafe { from_utf8_unchecked(raw, "`float()` filters out non-ASCII") };
+            let raw = from_utf8_unchecked!(raw, "`float()` filters out non-ASCII");
             f.set_repr_unchecked(Repr::new_unchecked(raw));
         }
         Value::Boolean(ref mut f) => {
-            let raw = unsafe { from_utf8_unchecked(raw, "`boolean()` filters out non-ASCII") };
+            let raw = from_utf8_unchecked!(raw, "`boolean()` filters out non-ASCII");
             f.set_repr_unchecked(Repr::new_unchecked(raw));
         }
+        #[cfg(feature = "datetime")]
         Value::Datetime(ref mut f) => {
-            let raw = unsafe { from_utf8_unchecked(raw, "`date_time()` filters out non-ASCII") };
+            let raw = from_utf8_unchecked!(raw, "`date_time()` filters out non-ASCII");
+            f.set_repr_unchecked(Repr::new_unchecked(raw));
+        }
+        Value::OpaqueDatetime(ref mut f) => {
+            let raw = from_utf8_unchecked!(raw, "`date_time()` filters out non-ASCII");
             f.set_repr_unchecked(Repr::new_unchecked(raw));
         }
         Value::Array(_) | Value::InlineTable(_) => {}
@@ -0,0 +1,293 @@
+//! Looking up and editing a [`Document`] by key expressions, like `a."b.c"`, as the language
+//! bindings do
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::item::expected;
+#[cfg(feature = "datetime")]
+use crate::Datetime;
+use crate::{
+    Array, ArrayOfTables, Decor, Document, InlineTable, Item, Key, Table, TableLike, TomlError,
+    Value,
+};
+
+impl Document {
+    /// Look up the item at the key expression `key`, like `a.b` or `a."b.c"`.
+    ///
+    /// Returns `Ok(None)` if the item doesn't exist, and an error if `key` isn't a valid key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "[a]\n\"b.c\" = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// let item = doc.get_path("a.\"b.c\"").unwrap();
+    /// assert_eq!(item.and_then(|i| i.as_integer()), Some(1));
+    /// assert!(doc.get_path("a.b").unwrap().is_none());
+    /// assert!(doc.get_path("a.").is_err());
+    /// ```
+    pub fn get_path(&self, key: &str) -> Result<Option<&Item>, TomlError> {
+        let path = parse_path(key)?;
+        let mut item = self.as_item();
+        for k in &path {
+            item = match item.get(k.get()) {
+                Some(item) if !item.is_none() => item,
+                _ => return Ok(None),
+            };
+        }
+        Ok(Some(item))
+    }
+
+    /// Look up the item at the key expression `key` for its panicking accessors, which report
+    /// `key` when the item is missing or of another type.
+    ///
+    /// # Panics
+    ///
+    /// If `key` isn't a valid key.  The accessors of the result panic with a message like:
+    ///
+    /// ```text
+    /// expected an integer at `owner.name`, found string: "\"Tom\""
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "[owner]\nname = \"Tom\"\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.expect_path("owner.name").expect_str(), "Tom");
+    /// ```
+    #[track_caller]
+    pub fn expect_path<'a>(&'a self, key: &'a str) -> ItemAt<'a> {
+        let item = match self.get_path(key) {
+            Ok(item) => item,
+            Err(err) => panic!("invalid key expression `{}`: {}", key, err),
+        };
+        ItemAt { item, path: key }
+    }
+
+    /// Set the value at the key expression `key`, creating tables as needed.
+    ///
+    /// When replacing an existing value, its surrounding whitespace and comments are kept.  New
+    /// keys keep the quoting they have in `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[a]\nb = 1  # kept\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_path("a.b", 2).unwrap();
+    /// doc.set_path("c.'d.e'", "new").unwrap();
+    /// assert_eq!(doc.to_string(), "[a]\nb = 2  # kept\n\n[c]\n'd.e' = \"new\"\n");
+    ///
+    /// assert!(doc.set_path("a.b.c", 3).is_err());
+    /// assert!(doc.set_path("a", 3).is_err());
+    /// ```
+    pub fn set_path(&mut self, key: &str, value: impl Into<Value>) -> Result<(), PathError> {
+        let path = parse_path(key).map_err(PathError::Parse)?;
+        let (last, parents) = path
+            .split_last()
+            .expect("key expressions have at least one key");
+        let mut value = value.into();
+        value.decor_mut().clear();
+
+        let mut table = self.as_table_mut() as &mut dyn TableLike;
+        for k in parents {
+            let item = table.entry_format(k).or_insert(crate::table());
+            if let Item::Table(t) = item {
+                if t.is_empty() {
+                    t.set_implicit(true);
+                }
+            }
+            table = item
+                .as_table_like_mut()
+                .ok_or_else(|| PathError::NotATable(k.get().to_owned()))?;
+        }
+        match table.get_mut(last.get()) {
+            Some(Item::Value(existing)) => {
+                *value.decor_mut() = existing.decor().clone();
+                *existing = value;
+            }
+            Some(Item::Table(_)) | Some(Item::ArrayOfTables(_)) => {
+                return Err(PathError::IsATable(key.to_owned()));
+            }
+            Some(Item::None) | None => {
+                table.entry_format(last).or_insert(Item::Value(value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the value or table at the key expression `key`.
+    ///
+    /// Returns `Ok(None)` if the item doesn't exist, and an error if `key` isn't a valid key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a.b = 1\nc = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.remove_path("a.b").unwrap().is_some());
+    /// assert!(doc.remove_path("a.b").unwrap().is_none());
+    /// assert_eq!(doc.to_string(), "c = 2\n");
+    /// ```
+    pub fn remove_path(&mut self, key: &str) -> Result<Option<Item>, TomlError> {
+        let path = parse_path(key)?;
+        let (last, parents) = path
+            .split_last()
+            .expect("key expressions have at least one key");
+        let mut item = self.as_item_mut();
+        for k in parents {
+            item = match item.get_mut(k.get()) {
+                Some(item) => item,
+                None => return Ok(None),
+            };
+        }
+        Ok(item
+            .as_table_like_mut()
+            .and_then(|t| t.remove(last.get()))
+            .filter(|removed| !removed.is_none()))
+    }
+}
+
+/// An item looked up by [`Document::expect_path`], whose accessors panic with its path
+#[derive(Debug, Clone, Copy)]
+pub struct ItemAt<'a> {
+    item: Option<&'a Item>,
+    path: &'a str,
+}
+
+impl<'a> ItemAt<'a> {
+    /// The item, if it exists.
+    pub fn item(&self) -> Option<&'a Item> {
+        self.item
+    }
+
+    /// The key expression the item was looked up by.
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// Casts the item to value, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_value(&self) -> &'a Value {
+        match self.item.and_then(Item::as_value) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a value"),
+        }
+    }
+
+    /// Casts the item to table, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_table(&self) -> &'a Table {
+        match self.item.and_then(Item::as_table) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a table"),
+        }
+    }
+
+    /// Casts the item to array of tables, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_array_of_tables(&self) -> &'a ArrayOfTables {
+        match self.item.and_then(Item::as_array_of_tables) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "an array of tables"),
+        }
+    }
+
+    /// Casts the item to integer, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_integer(&self) -> i64 {
+        match self.item.and_then(Item::as_integer) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "an integer"),
+        }
+    }
+
+    /// Casts the item to float, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_float(&self) -> f64 {
+        match self.item.and_then(Item::as_float) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a float"),
+        }
+    }
+
+    /// Casts the item to boolean, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_bool(&self) -> bool {
+        match self.item.and_then(Item::as_bool) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a boolean"),
+        }
+    }
+
+    /// Casts the item to string, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_str(&self) -> &'a str {
+        match self.item.and_then(Item::as_str) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a string"),
+        }
+    }
+
+    /// Casts the item to datetime, panicking if it is not one.
+    #[cfg(feature = "datetime")]
+    #[track_caller]
+    pub fn expect_datetime(&self) -> &'a Datetime {
+        match self.item.and_then(Item::as_datetime) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "a datetime"),
+        }
+    }
+
+    /// Casts the item to array, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_array(&self) -> &'a Array {
+        match self.item.and_then(Item::as_array) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "an array"),
+        }
+    }
+
+    /// Casts the item to inline table, panicking if it is not one.
+    #[track_caller]
+    pub fn expect_inline_table(&self) -> &'a InlineTable {
+        match self.item.and_then(Item::as_inline_table) {
+            Some(v) => v,
+            None => expected(self.item, Some(self.path), "an inline table"),
+        }
+    }
+}
+
+/// Why [`Document::set_path`] couldn't set a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathError {
+    /// The key expression isn't a valid key
+    Parse(TomlError),
+    /// A key on the way to the value refers to a value rather than a table
+    NotATable(String),
+    /// The key expression refers to a table rather than a value
+    IsATable(String),
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PathError::Parse(err) => err.fmt(f),
+            PathError::NotATable(key) => write!(f, "`{}` is not a table", key),
+            PathError::IsATable(key) => write!(f, "`{}` is a table", key),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError {}
+
+// Keeps the quoting of keys but uses the default whitespace around them
+pub(crate) fn parse_path(key: &str) -> Result<Vec<Key>, TomlError> {
+    let path = Key::parse(key)?;
+    Ok(path
+        .into_iter()
+        .map(|k| k.with_decor(Decor::default()))
+        .collect())
+}
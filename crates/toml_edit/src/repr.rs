@@ -1,6 +1,10 @@
-use std::borrow::Cow;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 
-use crate::InternalString;
+use crate::format::own_line_comments;
+use crate::{EncodingVersion, InternalString};
 
 /// A value together with its `to_string` representation,
 /// including surrounding it whitespaces and comments.
@@ -46,6 +50,13 @@ where
             .unwrap_or_else(|| Cow::Owned(self.value.to_repr()))
     }
 
+    /// Returns the raw representation, if available.
+    ///
+    /// For parsed values, this is exactly how the value appeared in the source.
+    pub fn as_repr(&self) -> Option<&Repr> {
+        self.repr.as_ref()
+    }
+
     /// Returns the surrounding whitespace
     pub fn decor_mut(&mut self) -> &mut Decor {
         &mut self.decor
@@ -58,15 +69,76 @@ where
 
     /// Auto formats the value.
     pub fn fmt(&mut self) {
-        self.repr = Some(self.value.to_repr());
+        let repr = match &self.repr {
+            Some(repr) => self.value.to_repr_like(repr),
+            None => self.value.to_repr(),
+        };
+        self.repr = Some(repr);
+    }
+}
+
+#[cfg(feature = "datetime")]
+impl Formatted<crate::Datetime> {
+    /// Returns the digits of the fractional seconds, as written in the representation.
+    ///
+    /// Digits past nanoseconds are truncated from the value but kept in the representation, so
+    /// a parsed `17:45:00.123456789012345` is written back unchanged, including after
+    /// [`Formatted::fmt`], as long as the nanoseconds stay the same.  Returns `None` without a
+    /// representation or fractional seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut value = "17:45:00.123456789012345".parse::<toml_edit::Value>().unwrap();
+    /// value.fmt_recursive();
+    /// assert_eq!(value.to_string(), "17:45:00.123456789012345");
+    /// if let toml_edit::Value::Datetime(time) = &value {
+    ///     assert_eq!(time.value().time.unwrap().nanosecond, 123_456_789);
+    ///     assert_eq!(time.secfrac(), Some("123456789012345"));
+    /// }
+    /// ```
+    pub fn secfrac(&self) -> Option<&str> {
+        self.repr
+            .as_ref()
+            .and_then(|repr| crate::encode::datetime_secfrac(repr.as_raw()))
+    }
+}
+
+impl Formatted<f64> {
+    /// Replaces the value, writing it in the same notation as the current representation.
+    ///
+    /// An exponent, its case and explicit `+` signs, a fractional part, and `_` separators
+    /// between digits are carried over, so `1e200` becomes `2.5e200` rather than a long run of
+    /// digits.  Without a representation to follow, or when either value is not finite, the
+    /// value is auto formatted.  The decor is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "big = 1E+200\nsmall = 1_000.5\n".parse::<toml_edit::Document>().unwrap();
+    /// for (_, item) in doc.iter_mut() {
+    ///     if let Some(toml_edit::Value::Float(f)) = item.as_value_mut() {
+    ///         let value = *f.value() * 2.0;
+    ///         f.set_value_preserving_repr_style(value);
+    ///     }
+    /// }
+    /// assert_eq!(doc.to_string(), "big = 2E+200\nsmall = 2_001.0\n");
+    /// ```
+    pub fn set_value_preserving_repr_style(&mut self, value: f64) {
+        let repr = match &self.repr {
+            Some(repr) => crate::encode::to_f64_repr_like(value, repr.as_raw()),
+            None => value.to_repr(),
+        };
+        self.value = value;
+        self.repr = Some(repr);
     }
 }
 
-impl<T> std::fmt::Display for Formatted<T>
+impl<T> core::fmt::Display for Formatted<T>
 where
     T: ValueRepr,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, ("", ""))
     }
 }
@@ -74,6 +146,18 @@ where
 pub trait ValueRepr: crate::private::Sealed {
     /// The TOML representation of the value
     fn to_repr(&self) -> Repr;
+
+    /// The TOML representation of the value in the default format of `version`
+    fn to_repr_with(&self, version: EncodingVersion) -> Repr {
+        let _ = version;
+        self.to_repr()
+    }
+
+    /// The TOML representation of the value, keeping what the value can't hold from `like`
+    fn to_repr_like(&self, like: &Repr) -> Repr {
+        let _ = like;
+        self.to_repr()
+    }
 }
 
 /// TOML-encoded value
@@ -95,8 +179,8 @@ impl Repr {
     }
 }
 
-impl std::fmt::Display for Repr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Repr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.as_raw().fmt(f)
     }
 }
@@ -144,4 +228,118 @@ impl Decor {
     pub fn set_suffix(&mut self, suffix: impl Into<InternalString>) {
         self.suffix = Some(suffix.into());
     }
+
+    /// Set the prefix, checking that it is only whitespace, newlines, and comments.
+    ///
+    /// Whether a newline or comment is allowed also depends on where the decor is written, see
+    /// [`Document::audit_decor`][crate::Document::audit_decor].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut decor = toml_edit::Decor::default();
+    /// assert!(decor.try_set_prefix("\n# note\n").is_ok());
+    /// assert_eq!(decor.prefix(), Some("\n# note\n"));
+    ///
+    /// assert!(decor.try_set_prefix("note").is_err());
+    /// assert_eq!(decor.prefix(), Some("\n# note\n"));
+    /// ```
+    pub fn try_set_prefix(
+        &mut self,
+        prefix: impl Into<InternalString>,
+    ) -> Result<(), crate::TomlError> {
+        let prefix = prefix.into();
+        crate::parser::parse_decor(&prefix)?;
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+
+    /// Set the suffix, checking that it is only whitespace, newlines, and comments.
+    ///
+    /// See [`Decor::try_set_prefix`].
+    pub fn try_set_suffix(
+        &mut self,
+        suffix: impl Into<InternalString>,
+    ) -> Result<(), crate::TomlError> {
+        let suffix = suffix.into();
+        crate::parser::parse_decor(&suffix)?;
+        self.suffix = Some(suffix);
+        Ok(())
+    }
+
+    // The comment lines directly above the decorated line, without their `#` and the space after
+    // it
+    pub(crate) fn comment(&self) -> Option<String> {
+        let (_, lines, _) = split_prefix(self.prefix().unwrap_or_default());
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    // Replaces the comment lines directly above the decorated line, keeping whatever comes before
+    // them and the indentation of the line
+    pub(crate) fn set_comment(&mut self, comment: &str, default_prefix: &str) {
+        let (head, _, indent) = split_prefix(self.prefix().unwrap_or(default_prefix));
+        let mut prefix = head.to_owned();
+        for line in comment.lines() {
+            prefix.push_str(indent);
+            prefix.push('#');
+            if !line.is_empty() {
+                prefix.push(' ');
+                prefix.push_str(line);
+            }
+            prefix.push('\n');
+        }
+        prefix.push_str(indent);
+        self.set_prefix(prefix);
+    }
+
+    // The comment in the suffix, ending the line, without its `#` and the space after it
+    pub(crate) fn trailing_comment(&self) -> Option<String> {
+        own_line_comments(self.suffix().unwrap_or_default())
+            .next()
+            .map(|comment| strip_comment_marker(comment).to_owned())
+    }
+
+    // Replaces the comment in the suffix with the first line of `comment`, keeping the
+    // whitespace before it
+    pub(crate) fn set_trailing_comment(&mut self, comment: &str) {
+        let raw = self.suffix().unwrap_or_default();
+        let suffix = match comment.lines().next() {
+            Some(line) => {
+                let ws = raw
+                    .find('#')
+                    .map(|i| &raw[..i])
+                    .filter(|ws| !ws.is_empty())
+                    .unwrap_or(" ");
+                format!("{}# {}", ws, line).trim_end().to_owned()
+            }
+            None => String::new(),
+        };
+        self.set_suffix(suffix);
+    }
+}
+
+// Splits a prefix into what comes before the comment lines directly above the decorated line,
+// those comments, and the indentation of the line
+fn split_prefix(raw: &str) -> (&str, Vec<&str>, &str) {
+    let (mut head, indent) = match raw.rfind('\n') {
+        Some(i) => raw.split_at(i + 1),
+        None => ("", raw),
+    };
+    let mut comments = Vec::new();
+    while let Some(lines) = head.strip_suffix('\n') {
+        let start = lines.rfind('\n').map_or(0, |i| i + 1);
+        let line = lines[start..].trim();
+        if !line.starts_with('#') {
+            break;
+        }
+        comments.push(strip_comment_marker(line));
+        head = &head[..start];
+    }
+    comments.reverse();
+    (head, comments, indent)
+}
+
+fn strip_comment_marker(comment: &str) -> &str {
+    let comment = &comment[1..];
+    comment.strip_prefix(' ').unwrap_or(comment)
 }
@@ -0,0 +1,260 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "regex")]
+use core::ops::Range;
+
+use crate::visit::{visit_table_like_kv, Visit};
+use crate::visit_mut::{visit_table_like_kv_mut, VisitMut};
+use crate::{Document, Item, Key, KeyMut, TomlError, Value};
+
+impl Document {
+    /// Find the items, at any depth, for which `predicate` returns `true`.
+    ///
+    /// `predicate` is given the keys leading to each item, including its own, for every key/value
+    /// pair in the document, including subtables and the pairs of inline tables.  Elements of
+    /// arrays and arrays of tables are searched under the array's path.  Matches are returned in
+    /// key order along with their paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "[dependencies]\nserde = '1'\nregex = { version = '1', optional = true }\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let optional = doc.find(|_, item| item.get("optional").and_then(|o| o.as_bool()) == Some(true));
+    /// assert_eq!(optional.len(), 1);
+    /// assert_eq!(optional[0].0, ["dependencies", "regex"]);
+    ///
+    /// let versions = doc.find(|path, _| path.last() == Some(&"version"));
+    /// assert_eq!(versions[0].1.as_str(), Some("1"));
+    /// ```
+    pub fn find<F>(&self, predicate: F) -> Vec<(Vec<&str>, &Item)>
+    where
+        F: FnMut(&[&str], &Item) -> bool,
+    {
+        let mut finder = Finder {
+            path: Vec::new(),
+            predicate,
+            found: Vec::new(),
+        };
+        finder.visit_document(self);
+        finder.found
+    }
+
+    /// Replace every value whose path matches `pattern` with the result of `f`, keeping the
+    /// value's decor, including its comments.
+    ///
+    /// `pattern` is a dotted key where a `*` matches any one key, like `credentials.*`.  It must
+    /// match the whole path to a value, following the same paths as [`Document::find`], so values
+    /// are found within inline tables and arrays of tables.  Arrays are passed to `f` whole.
+    ///
+    /// Returns the number of values replaced, or an error if `pattern` is not a valid key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "[credentials]\nuser = 'me'  # who\ntoken = 'hunter2'\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let redacted = doc
+    ///     .map_values_at("credentials.*", |_| toml_edit::Value::from("***"))
+    ///     .unwrap();
+    /// assert_eq!(redacted, 2);
+    /// assert_eq!(doc.to_string(), "[credentials]\nuser = \"***\"  # who\ntoken = \"***\"\n");
+    /// ```
+    pub fn map_values_at<F>(&mut self, pattern: &str, f: F) -> Result<usize, TomlError>
+    where
+        F: FnMut(&Value) -> Value,
+    {
+        let pattern = split_pattern(pattern)
+            .into_iter()
+            .map(|segment| match segment.trim() {
+                "*" => Ok(None),
+                segment => segment.parse::<Key>().map(Some),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut mapper = Mapper {
+            pattern,
+            path: Vec::new(),
+            f,
+            count: 0,
+        };
+        mapper.visit_document_mut(self);
+        Ok(mapper.count)
+    }
+
+    /// Find the matches of `regex` within string values, at any depth.
+    ///
+    /// Each non-overlapping match is returned, in key order, with the path to its string as for
+    /// [`Document::find`].  Keys themselves are not searched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "[package]\nrepository = 'http://example.com'\n\n[urls]\nmirrors = ['https://a', 'http://b']\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let insecure = regex::Regex::new("http://[a-z.]+").unwrap();
+    /// let matches = doc.find_string_values(&insecure);
+    /// assert_eq!(matches.len(), 2);
+    /// assert_eq!(matches[0].path(), ["package", "repository"]);
+    /// assert_eq!(matches[0].as_str(), "http://example.com");
+    /// assert_eq!(matches[1].path(), ["urls", "mirrors"]);
+    /// assert_eq!(matches[1].value(), "http://b");
+    /// assert_eq!(matches[1].span(), 0..8);
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn find_string_values(&self, regex: &regex::Regex) -> Vec<StringMatch<'_>> {
+        let mut finder = StringFinder {
+            path: Vec::new(),
+            regex,
+            matches: Vec::new(),
+        };
+        finder.visit_document(self);
+        finder.matches
+    }
+}
+
+/// A match of [`Document::find_string_values`]
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringMatch<'d> {
+    path: Vec<&'d str>,
+    value: &'d str,
+    span: Range<usize>,
+}
+
+#[cfg(feature = "regex")]
+impl<'d> StringMatch<'d> {
+    /// The keys leading to the string.
+    pub fn path(&self) -> &[&'d str] {
+        &self.path
+    }
+
+    /// The string containing the match.
+    pub fn value(&self) -> &'d str {
+        self.value
+    }
+
+    /// The byte range of the match within the string.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The matched text.
+    pub fn as_str(&self) -> &'d str {
+        &self.value[self.span.clone()]
+    }
+}
+
+struct Finder<'d, F> {
+    path: Vec<&'d str>,
+    predicate: F,
+    found: Vec<(Vec<&'d str>, &'d Item)>,
+}
+
+impl<'d, F> Visit<'d> for Finder<'d, F>
+where
+    F: FnMut(&[&str], &Item) -> bool,
+{
+    fn visit_table_like_kv(&mut self, key: &'d str, node: &'d Item) {
+        self.path.push(key);
+        if (self.predicate)(&self.path, node) {
+            self.found.push((self.path.clone(), node));
+        }
+        visit_table_like_kv(self, key, node);
+        self.path.pop();
+    }
+}
+
+struct Mapper<F> {
+    // `None` matches any key
+    pattern: Vec<Option<Key>>,
+    path: Vec<String>,
+    f: F,
+    count: usize,
+}
+
+impl<F> Mapper<F> {
+    fn is_match(&self) -> bool {
+        self.pattern.len() == self.path.len()
+            && self
+                .pattern
+                .iter()
+                .zip(self.path.iter())
+                .all(|(pattern, key)| pattern.as_ref().map_or(true, |p| p.get() == key))
+    }
+}
+
+impl<F> VisitMut for Mapper<F>
+where
+    F: FnMut(&Value) -> Value,
+{
+    fn visit_table_like_kv_mut(&mut self, key: KeyMut<'_>, node: &mut Item) {
+        self.path.push(key.get().to_owned());
+        match node {
+            Item::Value(value) if self.is_match() => {
+                let decor = value.decor().clone();
+                *value = (self.f)(value);
+                *value.decor_mut() = decor;
+                self.count += 1;
+            }
+            _ if self.path.len() < self.pattern.len() => visit_table_like_kv_mut(self, key, node),
+            _ => {}
+        }
+        self.path.pop();
+    }
+}
+
+// Splits a dotted key on the dots outside of quotes
+fn split_pattern(pattern: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in pattern.char_indices() {
+        if core::mem::take(&mut escaped) {
+            continue;
+        }
+        match (c, quote) {
+            ('"', None) | ('\'', None) => quote = Some(c),
+            ('\\', Some('"')) => escaped = true,
+            (c, Some(q)) if c == q => quote = None,
+            ('.', None) => {
+                segments.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&pattern[start..]);
+    segments
+}
+
+#[cfg(feature = "regex")]
+struct StringFinder<'d, 'r> {
+    path: Vec<&'d str>,
+    regex: &'r regex::Regex,
+    matches: Vec<StringMatch<'d>>,
+}
+
+#[cfg(feature = "regex")]
+impl<'d, 'r> Visit<'d> for StringFinder<'d, 'r> {
+    fn visit_table_like_kv(&mut self, key: &'d str, node: &'d Item) {
+        self.path.push(key);
+        visit_table_like_kv(self, key, node);
+        self.path.pop();
+    }
+
+    fn visit_string(&mut self, node: &'d crate::Formatted<String>) {
+        let value = node.value().as_str();
+        for m in self.regex.find_iter(value) {
+            self.matches.push(StringMatch {
+                path: self.path.clone(),
+                value,
+                span: m.range(),
+            });
+        }
+    }
+}
@@ -3,16 +3,21 @@ use super::Error;
 #[doc(hidden)]
 pub struct SerializeItemArray {
     values: Vec<crate::Item>,
+    ser: super::ItemSerializer,
 }
 
 impl SerializeItemArray {
-    pub(crate) fn new() -> Self {
-        Self { values: Vec::new() }
+    pub(crate) fn new(ser: super::ItemSerializer) -> Self {
+        Self {
+            values: Vec::new(),
+            ser,
+        }
     }
 
-    pub(crate) fn with_capacity(len: usize) -> Self {
+    pub(crate) fn with_capacity(len: usize, ser: super::ItemSerializer) -> Self {
         Self {
             values: Vec::with_capacity(len),
+            ser,
         }
     }
 }
@@ -25,7 +30,7 @@ impl serde::ser::SerializeSeq for SerializeItemArray {
     where
         T: serde::ser::Serialize,
     {
-        let value = value.serialize(super::ItemSerializer {})?;
+        let value = value.serialize(self.ser)?;
         self.values.push(value);
         Ok(())
     }
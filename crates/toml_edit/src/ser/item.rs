@@ -9,14 +9,55 @@ use super::{Error, ErrorKind};
 ///
 /// Currently a serializer always writes its output to an in-memory `String`,
 /// which is passed in when creating the serializer itself.
-#[derive(Default)]
+#[derive(Default, Copy, Clone)]
 #[non_exhaustive]
-pub struct ItemSerializer {}
+pub struct ItemSerializer {
+    wide_integers: bool,
+}
 
 impl ItemSerializer {
     /// Creates a new serializer generate a TOML document.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Serialize integers outside of `i64` rather than failing, see [`Value::from_u64`] and
+    /// [`Value::from_i128`].
+    ///
+    /// TOML integers are signed 64-bit, so reading the output back requires
+    /// [`ParseOptions::lenient_prefixed_integers`], or
+    /// [`ParseOptions::extended_integers`] for integers outside of `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::ser::Serializer;
+    /// use toml_edit::ParseOptions;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Ids {
+    ///     large: u64,
+    /// }
+    ///
+    /// let ids = Ids { large: u64::MAX };
+    /// assert!(toml_edit::ser::to_string(&ids).is_err());
+    ///
+    /// let serializer = Serializer::new().wide_integers(true);
+    /// let doc = toml_edit::ser::to_document_with(&ids, serializer).unwrap();
+    /// assert_eq!(doc.to_string(), "large = 0xffffffffffffffff\n");
+    ///
+    /// let options = ParseOptions::new().lenient_prefixed_integers(true);
+    /// let doc = toml_edit::Document::parse_with(&doc.to_string(), options).unwrap();
+    /// assert_eq!(doc["large"].as_u64(), Some(u64::MAX));
+    /// ```
+    ///
+    /// [`Value::from_u64`]: crate::Value::from_u64
+    /// [`Value::from_i128`]: crate::Value::from_i128
+    /// [`ParseOptions::extended_integers`]: crate::ParseOptions::extended_integers
+    /// [`ParseOptions::lenient_prefixed_integers`]: crate::ParseOptions::lenient_prefixed_integers
+    pub fn wide_integers(mut self, yes: bool) -> Self {
+        self.wide_integers = yes;
+        self
     }
 }
 
@@ -64,11 +105,40 @@ impl serde::ser::Serializer for ItemSerializer {
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        self.serialize_i64(v as i64)
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) if self.wide_integers => Ok(crate::Item::Value(crate::Value::from_u64(v))),
+            Err(_) => Err(ErrorKind::OutOfRange("u64").into()),
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        match (i64::try_from(v), u64::try_from(v)) {
+            (Ok(v), _) => self.serialize_i64(v),
+            (_, Ok(v)) => self.serialize_u64(v),
+            _ if self.wide_integers => Ok(crate::Item::Value(crate::Value::from_i128(v))),
+            _ => Err(ErrorKind::OutOfRange("i128").into()),
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        match i128::try_from(v) {
+            Ok(v) => self.serialize_i128(v),
+            Err(_) => Err(ErrorKind::OutOfRange("u128").into()),
+        }
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        self.serialize_f64(v as f64)
+        // Widen through the shortest decimal so `0.1_f32` is written as `0.1` rather than
+        // `0.10000000149011612`, its exact value
+        let v = if v.is_finite() {
+            v.to_string()
+                .parse::<f64>()
+                .expect("`f32` displays as a valid `f64`")
+        } else {
+            v as f64
+        };
+        self.serialize_f64(v)
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
@@ -143,8 +213,8 @@ impl serde::ser::Serializer for ItemSerializer {
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         let serializer = match len {
-            Some(len) => super::SerializeItemArray::with_capacity(len),
-            None => super::SerializeItemArray::new(),
+            Some(len) => super::SerializeItemArray::with_capacity(len, self),
+            None => super::SerializeItemArray::new(self),
         };
         Ok(serializer)
     }
@@ -173,8 +243,8 @@ impl serde::ser::Serializer for ItemSerializer {
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         let serializer = match len {
-            Some(len) => super::SerializeItemTable::with_capacity(len),
-            None => super::SerializeItemTable::new(),
+            Some(len) => super::SerializeItemTable::with_capacity(len, self),
+            None => super::SerializeItemTable::new(self),
         };
         Ok(serializer)
     }
@@ -68,10 +68,11 @@ impl From<ErrorKind> for Error {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum ErrorKind {
+pub(crate) enum ErrorKind {
     UnsupportedType,
     UnsupportedNone,
     KeyNotString,
+    OutOfRange(&'static str),
     Custom(String),
 }
 
@@ -81,6 +82,7 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::UnsupportedType => "unsupported Rust type".fmt(formatter),
             ErrorKind::UnsupportedNone => "unsupported None value".fmt(formatter),
             ErrorKind::KeyNotString => "map key was not a string".fmt(formatter),
+            ErrorKind::OutOfRange(ty) => write!(formatter, "out-of-range value for {} type", ty),
             ErrorKind::Custom(s) => s.fmt(formatter),
         }
     }
@@ -161,7 +163,20 @@ pub fn to_document<T: ?Sized>(value: &T) -> Result<crate::Document, Error>
 where
     T: serde::ser::Serialize,
 {
-    let item = to_item(value)?;
+    to_document_with(value, Serializer::new())
+}
+
+/// Serialize the given data structure into a TOML document, customizing the serializer
+///
+/// See [`Serializer::wide_integers`].
+pub fn to_document_with<T: ?Sized>(
+    value: &T,
+    serializer: Serializer,
+) -> Result<crate::Document, Error>
+where
+    T: serde::ser::Serialize,
+{
+    let item = value.serialize(serializer)?;
     let root = item.into_table().map_err(|_| ErrorKind::UnsupportedType)?;
     Ok(root.into())
 }
@@ -169,6 +184,32 @@ where
 /// Serialize the given data structure into a TOML data structure
 ///
 /// This would allow custom formatting to be applied, mixing with format preserving edits, etc.
+///
+/// Maps and structs are produced as inline tables; use [`Item::into_table`][crate::Item::into_table]
+/// to splice them in as a standard table.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Dependency {
+///     version: String,
+///     optional: bool,
+/// }
+///
+/// let mut doc = "[dependencies] # keep me\n".parse::<toml_edit::Document>().unwrap();
+/// let dep = Dependency {
+///     version: "1.0".to_owned(),
+///     optional: true,
+/// };
+/// doc["dependencies"]["serde"] = toml_edit::ser::to_item(&dep).unwrap();
+/// assert_eq!(
+///     doc.to_string(),
+///     "[dependencies] # keep me\nserde = { version = \"1.0\", optional = true }\n"
+/// );
+/// ```
 pub fn to_item<T: ?Sized>(value: &T) -> Result<crate::Item, Error>
 where
     T: serde::ser::Serialize,
@@ -177,4 +218,17 @@ where
     Ok(item)
 }
 
+/// Serialize the given data structure into a TOML value
+///
+/// Maps and structs are produced as inline tables, ready to be inserted into an [`Array`][crate::Array]
+/// or [`InlineTable`][crate::InlineTable].
+pub fn to_value<T: ?Sized>(value: &T) -> Result<crate::Value, Error>
+where
+    T: serde::ser::Serialize,
+{
+    let item = to_item(value)?;
+    item.into_value()
+        .map_err(|_| ErrorKind::UnsupportedType.into())
+}
+
 pub use item::ItemSerializer as Serializer;
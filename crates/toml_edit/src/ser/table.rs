@@ -6,15 +6,15 @@ pub struct SerializeItemTable {
 }
 
 impl SerializeItemTable {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(ser: super::ItemSerializer) -> Self {
         Self {
-            inner: SerializeKeyValuePairs::new(),
+            inner: SerializeKeyValuePairs::new(ser),
         }
     }
 
-    pub(crate) fn with_capacity(len: usize) -> Self {
+    pub(crate) fn with_capacity(len: usize, ser: super::ItemSerializer) -> Self {
         Self {
-            inner: SerializeKeyValuePairs::with_capacity(len),
+            inner: SerializeKeyValuePairs::with_capacity(len, ser),
         }
     }
 }
@@ -73,18 +73,20 @@ impl serde::ser::SerializeStruct for SerializeItemTable {
 struct SerializeKeyValuePairs {
     items: crate::table::KeyValuePairs,
     key: Option<crate::InternalString>,
+    ser: super::ItemSerializer,
 }
 
 impl SerializeKeyValuePairs {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(ser: super::ItemSerializer) -> Self {
         Self {
             items: Default::default(),
             key: Default::default(),
+            ser,
         }
     }
 
-    pub(crate) fn with_capacity(len: usize) -> Self {
-        let mut s = Self::new();
+    pub(crate) fn with_capacity(len: usize, ser: super::ItemSerializer) -> Self {
+        let mut s = Self::new(ser);
         s.items.reserve(len);
         s
     }
@@ -107,7 +109,7 @@ impl serde::ser::SerializeMap for SerializeKeyValuePairs {
     where
         T: serde::ser::Serialize,
     {
-        let res = value.serialize(super::ItemSerializer {});
+        let res = value.serialize(self.ser);
         let item = match res {
             Ok(item) => item,
             Err(e) => {
@@ -142,7 +144,7 @@ impl serde::ser::SerializeStruct for SerializeKeyValuePairs {
     where
         T: serde::ser::Serialize,
     {
-        let res = value.serialize(super::ItemSerializer {});
+        let res = value.serialize(self.ser);
         let item = match res {
             Ok(item) => item,
             Err(e) => {
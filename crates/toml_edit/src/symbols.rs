@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::trivia::Tracker;
+use crate::Document;
+
+impl Document {
+    /// Returns an iterator over the keys and table headers of the document, in the order they
+    /// are written.
+    ///
+    /// Each part of a dotted key is its own symbol, including keys within inline tables, while a
+    /// table header is one symbol for the whole `[header]`.  Spans are byte ranges in the
+    /// document's [`Display`][std::fmt::Display] output, as with [`Document::trivia`], and are
+    /// found in one walk over the document.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::SymbolKind;
+    ///
+    /// let input = "[package]\nname = 'demo'\nmetadata.docs = { all = true }\n\n[[bin]]\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let symbols = doc
+    ///     .symbols()
+    ///     .map(|s| (s.path().join("."), s.kind(), &input[s.span()]))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     symbols,
+    ///     [
+    ///         ("package".to_owned(), SymbolKind::Table, "[package]"),
+    ///         ("package.name".to_owned(), SymbolKind::Key, "name"),
+    ///         ("package.metadata".to_owned(), SymbolKind::Key, "metadata"),
+    ///         ("package.metadata.docs".to_owned(), SymbolKind::Key, "docs"),
+    ///         ("package.metadata.docs.all".to_owned(), SymbolKind::Key, "all"),
+    ///         ("bin".to_owned(), SymbolKind::ArrayOfTables, "[[bin]]"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol<'_>> + '_ {
+        Tracker::track(self, false).symbols.into_iter()
+    }
+}
+
+/// A key or table header, see [`Document::symbols`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol<'d> {
+    pub(crate) path: Vec<&'d str>,
+    pub(crate) kind: SymbolKind,
+    pub(crate) span: Range<usize>,
+}
+
+impl<'d> Symbol<'d> {
+    /// The keys leading to the symbol, ending with the key itself.
+    ///
+    /// Keys within an array's inline tables share the array's path.
+    pub fn path(&self) -> &[&'d str] {
+        &self.path
+    }
+
+    /// What the symbol is.
+    pub fn kind(&self) -> SymbolKind {
+        self.kind
+    }
+
+    /// The byte range of the key, or of the header including its brackets, in the document's
+    /// output.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// What a [`Symbol`] is
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// A `[table]` header
+    Table,
+    /// An `[[array.of.tables]]` header
+    ArrayOfTables,
+    /// A key of a key/value pair, or one part of a dotted key
+    Key,
+}
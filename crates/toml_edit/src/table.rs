@@ -1,13 +1,21 @@
-use std::iter::FromIterator;
-
-use indexmap::map::IndexMap;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
 
+use crate::collections::IndexMap;
 use crate::key::Key;
-use crate::repr::Decor;
+use crate::repr::{Decor, Repr};
 use crate::value::DEFAULT_VALUE_DECOR;
-use crate::{InlineTable, InternalString, Item, KeyMut, Value};
+use crate::{Array, FromItem, InlineTable, InternalString, Item, KeyMut, Value};
 
 /// Type representing a TOML non-inline table
+///
+/// Key/value pairs are kept in insertion order: parsed tables iterate in the order the keys
+/// appear in the document and new keys are appended.  [`Table::cursor_front`] can be used to
+/// rearrange them.  When rendering a [`Document`][crate::Document], values are written in this
+/// order while subtables are written by [`Table::position`].
 #[derive(Clone, Debug, Default)]
 pub struct Table {
     // Comments/spaces before and after the header
@@ -55,6 +63,48 @@ impl Table {
         t.fmt();
         t
     }
+
+    /// Convert to an inline table, along with every table nested within it
+    ///
+    /// Subtables become nested inline tables and arrays of tables become arrays of inline tables,
+    /// while dotted keys stay dotted.  This is the inverse of [`InlineTable::into_table_recursive`].
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "[a]\nb.c = 1\n\n[a.d]\ne = 2\n\n[[a.f]]\ng = 3\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let a = doc.remove("a").unwrap().into_table().unwrap();
+    /// doc["a"] = toml_edit::value(a.into_inline_table_recursive());
+    /// assert_eq!(doc.to_string(), "a = { b.c = 1, d = { e = 2 }, f = [{ g = 3 }] }\n");
+    /// ```
+    pub fn into_inline_table_recursive(self) -> InlineTable {
+        let mut items = self.items;
+        for kv in items.values_mut() {
+            kv.value = match core::mem::take(&mut kv.value) {
+                Item::Table(table) => {
+                    kv.key.decor.clear();
+                    let dotted = table.is_dotted();
+                    let mut table = table.into_inline_table_recursive();
+                    table.set_dotted(dotted);
+                    Item::Value(Value::InlineTable(table))
+                }
+                Item::ArrayOfTables(array) => {
+                    kv.key.decor.clear();
+                    let mut array = array
+                        .into_iter()
+                        .map(Table::into_inline_table_recursive)
+                        .collect::<Array>();
+                    array.fmt();
+                    Item::Value(Value::Array(array))
+                }
+                item => item,
+            };
+        }
+        let mut t = InlineTable::with_pairs(items);
+        t.fmt();
+        t
+    }
 }
 
 /// Formatting
@@ -102,6 +152,34 @@ impl Table {
         decorate_table(self);
     }
 
+    /// Auto formats the table, its keys, and everything nested within it.
+    ///
+    /// Unlike [`Table::fmt`], this also resets the representation of keys and values and
+    /// descends into arrays, inline tables, and subtables.  Only this table's subtree is
+    /// touched, so the rest of a document keeps its formatting.  To keep representations and lay
+    /// out the subtree with [`FormatOptions`][crate::FormatOptions], see [`Table::fmt_with`].
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "[a]\nx   =   0x1\n\n[b]\ny   =   [ 1,2 ]\n".parse::<toml_edit::Document>().unwrap();
+    /// doc["b"].as_table_mut().unwrap().fmt_recursive();
+    /// assert_eq!(doc.to_string(), "[a]\nx   =   0x1\n\n[b]\ny = [1, 2]\n");
+    /// ```
+    pub fn fmt_recursive(&mut self) {
+        for kv in self.items.values_mut() {
+            kv.key.fmt();
+            kv.value.fmt_recursive();
+        }
+        decorate_table(self);
+    }
+
+    /// Write the table's key/value pairs, as its [`Display`][std::fmt::Display] does, to
+    /// `writer` without first rendering them to a `String`.
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
     /// Sorts Key/Value Pairs of the table.
     ///
     /// Doesn't affect subtables or subarrays.
@@ -124,20 +202,20 @@ impl Table {
     /// values or their combination as needed).
     pub fn sort_values_by<F>(&mut self, mut compare: F)
     where
-        F: FnMut(&Key, &Item, &Key, &Item) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Item, &Key, &Item) -> core::cmp::Ordering,
     {
         self.sort_values_by_internal(&mut compare);
     }
 
     fn sort_values_by_internal<F>(&mut self, compare: &mut F)
     where
-        F: FnMut(&Key, &Item, &Key, &Item) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Item, &Key, &Item) -> core::cmp::Ordering,
     {
         let modified_cmp = |_: &InternalString,
                             val1: &TableKeyValue,
                             _: &InternalString,
                             val2: &TableKeyValue|
-         -> std::cmp::Ordering {
+         -> core::cmp::Ordering {
             compare(&val1.key, &val1.value, &val2.key, &val2.value)
         };
 
@@ -153,6 +231,67 @@ impl Table {
         }
     }
 
+    /// Sorts Key/Value Pairs of the table and of all tables nested within it.
+    ///
+    /// Unlike [`Table::sort_values`], this descends into subtables and arrays of tables, with
+    /// headers reordered to follow their sorted keys.  The elements of arrays of tables keep their
+    /// order, as do inline tables.  Comments and whitespace stay with the key or header they
+    /// decorate.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "b = 1\na = 2\n\n[z]\ny = 1\n\n[c]\n# first\nf = 1\ne = 2\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// doc.sort_keys_recursive();
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "a = 2\nb = 1\n\n[c]\ne = 2\n# first\nf = 1\n\n[z]\ny = 1\n"
+    /// );
+    /// ```
+    pub fn sort_keys_recursive(&mut self) {
+        self.sort_values_by_path(|_, k1, _, k2, _| k1.get().cmp(k2.get()));
+    }
+
+    /// Sort Key/Value Pairs of the table and of all tables nested within it using the comparison
+    /// function `compare`.
+    ///
+    /// `compare` also receives the keys leading from this table to the table being sorted, so
+    /// each table can be ordered differently.  Returning [`Ordering::Equal`][std::cmp::Ordering]
+    /// keeps the existing order.  Headers are reordered as for [`Table::sort_keys_recursive`].
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "[package]\nname = 'demo'\nversion = '1.0.0'\n\n[dependencies]\nserde = '1'\nregex = '1'\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// doc.sort_values_by_path(|path, k1, _, k2, _| match path {
+    ///     ["dependencies"] => k1.get().cmp(k2.get()),
+    ///     _ => std::cmp::Ordering::Equal,
+    /// });
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\nname = 'demo'\nversion = '1.0.0'\n\n[dependencies]\nregex = '1'\nserde = '1'\n"
+    /// );
+    /// ```
+    pub fn sort_values_by_path<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&[&str], &Key, &Item, &Key, &Item) -> core::cmp::Ordering,
+    {
+        // Headers are written by position, so the subtree's positions are handed back out in the
+        // sorted order
+        let mut positions = Vec::new();
+        visit_headers_mut(self, &mut |table| positions.extend(table.doc_position));
+        sort_values_by_path(self, &mut Vec::new(), &mut compare);
+        positions.sort_unstable();
+        let mut positions = positions.into_iter();
+        visit_headers_mut(self, &mut |table| {
+            if table.doc_position.is_some() {
+                table.doc_position = positions.next();
+            }
+        });
+    }
+
     /// If a table has no key/value pairs and implicit, it will not be displayed.
     ///
     /// # Examples
@@ -179,6 +318,69 @@ impl Table {
         self.implicit
     }
 
+    /// Makes the table explicit, so its header is written even without key/value pairs.
+    ///
+    /// Unlike `set_implicit(false)`, a table without a position, like one that was only implied
+    /// by the headers of its subtables, is given the position of the first header nested under
+    /// it.  Its header is then written just before its subtables rather than after whichever
+    /// table precedes it in the document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toml_edit::Document;
+    /// let mut doc = "[z]\n[a.b]\n".parse::<Document>().unwrap();
+    /// doc.as_table_mut().sort_values();
+    ///
+    /// let a = doc["a"].as_table_mut().unwrap();
+    /// assert!(a.is_implicit());
+    /// a.set_explicit();
+    /// assert_eq!(doc.to_string(), "[z]\n\n[a]\n[a.b]\n");
+    /// ```
+    pub fn set_explicit(&mut self) {
+        if self.implicit && self.doc_position.is_none() {
+            let mut first = None;
+            visit_headers_mut(self, &mut |table| {
+                first = match (first, table.doc_position) {
+                    (Some(first), Some(position)) => Some(core::cmp::min(first, position)),
+                    (first, position) => first.or(position),
+                };
+            });
+            self.doc_position = first;
+        }
+        self.implicit = false;
+    }
+
+    /// Makes the table and every table nested under it explicit, see [`Table::set_explicit`].
+    ///
+    /// Tables of dotted keys are left alone, as they have no header to write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use toml_edit::Document;
+    /// let mut doc = "[a.b.c]\nd.e = 1\n".parse::<Document>().unwrap();
+    /// doc["a"].as_table_mut().unwrap().make_explicit_recursive();
+    /// assert_eq!(doc.to_string(), "[a]\n\n[a.b]\n[a.b.c]\nd.e = 1\n");
+    /// ```
+    pub fn make_explicit_recursive(&mut self) {
+        if self.is_dotted() {
+            return;
+        }
+        for kv in self.items.values_mut() {
+            match &mut kv.value {
+                Item::Table(table) => table.make_explicit_recursive(),
+                Item::ArrayOfTables(array) => {
+                    for table in array.iter_mut() {
+                        table.make_explicit_recursive();
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.set_explicit();
+    }
+
     /// Change this table's dotted status
     pub fn set_dotted(&mut self, yes: bool) {
         self.dotted = yes;
@@ -213,6 +415,44 @@ impl Table {
         &self.decor
     }
 
+    /// Returns the comment on the lines directly above the table header, without the `# `
+    /// starting each line.
+    pub fn comment(&self) -> Option<String> {
+        self.decor.comment()
+    }
+
+    /// Replaces the comment on the lines above the table header.
+    ///
+    /// Each line of `comment` is written as a `# ` comment line, keeping the blank line and any
+    /// comments before them.  An empty `comment` removes the comment lines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "a = 1\n\n[server] # legacy\nport = 80\n";
+    /// let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let server = doc["server"].as_table_mut().unwrap();
+    /// server.set_comment("Where to listen");
+    /// assert_eq!(server.trailing_comment().as_deref(), Some("legacy"));
+    /// server.set_trailing_comment("");
+    /// assert_eq!(doc.to_string(), "a = 1\n\n# Where to listen\n[server]\nport = 80\n");
+    /// ```
+    pub fn set_comment(&mut self, comment: &str) {
+        self.decor.set_comment(comment, DEFAULT_TABLE_DECOR.0);
+    }
+
+    /// Returns the comment after the table header, on the same line, without its `# `.
+    pub fn trailing_comment(&self) -> Option<String> {
+        self.decor.trailing_comment()
+    }
+
+    /// Replaces the comment after the table header with the first line of `comment`.
+    ///
+    /// An empty `comment` removes it.
+    pub fn set_trailing_comment(&mut self, comment: &str) {
+        self.decor.set_trailing_comment(comment);
+    }
+
     /// Returns the decor associated with a given key of the table.
     pub fn key_decor_mut(&mut self, key: &str) -> Option<&mut Decor> {
         self.items.get_mut(key).map(|kv| &mut kv.key.decor)
@@ -222,6 +462,13 @@ impl Table {
     pub fn key_decor(&self, key: &str) -> Option<&Decor> {
         self.items.get(key).map(|kv| &kv.key.decor)
     }
+
+    /// Returns the raw representation of a given key of the table, as it appeared when parsed.
+    ///
+    /// Returns `None` if the key is absent or was not parsed or explicitly formatted.
+    pub fn get_original_repr(&self, key: &str) -> Option<&Repr> {
+        self.items.get(key).and_then(|kv| kv.key.as_repr())
+    }
 }
 
 impl Table {
@@ -245,6 +492,30 @@ impl Table {
         )
     }
 
+    /// Returns an iterator over the key/value pairs whose items are a `T`, cast to `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let doc = "a = 'x'\nb = 1\nc = 'y'\n".parse::<toml_edit::Document>().unwrap();
+    /// let strings = doc.values_of::<&str>().collect::<Vec<_>>();
+    /// assert_eq!(strings, [("a", "x"), ("c", "y")]);
+    /// ```
+    pub fn values_of<'a, T: FromItem<'a>>(&'a self) -> impl Iterator<Item = (&'a str, T)> + 'a {
+        self.iter()
+            .filter_map(|(key, item)| T::from_item(item).map(|value| (key, value)))
+    }
+
+    /// Returns an iterator over the subtables, see [`Table::values_of`].
+    pub fn tables(&self) -> impl Iterator<Item = (&str, &Table)> + '_ {
+        self.values_of()
+    }
+
+    /// Returns an iterator over the array values, see [`Table::values_of`].
+    pub fn arrays(&self) -> impl Iterator<Item = (&str, &Array)> + '_ {
+        self.values_of()
+    }
+
     /// Returns the number of non-empty items in the table.
     pub fn len(&self) -> usize {
         self.items.iter().filter(|i| !(i.1).value.is_none()).count()
@@ -260,6 +531,30 @@ impl Table {
         self.items.clear()
     }
 
+    /// Returns a cursor pointing at the first key/value pair, for reordering entries in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "b = 2\nc = 3\na = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// let mut cursor = doc.as_table_mut().cursor_front();
+    /// while cursor.key().map_or(false, |k| k.get() != "c") {
+    ///     cursor.move_next();
+    /// }
+    /// let (key, item) = cursor.remove_current().unwrap();
+    /// let mut cursor = doc.as_table_mut().cursor_front();
+    /// cursor.insert_formatted_before_cursor(&key, item);
+    /// assert_eq!(doc.to_string(), "c = 3\nb = 2\na = 1\n");
+    /// ```
+    pub fn cursor_front(&mut self) -> TableCursor<'_> {
+        let mut cursor = TableCursor {
+            table: self,
+            index: 0,
+        };
+        cursor.skip_empty();
+        cursor
+    }
+
     /// Gets the given key's corresponding entry in the Table for in-place manipulation.
     pub fn entry<'a>(&'a mut self, key: &str) -> Entry<'a> {
         // Accept a `&str` rather than an owned type to keep `InternalString`, well, internal
@@ -315,6 +610,16 @@ impl Table {
     }
 
     /// Return mutable references to the key-value pair stored for key, if it is present, else None.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "version = '1.0'\n".parse::<toml_edit::Document>().unwrap();
+    /// let (mut key, item) = doc.get_key_value_mut("version").unwrap();
+    /// key.decor_mut().set_prefix("# Bumped on release\n");
+    /// *item = toml_edit::value("1.1");
+    /// assert_eq!(doc.to_string(), "# Bumped on release\nversion = \"1.1\"\n");
+    /// ```
     pub fn get_key_value_mut<'a>(&'a mut self, key: &str) -> Option<(KeyMut<'a>, &'a mut Item)> {
         self.items.get_mut(key).and_then(|kv| {
             if !kv.value.is_none() {
@@ -382,10 +687,70 @@ impl Table {
     pub fn remove_entry(&mut self, key: &str) -> Option<(Key, Item)> {
         self.items.shift_remove(key).map(|kv| (kv.key, kv.value))
     }
+
+    /// Renames the key `old` to `new` in place, keeping its decor, like comments, and its
+    /// position in the table.
+    ///
+    /// The new key is written bare when it can be, quoted otherwise.  Returns `false`, leaving
+    /// the table unchanged, if `old` is not in the table or `new` already is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a = 1\n# the b\nb = 2 # two\nc = 3\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// assert!(doc.rename_key("b", "bee"));
+    /// assert!(!doc.rename_key("a", "c"));
+    /// assert_eq!(doc.to_string(), "a = 1\n# the b\nbee = 2 # two\nc = 3\n");
+    /// ```
+    pub fn rename_key(&mut self, old: &str, new: &str) -> bool {
+        rename_key(&mut self.items, old, new)
+    }
+
+    /// Retains only the key/value pairs for which `keep` returns `true`, keeping their order and
+    /// decor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut doc = "a = 1\n# b\nb = 'x'\nc = 3\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.retain(|_, item| item.is_integer());
+    /// assert_eq!(doc.to_string(), "a = 1\nc = 3\n");
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str, &mut Item) -> bool,
+    {
+        self.items
+            .retain(|key, kv| kv.value.is_none() || keep(key, &mut kv.value));
+    }
+}
+
+// Renames the key in place, as removing and inserting it would move it to the end
+pub(crate) fn rename_key(items: &mut KeyValuePairs, old: &str, new: &str) -> bool {
+    if old == new {
+        return items.contains_key(old);
+    }
+    if items.contains_key(new) {
+        return false;
+    }
+    let index = match items.get_index_of(old) {
+        Some(index) => index,
+        None => return false,
+    };
+    let (_, kv) = items
+        .swap_remove_index(index)
+        .expect("index is within the table");
+    let key = Key::new(new).with_decor(kv.key.decor);
+    items.insert(new.into(), TableKeyValue::new(key, kv.value));
+    let last = items.len() - 1;
+    items.swap_indices(index, last);
+    true
 }
 
-impl std::fmt::Display for Table {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use crate::encode::Encode;
         let children = self.get_values();
         // print table body
@@ -453,6 +818,44 @@ fn decorate_table(table: &mut Table) {
     }
 }
 
+fn sort_values_by_path<F>(table: &mut Table, path: &mut Vec<String>, compare: &mut F)
+where
+    F: FnMut(&[&str], &Key, &Item, &Key, &Item) -> core::cmp::Ordering,
+{
+    let keys = path.iter().map(String::as_str).collect::<Vec<_>>();
+    table
+        .items
+        .sort_by(|_, val1, _, val2| compare(&keys, &val1.key, &val1.value, &val2.key, &val2.value));
+    for kv in table.items.values_mut() {
+        path.push(kv.key.get().to_owned());
+        match &mut kv.value {
+            Item::Table(table) => sort_values_by_path(table, path, compare),
+            Item::ArrayOfTables(array) => {
+                for table in array.iter_mut() {
+                    sort_values_by_path(table, path, compare);
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+pub(crate) fn visit_headers_mut(table: &mut Table, callback: &mut dyn FnMut(&mut Table)) {
+    callback(table);
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::Table(table) if !table.is_dotted() => visit_headers_mut(table, callback),
+            Item::ArrayOfTables(array) => {
+                for table in array.iter_mut() {
+                    visit_headers_mut(table, callback);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // `key1 = value1`
 pub(crate) const DEFAULT_KEY_DECOR: (&str, &str) = ("", " ");
 pub(crate) const DEFAULT_TABLE_DECOR: (&str, &str) = ("\n", "");
@@ -532,6 +935,8 @@ pub trait TableLike: crate::private::Sealed {
     fn key_decor_mut(&mut self, key: &str) -> Option<&mut Decor>;
     /// Returns the decor associated with a given key of the table.
     fn key_decor(&self, key: &str) -> Option<&Decor>;
+    /// Returns the raw representation of a given key of the table, as it appeared when parsed.
+    fn get_original_repr(&self, key: &str) -> Option<&Repr>;
 }
 
 impl TableLike for Table {
@@ -594,6 +999,9 @@ impl TableLike for Table {
     fn key_decor(&self, key: &str) -> Option<&Decor> {
         self.key_decor(key)
     }
+    fn get_original_repr(&self, key: &str) -> Option<&Repr> {
+        self.get_original_repr(key)
+    }
 }
 
 /// A view into a single location in a map, which may be vacant or occupied.
@@ -623,6 +1031,15 @@ impl<'a> Entry<'a> {
         }
     }
 
+    /// Returns the index of the entry among the table's key/value pairs, where a vacant entry
+    /// will be inserted
+    pub fn position(&self) -> usize {
+        match self {
+            Entry::Occupied(e) => e.position(),
+            Entry::Vacant(e) => e.position(),
+        }
+    }
+
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     pub fn or_insert(self, default: Item) -> &'a mut Item {
@@ -668,6 +1085,11 @@ impl<'a> OccupiedEntry<'a> {
         self.entry.get_mut().key.as_mut()
     }
 
+    /// Returns the index of the entry among the table's key/value pairs
+    pub fn position(&self) -> usize {
+        self.entry.index()
+    }
+
     /// Gets a reference to the value in the entry.
     pub fn get(&self) -> &Item {
         &self.entry.get().value
@@ -686,7 +1108,7 @@ impl<'a> OccupiedEntry<'a> {
 
     /// Sets the value of the entry, and returns the entry's old value
     pub fn insert(&mut self, mut value: Item) -> Item {
-        std::mem::swap(&mut value, &mut self.entry.get_mut().value);
+        core::mem::swap(&mut value, &mut self.entry.get_mut().value);
         value
     }
 
@@ -718,6 +1140,21 @@ impl<'a> VacantEntry<'a> {
         self.entry.key().as_str()
     }
 
+    /// Gets a mutable reference to the key that will be inserted, to set its decor or
+    /// representation beforehand
+    pub fn key_mut(&mut self) -> KeyMut<'_> {
+        let entry = &self.entry;
+        self.key
+            .get_or_insert_with(|| Key::new(entry.key().as_str()))
+            .as_mut()
+    }
+
+    /// Returns the index the entry will have among the table's key/value pairs, after the
+    /// existing ones
+    pub fn position(&self) -> usize {
+        self.entry.index()
+    }
+
     /// Sets the value of the entry with the VacantEntry's key,
     /// and returns a mutable reference to it
     pub fn insert(self, value: Item) -> &'a mut Item {
@@ -725,4 +1162,138 @@ impl<'a> VacantEntry<'a> {
         let key = self.key.unwrap_or_else(|| Key::new(entry.key().as_str()));
         &mut entry.insert(TableKeyValue::new(key, value)).value
     }
+
+    /// Sets the value of the entry with the VacantEntry's key written with `decor`, and returns
+    /// a mutable reference to the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{value, Decor, Entry};
+    ///
+    /// let mut doc = "a = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// if let Entry::Vacant(entry) = doc.entry("b") {
+    ///     assert_eq!(entry.position(), 1);
+    ///     entry.insert_with_decor(value(2), Decor::new("# added\n", " "));
+    /// }
+    /// assert_eq!(doc.to_string(), "a = 1\n# added\nb = 2\n");
+    /// ```
+    pub fn insert_with_decor(mut self, value: Item, decor: Decor) -> &'a mut Item {
+        *self.key_mut().decor_mut() = decor;
+        self.insert(value)
+    }
+}
+
+/// A cursor over the key/value pairs of a [`Table`], created by [`Table::cursor_front`].
+///
+/// The cursor points either at an entry or past the last entry.  Empty items are skipped, as
+/// with [`Table::iter`].
+pub struct TableCursor<'t> {
+    table: &'t mut Table,
+    index: usize,
+}
+
+impl<'t> TableCursor<'t> {
+    /// Returns the key of the current entry, or `None` if the cursor is past the end.
+    pub fn key(&self) -> Option<&Key> {
+        self.table
+            .items
+            .get_index(self.index)
+            .map(|(_, kv)| &kv.key)
+    }
+
+    /// Returns the item of the current entry, or `None` if the cursor is past the end.
+    pub fn item(&self) -> Option<&Item> {
+        self.table
+            .items
+            .get_index(self.index)
+            .map(|(_, kv)| &kv.value)
+    }
+
+    /// Returns the item of the current entry, or `None` if the cursor is past the end.
+    pub fn item_mut(&mut self) -> Option<&mut Item> {
+        self.table
+            .items
+            .get_index_mut(self.index)
+            .map(|(_, kv)| &mut kv.value)
+    }
+
+    /// Returns true iff the cursor is past the last entry.
+    pub fn is_at_end(&self) -> bool {
+        self.index >= self.table.items.len()
+    }
+
+    /// Moves to the next entry, returning `false` if the cursor was already past the end.
+    pub fn move_next(&mut self) -> bool {
+        if self.is_at_end() {
+            return false;
+        }
+        self.index += 1;
+        self.skip_empty();
+        true
+    }
+
+    /// Moves to the previous entry, returning `false` if there is none.
+    pub fn move_prev(&mut self) -> bool {
+        let prev = (0..self.index).rev().find(|&i| {
+            self.table
+                .items
+                .get_index(i)
+                .map_or(false, |(_, kv)| !kv.value.is_none())
+        });
+        match prev {
+            Some(index) => {
+                self.index = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts a key/value pair before the current entry, leaving the cursor on the current entry.
+    ///
+    /// If the key is already present, it is moved here and its old item is returned.
+    pub fn insert_before_cursor(&mut self, key: &str, item: Item) -> Option<Item> {
+        self.insert_formatted_before_cursor(&Key::new(key), item)
+    }
+
+    /// Inserts a key/value pair before the current entry, leaving the cursor on the current entry.
+    ///
+    /// If the key is already present, it is moved here and its old item is returned.
+    pub fn insert_formatted_before_cursor(&mut self, key: &Key, item: Item) -> Option<Item> {
+        let old = self.remove_key(key.get());
+        let kv = TableKeyValue::new(key.to_owned(), item);
+        let (index, _) = self.table.items.insert_full(key.get().into(), kv);
+        self.table.items.move_index(index, self.index);
+        self.index += 1;
+        old
+    }
+
+    /// Removes the current entry and moves to the next one.
+    ///
+    /// Returns `None` if the cursor is past the end.
+    pub fn remove_current(&mut self) -> Option<(Key, Item)> {
+        let (_, kv) = self.table.items.shift_remove_index(self.index)?;
+        self.skip_empty();
+        Some((kv.key, kv.value))
+    }
+
+    fn remove_key(&mut self, key: &str) -> Option<Item> {
+        let (index, _, kv) = self.table.items.shift_remove_full(key)?;
+        if index < self.index {
+            self.index -= 1;
+        } else if index == self.index {
+            self.skip_empty();
+        }
+        Some(kv.value)
+    }
+
+    fn skip_empty(&mut self) {
+        while let Some((_, kv)) = self.table.items.get_index(self.index) {
+            if !kv.value.is_none() {
+                break;
+            }
+            self.index += 1;
+        }
+    }
 }
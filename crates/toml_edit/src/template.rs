@@ -0,0 +1,194 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use crate::collections::IndexMap;
+use crate::table::Iter;
+use crate::{Document, Item, TableLike, Value};
+
+impl Document {
+    /// Match the document against `pattern`, binding its placeholders.
+    ///
+    /// A placeholder is a string starting with `$`, like `"$version"`, and matches any item,
+    /// including tables.  Write `$$` for a string that starts with a literal `$`.  A placeholder
+    /// used more than once must match equal items each time.
+    ///
+    /// Everything else in `pattern` must be present in the document: tables match if they have
+    /// all of the pattern's keys, whether defined with a header, dotted keys, or inline, and may
+    /// have more, while arrays and arrays of tables must match element by element, with an array
+    /// of inline tables in `pattern` also matching an array of tables.  Formatting is ignored
+    /// throughout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let manifest = r#"
+    /// [package]
+    /// name = "demo"
+    /// version = "1.2.3"
+    /// edition = "2021"
+    ///
+    /// [dependencies]
+    /// serde = { version = "1.0", features = ["derive"] }
+    /// "#;
+    /// let pattern = r#"
+    /// package = { name = "$name", version = "$version", edition = "2021" }
+    /// dependencies.serde.version = "$serde"
+    /// "#;
+    /// let doc = manifest.parse::<toml_edit::Document>().unwrap();
+    /// let pattern = pattern.parse::<toml_edit::Document>().unwrap();
+    ///
+    /// let bindings = doc.match_template(&pattern).unwrap();
+    /// assert_eq!(bindings.get("name").and_then(|i| i.as_str()), Some("demo"));
+    /// assert_eq!(bindings.get("version").and_then(|i| i.as_str()), Some("1.2.3"));
+    /// assert_eq!(bindings.get("serde").and_then(|i| i.as_str()), Some("1.0"));
+    ///
+    /// let pattern = "package.edition = '2018'".parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.match_template(&pattern).is_none());
+    /// ```
+    pub fn match_template(&self, pattern: &Document) -> Option<Bindings> {
+        let mut matcher = Matcher {
+            bindings: Bindings::default(),
+            placeholders: true,
+        };
+        matcher
+            .match_item(pattern.as_item(), self.as_item())
+            .then(|| matcher.bindings)
+    }
+}
+
+/// The items bound to placeholders by [`Document::match_template`]
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    items: IndexMap<String, Item>,
+}
+
+impl Bindings {
+    /// The item bound to the placeholder `name`, without its leading `$`.
+    pub fn get(&self, name: &str) -> Option<&Item> {
+        self.items.get(name)
+    }
+
+    /// The placeholders and their items, in the order they appear in the pattern.
+    pub fn iter(&self) -> Iter<'_> {
+        Box::new(self.items.iter().map(|(name, item)| (name.as_str(), item)))
+    }
+
+    /// The number of placeholders bound.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the pattern had no placeholders.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+struct Matcher {
+    bindings: Bindings,
+    // When comparing two bound items, strings are only ever literals
+    placeholders: bool,
+}
+
+impl Matcher {
+    fn match_item(&mut self, pattern: &Item, item: &Item) -> bool {
+        if let Some(name) = pattern.as_str().and_then(|s| self.placeholder(s)) {
+            return self.bind(name, item);
+        }
+        match (pattern, item) {
+            (Item::Value(pattern), Item::Value(value)) => self.match_value(pattern, value),
+            (Item::ArrayOfTables(pattern), Item::ArrayOfTables(array)) => {
+                pattern.len() == array.len()
+                    && pattern
+                        .iter()
+                        .zip(array.iter())
+                        .all(|(pattern, table)| self.match_table_like(pattern, table))
+            }
+            (Item::Value(Value::Array(pattern)), Item::ArrayOfTables(array)) => {
+                pattern.len() == array.len()
+                    && pattern
+                        .iter()
+                        .zip(array.iter())
+                        .all(|(pattern, table)| match pattern {
+                            Value::InlineTable(pattern) => self.match_table_like(pattern, table),
+                            _ => false,
+                        })
+            }
+            _ => match (pattern.as_table_like(), item.as_table_like()) {
+                (Some(pattern), Some(table)) => self.match_table_like(pattern, table),
+                _ => false,
+            },
+        }
+    }
+
+    fn match_table_like(&mut self, pattern: &dyn TableLike, table: &dyn TableLike) -> bool {
+        pattern.iter().all(|(key, pattern)| match table.get(key) {
+            Some(item) => self.match_item(pattern, item),
+            None => false,
+        })
+    }
+
+    fn match_value(&mut self, pattern: &Value, value: &Value) -> bool {
+        match (pattern, value) {
+            (Value::String(pattern), _) => match self.placeholder(pattern.value()) {
+                Some(name) => self.bind(name, &Item::Value(value.clone())),
+                None => value.as_str() == Some(self.literal(pattern.value())),
+            },
+            (Value::Integer(pattern), Value::Integer(value)) => pattern.value() == value.value(),
+            (Value::WideInteger(pattern), Value::WideInteger(value)) => {
+                pattern.value() == value.value()
+            }
+            (Value::Float(pattern), Value::Float(value)) => pattern.value() == value.value(),
+            (Value::Boolean(pattern), Value::Boolean(value)) => pattern.value() == value.value(),
+            #[cfg(feature = "datetime")]
+            (Value::Datetime(pattern), Value::Datetime(value)) => pattern.value() == value.value(),
+            (Value::OpaqueDatetime(pattern), Value::OpaqueDatetime(value)) => {
+                pattern.value() == value.value()
+            }
+            (Value::Array(pattern), Value::Array(array)) => {
+                pattern.len() == array.len()
+                    && pattern
+                        .iter()
+                        .zip(array.iter())
+                        .all(|(pattern, value)| self.match_value(pattern, value))
+            }
+            (Value::InlineTable(pattern), Value::InlineTable(table)) => {
+                self.match_table_like(pattern, table)
+            }
+            _ => false,
+        }
+    }
+
+    fn placeholder<'s>(&self, s: &'s str) -> Option<&'s str> {
+        let name = s.strip_prefix('$').filter(|_| self.placeholders)?;
+        (!name.is_empty() && !name.starts_with('$')).then(|| name)
+    }
+
+    fn literal<'s>(&self, s: &'s str) -> &'s str {
+        match s.strip_prefix("$$") {
+            Some(_) if self.placeholders => &s[1..],
+            _ => s,
+        }
+    }
+
+    fn bind(&mut self, name: &str, item: &Item) -> bool {
+        let mut item = item.clone();
+        if let Item::Value(value) = &mut item {
+            value.decor_mut().clear();
+        }
+        match self.bindings.items.get(name) {
+            Some(bound) => {
+                let mut equal = Matcher {
+                    bindings: Bindings::default(),
+                    placeholders: false,
+                };
+                equal.match_item(bound, &item) && equal.match_item(&item, bound)
+            }
+            None => {
+                self.bindings.items.insert(name.to_owned(), item);
+                true
+            }
+        }
+    }
+}
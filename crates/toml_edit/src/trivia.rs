@@ -0,0 +1,485 @@
+use alloc::borrow::{Cow, ToOwned};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::document::DecorProvider;
+use crate::document::NewlineStyle;
+use crate::encode::{
+    decor_or, default_header_decor, default_key_value_decor, default_value_decor,
+    visit_nested_tables, SortPositions,
+};
+use crate::inline_table::DEFAULT_INLINE_KEY_DECOR;
+use crate::repr::{Formatted, ValueRepr};
+use crate::symbols::{Symbol, SymbolKind};
+use crate::table::DEFAULT_KEY_PATH_DECOR;
+use crate::value::{
+    DEFAULT_LEADING_VALUE_DECOR, DEFAULT_TRAILING_VALUE_DECOR, DEFAULT_VALUE_DECOR,
+};
+use crate::{Array, Decor, Document, InlineTable, Key, Table, Value};
+
+impl Document {
+    /// Returns an iterator over the whitespace and comments of the document, in the order they
+    /// are written.
+    ///
+    /// Every decor segment is included, even when empty or left to the default, along with the
+    /// whitespace trailing arrays, inline tables, and the document itself.  Each segment's span is
+    /// its byte range in the document's [`Display`][std::fmt::Display] output, which for an
+    /// unmodified parsed document is its range in the input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::TriviaKind;
+    ///
+    /// let input = "# deps\n[dependencies]\n  serde  = '1' # latest\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// let comments = doc
+    ///     .trivia()
+    ///     .filter(|t| t.as_str().contains('#'))
+    ///     .map(|t| (t.kind(), t.path().to_vec(), &input[t.span()]))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     comments,
+    ///     [
+    ///         (TriviaKind::HeaderPrefix, vec!["dependencies"], "# deps\n"),
+    ///         (TriviaKind::ValueSuffix, vec!["dependencies", "serde"], " # latest"),
+    ///     ]
+    /// );
+    ///
+    /// let indent = doc
+    ///     .trivia()
+    ///     .find(|t| t.kind() == TriviaKind::KeyPrefix && t.path() == ["dependencies", "serde"])
+    ///     .unwrap();
+    /// assert_eq!(indent.as_str(), "  ");
+    /// ```
+    pub fn trivia(&self) -> impl Iterator<Item = Trivia<'_>> + '_ {
+        Tracker::track(self, true).trivia.into_iter()
+    }
+
+    /// Returns an iterator over the whitespace and comments that would not write valid TOML.
+    ///
+    /// A segment is reported when it has more than whitespace, newlines, and comments, or when
+    /// its newlines or comments are not allowed where it is written, like a comment in a key's
+    /// decor or an unterminated comment before a value.  An empty iterator means the document's
+    /// decor can be written and parsed back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::TriviaKind;
+    ///
+    /// let mut doc = "[package]\nname = 'demo'\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.audit_decor().count(), 0);
+    ///
+    /// let mut key = doc["package"].as_table_mut().unwrap().key_decor_mut("name").unwrap();
+    /// key.set_suffix(" # oops ");
+    /// let invalid = doc.audit_decor().collect::<Vec<_>>();
+    /// assert_eq!(invalid.len(), 1);
+    /// assert_eq!(invalid[0].kind(), TriviaKind::KeySuffix);
+    /// assert_eq!(invalid[0].path(), ["package", "name"]);
+    /// ```
+    pub fn audit_decor(&self) -> impl Iterator<Item = Trivia<'_>> + '_ {
+        self.trivia().filter(|t| !t.is_valid())
+    }
+}
+
+/// A segment of whitespace and comments, see [`Document::trivia`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'d> {
+    path: Vec<&'d str>,
+    kind: TriviaKind,
+    text: Cow<'d, str>,
+    span: Range<usize>,
+    placement: Placement,
+}
+
+impl<'d> Trivia<'d> {
+    /// The keys leading to the segment's owner.
+    ///
+    /// Elements of arrays share the array's path and the document's trailing whitespace has an
+    /// empty path.
+    pub fn path(&self) -> &[&'d str] {
+        &self.path
+    }
+
+    /// Where the segment is relative to its owner.
+    pub fn kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    /// The segment's whitespace and comments, with line endings as stored in the document.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte range of the segment in the document's output.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    fn is_valid(&self) -> bool {
+        match self.placement {
+            Placement::Inline => self.text.bytes().all(|b| b == b' ' || b == b'\t'),
+            Placement::Multiline => {
+                let last_line = self.text.rsplit('\n').next().unwrap_or_default();
+                crate::parser::parse_decor(&self.text).is_ok() && !last_line.contains('#')
+            }
+            Placement::EndOfLine => crate::parser::parse_decor(&self.text).is_ok(),
+        }
+    }
+}
+
+// What may be written in a segment, depending on what follows it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Placement {
+    // Only whitespace, as more of the line follows
+    Inline,
+    // Newlines and comments, as long as the last line has no comment
+    Multiline,
+    // Newlines and comments, as a newline or the end of the document follows
+    EndOfLine,
+}
+
+// The decor's own text, or a copy of the default written in its place
+fn or_owned<'d>(decor: Option<&'d str>, default: &str) -> Cow<'d, str> {
+    match decor {
+        Some(decor) => Cow::Borrowed(decor),
+        None => Cow::Owned(default.to_owned()),
+    }
+}
+
+/// Where a [`Trivia`] segment is relative to its owner
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// Before a key, including the indentation and comment lines before a key/value pair
+    KeyPrefix,
+    /// After a key, before a `.` or `=`
+    KeySuffix,
+    /// Before a value, after the `=`, `[`, or `,`
+    ValuePrefix,
+    /// After a value, including a comment ending the line of a key/value pair
+    ValueSuffix,
+    /// Before a table header, including the blank and comment lines before it
+    HeaderPrefix,
+    /// After a table header
+    HeaderSuffix,
+    /// After the last element of an array or inline table, or at the end of the document
+    Trailing,
+}
+
+// Follows the document's `Display` output, recording the spans of trivia and symbols
+pub(crate) struct Tracker<'d> {
+    offset: usize,
+    newline: NewlineStyle,
+    provider: Option<&'d DecorProvider>,
+    path: Vec<&'d str>,
+    record_trivia: bool,
+    pub(crate) trivia: Vec<Trivia<'d>>,
+    pub(crate) symbols: Vec<Symbol<'d>>,
+}
+
+impl<'d> Tracker<'d> {
+    pub(crate) fn track(doc: &'d Document, record_trivia: bool) -> Self {
+        let mut tracker = Tracker {
+            offset: if doc.bom { '\u{FEFF}'.len_utf8() } else { 0 },
+            newline: doc.newline,
+            provider: doc.default_decor.as_ref(),
+            path: Vec::new(),
+            record_trivia,
+            trivia: Vec::new(),
+            symbols: Vec::new(),
+        };
+
+        // Tables are written in the same order as by `Display`
+        let mut path = Vec::new();
+        let mut positions = SortPositions::new(doc.order.as_ref());
+        let mut tables = Vec::new();
+        visit_nested_tables(doc.as_table(), &mut path, false, &mut |t, p, is_array| {
+            tables.push((positions.next(t, p), t, p.clone(), is_array));
+            Ok(())
+        })
+        .unwrap();
+        tables.sort_by_key(|&(id, _, _, _)| id);
+        let mut first_table = true;
+        for (_, table, path, is_array) in tables {
+            tracker.table(table, &path, is_array, &mut first_table);
+        }
+        tracker.path.clear();
+        tracker.push(
+            TriviaKind::Trailing,
+            Cow::Borrowed(doc.trailing()),
+            Placement::EndOfLine,
+        );
+
+        tracker
+    }
+
+    fn table(
+        &mut self,
+        table: &'d Table,
+        path: &[&'d Key],
+        is_array_of_tables: bool,
+        first_table: &mut bool,
+    ) {
+        let children = table.get_values();
+        let is_visible_std_table = !(table.implicit && children.is_empty());
+
+        self.path.clear();
+        if path.is_empty() {
+            if !children.is_empty() {
+                *first_table = false;
+            }
+        } else if is_array_of_tables || is_visible_std_table {
+            let default_decor =
+                default_header_decor(self.provider, path, is_array_of_tables, first_table);
+            let (prefix, suffix) = decor_or(&table.decor, &default_decor);
+            let brackets = if is_array_of_tables { 2 } else { 1 };
+            self.path.extend(path.iter().map(|k| k.get()));
+            self.push(
+                TriviaKind::HeaderPrefix,
+                or_owned(table.decor.prefix(), prefix),
+                Placement::Multiline,
+            );
+            let start = self.offset;
+            self.offset += brackets;
+            self.path.clear();
+            self.key_path(path, DEFAULT_KEY_PATH_DECOR, Placement::Inline, false);
+            self.offset += brackets;
+            self.symbols.push(Symbol {
+                path: self.path.clone(),
+                kind: if is_array_of_tables {
+                    SymbolKind::ArrayOfTables
+                } else {
+                    SymbolKind::Table
+                },
+                span: start..self.offset,
+            });
+            self.push(
+                TriviaKind::HeaderSuffix,
+                or_owned(table.decor.suffix(), suffix),
+                Placement::EndOfLine,
+            );
+            self.advance("\n");
+        }
+
+        for (key_path, value) in children {
+            self.path.clear();
+            self.path.extend(path.iter().map(|k| k.get()));
+            let provided = default_value_decor(self.provider, path, &key_path);
+            let (key_decor, value_decor) = default_key_value_decor(&provided);
+            self.key_path(&key_path, key_decor, Placement::Multiline, true);
+            self.offset += 1;
+            self.value(
+                value,
+                value_decor,
+                (Placement::Inline, Placement::EndOfLine),
+            );
+            self.advance("\n");
+        }
+    }
+
+    // Pushes each key onto the path, recording them as symbols unless part of a header
+    fn key_path(
+        &mut self,
+        keys: &[&'d Key],
+        default_decor: (&str, &str),
+        leading: Placement,
+        record_keys: bool,
+    ) {
+        for (i, key) in keys.iter().enumerate() {
+            let first = i == 0;
+            let last = i + 1 == keys.len();
+            let prefix = if first {
+                default_decor.0
+            } else {
+                DEFAULT_KEY_PATH_DECOR.0
+            };
+            let suffix = if last {
+                default_decor.1
+            } else {
+                DEFAULT_KEY_PATH_DECOR.1
+            };
+
+            if !first {
+                self.offset += 1;
+            }
+            self.path.push(key.get());
+            let decor = key.decor();
+            let placement = if first { leading } else { Placement::Inline };
+            self.push(
+                TriviaKind::KeyPrefix,
+                or_owned(decor.prefix(), prefix),
+                placement,
+            );
+            let start = self.offset;
+            self.advance(key.to_repr().as_raw());
+            if record_keys {
+                self.symbols.push(Symbol {
+                    path: self.path.clone(),
+                    kind: SymbolKind::Key,
+                    span: start..self.offset,
+                });
+            }
+            self.push(
+                TriviaKind::KeySuffix,
+                or_owned(decor.suffix(), suffix),
+                Placement::Inline,
+            );
+        }
+    }
+
+    fn value(
+        &mut self,
+        value: &'d Value,
+        default_decor: (&str, &str),
+        placement: (Placement, Placement),
+    ) {
+        match value {
+            Value::String(f) => self.formatted(f, default_decor, placement),
+            Value::Integer(f) => self.formatted(f, default_decor, placement),
+            Value::WideInteger(f) => self.formatted(f, default_decor, placement),
+            Value::Float(f) => self.formatted(f, default_decor, placement),
+            Value::Boolean(f) => self.formatted(f, default_decor, placement),
+            #[cfg(feature = "datetime")]
+            Value::Datetime(f) => self.formatted(f, default_decor, placement),
+            Value::OpaqueDatetime(f) => self.formatted(f, default_decor, placement),
+            Value::Array(array) => self.array(array, default_decor, placement),
+            Value::InlineTable(table) => self.inline_table(table, default_decor, placement),
+        }
+    }
+
+    fn formatted<T: ValueRepr>(
+        &mut self,
+        f: &'d Formatted<T>,
+        default_decor: (&str, &str),
+        placement: (Placement, Placement),
+    ) {
+        self.value_prefix(f.decor(), default_decor, placement.0);
+        match f.to_repr() {
+            Cow::Borrowed(repr) => self.offset += repr.as_raw().len(),
+            Cow::Owned(repr) => self.advance(repr.as_raw()),
+        }
+        self.value_suffix(f.decor(), default_decor, placement.1);
+    }
+
+    fn array(
+        &mut self,
+        array: &'d Array,
+        default_decor: (&str, &str),
+        placement: (Placement, Placement),
+    ) {
+        self.value_prefix(array.decor(), default_decor, placement.0);
+        self.offset += 1;
+        for (i, elem) in array.iter().enumerate() {
+            let inner_decor = if i == 0 {
+                DEFAULT_LEADING_VALUE_DECOR
+            } else {
+                self.offset += 1;
+                DEFAULT_VALUE_DECOR
+            };
+            self.value(
+                elem,
+                inner_decor,
+                (Placement::Multiline, Placement::Multiline),
+            );
+        }
+        if array.trailing_comma() && !array.is_empty() {
+            self.offset += 1;
+        }
+        self.push(
+            TriviaKind::Trailing,
+            Cow::Borrowed(array.trailing()),
+            Placement::Multiline,
+        );
+        self.offset += 1;
+        self.value_suffix(array.decor(), default_decor, placement.1);
+    }
+
+    fn inline_table(
+        &mut self,
+        table: &'d InlineTable,
+        default_decor: (&str, &str),
+        placement: (Placement, Placement),
+    ) {
+        self.value_prefix(table.decor(), default_decor, placement.0);
+        self.offset += 1;
+        let depth = self.path.len();
+        let children = table.get_values();
+        let len = children.len();
+        for (i, (key_path, value)) in children.into_iter().enumerate() {
+            if i != 0 {
+                self.offset += 1;
+            }
+            let inner_decor = if i == len - 1 {
+                DEFAULT_TRAILING_VALUE_DECOR
+            } else {
+                DEFAULT_VALUE_DECOR
+            };
+            self.key_path(&key_path, DEFAULT_INLINE_KEY_DECOR, Placement::Inline, true);
+            self.offset += 1;
+            self.value(value, inner_decor, (Placement::Inline, Placement::Inline));
+            self.path.truncate(depth);
+        }
+        if table.trailing_comma() && len != 0 {
+            self.offset += 1;
+        }
+        self.push(
+            TriviaKind::Trailing,
+            Cow::Borrowed(table.trailing()),
+            Placement::Inline,
+        );
+        self.offset += 1;
+        self.value_suffix(table.decor(), default_decor, placement.1);
+    }
+
+    fn value_prefix(
+        &mut self,
+        decor: &'d Decor,
+        default_decor: (&str, &str),
+        placement: Placement,
+    ) {
+        self.push(
+            TriviaKind::ValuePrefix,
+            or_owned(decor.prefix(), default_decor.0),
+            placement,
+        );
+    }
+
+    fn value_suffix(
+        &mut self,
+        decor: &'d Decor,
+        default_decor: (&str, &str),
+        placement: Placement,
+    ) {
+        self.push(
+            TriviaKind::ValueSuffix,
+            or_owned(decor.suffix(), default_decor.1),
+            placement,
+        );
+    }
+
+    fn push(&mut self, kind: TriviaKind, text: Cow<'d, str>, placement: Placement) {
+        let start = self.offset;
+        self.advance(&text);
+        if !self.record_trivia {
+            return;
+        }
+        self.trivia.push(Trivia {
+            path: self.path.clone(),
+            kind,
+            text,
+            span: start..self.offset,
+            placement,
+        });
+    }
+
+    // Moves past `text` as written in the document's line endings, where only a lone `\n` changes
+    fn advance(&mut self, text: &str) {
+        self.offset += match self.newline {
+            NewlineStyle::Lf => text.len(),
+            NewlineStyle::CrLf => {
+                text.len() + text.matches('\n').count() - text.matches("\r\n").count()
+            }
+        };
+    }
+}
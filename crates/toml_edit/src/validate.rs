@@ -0,0 +1,232 @@
+//! The `debug-validate` check that a document's output parses back to the same document
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Array, Document, InlineTable, Item, ParseOptions, Table, Value};
+
+// Panics, naming the path of the first difference, when `output` does not parse back to `doc`
+pub(crate) fn validate(doc: &Document, output: &str) {
+    // Integers outside of `i64` are written by `Value::from_u64` and `Value::from_i128`
+    let options = ParseOptions::new().extended_integers(true);
+    let parsed = match Document::parse_with(output, options) {
+        Ok(parsed) => parsed,
+        Err(err) => panic!(
+            "`Document` output is not valid TOML: {}\n--- output ---\n{}",
+            err, output
+        ),
+    };
+
+    let mut path = Vec::new();
+    let expected = table_node(doc.as_table());
+    let actual = table_node(parsed.as_table());
+    if let Err(reason) = compare(&mut path, &expected, &actual) {
+        let path = if path.is_empty() {
+            "the root table".to_owned()
+        } else {
+            format!("`{}`", display_path(&path))
+        };
+        panic!(
+            "`Document` output does not parse back to the same document, at {}: {}\n--- output ---\n{}",
+            path, reason, output
+        );
+    }
+}
+
+// Writes `a.b[0].c`, from array indices that already have their brackets
+fn display_path(path: &[String]) -> String {
+    let mut display = String::new();
+    for part in path {
+        if !display.is_empty() && !part.starts_with('[') {
+            display.push('.');
+        }
+        display.push_str(part);
+    }
+    display
+}
+
+// What a document means, without how it is written
+enum Node<'d> {
+    Value(&'d Value),
+    // Opaque datetimes are written as they were read, so they mean the datetime they are read as
+    #[cfg(feature = "datetime")]
+    Datetime(toml_datetime::Datetime),
+    Table(Vec<(&'d str, Node<'d>)>),
+    Array(Vec<Node<'d>>),
+}
+
+impl<'d> Node<'d> {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Node::Value(v) => v.type_name(),
+            #[cfg(feature = "datetime")]
+            Node::Datetime(_) => "datetime",
+            Node::Table(_) => "table",
+            Node::Array(_) => "array",
+        }
+    }
+}
+
+// `None` for items that are not written, like empty arrays of tables and empty implicit tables
+fn item_node(item: &Item) -> Option<Node<'_>> {
+    match item {
+        Item::None => None,
+        Item::Value(v) => value_node(v),
+        Item::Table(t) => {
+            let node = table_node(t);
+            match &node {
+                Node::Table(entries)
+                    if entries.is_empty() && (t.is_implicit() || t.is_dotted()) =>
+                {
+                    None
+                }
+                _ => Some(node),
+            }
+        }
+        Item::ArrayOfTables(a) if a.is_empty() => None,
+        Item::ArrayOfTables(a) => Some(Node::Array(a.iter().map(table_node).collect())),
+    }
+}
+
+fn value_node(value: &Value) -> Option<Node<'_>> {
+    match value {
+        #[cfg(feature = "datetime")]
+        Value::Datetime(d) => Some(Node::Datetime(*d.value())),
+        #[cfg(feature = "datetime")]
+        Value::OpaqueDatetime(d) => Some(Node::Datetime(d.value().to_datetime())),
+        Value::Array(a) => Some(array_node(a)),
+        Value::InlineTable(t) => {
+            let node = inline_table_node(t);
+            match &node {
+                Node::Table(entries) if entries.is_empty() && t.is_dotted() => None,
+                _ => Some(node),
+            }
+        }
+        v => Some(Node::Value(v)),
+    }
+}
+
+fn table_node(table: &Table) -> Node<'_> {
+    Node::Table(
+        table
+            .iter()
+            .filter_map(|(k, item)| item_node(item).map(|node| (k, node)))
+            .collect(),
+    )
+}
+
+fn inline_table_node(table: &InlineTable) -> Node<'_> {
+    Node::Table(
+        table
+            .iter()
+            .filter_map(|(k, value)| value_node(value).map(|node| (k, node)))
+            .collect(),
+    )
+}
+
+fn array_node(array: &Array) -> Node<'_> {
+    // Empty inline tables within arrays are still written
+    Node::Array(
+        array
+            .iter()
+            .map(|v| match v {
+                Value::InlineTable(t) => inline_table_node(t),
+                v => value_node(v).expect("only dotted inline tables are hidden"),
+            })
+            .collect(),
+    )
+}
+
+fn compare(path: &mut Vec<String>, expected: &Node<'_>, actual: &Node<'_>) -> Result<(), String> {
+    match (expected, actual) {
+        (Node::Value(expected), Node::Value(actual)) => {
+            if same_value(expected, actual) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected {}, found {}",
+                    expected.to_repr().as_raw(),
+                    actual.to_repr().as_raw()
+                ))
+            }
+        }
+        #[cfg(feature = "datetime")]
+        (Node::Datetime(expected), Node::Datetime(actual)) => {
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(format!("expected {}, found {}", expected, actual))
+            }
+        }
+        (Node::Table(expected), Node::Table(actual)) => {
+            for (key, expected) in expected {
+                path.push((*key).to_owned());
+                match actual.iter().find(|(k, _)| same_key(k, key)) {
+                    Some((_, actual)) => compare(path, expected, actual)?,
+                    None => return Err("missing from the output".to_owned()),
+                }
+                path.pop();
+            }
+            match actual
+                .iter()
+                .find(|(k, _)| !expected.iter().any(|(e, _)| same_key(e, k)))
+            {
+                Some((key, _)) => {
+                    path.push((*key).to_owned());
+                    Err("not in the document".to_owned())
+                }
+                None => Ok(()),
+            }
+        }
+        (Node::Array(expected), Node::Array(actual)) => {
+            if expected.len() != actual.len() {
+                return Err(format!(
+                    "expected {} elements, found {}",
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                path.push(format!("[{}]", i));
+                compare(path, expected, actual)?;
+                path.pop();
+            }
+            Ok(())
+        }
+        (expected, actual) => Err(format!(
+            "expected {}, found {}",
+            expected.type_name(),
+            actual.type_name()
+        )),
+    }
+}
+
+// Keys read with `ParseOptions::normalize_keys` are written as they were in the input
+fn same_key(expected: &str, actual: &str) -> bool {
+    #[cfg(feature = "unicode-normalization")]
+    {
+        use unicode_normalization::UnicodeNormalization;
+        expected.nfc().eq(actual.nfc())
+    }
+    #[cfg(not(feature = "unicode-normalization"))]
+    {
+        expected == actual
+    }
+}
+
+fn same_value(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::String(e), Value::String(a)) => e.value() == a.value(),
+        (Value::Integer(e), Value::Integer(a)) => e.value() == a.value(),
+        (Value::WideInteger(e), Value::WideInteger(a)) => e.value() == a.value(),
+        (Value::Float(e), Value::Float(a)) => {
+            let (e, a) = (*e.value(), *a.value());
+            e.to_bits() == a.to_bits() || (e.is_nan() && a.is_nan())
+        }
+        (Value::Boolean(e), Value::Boolean(a)) => e.value() == a.value(),
+        (Value::OpaqueDatetime(e), Value::OpaqueDatetime(a)) => e.value() == a.value(),
+        _ => false,
+    }
+}
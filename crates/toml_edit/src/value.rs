@@ -1,12 +1,17 @@
-use std::iter::FromIterator;
-use std::str::FromStr;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::iter::FromIterator;
+use core::str::FromStr;
 
+#[cfg(feature = "datetime")]
 use toml_datetime::*;
 
+use crate::encode::{escape_str, StringStyle};
 use crate::key::Key;
 use crate::parser;
-use crate::repr::{Decor, Formatted};
-use crate::{Array, InlineTable, InternalString};
+use crate::repr::{Decor, Formatted, Repr};
+use crate::{Array, InlineTable, InternalString, OpaqueDatetime};
 
 /// Representation of a TOML Value (as part of a Key/Value Pair).
 #[derive(Debug, Clone)]
@@ -15,33 +20,75 @@ pub enum Value {
     String(Formatted<String>),
     /// A 64-bit integer value.
     Integer(Formatted<i64>),
+    /// An integer value outside of `i64`, see [`Value::from_u64`], [`Value::from_i128`], and
+    /// [`ParseOptions::extended_integers`][crate::ParseOptions::extended_integers].
+    WideInteger(Formatted<i128>),
     /// A 64-bit float value.
     Float(Formatted<f64>),
     /// A boolean value.
     Boolean(Formatted<bool>),
     /// An RFC 3339 formatted date-time with offset.
+    #[cfg(feature = "datetime")]
     Datetime(Formatted<Datetime>),
+    /// A date-time kept as its text, see [`OpaqueDatetime`].
+    OpaqueDatetime(Formatted<OpaqueDatetime>),
     /// An inline array of values.
     Array(Array),
     /// An inline table of key/value pairs.
     InlineTable(InlineTable),
 }
 
+/// The type of a [`Value`], without its contents.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// A string value.
+    String,
+    /// A 64-bit integer value.
+    Integer,
+    /// A 64-bit float value.
+    Float,
+    /// A boolean value.
+    Boolean,
+    /// An RFC 3339 formatted date-time with offset.
+    Datetime,
+    /// An inline array of values.
+    Array,
+    /// An inline table of key/value pairs.
+    InlineTable,
+}
+
 /// Downcasting
 impl Value {
     /// Text description of value type
     pub fn type_name(&self) -> &'static str {
         match self {
             Value::String(..) => "string",
-            Value::Integer(..) => "integer",
+            Value::Integer(..) | Value::WideInteger(..) => "integer",
             Value::Float(..) => "float",
             Value::Boolean(..) => "boolean",
+            #[cfg(feature = "datetime")]
             Value::Datetime(..) => "datetime",
+            Value::OpaqueDatetime(..) => "datetime",
             Value::Array(..) => "array",
             Value::InlineTable(..) => "inline table",
         }
     }
 
+    /// The type of the value
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::String(..) => ValueKind::String,
+            Value::Integer(..) | Value::WideInteger(..) => ValueKind::Integer,
+            Value::Float(..) => ValueKind::Float,
+            Value::Boolean(..) => ValueKind::Boolean,
+            #[cfg(feature = "datetime")]
+            Value::Datetime(..) => ValueKind::Datetime,
+            Value::OpaqueDatetime(..) => ValueKind::Datetime,
+            Value::Array(..) => ValueKind::Array,
+            Value::InlineTable(..) => ValueKind::InlineTable,
+        }
+    }
+
     /// Casts `self` to str.
     pub fn as_str(&self) -> Option<&str> {
         match *self {
@@ -56,6 +103,8 @@ impl Value {
     }
 
     /// Casts `self` to integer.
+    ///
+    /// Returns `None` for [`Value::WideInteger`], see [`Value::as_u64`] and [`Value::as_i128`].
     pub fn as_integer(&self) -> Option<i64> {
         match *self {
             Value::Integer(ref value) => Some(*value.value()),
@@ -63,9 +112,30 @@ impl Value {
         }
     }
 
-    /// Returns true iff `self` is an integer.
+    /// Returns true iff `self` is an integer, including a [`Value::WideInteger`].
     pub fn is_integer(&self) -> bool {
-        self.as_integer().is_some()
+        matches!(self, Value::Integer(_) | Value::WideInteger(_))
+    }
+
+    /// Casts `self` to an unsigned integer.
+    ///
+    /// This includes a [`Value::WideInteger`] up to `u64::MAX`, see [`Value::from_u64`],
+    /// [`ParseOptions::lenient_prefixed_integers`][crate::ParseOptions::lenient_prefixed_integers],
+    /// and [`ParseOptions::extended_integers`][crate::ParseOptions::extended_integers].
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_i128().and_then(|i| u64::try_from(i).ok())
+    }
+
+    /// Casts `self` to a 128-bit integer.
+    ///
+    /// This includes a [`Value::WideInteger`], see [`Value::from_i128`] and
+    /// [`ParseOptions::extended_integers`][crate::ParseOptions::extended_integers].
+    pub fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Value::Integer(ref value) => Some(*value.value() as i128),
+            Value::WideInteger(ref value) => Some(*value.value()),
+            _ => None,
+        }
     }
 
     /// Casts `self` to float.
@@ -95,6 +165,9 @@ impl Value {
     }
 
     /// Casts `self` to date-time.
+    ///
+    /// Returns `None` for [`Value::OpaqueDatetime`], see [`Value::as_opaque_datetime`].
+    #[cfg(feature = "datetime")]
     pub fn as_datetime(&self) -> Option<&Datetime> {
         match *self {
             Value::Datetime(ref value) => Some(value.value()),
@@ -102,9 +175,17 @@ impl Value {
         }
     }
 
-    /// Returns true iff `self` is a date-time.
+    /// Casts `self` to a date-time kept as its text.
+    pub fn as_opaque_datetime(&self) -> Option<&OpaqueDatetime> {
+        match *self {
+            Value::OpaqueDatetime(ref value) => Some(value.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns true iff `self` is a date-time, including a [`Value::OpaqueDatetime`].
     pub fn is_datetime(&self) -> bool {
-        self.as_datetime().is_some()
+        self.kind() == ValueKind::Datetime
     }
 
     /// Casts `self` to array.
@@ -161,9 +242,12 @@ impl Value {
         match self {
             Value::String(f) => f.decor_mut(),
             Value::Integer(f) => f.decor_mut(),
+            Value::WideInteger(f) => f.decor_mut(),
             Value::Float(f) => f.decor_mut(),
             Value::Boolean(f) => f.decor_mut(),
+            #[cfg(feature = "datetime")]
             Value::Datetime(f) => f.decor_mut(),
+            Value::OpaqueDatetime(f) => f.decor_mut(),
             Value::Array(a) => a.decor_mut(),
             Value::InlineTable(t) => t.decor_mut(),
         }
@@ -179,14 +263,71 @@ impl Value {
         match *self {
             Value::String(ref f) => f.decor(),
             Value::Integer(ref f) => f.decor(),
+            Value::WideInteger(ref f) => f.decor(),
             Value::Float(ref f) => f.decor(),
             Value::Boolean(ref f) => f.decor(),
+            #[cfg(feature = "datetime")]
             Value::Datetime(ref f) => f.decor(),
+            Value::OpaqueDatetime(ref f) => f.decor(),
             Value::Array(ref a) => a.decor(),
             Value::InlineTable(ref t) => t.decor(),
         }
     }
 
+    /// Returns the raw representation of the value, without its decor.
+    ///
+    /// Arrays and inline tables are written out as they are displayed, including the whitespace
+    /// and comments within them, so for a parsed value this is exactly what is in the source.
+    /// # Example
+    /// ```rust
+    /// let doc = "ports = [ 80, 0x1bb ] # web\n".parse::<toml_edit::Document>().unwrap();
+    /// let ports = doc["ports"].as_value().unwrap();
+    /// assert_eq!(ports.to_repr().as_raw(), "[ 80, 0x1bb ]");
+    /// assert_eq!(ports.as_array().unwrap().get(1).unwrap().to_repr().as_raw(), "0x1bb");
+    /// ```
+    pub fn to_repr(&self) -> Cow<'_, Repr> {
+        match self {
+            Value::String(f) => f.to_repr(),
+            Value::Integer(f) => f.to_repr(),
+            Value::WideInteger(f) => f.to_repr(),
+            Value::Float(f) => f.to_repr(),
+            Value::Boolean(f) => f.to_repr(),
+            #[cfg(feature = "datetime")]
+            Value::Datetime(f) => f.to_repr(),
+            Value::OpaqueDatetime(f) => f.to_repr(),
+            Value::Array(_) | Value::InlineTable(_) => {
+                let mut value = self.clone();
+                value.decor_mut().clear();
+                Cow::Owned(Repr::new_unchecked(value.to_string()))
+            }
+        }
+    }
+
+    /// Returns the raw representation of the value as it appeared in the source, if available.
+    ///
+    /// Only parsed or auto formatted scalars have a representation, see [`Value::to_repr`] for
+    /// created values, arrays, and inline tables.
+    /// # Example
+    /// ```rust
+    /// let v = "1_000".parse::<toml_edit::Value>().unwrap();
+    /// assert_eq!(v.as_repr().unwrap().as_raw(), "1_000");
+    /// assert!(toml_edit::Value::from(1000).as_repr().is_none());
+    /// assert!(toml_edit::Value::from_iter([1]).as_repr().is_none());
+    /// ```
+    pub fn as_repr(&self) -> Option<&Repr> {
+        match self {
+            Value::String(f) => f.as_repr(),
+            Value::Integer(f) => f.as_repr(),
+            Value::WideInteger(f) => f.as_repr(),
+            Value::Float(f) => f.as_repr(),
+            Value::Boolean(f) => f.as_repr(),
+            #[cfg(feature = "datetime")]
+            Value::Datetime(f) => f.as_repr(),
+            Value::OpaqueDatetime(f) => f.as_repr(),
+            Value::Array(_) | Value::InlineTable(_) => None,
+        }
+    }
+
     /// Sets the prefix and the suffix for value.
     /// # Example
     /// ```rust
@@ -212,6 +353,85 @@ impl Value {
         let decor = self.decor_mut();
         *decor = Decor::new(prefix, suffix);
     }
+
+    /// Returns the comment ending the value's line, without its `# `.
+    pub fn trailing_comment(&self) -> Option<String> {
+        self.decor().trailing_comment()
+    }
+
+    /// Replaces the comment ending the value's line with the first line of `comment`.
+    ///
+    /// The whitespace before an existing comment is kept, otherwise the comment is separated
+    /// from the value by one space.  An empty `comment` removes the comment along with that
+    /// whitespace.
+    ///
+    /// Only the last value of a key/value pair ends its line, so this is not for the elements of
+    /// an array or inline table.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = "version = '1.0'\nedition = '2021'    # latest\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// for (_, item) in doc.iter_mut() {
+    ///     item.as_value_mut().unwrap().set_trailing_comment("managed by bot\n");
+    /// }
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "version = '1.0' # managed by bot\nedition = '2021'    # managed by bot\n"
+    /// );
+    /// ```
+    pub fn set_trailing_comment(&mut self, comment: &str) {
+        self.decor_mut().set_trailing_comment(comment);
+    }
+
+    /// Auto formats the value and everything nested within it.
+    ///
+    /// The value's own decor is left alone, as it belongs to its parent's layout.
+    /// # Example
+    /// ```rust
+    /// let mut v = "{ a = 0x10 ,b=[ 'x' ] }".parse::<toml_edit::Value>().unwrap();
+    /// v.fmt_recursive();
+    /// assert_eq!(&v.to_string(), "{ a = 16, b = [\"x\"] }");
+    /// ```
+    pub fn fmt_recursive(&mut self) {
+        match self {
+            Value::String(f) => f.fmt(),
+            Value::Integer(f) => f.fmt(),
+            Value::WideInteger(f) => f.fmt(),
+            Value::Float(f) => f.fmt(),
+            Value::Boolean(f) => f.fmt(),
+            #[cfg(feature = "datetime")]
+            Value::Datetime(f) => f.fmt(),
+            Value::OpaqueDatetime(f) => f.fmt(),
+            Value::Array(a) => a.fmt_recursive(),
+            Value::InlineTable(t) => t.fmt_recursive(),
+        }
+    }
+
+    /// Write the value to `writer` without first rendering it to a `String`.
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
+    /// Create a string value, quoted and escaped in `style`.
+    ///
+    /// Returns `None` if `value` can't be written in `style`, see [`escape_str`].
+    /// # Example
+    /// ```rust
+    /// use toml_edit::{StringStyle, Value};
+    ///
+    /// let v = Value::from_str_escaped("one\ntwo", StringStyle::MultilineBasic).unwrap();
+    /// assert_eq!(&v.to_string(), "\"\"\"\none\ntwo\"\"\"");
+    /// assert_eq!(v.as_str(), Some("one\ntwo"));
+    /// ```
+    pub fn from_str_escaped(value: &str, style: StringStyle) -> Option<Self> {
+        let repr = escape_str(value, style)?;
+        let mut formatted = Formatted::new(value.to_owned());
+        formatted.set_repr_unchecked(Repr::new_unchecked(repr));
+        Some(Value::String(formatted))
+    }
 }
 
 impl FromStr for Value {
@@ -259,6 +479,66 @@ impl From<InternalString> for Value {
     }
 }
 
+impl Value {
+    /// An integer for `value`, written in hexadecimal if it is above `i64::MAX`
+    ///
+    /// Larger values are a [`Value::WideInteger`]: [`Value::as_u64`] returns `value` while
+    /// [`Value::as_integer`] returns `None`.  Reading them back requires
+    /// [`ParseOptions::lenient_prefixed_integers`][crate::ParseOptions::lenient_prefixed_integers].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions, Value};
+    ///
+    /// let mut doc = Document::new();
+    /// doc["small"] = toml_edit::Item::Value(Value::from_u64(42));
+    /// doc["large"] = toml_edit::Item::Value(Value::from_u64(u64::MAX));
+    /// assert_eq!(doc.to_string(), "small = 42\nlarge = 0xffffffffffffffff\n");
+    ///
+    /// let options = ParseOptions::new().lenient_prefixed_integers(true);
+    /// let doc = Document::parse_with(&doc.to_string(), options).unwrap();
+    /// assert_eq!(doc["large"].as_u64(), Some(u64::MAX));
+    /// assert_eq!(doc["large"].as_integer(), None);
+    /// ```
+    pub fn from_u64(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Value::Integer(Formatted::new(value)),
+            Err(_) => {
+                let mut f = Formatted::new(value as i128);
+                f.set_repr_unchecked(Repr::new_unchecked(format!("{:#x}", value)));
+                Value::WideInteger(f)
+            }
+        }
+    }
+
+    /// An integer for `value`, written in decimal if it is outside of `i64`
+    ///
+    /// Larger values are a [`Value::WideInteger`], like with [`Value::from_u64`], and are read
+    /// back with [`Value::as_i128`].  Parsing them again requires
+    /// [`ParseOptions::extended_integers`][crate::ParseOptions::extended_integers].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use toml_edit::{Document, ParseOptions, Value};
+    ///
+    /// let mut doc = Document::new();
+    /// doc["total"] = toml_edit::Item::Value(Value::from_i128(-(1 << 100)));
+    /// assert_eq!(doc.to_string(), "total = -1267650600228229401496703205376\n");
+    ///
+    /// let options = ParseOptions::new().extended_integers(true);
+    /// let doc = Document::parse_with(&doc.to_string(), options).unwrap();
+    /// assert_eq!(doc["total"].as_i128(), Some(-(1 << 100)));
+    /// ```
+    pub fn from_i128(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Value::Integer(Formatted::new(value)),
+            Err(_) => Value::WideInteger(Formatted::new(value)),
+        }
+    }
+}
+
 impl From<i64> for Value {
     fn from(i: i64) -> Self {
         Value::Integer(Formatted::new(i))
@@ -277,12 +557,14 @@ impl From<bool> for Value {
     }
 }
 
+#[cfg(feature = "datetime")]
 impl From<Datetime> for Value {
     fn from(d: Datetime) -> Self {
         Value::Datetime(Formatted::new(d))
     }
 }
 
+#[cfg(feature = "datetime")]
 impl From<Date> for Value {
     fn from(d: Date) -> Self {
         let d: Datetime = d.into();
@@ -290,6 +572,7 @@ impl From<Date> for Value {
     }
 }
 
+#[cfg(feature = "datetime")]
 impl From<Time> for Value {
     fn from(d: Time) -> Self {
         let d: Datetime = d.into();
@@ -297,6 +580,12 @@ impl From<Time> for Value {
     }
 }
 
+impl From<OpaqueDatetime> for Value {
+    fn from(d: OpaqueDatetime) -> Self {
+        Value::OpaqueDatetime(Formatted::new(d))
+    }
+}
+
 impl From<Array> for Value {
     fn from(array: Array) -> Self {
         Value::Array(array)
@@ -329,8 +618,8 @@ impl<K: Into<Key>, V: Into<Value>> FromIterator<(K, V)> for Value {
     }
 }
 
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, ("", ""))
     }
 }
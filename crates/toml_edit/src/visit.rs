@@ -72,8 +72,13 @@
 //! For a more complex example where the visitor has internal state, see `examples/visit.rs`
 //! [on GitHub](https://github.com/ordian/toml_edit/blob/master/examples/visit.rs).
 
+use alloc::string::String;
+
+#[cfg(feature = "datetime")]
+use crate::Datetime;
 use crate::{
-    Array, ArrayOfTables, Datetime, Document, Formatted, InlineTable, Item, Table, TableLike, Value,
+    Array, ArrayOfTables, Document, Formatted, InlineTable, Item, OpaqueDatetime, Table, TableLike,
+    Value,
 };
 
 /// Document tree traversal to mutate an exclusive borrow of a document tree in-place.
@@ -120,10 +125,15 @@ pub trait Visit<'doc> {
         visit_boolean(self, node)
     }
 
+    #[cfg(feature = "datetime")]
     fn visit_datetime(&mut self, node: &'doc Formatted<Datetime>) {
         visit_datetime(self, node);
     }
 
+    fn visit_opaque_datetime(&mut self, node: &'doc Formatted<OpaqueDatetime>) {
+        visit_opaque_datetime(self, node);
+    }
+
     fn visit_float(&mut self, node: &'doc Formatted<f64>) {
         visit_float(self, node)
     }
@@ -132,6 +142,10 @@ pub trait Visit<'doc> {
         visit_integer(self, node)
     }
 
+    fn visit_wide_integer(&mut self, node: &'doc Formatted<i128>) {
+        visit_wide_integer(self, node)
+    }
+
     fn visit_string(&mut self, node: &'doc Formatted<String>) {
         visit_string(self, node)
     }
@@ -211,9 +225,12 @@ where
     match node {
         Value::String(s) => v.visit_string(s),
         Value::Integer(i) => v.visit_integer(i),
+        Value::WideInteger(i) => v.visit_wide_integer(i),
         Value::Float(f) => v.visit_float(f),
         Value::Boolean(b) => v.visit_boolean(b),
+        #[cfg(feature = "datetime")]
         Value::Datetime(dt) => v.visit_datetime(dt),
+        Value::OpaqueDatetime(dt) => v.visit_opaque_datetime(dt),
         Value::Array(array) => v.visit_array(array),
         Value::InlineTable(table) => v.visit_inline_table(table),
     }
@@ -230,7 +247,10 @@ macro_rules! empty_visit {
 }
 
 empty_visit!(visit_boolean, Formatted<bool>);
+#[cfg(feature = "datetime")]
 empty_visit!(visit_datetime, Formatted<Datetime>);
+empty_visit!(visit_opaque_datetime, Formatted<OpaqueDatetime>);
 empty_visit!(visit_float, Formatted<f64>);
 empty_visit!(visit_integer, Formatted<i64>);
+empty_visit!(visit_wide_integer, Formatted<i128>);
 empty_visit!(visit_string, Formatted<String>);
@@ -85,8 +85,12 @@
 //! For a more complex example where the visitor has internal state, see `examples/visit.rs`
 //! [on GitHub](https://github.com/ordian/toml_edit/blob/master/examples/visit.rs).
 
+use alloc::string::String;
+
+#[cfg(feature = "datetime")]
+use crate::Datetime;
 use crate::{
-    Array, ArrayOfTables, Datetime, Document, Formatted, InlineTable, Item, KeyMut, Table,
+    Array, ArrayOfTables, Document, Formatted, InlineTable, Item, KeyMut, OpaqueDatetime, Table,
     TableLike, Value,
 };
 
@@ -136,10 +140,15 @@ pub trait VisitMut {
         visit_boolean_mut(self, node)
     }
 
+    #[cfg(feature = "datetime")]
     fn visit_datetime_mut(&mut self, node: &mut Formatted<Datetime>) {
         visit_datetime_mut(self, node);
     }
 
+    fn visit_opaque_datetime_mut(&mut self, node: &mut Formatted<OpaqueDatetime>) {
+        visit_opaque_datetime_mut(self, node);
+    }
+
     fn visit_float_mut(&mut self, node: &mut Formatted<f64>) {
         visit_float_mut(self, node)
     }
@@ -148,6 +157,10 @@ pub trait VisitMut {
         visit_integer_mut(self, node)
     }
 
+    fn visit_wide_integer_mut(&mut self, node: &mut Formatted<i128>) {
+        visit_wide_integer_mut(self, node)
+    }
+
     fn visit_string_mut(&mut self, node: &mut Formatted<String>) {
         visit_string_mut(self, node)
     }
@@ -227,9 +240,12 @@ where
     match node {
         Value::String(s) => v.visit_string_mut(s),
         Value::Integer(i) => v.visit_integer_mut(i),
+        Value::WideInteger(i) => v.visit_wide_integer_mut(i),
         Value::Float(f) => v.visit_float_mut(f),
         Value::Boolean(b) => v.visit_boolean_mut(b),
+        #[cfg(feature = "datetime")]
         Value::Datetime(dt) => v.visit_datetime_mut(dt),
+        Value::OpaqueDatetime(dt) => v.visit_opaque_datetime_mut(dt),
         Value::Array(array) => v.visit_array_mut(array),
         Value::InlineTable(table) => v.visit_inline_table_mut(table),
     }
@@ -246,7 +262,10 @@ macro_rules! empty_visit_mut {
 }
 
 empty_visit_mut!(visit_boolean_mut, Formatted<bool>);
+#[cfg(feature = "datetime")]
 empty_visit_mut!(visit_datetime_mut, Formatted<Datetime>);
+empty_visit_mut!(visit_opaque_datetime_mut, Formatted<OpaqueDatetime>);
 empty_visit_mut!(visit_float_mut, Formatted<f64>);
 empty_visit_mut!(visit_integer_mut, Formatted<i64>);
+empty_visit_mut!(visit_wide_integer_mut, Formatted<i128>);
 empty_visit_mut!(visit_string_mut, Formatted<String>);
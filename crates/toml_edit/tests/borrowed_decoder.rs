@@ -0,0 +1,89 @@
+#![cfg(feature = "datetime")]
+
+#[derive(Copy, Clone)]
+pub struct Decoder;
+
+impl toml_test_harness::Decoder for Decoder {
+    fn name(&self) -> &str {
+        "toml_edit::borrowed"
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+        let data = std::str::from_utf8(data).map_err(toml_test_harness::Error::new)?;
+        let document =
+            toml_edit::borrowed::Document::parse(data).map_err(toml_test_harness::Error::new)?;
+        table_to_decoded(&document)
+    }
+}
+
+fn item_to_decoded(
+    value: &toml_edit::borrowed::Item<'_>,
+) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+    match value {
+        toml_edit::borrowed::Item::Value(v) => value_to_decoded(v),
+        toml_edit::borrowed::Item::Table(v) => table_to_decoded(v),
+        toml_edit::borrowed::Item::ArrayOfTables(v) => {
+            let v: Result<_, toml_test_harness::Error> = v.iter().map(table_to_decoded).collect();
+            Ok(toml_test_harness::Decoded::Array(v?))
+        }
+    }
+}
+
+fn value_to_decoded(
+    value: &toml_edit::borrowed::Value<'_>,
+) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+    match value {
+        toml_edit::borrowed::Value::Integer(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::from(*v.value()),
+        )),
+        toml_edit::borrowed::Value::WideInteger(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::Integer(v.value().to_string()),
+        )),
+        toml_edit::borrowed::Value::String(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::from(v.value().as_ref()),
+        )),
+        toml_edit::borrowed::Value::Float(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::from(*v.value()),
+        )),
+        toml_edit::borrowed::Value::Datetime(_) => {
+            datetime_to_decoded(&value.as_datetime().unwrap())
+        }
+        toml_edit::borrowed::Value::Boolean(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::from(*v.value()),
+        )),
+        toml_edit::borrowed::Value::Array(v) => {
+            let v: Result<_, toml_test_harness::Error> =
+                v.value().iter().map(value_to_decoded).collect();
+            Ok(toml_test_harness::Decoded::Array(v?))
+        }
+        toml_edit::borrowed::Value::InlineTable(v) => table_to_decoded(v.value()),
+    }
+}
+
+fn table_to_decoded(
+    value: &toml_edit::borrowed::Table<'_>,
+) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+    let table: Result<_, toml_test_harness::Error> = value
+        .iter()
+        .map(|(k, v)| {
+            let k = k.get().to_owned();
+            let v = item_to_decoded(v)?;
+            Ok((k, v))
+        })
+        .collect();
+    Ok(toml_test_harness::Decoded::Table(table?))
+}
+
+fn datetime_to_decoded(
+    v: &toml_edit::Datetime,
+) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+    let value = v.to_string();
+    let value = match (v.date.is_some(), v.time.is_some(), v.offset.is_some()) {
+        (true, true, true) => toml_test_harness::DecodedValue::Datetime(value),
+        (true, true, false) => toml_test_harness::DecodedValue::DatetimeLocal(value),
+        (true, false, false) => toml_test_harness::DecodedValue::DateLocal(value),
+        (false, true, false) => toml_test_harness::DecodedValue::TimeLocal(value),
+        _ => unreachable!("Unsupported case"),
+    };
+    Ok(toml_test_harness::Decoded::Value(value))
+}
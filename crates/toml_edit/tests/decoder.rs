@@ -42,24 +42,17 @@ fn value_to_decoded(
         toml_edit::Value::Integer(v) => Ok(toml_test_harness::Decoded::Value(
             toml_test_harness::DecodedValue::from(*v.value()),
         )),
+        toml_edit::Value::WideInteger(v) => Ok(toml_test_harness::Decoded::Value(
+            toml_test_harness::DecodedValue::Integer(v.value().to_string()),
+        )),
         toml_edit::Value::String(v) => Ok(toml_test_harness::Decoded::Value(
             toml_test_harness::DecodedValue::from(v.value()),
         )),
         toml_edit::Value::Float(v) => Ok(toml_test_harness::Decoded::Value(
             toml_test_harness::DecodedValue::from(*v.value()),
         )),
-        toml_edit::Value::Datetime(v) => {
-            let v = v.value();
-            let value = v.to_string();
-            let value = match (v.date.is_some(), v.time.is_some(), v.offset.is_some()) {
-                (true, true, true) => toml_test_harness::DecodedValue::Datetime(value),
-                (true, true, false) => toml_test_harness::DecodedValue::DatetimeLocal(value),
-                (true, false, false) => toml_test_harness::DecodedValue::DateLocal(value),
-                (false, true, false) => toml_test_harness::DecodedValue::TimeLocal(value),
-                _ => unreachable!("Unsupported case"),
-            };
-            Ok(toml_test_harness::Decoded::Value(value))
-        }
+        toml_edit::Value::Datetime(v) => datetime_to_decoded(v.value()),
+        toml_edit::Value::OpaqueDatetime(v) => datetime_to_decoded(&v.value().to_datetime()),
         toml_edit::Value::Boolean(v) => Ok(toml_test_harness::Decoded::Value(
             toml_test_harness::DecodedValue::from(*v.value()),
         )),
@@ -98,3 +91,17 @@ fn inline_table_to_decoded(
         .collect();
     Ok(toml_test_harness::Decoded::Table(table?))
 }
+
+fn datetime_to_decoded(
+    v: &toml_edit::Datetime,
+) -> Result<toml_test_harness::Decoded, toml_test_harness::Error> {
+    let value = v.to_string();
+    let value = match (v.date.is_some(), v.time.is_some(), v.offset.is_some()) {
+        (true, true, true) => toml_test_harness::DecodedValue::Datetime(value),
+        (true, true, false) => toml_test_harness::DecodedValue::DatetimeLocal(value),
+        (true, false, false) => toml_test_harness::DecodedValue::DateLocal(value),
+        (false, true, false) => toml_test_harness::DecodedValue::TimeLocal(value),
+        _ => unreachable!("Unsupported case"),
+    };
+    Ok(toml_test_harness::Decoded::Value(value))
+}
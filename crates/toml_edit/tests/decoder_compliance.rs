@@ -1,8 +1,26 @@
+#[cfg(feature = "datetime")]
 mod decoder;
 
+#[cfg(feature = "datetime")]
 fn main() {
     let decoder = decoder::Decoder;
     let mut harness = toml_test_harness::DecoderHarness::new(decoder);
-    harness.ignore(["valid/string/escape-esc.toml"]).unwrap();
+    let mut ignored = vec!["valid/string/escape-esc.toml"];
+    if cfg!(feature = "toml-1.1") {
+        // TOML 1.1 allows newlines and a trailing comma within inline tables, and Unicode
+        // letters in bare keys
+        ignored.extend([
+            "invalid/inline-table/linebreak-1.toml",
+            "invalid/inline-table/linebreak-2.toml",
+            "invalid/inline-table/linebreak-3.toml",
+            "invalid/inline-table/linebreak-4.toml",
+            "invalid/inline-table/trailing-comma.toml",
+            "invalid/key/special-character.toml",
+        ]);
+    }
+    harness.ignore(ignored).unwrap();
     harness.test();
 }
+
+#[cfg(not(feature = "datetime"))]
+fn main() {}
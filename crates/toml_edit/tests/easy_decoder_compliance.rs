@@ -5,7 +5,20 @@ mod easy_decoder;
 fn main() {
     let decoder = easy_decoder::Decoder;
     let mut harness = toml_test_harness::DecoderHarness::new(decoder);
-    harness.ignore(["valid/string/escape-esc.toml"]).unwrap();
+    let mut ignored = vec!["valid/string/escape-esc.toml"];
+    if cfg!(feature = "toml-1.1") {
+        // TOML 1.1 allows newlines and a trailing comma within inline tables, and Unicode
+        // letters in bare keys
+        ignored.extend([
+            "invalid/inline-table/linebreak-1.toml",
+            "invalid/inline-table/linebreak-2.toml",
+            "invalid/inline-table/linebreak-3.toml",
+            "invalid/inline-table/linebreak-4.toml",
+            "invalid/inline-table/trailing-comma.toml",
+            "invalid/key/special-character.toml",
+        ]);
+    }
+    harness.ignore(ignored).unwrap();
     harness.test();
 }
 
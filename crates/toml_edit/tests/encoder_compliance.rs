@@ -1,9 +1,15 @@
+#[cfg(feature = "datetime")]
 mod decoder;
+#[cfg(feature = "datetime")]
 mod encoder;
 
+#[cfg(feature = "datetime")]
 fn main() {
     let encoder = encoder::Encoder;
     let decoder = decoder::Decoder;
     let harness = toml_test_harness::EncoderHarness::new(encoder, decoder);
     harness.test();
 }
+
+#[cfg(not(feature = "datetime"))]
+fn main() {}
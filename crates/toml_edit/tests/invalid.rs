@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use toml_edit::Document;
 
 fn main() {
@@ -5,8 +7,7 @@ fn main() {
     let tests = toml_test_data::invalid()
         .map(|case| {
             libtest_mimic::Trial::test(case.name.display().to_string(), || {
-                let expect_path =
-                    std::path::Path::new("tests/fixtures").join(case.name.with_extension("stderr"));
+                let expect_path = expect_path(case.name);
                 let err = match run_case(case.fixture) {
                     Ok(()) => "".to_owned(),
                     Err(err) => err,
@@ -19,6 +20,19 @@ fn main() {
     libtest_mimic::run(&args, tests).exit()
 }
 
+// Under TOML 1.1, some cases are valid or fail elsewhere, and have their own expectations
+fn expect_path(name: &Path) -> PathBuf {
+    let name = name.with_extension("stderr");
+    let fixtures = Path::new("tests/fixtures");
+    if cfg!(feature = "toml-1.1") {
+        let toml_1_1 = fixtures.join("toml-1.1").join(&name);
+        if toml_1_1.exists() {
+            return toml_1_1;
+        }
+    }
+    fixtures.join(name)
+}
+
 fn run_case(input: &[u8]) -> Result<(), String> {
     let raw = std::str::from_utf8(input).map_err(|e| e.to_string())?;
     let _ = raw.parse::<Document>().map_err(|e| e.to_string())?;
@@ -77,3 +77,279 @@ other = "world"
 "#;
     assert_eq(actual, expected);
 }
+
+#[test]
+fn dotted_to_headers() {
+    let toml = r#"title = "example"
+# the owner
+owner.name = "Tom"
+owner.dob.year = 1979
+[servers]
+alpha.ip = "10.0.0.1"
+"#;
+    let mut doc = toml.parse::<Document>().unwrap();
+
+    assert!(doc.convert_dotted_to_headers(&[]));
+
+    let actual = doc.to_string();
+    let expected = r#"title = "example"
+
+# the owner
+[owner]
+name = "Tom"
+
+[owner.dob]
+year = 1979
+[servers]
+
+[servers.alpha]
+ip = "10.0.0.1"
+"#;
+    assert_eq(actual, expected);
+}
+
+#[test]
+fn dotted_to_headers_missing_path() {
+    let mut doc = "a = 1".parse::<Document>().unwrap();
+    assert!(!doc.convert_dotted_to_headers(&["a"]));
+    assert!(!doc.convert_dotted_to_headers(&["b"]));
+}
+
+#[test]
+fn headers_to_dotted() {
+    let toml = r#"[package]
+name = "foo"
+
+[package.metadata.docs]
+all-features = true
+
+[profile.release]
+lto = true
+"#;
+    let mut doc = toml.parse::<Document>().unwrap();
+
+    assert!(doc.convert_headers_to_dotted(&[], 3));
+
+    let actual = doc.to_string();
+    let expected = r#"package.name = "foo"
+package.metadata.docs.all-features = true
+profile.release.lto = true
+"#;
+    assert_eq(actual, expected);
+}
+
+#[test]
+fn headers_to_dotted_max_depth() {
+    let toml = r#"[package]
+name = "foo"
+
+[package.metadata.docs]
+all-features = true
+"#;
+    let mut doc = toml.parse::<Document>().unwrap();
+
+    assert!(doc.convert_headers_to_dotted(&[], 1));
+
+    let actual = doc.to_string();
+    let expected = r#"[package]
+name = "foo"
+
+[package.metadata]
+docs.all-features = true
+"#;
+    assert_eq(actual, expected);
+}
+
+#[test]
+fn headers_to_dotted_keeps_comments() {
+    let toml = r#"[a]
+b = 1
+
+# about c
+[c]
+d = 1
+e = 2
+
+[f]
+# about g
+g = 1
+"#;
+    let mut doc = toml.parse::<Document>().unwrap();
+
+    assert!(doc.convert_headers_to_dotted(&[], 1));
+
+    let actual = doc.to_string();
+    let expected = r#"a.b = 1
+
+# about c
+[c]
+d = 1
+e = 2
+
+[f]
+# about g
+g = 1
+"#;
+    assert_eq(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn document_to_json() {
+    let toml = r#"
+title = "example"
+ratio = 0.5
+weird = [nan, -inf]
+when = 1979-05-27T07:32:00-08:00
+dates = [1979-05-27, 07:32:00]
+
+[owner]
+name = "Tom"
+tags = { a = true }
+
+[[items]]
+id = 1
+
+[[items]]
+id = 2
+"#;
+    let doc = toml.parse::<Document>().unwrap();
+
+    let actual = serde_json::to_string_pretty(&doc.to_json()).unwrap();
+    let expected = r#"{
+  "title": "example",
+  "ratio": 0.5,
+  "weird": [
+    "nan",
+    "-inf"
+  ],
+  "when": "1979-05-27T07:32:00-08:00",
+  "dates": [
+    "1979-05-27",
+    "07:32:00"
+  ],
+  "owner": {
+    "name": "Tom",
+    "tags": {
+      "a": true
+    }
+  },
+  "items": [
+    {
+      "id": 1
+    },
+    {
+      "id": 2
+    }
+  ]
+}"#;
+    assert_eq(expected, actual);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn document_from_json() {
+    let json = serde_json::json!({
+        "package": {
+            "name": "foo",
+            "version": "0.1.0",
+            "authors": ["someone <someone@example.com>", "someone else <else@example.com>"],
+        },
+        "zebra": 1,
+        "dependencies": {
+            "serde": {"version": "1", "features": ["derive"]},
+            "serde_json": "1",
+        },
+        "bin": [{"name": "a", "path": "src/bin/a.rs"}, {"name": "b", "path": "src/bin/b.rs", "test": false}],
+        "points": [{"x": 1, "y": 2}],
+        "empty": {},
+    });
+    let doc = Document::from_json(&json).unwrap();
+
+    let expected = r#"zebra = 1
+points = [{ x = 1, y = 2 }]
+empty = {}
+
+[package]
+name = "foo"
+version = "0.1.0"
+authors = ["someone <someone@example.com>", "someone else <else@example.com>"]
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+
+[[bin]]
+name = "a"
+path = "src/bin/a.rs"
+
+[[bin]]
+name = "b"
+path = "src/bin/b.rs"
+test = false
+"#;
+    assert_eq(expected, doc.to_string());
+    assert_eq!(doc.to_json(), json);
+
+    assert!(Document::from_json(&serde_json::json!([1])).is_err());
+    assert!(Document::from_json(&serde_json::json!({ "a": null })).is_err());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn document_json_datetimes() {
+    use toml_edit::JsonDatetime;
+
+    let toml = r#"offset = 1979-05-27T00:32:00.5-07:00
+local = 1969-12-31T23:59:59
+date = 2000-03-01
+time = 07:32:00
+nested = [{ at = 1970-01-01T00:00:00Z }]
+"#;
+    let doc = toml.parse::<Document>().unwrap();
+
+    assert_eq!(
+        doc.to_json_with(JsonDatetime::UnixSeconds),
+        serde_json::json!({
+            "offset": 296638320.5,
+            "local": -1,
+            "date": 951868800,
+            "time": "07:32:00",
+            "nested": [{"at": 0}],
+        })
+    );
+
+    let tagged = doc.to_json_with(JsonDatetime::Tagged);
+    assert_eq!(
+        tagged["date"],
+        serde_json::json!({"$__toml_private_datetime": "2000-03-01"})
+    );
+    let doc = Document::from_json(&tagged).unwrap();
+    assert_eq(toml, doc.to_string());
+    assert_eq!(doc.to_json_with(JsonDatetime::Tagged), tagged);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_key_order() {
+    let json: serde_json::Value =
+        serde_json::from_str(r#"{"zebra": 1, "apple": 2, "mango": {"b": 1, "a": 2}}"#).unwrap();
+    let doc = Document::from_json(&json).unwrap();
+    assert_eq(
+        r#"zebra = 1
+apple = 2
+mango = { b = 1, a = 2 }
+"#,
+        doc.to_string(),
+    );
+
+    let json = doc.to_json();
+    let keys = json.as_object().unwrap().keys().collect::<Vec<_>>();
+    assert_eq!(keys, ["zebra", "apple", "mango"]);
+    let keys = json["mango"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["b", "a"]);
+}
@@ -2,7 +2,9 @@ use std::fmt;
 use std::iter::FromIterator;
 
 use snapbox::assert_eq;
-use toml_edit::{array, table, value, Document, Item, Key, Table, Value};
+use toml_edit::{
+    array, table, value, Decor, Document, Item, ItemKind, Key, Table, TriviaKind, Value,
+};
 
 macro_rules! parse_key {
     ($s:expr) => {{
@@ -494,6 +496,151 @@ fn test_sort_values_by() {
     );
 }
 
+#[test]
+fn test_sort_keys_recursive() {
+    given(
+        r#"
+        [a.z]
+        y = 1
+
+        [a]
+        # this comment is attached to b
+        b = 2 # as well as this
+        a = 1
+        d.y = 1
+        d.x = 2
+
+        [[a.c]]
+        n = 1
+        m = 2
+
+        [[a.c]]
+        l = 3
+
+        [a.e]"#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        let a = as_table!(a);
+        a.sort_keys_recursive();
+    })
+    .produces_display(
+        r#"
+        [a]
+        a = 1
+        # this comment is attached to b
+        b = 2 # as well as this
+        d.x = 2
+        d.y = 1
+
+        [[a.c]]
+        m = 2
+        n = 1
+
+        [[a.c]]
+        l = 3
+
+        [a.e]
+
+        [a.z]
+        y = 1
+"#,
+    );
+}
+
+#[test]
+fn test_sort_values_by_path() {
+    given(
+        r#"
+        [package]
+        name = "demo"
+        version = "1.0.0"
+
+        [dependencies]
+        serde = "1"
+        regex = "1"
+        target.b = 1
+        target.a = 2
+
+        [[bin]]
+        name = "main"
+        path = "main.rs""#,
+    )
+    .running(|root| {
+        root.sort_values_by_path(|path, k1, _, k2, _| match path {
+            ["dependencies"] | ["dependencies", "target"] => k1.get().cmp(k2.get()),
+            ["bin"] => k2.get().cmp(k1.get()),
+            _ => std::cmp::Ordering::Equal,
+        });
+    })
+    .produces_display(
+        r#"
+        [package]
+        name = "demo"
+        version = "1.0.0"
+
+        [dependencies]
+        regex = "1"
+        serde = "1"
+        target.a = 2
+        target.b = 1
+
+        [[bin]]
+        path = "main.rs"
+        name = "main"
+"#,
+    );
+}
+
+#[test]
+fn test_cursor_reorder() {
+    given(
+        r#"
+        [a]
+        # group one
+        x1 = 1
+        y1 = 2
+        x2 = 3 # trailing
+        y2 = 4
+        "#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        let a = as_table!(a);
+        // Move the `x` keys in front of the `y` keys, keeping their formatting.
+        let mut cursor = a.cursor_front();
+        let mut xs = Vec::new();
+        while let Some(key) = cursor.key() {
+            if key.get().starts_with('x') {
+                xs.push(cursor.remove_current().unwrap());
+            } else {
+                cursor.move_next();
+            }
+        }
+        let mut cursor = a.cursor_front();
+        for (key, item) in xs {
+            assert!(cursor.insert_formatted_before_cursor(&key, item).is_none());
+        }
+        assert_eq!(cursor.key().map(|k| k.get()), Some("y1"));
+        assert!(cursor.move_prev());
+        assert_eq!(cursor.key().map(|k| k.get()), Some("x2"));
+        // Re-inserting an existing key moves it
+        let old = cursor.insert_before_cursor("y2", toml_edit::value(5));
+        assert_eq!(old.and_then(|i| i.as_integer()), Some(4));
+        assert_eq!(cursor.key().map(|k| k.get()), Some("x2"));
+    })
+    .produces_display(
+        r#"
+        [a]
+        # group one
+        x1 = 1
+y2 = 5
+        x2 = 3 # trailing
+        y1 = 2
+        "#,
+    );
+}
+
 #[test]
 fn test_set_position() {
     given(
@@ -687,6 +834,79 @@ fn test_format_array() {
     );
 }
 
+#[test]
+fn test_format_subtree() {
+    given(
+        r#"
+[untouched]
+a   =   0x10   # hex
+
+[touched]
+b   =   [ 1,{ c=0o7 },
+  "d" ]
+e   =   'e'
+[touched.nested]
+'f'=  1_000
+"#,
+    )
+    .running(|root| {
+        root["touched"].fmt_recursive();
+    })
+    .produces_display(
+        r#"
+[untouched]
+a   =   0x10   # hex
+
+[touched]
+b = [1, { c = 7 }, "d"]
+e = "e"
+[touched.nested]
+f = 1000
+"#,
+    );
+}
+
+#[test]
+fn test_format_subtree_with_options() {
+    let options = toml_edit::FormatOptions::new()
+        .indent_tables(true)
+        .indent(2)
+        .array_width(12);
+    given(
+        r#"
+[untouched]
+a   =   [ 1,2 ]   # kept
+
+[touched]
+b   =   [ 1,{ c=0o7 },
+  "d" ]
+e   =   'e'
+[touched.nested]
+'f'=  1_000
+"#,
+    )
+    .running(|root| {
+        root["touched"].fmt_with(&options);
+    })
+    .produces_display(
+        r#"
+[untouched]
+a   =   [ 1,2 ]   # kept
+
+[touched]
+b = [
+  1,
+  { c = 0o7 },
+  "d",
+]
+e = 'e'
+
+  [touched.nested]
+  'f' = 1_000
+"#,
+    );
+}
+
 macro_rules! as_inline_table {
     ($entry:ident) => {{
         assert!($entry.is_value());
@@ -815,6 +1035,92 @@ fn test_inline_table_append() {
     assert_eq!(b.len(), 3);
 }
 
+#[test]
+fn test_table_inline_table_recursive_round_trip() {
+    let inline = r#"pkg = { name = "demo", dep.serde.version = "1", meta = { tags = ["a"], bin = [{ path = "main.rs" }, { path = "cli.rs", opt = { lto = true } }] } }
+"#;
+    let tables = r#"[pkg]
+name = "demo"
+dep.serde.version = "1"
+
+[pkg.meta]
+tags = ["a"]
+
+[[pkg.meta.bin]]
+path = "main.rs"
+
+[[pkg.meta.bin]]
+path = "cli.rs"
+
+[pkg.meta.bin.opt]
+lto = true
+"#;
+
+    let mut doc = inline.parse::<Document>().unwrap();
+    let pkg = doc
+        .remove("pkg")
+        .and_then(|pkg| pkg.as_inline_table().cloned())
+        .unwrap();
+    doc["pkg"] = Item::Table(pkg.into_table_recursive());
+    assert_eq(tables, doc.to_string());
+
+    let pkg = doc.remove("pkg").unwrap().into_table().unwrap();
+    doc["pkg"] = value(pkg.into_inline_table_recursive());
+    assert_eq(inline, doc.to_string());
+}
+
+#[test]
+fn test_array_of_tables_array_round_trip() {
+    let tables = r#"name = "demo"
+
+# the main binary
+[[bin]] # main
+path = "main.rs"
+
+[[bin]]
+path = "cli.rs"
+"#;
+    let inline = r#"name = "demo"
+bin = [
+    # the main binary
+    # main
+    { path = "main.rs" },
+    { path = "cli.rs" },
+]
+"#;
+
+    let mut doc = tables.parse::<Document>().unwrap();
+    let bin = doc.remove("bin").unwrap().into_array_of_tables().unwrap();
+    doc["bin"] = value(bin.into_array());
+    assert_eq(inline, doc.to_string());
+
+    let bin = doc.remove("bin").unwrap().into_value().unwrap();
+    let bin = bin
+        .as_array()
+        .unwrap()
+        .clone()
+        .into_array_of_tables()
+        .unwrap();
+    doc.insert("bin", Item::ArrayOfTables(bin));
+    assert_eq(
+        r#"name = "demo"
+
+# the main binary
+# main
+[[bin]]
+path = "main.rs"
+
+[[bin]]
+path = "cli.rs"
+"#,
+        doc.to_string(),
+    );
+
+    let mixed = toml_edit::Array::from_iter(vec![Value::from(1)]);
+    assert_eq!(mixed.into_array_of_tables().unwrap_err().len(), 1);
+    assert!(toml_edit::Array::new().into_array_of_tables().is_err());
+}
+
 #[test]
 fn test_insert_dotted_into_std_table() {
     given("")
@@ -853,3 +1159,1432 @@ src.git = "https://github.com/nixos/nixpkgs"
 "#,
         );
 }
+
+#[test]
+fn test_escape_str_round_trips() {
+    use toml_edit::StringStyle;
+
+    let styles = [
+        StringStyle::Basic,
+        StringStyle::MultilineBasic,
+        StringStyle::Literal,
+        StringStyle::MultilineLiteral,
+    ];
+    let inputs = [
+        "",
+        "plain",
+        "it's",
+        "ends with '",
+        "ends with ''",
+        "'''",
+        r#"say "hi""#,
+        r#"""""#,
+        r"C:\dir\",
+        "tab\there",
+        "one\ntwo\n",
+        "\nleading newline",
+        "crlf\r\n",
+        "bell\u{7}",
+        "del\u{7f}",
+        "ünïcödé ✓",
+    ];
+    for input in inputs {
+        for style in styles {
+            let value = match Value::from_str_escaped(input, style) {
+                Some(value) => value,
+                None => {
+                    assert!(
+                        matches!(style, StringStyle::Literal | StringStyle::MultilineLiteral),
+                        "{:?} should be representable in {:?}",
+                        input,
+                        style
+                    );
+                    continue;
+                }
+            };
+            let rendered = value.to_string();
+            let parsed = rendered.parse::<Value>().unwrap_or_else(|e| {
+                panic!(
+                    "{:?} in {:?} rendered as {:?}: {}",
+                    input, style, rendered, e
+                )
+            });
+            assert_eq!(
+                parsed.as_str(),
+                Some(input),
+                "{:?} in {:?}",
+                rendered,
+                style
+            );
+        }
+    }
+
+    assert_eq(
+        r#"'C:\dir'"#,
+        toml_edit::escape_str(r"C:\dir", StringStyle::Literal).unwrap(),
+    );
+    assert_eq(
+        r#""tab\there \"quoted\"""#,
+        toml_edit::escape_str("tab\there \"quoted\"", StringStyle::Basic).unwrap(),
+    );
+    assert_eq!(toml_edit::escape_str("it's", StringStyle::Literal), None);
+    assert_eq!(
+        toml_edit::escape_str("a'''b", StringStyle::MultilineLiteral),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_write_to() {
+    let input = r#"# leading
+a = { b = [1, 2], c = "d" }  # inline
+
+[e]
+f = 1.5
+"#;
+    let doc = input.parse::<Document>().unwrap();
+
+    let mut out = Vec::new();
+    doc.write_to(&mut out).unwrap();
+    assert_eq(input, String::from_utf8(out).unwrap());
+
+    let mut out = Vec::new();
+    doc["e"].as_table().unwrap().write_to(&mut out).unwrap();
+    assert_eq("f = 1.5\n", String::from_utf8(out).unwrap());
+
+    let mut out = Vec::new();
+    doc["a"].as_value().unwrap().write_to(&mut out).unwrap();
+    assert_eq(
+        r#" { b = [1, 2], c = "d" }  # inline"#,
+        String::from_utf8(out).unwrap(),
+    );
+
+    struct Full;
+    impl std::io::Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let err = doc.write_to(&mut Full).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_metrics() {
+    use toml_edit::ValueKind;
+
+    let doc = r#"
+title = "metrics"
+ports = [8000, 8001]
+
+[[servers]]
+name = "alpha"
+tags = { env = "prod", roles = ["web", "db"] }
+
+[[servers]]
+name = "beta"
+
+[servers.limits]
+cpu.cores = 4
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    assert_eq!(doc.len_recursive(), 11);
+    assert_eq!(
+        doc["servers"]
+            .as_array_of_tables()
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .len_recursive(),
+        4
+    );
+    assert_eq!(doc.max_depth(), 3);
+    assert_eq!(doc.count_kind(ValueKind::String), 6);
+    assert_eq!(doc.count_kind(ValueKind::Integer), 3);
+    assert_eq!(doc.count_kind(ValueKind::Array), 2);
+    assert_eq!(doc.count_kind(ValueKind::InlineTable), 1);
+    assert_eq!(doc.count_kind(ValueKind::Datetime), 0);
+    assert_eq!(Document::new().max_depth(), 0);
+}
+
+#[test]
+fn test_expect() {
+    let doc = r#"
+title = "TOML"
+numbers = [1, 2, 3]
+
+[owner]
+name = "Tom"
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(doc["title"].expect_str(), "TOML");
+    assert_eq!(doc["numbers"].expect_array().len(), 3);
+    assert_eq!(doc["owner"].expect_table()["name"].expect_str(), "Tom");
+
+    let message = |f: fn(&Document)| {
+        let err = std::panic::catch_unwind(|| f(&doc)).unwrap_err();
+        err.downcast::<String>().unwrap()
+    };
+    assert_eq!(
+        *message(|doc| {
+            doc["title"].expect_integer();
+        }),
+        r#"expected an integer, found string: "\"TOML\"""#
+    );
+    assert_eq!(
+        *message(|doc| {
+            doc["owner"].expect_value();
+        }),
+        r#"expected a value, found table: "name = \"Tom\"""#
+    );
+    assert_eq!(
+        *message(|doc| {
+            doc["owner"]["name"].expect_table();
+        }),
+        r#"expected a table, found string: "\"Tom\"""#
+    );
+    let err = std::panic::catch_unwind(|| Item::None.expect_table()).unwrap_err();
+    assert_eq!(
+        *err.downcast::<String>().unwrap(),
+        "expected a table, found none"
+    );
+}
+
+#[test]
+fn test_expect_path() {
+    let doc = r#"
+[owner]
+name = "Tom"
+"dotted.key" = 1
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(doc.expect_path("owner.name").expect_str(), "Tom");
+    assert_eq!(doc.expect_path("owner.\"dotted.key\"").expect_integer(), 1);
+    assert_eq!(doc.expect_path("owner").expect_table().len(), 2);
+    assert!(doc.expect_path("owner.age").item().is_none());
+
+    let message = |f: fn(&Document)| {
+        let err = std::panic::catch_unwind(|| f(&doc)).unwrap_err();
+        err.downcast::<String>().unwrap()
+    };
+    assert_eq!(
+        *message(|doc| {
+            doc.expect_path("owner.name").expect_integer();
+        }),
+        r#"expected an integer at `owner.name`, found string: "\"Tom\"""#
+    );
+    assert_eq!(
+        *message(|doc| {
+            doc.expect_path("owner.age").expect_integer();
+        }),
+        "expected an integer at `owner.age`, found none"
+    );
+    assert_eq!(
+        *message(|doc| {
+            doc.expect_path("owner.name.first").expect_str();
+        }),
+        "expected a string at `owner.name.first`, found none"
+    );
+    assert!(message(|doc| {
+        doc.expect_path("owner.");
+    })
+    .starts_with("invalid key expression `owner.`"));
+}
+
+#[test]
+fn test_values_of() {
+    let doc = r#"
+name = "demo"
+version = 2
+tags = ["a", "b"]
+inline = { x = 1 }
+
+[dependencies]
+serde = "1"
+
+[[bin]]
+name = "demo"
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    let strings = doc.values_of::<&str>().collect::<Vec<_>>();
+    assert_eq!(strings, [("name", "demo")]);
+    let integers = doc.values_of::<i64>().collect::<Vec<_>>();
+    assert_eq!(integers, [("version", 2)]);
+    let tables = doc.tables().map(|(key, _)| key).collect::<Vec<_>>();
+    assert_eq!(tables, ["dependencies"]);
+    let arrays = doc
+        .arrays()
+        .map(|(key, array)| (key, array.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(arrays, [("tags", 2)]);
+    let inline = doc
+        .values_of::<&toml_edit::InlineTable>()
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(inline, ["inline"]);
+    let bins = doc
+        .values_of::<&toml_edit::ArrayOfTables>()
+        .map(|(key, bins)| (key, bins.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(bins, [("bin", 1)]);
+}
+
+#[test]
+fn test_match_template() {
+    let doc = r#"
+[package]
+name = "demo"
+version = "0.1.0"
+authors = ["a", "b"]
+
+[dependencies]
+serde = { version = "1.0", optional = true }
+
+[[bin]]
+name = "demo"
+path = "src/main.rs"
+"#
+    .parse::<Document>()
+    .unwrap();
+    let matches = |pattern: &str| {
+        let pattern = pattern.parse::<Document>().unwrap();
+        doc.match_template(&pattern)
+    };
+
+    let bindings = matches(
+        r#"
+package.name = "$name"
+package.authors = ["a", "$second"]
+dependencies = "$deps"
+bin = [{ name = "$name", path = "$path" }]
+"#,
+    );
+    let bindings = bindings.unwrap();
+    let names = bindings.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "second", "deps", "path"]);
+    assert_eq!(bindings.get("second").and_then(|i| i.as_str()), Some("b"));
+    assert!(bindings.get("deps").unwrap().is_table());
+    assert_eq!(
+        bindings.get("path").unwrap().to_string(),
+        r#""src/main.rs""#
+    );
+
+    // Repeated placeholders must bind equal items
+    assert!(matches("package.name = '$x'\npackage.version = '$x'\n").is_none());
+    // Arrays must have the same length
+    assert!(matches("package.authors = ['a']\n").is_none());
+    // Missing keys and mismatched types don't match
+    assert!(matches("package.license = '$license'\n").is_none());
+    assert!(matches("dependencies.serde.optional = 'true'\n").is_none());
+    // `$$` escapes a literal `$`
+    let doc = "price = '$5'\n".parse::<Document>().unwrap();
+    let pattern = "price = '$$5'\n".parse::<Document>().unwrap();
+    assert!(doc.match_template(&pattern).unwrap().is_empty());
+}
+
+#[test]
+fn test_format() {
+    use toml_edit::FormatOptions;
+
+    let input = r#"# top
+a=1
+arr = [ # first
+  1, # one
+  # before two
+  2
+  # end
+]
+nested = [[1,2],[3,4]]
+t = { x=1, y.z=[1,2] }
+
+[x.y]
+b  =  "s"
+[[x.y.z]]
+c=1
+
+
+# trailing
+"#;
+    let options = FormatOptions::new()
+        .indent(2)
+        .indent_tables(true)
+        .space_around_eq(false)
+        .array_width(12)
+        .trailing_newline(false);
+    let expected = r#"# top
+a=1
+arr=[ # first
+  1, # one
+  # before two
+  2,
+  # end
+]
+nested=[
+  [1, 2],
+  [3, 4],
+]
+t={ x=1, y.z=[1, 2] }
+
+  [x.y]
+  b="s"
+
+    [[x.y.z]]
+    c=1
+
+# trailing"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.format(&options);
+    assert_eq(expected, doc.to_string());
+
+    // Formatting is stable
+    let mut doc = expected.parse::<Document>().unwrap();
+    doc.format(&options);
+    assert_eq(expected, doc.to_string());
+}
+
+#[test]
+fn test_find() {
+    let doc = r#"
+name = "root"
+inline = { name = "inline", nested.name = "dotted" }
+
+[[bin]]
+name = "first"
+
+[[bin]]
+name = "second"
+"#
+    .parse::<Document>()
+    .unwrap();
+    let names = doc
+        .find(|path, _| path.last() == Some(&"name"))
+        .into_iter()
+        .map(|(path, item)| (path.join("."), item.as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("name".to_owned(), "root"),
+            ("inline.name".to_owned(), "inline"),
+            ("inline.nested.name".to_owned(), "dotted"),
+            ("bin.name".to_owned(), "first"),
+            ("bin.name".to_owned(), "second"),
+        ]
+    );
+
+    let tables = doc.find(|_, item| item.is_array_of_tables());
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].0, ["bin"]);
+}
+
+#[test]
+fn test_iter_flatten() {
+    let mut doc = r#"
+name = "root"
+inline = { name = "inline", nested.name = "dotted", empty = {} }
+
+[[bin]]
+name = "first"
+
+[z]
+list = [1, 2]
+
+[[bin]]
+name = "second"
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc["added"] = toml_edit::table();
+    doc["added"]["value"] = value(1);
+    let items = doc
+        .iter_flatten()
+        .map(|(path, item)| {
+            let path = path.iter().map(|k| k.get()).collect::<Vec<_>>().join(".");
+            (path, item.type_name())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        [
+            ("name".to_owned(), "string"),
+            ("inline.name".to_owned(), "string"),
+            ("inline.nested.name".to_owned(), "string"),
+            ("inline.empty".to_owned(), "inline table"),
+            ("bin.name".to_owned(), "string"),
+            ("z.list".to_owned(), "array"),
+            ("added.value".to_owned(), "integer"),
+            ("bin.name".to_owned(), "string"),
+        ]
+    );
+}
+
+#[test]
+fn test_array_insert_matching() {
+    let mut doc = r#"
+multi = [
+    "a", # first
+    "c"
+]
+commented = [
+  1,
+  2, # two
+]
+compact = [1,2]
+empty = []
+"#
+    .parse::<Document>()
+    .unwrap();
+    let multi = doc["multi"].as_array_mut().unwrap();
+    multi.insert_matching(1, "b");
+    multi.push_matching("d");
+    doc["commented"].as_array_mut().unwrap().push_matching(3);
+    let compact = doc["compact"].as_array_mut().unwrap();
+    compact.insert_matching(0, 0);
+    compact.push_matching(3);
+    doc["empty"].as_array_mut().unwrap().push_matching(1);
+
+    assert_eq(
+        r#"
+multi = [
+    "a", # first
+    "b",
+    "c",
+    "d"
+]
+commented = [
+  1,
+  2, # two
+  3,
+]
+compact = [0,1,2,3]
+empty = [1]
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_array_style() {
+    let mut doc = r#"
+deps = [
+	"a",
+	"b"
+]
+features = ["x", "y"]
+empty = []
+"#
+    .parse::<Document>()
+    .unwrap();
+    let style = doc["deps"].as_array().unwrap().style();
+    assert_eq!(
+        style,
+        toml_edit::ArrayStyle::Multiline {
+            indent: "\t".to_owned(),
+            trailing_comma: false,
+        }
+    );
+    assert_eq!(
+        doc["empty"].as_array().unwrap().style(),
+        toml_edit::ArrayStyle::SingleLine
+    );
+    doc["features"].as_array_mut().unwrap().apply_style(&style);
+    doc["deps"]
+        .as_array_mut()
+        .unwrap()
+        .apply_style(&toml_edit::ArrayStyle::SingleLine);
+
+    assert_eq(
+        "\ndeps = [\"a\", \"b\"]\nfeatures = [\n\t\"x\",\n\t\"y\"\n]\nempty = []\n",
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_format_array_multiline() {
+    given(
+        r#"
+a = [ 1, [2, 3],
+  "4" ]
+b = [ ]
+"#,
+    )
+    .running(|root| {
+        let a = root["a"].as_array_mut().unwrap();
+        a.fmt_multiline("    ", false);
+        let b = root["b"].as_array_mut().unwrap();
+        b.fmt_multiline("    ", true);
+    })
+    .produces_display(
+        r#"
+a = [
+    1,
+    [2, 3],
+    "4"
+]
+b = []
+"#,
+    );
+
+    let mut doc = "a = [1, 2]\n".parse::<Document>().unwrap();
+    let options = toml_edit::FormatOptions::new()
+        .array_width(0)
+        .trailing_comma(false);
+    doc.format(&options);
+    assert_eq("a = [\n    1,\n    2\n]\n", doc.to_string());
+}
+
+#[test]
+fn test_map_values_at() {
+    let mut doc = r#"
+urls = { home = "http://home", "a.b" = "http://dotted" }
+
+[[mirror]]
+url = "http://one" # primary
+tags = ["http://not-a-url"]
+
+[[mirror]]
+url = "http://two"
+"#
+    .parse::<Document>()
+    .unwrap();
+    let https = |v: &Value| Value::from(v.as_str().unwrap().replace("http:", "https:"));
+
+    assert_eq!(doc.map_values_at("mirror.url", https).unwrap(), 2);
+    assert_eq!(doc.map_values_at(r#"urls."a.b""#, https).unwrap(), 1);
+    assert_eq!(doc.map_values_at("urls.home.*", https).unwrap(), 0);
+    let mut lens = Vec::new();
+    let count = doc
+        .map_values_at("*.tags", |v| {
+            lens.push(v.as_array().unwrap().len());
+            v.clone()
+        })
+        .unwrap();
+    assert_eq!((count, lens), (1, vec![1]));
+    assert_eq(
+        r#"
+urls = { home = "http://home", "a.b" = "https://dotted" }
+
+[[mirror]]
+url = "https://one" # primary
+tags = ["http://not-a-url"]
+
+[[mirror]]
+url = "https://two"
+"#,
+        doc.to_string(),
+    );
+
+    assert!(doc.map_values_at("a..b", https).is_err());
+}
+
+#[test]
+fn test_comment_api() {
+    let mut doc = r#"
+# Package
+[package]
+name = "foo"    # crate name
+  # indented
+  version = "1.0"
+
+[[bin]] # first
+name = "a"
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    assert_eq!(doc["package"].comment().as_deref(), Some("Package"));
+    assert_eq!(doc["package"]["name"].comment(), None);
+    assert_eq!(
+        doc["package"]["name"].trailing_comment().as_deref(),
+        Some("crate name")
+    );
+    assert_eq!(doc["bin"].trailing_comment().as_deref(), Some("first"));
+    let package = doc["package"].as_table_mut().unwrap();
+    let (mut key, _) = package.get_key_value_mut("version").unwrap();
+    assert_eq!(key.comment().as_deref(), Some("indented"));
+    key.set_comment("The version\n\nof the crate");
+
+    doc["package"].set_comment("");
+    doc["package"]["name"].set_trailing_comment("renamed\nignored");
+    doc["package"]["version"].set_trailing_comment("semver");
+    doc["bin"].set_comment("Binaries");
+    doc["bin"].set_trailing_comment("");
+    assert_eq(
+        r#"
+[package]
+name = "foo"    # renamed
+  # The version
+  #
+  # of the crate
+  version = "1.0" # semver
+
+# Binaries
+[[bin]]
+name = "a"
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_header_comments() {
+    let mut doc = r#"
+# Copyright
+#
+# Licensed under MIT
+
+name = "foo"
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(
+        doc.header_comments().collect::<Vec<_>>(),
+        ["Copyright", "", "Licensed under MIT"]
+    );
+    doc.set_header_comments(["Generated"]);
+    assert_eq("# Generated\n\nname = \"foo\"\n", doc.to_string());
+    doc.set_header_comments(Vec::<String>::new());
+    assert_eq("name = \"foo\"\n", doc.to_string());
+
+    // Comments directly above the first item belong to it
+    let mut doc = "# The package\n[package]\n".parse::<Document>().unwrap();
+    assert_eq!(doc.header_comments().count(), 0);
+    doc.set_header_comments(["Generated"]);
+    assert_eq("# Generated\n\n# The package\n[package]\n", doc.to_string());
+    assert_eq!(doc["package"].comment().as_deref(), Some("The package"));
+
+    let mut doc = "# Only comments\n".parse::<Document>().unwrap();
+    assert_eq!(doc.header_comments().collect::<Vec<_>>(), ["Only comments"]);
+    doc.set_header_comments(["Replaced"]);
+    assert_eq("# Replaced\n", doc.to_string());
+}
+
+#[test]
+fn test_value_trailing_comment() {
+    let mut doc = "a = 1  \nb = 2\t# old\nc = 3 #\n"
+        .parse::<Document>()
+        .unwrap();
+    let comment = |doc: &Document, key: &str| doc[key].as_value().unwrap().trailing_comment();
+    assert_eq!(comment(&doc, "a"), None);
+    assert_eq!(comment(&doc, "b").as_deref(), Some("old"));
+    assert_eq!(comment(&doc, "c").as_deref(), Some(""));
+
+    doc["a"].as_value_mut().unwrap().set_trailing_comment("new");
+    doc["b"]
+        .as_value_mut()
+        .unwrap()
+        .set_trailing_comment("first\r\nsecond");
+    doc["c"].as_value_mut().unwrap().set_trailing_comment("");
+    assert_eq("a = 1 # new\nb = 2\t# first\nc = 3\n", doc.to_string());
+}
+
+#[test]
+fn test_validated_decor() {
+    let input = r#"# header
+[package] # the package
+  name = "demo" # name
+list = [
+  1, # one
+  2,
+  # trailing
+]
+point = { x = 1 , y = 2 }
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.audit_decor().count(), 0);
+
+    let table = doc["package"].as_table_mut().unwrap();
+    let decor = table.decor_mut();
+    assert!(decor.try_set_prefix("\n\r\n# note\n").is_ok());
+    assert!(decor.try_set_suffix("  # unterminated").is_ok());
+    assert!(decor.try_set_prefix("x = 1\n").is_err());
+    assert!(decor.try_set_suffix("\r").is_err());
+    assert_eq!(decor.prefix(), Some("\n\r\n# note\n"));
+    assert_eq!(doc.audit_decor().count(), 0);
+
+    let table = doc["package"].as_table_mut().unwrap();
+    table.key_decor_mut("name").unwrap().set_suffix("\n");
+    table["list"]
+        .as_value_mut()
+        .unwrap()
+        .decor_mut()
+        .set_prefix(" # ");
+    table["point"]
+        .as_inline_table_mut()
+        .unwrap()
+        .key_decor_mut("x")
+        .unwrap()
+        .set_prefix("\n");
+    doc.set_trailing("# end");
+    let invalid = doc
+        .audit_decor()
+        .map(|t| (t.kind(), t.path().join(".")))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        invalid,
+        [
+            (TriviaKind::KeySuffix, "package.name".to_owned()),
+            (TriviaKind::ValuePrefix, "package.list".to_owned()),
+            (TriviaKind::KeyPrefix, "package.point.x".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_arrays_of_tables() {
+    let input = r#"title = "servers"
+
+[[server]]
+name = "alpha"
+
+[server.tls]
+cert = "a.pem"
+
+[[server]]
+
+[[server]]
+name = "gamma"
+
+[[server.port]]
+number = 80
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(doc.flatten_arrays_of_tables(&[]));
+    assert_eq(
+        r#"title = "servers"
+server.0.name = "alpha"
+server.0.tls.cert = "a.pem"
+server.1 = {}
+server.2.name = "gamma"
+server.2.port.0.number = 80
+"#,
+        doc.to_string(),
+    );
+
+    let mut doc = doc.to_string().parse::<Document>().unwrap();
+    assert!(doc.expand_indexed_tables(&[]));
+    assert_eq(
+        r#"title = "servers"
+
+[[server]]
+name = "alpha"
+
+[server.tls]
+cert = "a.pem"
+
+[[server]]
+
+[[server]]
+name = "gamma"
+
+[[server.port]]
+number = 80
+"#,
+        doc.to_string(),
+    );
+
+    let mut doc = "a.0.b = 1\na.2.b = 2\nc.0 = 1\n"
+        .parse::<Document>()
+        .unwrap();
+    assert!(doc.expand_indexed_tables(&[]));
+    assert_eq("a.0.b = 1\na.2.b = 2\nc.0 = 1\n", doc.to_string());
+    assert!(!doc.expand_indexed_tables(&["missing"]));
+}
+
+#[test]
+fn test_fill_defaults_from() {
+    let template = r#"# Project name
+name = "demo"
+point = { x = 0, y = 0 }
+
+# Server settings
+[server]
+host = "localhost" # where to listen
+port = 80
+
+[[plugin]]
+name = "core"
+
+[logging]
+level = "info"
+"#;
+    let template = template.parse::<Document>().unwrap();
+    let input = r#"point = { x = 1 }
+
+[logging]
+# Quiet please
+level = "warn"
+
+[server]
+port = 8080
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.fill_defaults_from(&template);
+    assert_eq(
+        r#"point = { x = 1, y = 0 }
+# Project name
+name = "demo"
+
+[logging]
+# Quiet please
+level = "warn"
+
+[server]
+port = 8080
+host = "localhost" # where to listen
+
+[[plugin]]
+name = "core"
+"#,
+        doc.to_string(),
+    );
+
+    // Filling again changes nothing
+    let filled = doc.to_string();
+    doc.fill_defaults_from(&template);
+    assert_eq(filled, doc.to_string());
+}
+
+#[test]
+fn test_rename_key() {
+    let input = r#"first = 1
+dotted.inner = { "old key" = 1, kept = 2 }
+last = 3
+
+# Comment on the table
+[table] # trailing
+value = 1
+
+[other]
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(doc.rename_key("dotted", "renamed"));
+    assert!(doc["renamed"]["inner"]
+        .as_inline_table_mut()
+        .unwrap()
+        .rename_key("old key", "new"));
+    assert!(doc.rename_key("table", "new table"));
+    assert!(doc.rename_key("first", "first"));
+    assert!(!doc.rename_key("missing", "x"));
+    assert!(!doc.rename_key("last", "other"));
+    assert_eq(
+        r#"first = 1
+renamed.inner = { new = 1, kept = 2 }
+last = 3
+
+# Comment on the table
+["new table"] # trailing
+value = 1
+
+[other]
+"#,
+        doc.to_string(),
+    );
+    let keys = doc.iter().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(keys, ["first", "renamed", "last", "new table", "other"]);
+    assert_eq!(doc["new table"]["value"].as_integer(), Some(1));
+}
+
+#[test]
+fn test_entry_decor_and_position() {
+    let mut doc = "a = 1\nb = { x = 1 }\n".parse::<Document>().unwrap();
+    match doc.entry("b") {
+        toml_edit::Entry::Occupied(mut entry) => {
+            assert_eq!(entry.position(), 1);
+            entry.key_mut().decor_mut().set_prefix("# bee\n");
+        }
+        toml_edit::Entry::Vacant(_) => panic!("`b` is in the table"),
+    }
+    match doc.entry("c") {
+        toml_edit::Entry::Vacant(mut entry) => {
+            assert_eq!(entry.position(), 2);
+            entry.key_mut().decor_mut().set_suffix("  ");
+            entry.insert(value(3));
+        }
+        toml_edit::Entry::Occupied(_) => panic!("`c` is not in the table"),
+    }
+
+    let b = doc["b"].as_inline_table_mut().unwrap();
+    match b.entry("y") {
+        toml_edit::InlineEntry::Vacant(entry) => {
+            assert_eq!(entry.position(), 1);
+            entry.insert_with_decor(Value::from(2), Decor::new(" ", ""));
+        }
+        toml_edit::InlineEntry::Occupied(_) => panic!("`y` is not in the table"),
+    }
+    assert_eq!(b.entry("x").position(), 0);
+
+    assert_eq(
+        "a = 1\n# bee\nb = { x = 1 , y= 2 }\nc  = 3\n",
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_retain() {
+    let input = r#"
+# numbers
+a = 1
+b = "x"
+c = { x = 1, y = "y", z = 3 }
+
+[[bin]]
+name = "keep"
+
+[[bin]]
+name = "drop"
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.retain(|key, item| key != "b" && !item.is_str());
+    doc["c"]
+        .as_inline_table_mut()
+        .unwrap()
+        .retain(|_, value| value.is_integer());
+    doc["bin"]
+        .as_array_of_tables_mut()
+        .unwrap()
+        .retain(|bin| bin["name"].as_str() == Some("keep"));
+
+    assert_eq(
+        r#"
+# numbers
+a = 1
+c = { x = 1, z = 3 }
+
+[[bin]]
+name = "keep"
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_prune() {
+    let input = r#"
+name = "demo"
+legacy.mode = "old"
+legacy.flag = true
+
+[tool.old]
+deprecated = 1
+
+[tool.kept]
+
+[[plugin]]
+name = "a"
+deprecated = 2
+
+[[plugin]]
+name = "b"
+settings = { deprecated = 3, level = 1 }
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    let mut paths = Vec::new();
+    doc.prune(|path, item| {
+        paths.push(path.join("."));
+        path.last() == Some(&"deprecated") || (path[0] == "legacy" && item.is_value())
+    });
+    assert!(paths.contains(&"plugin.settings".to_owned()));
+    assert!(!paths.contains(&"plugin.settings.deprecated".to_owned()));
+
+    assert_eq(
+        r#"
+name = "demo"
+
+[tool.old]
+
+[tool.kept]
+
+[[plugin]]
+name = "a"
+
+[[plugin]]
+name = "b"
+settings = { deprecated = 3, level = 1 }
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_move_table() {
+    let mut doc = r#"[package]
+name = "demo"
+
+[[bin]]
+name = "a"
+
+[dependencies]
+serde = "1"
+
+[dependencies.regex]
+version = "1"
+
+[[bin]]
+name = "b"
+
+[features]
+default = []
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc["added"] = toml_edit::table();
+    doc["added"]["value"] = value(1);
+
+    assert!(!doc.move_table_before(&["dependencies"], &["dependencies", "regex"]));
+    assert!(!doc.move_table_before(&["missing"], &["package"]));
+    assert!(!doc.move_table_after(&["package", "name"], &["features"]));
+    assert!(!doc.move_table_after(&["package"], &[]));
+
+    assert!(doc.move_table_before(&["features"], &["dependencies"]));
+    assert!(doc.move_table_after(&["package"], &["bin"]));
+    assert!(doc.move_table_before(&["added"], &["features"]));
+
+    assert_eq(
+        r#"[[bin]]
+name = "a"
+
+[added]
+value = 1
+
+[features]
+default = []
+
+[dependencies]
+serde = "1"
+
+[dependencies.regex]
+version = "1"
+
+[[bin]]
+name = "b"
+
+[package]
+name = "demo"
+"#,
+        doc.to_string(),
+    );
+    let positions = doc
+        .as_table()
+        .iter()
+        .filter_map(|(_, item)| item.as_table().and_then(|t| t.position()))
+        .collect::<Vec<_>>();
+    assert_eq!(positions, [7, 4, 3, 2]);
+}
+
+#[test]
+fn test_insert_table_after() {
+    let mut doc = r#"[package]
+name = "demo"
+
+[dependencies.serde]
+version = "1"
+
+[features]
+"#
+    .parse::<Document>()
+    .unwrap();
+    let mut lib = toml_edit::Table::new();
+    lib["path"] = value("src/lib.rs");
+    assert!(!doc.insert_table_after(&["missing"], &["lib"], lib.clone()));
+    assert!(!doc.insert_table_after(&["package"], &["package"], lib.clone()));
+    assert!(!doc.insert_table_after(&["package"], &["missing", "lib"], lib.clone()));
+    assert!(doc.insert_table_after(&["package"], &["lib"], lib));
+
+    let mut regex = toml_edit::Table::new();
+    regex["version"] = value("1");
+    assert!(doc.insert_table_after(&["features"], &["dependencies", "regex"], regex));
+
+    assert_eq(
+        r#"[package]
+name = "demo"
+
+[lib]
+path = "src/lib.rs"
+
+[dependencies.serde]
+version = "1"
+
+[features]
+
+[dependencies.regex]
+version = "1"
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_renumber_positions() {
+    let mut doc = "[a]\n[b]\n[[c]]\n[[c]]\n".parse::<Document>().unwrap();
+    doc["b"].as_table_mut().unwrap().set_position(10);
+    doc["a"]["new"] = toml_edit::table();
+    doc["a"]["new"]["x"] = value(1);
+    let before = doc.to_string();
+
+    doc.renumber_positions();
+    assert_eq!(doc.to_string(), before);
+    let mut positions = Vec::new();
+    for (_, item) in doc.iter() {
+        match item {
+            Item::Table(table) => positions.push(table.position()),
+            Item::ArrayOfTables(array) => positions.extend(array.iter().map(|t| t.position())),
+            _ => {}
+        }
+    }
+    assert_eq!(positions, [Some(1), Some(5), Some(3), Some(4)]);
+    assert_eq!(doc["a"]["new"].as_table().unwrap().position(), Some(2));
+}
+
+#[test]
+fn test_preserve_order() {
+    let input = r#"[package]
+name = "demo"
+
+[[bin]]
+name = "a"
+
+[dependencies]
+
+[package.metadata]
+docs = true
+"#;
+    let edit = |doc: &mut Document| {
+        let mut metadata = toml_edit::Table::new();
+        metadata["docs"] = value(false);
+        doc["package"]["metadata"] = Item::Table(metadata);
+        doc["package"]["lints"] = toml_edit::table();
+        doc["package"]["lints"]["all"] = value(true);
+        let mut bin = toml_edit::Table::new();
+        bin["name"] = value("b");
+        doc["bin"].as_array_of_tables_mut().unwrap().push(bin);
+    };
+
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(!doc.preserve_order());
+    edit(&mut doc);
+    assert_eq(
+        r#"[package]
+name = "demo"
+
+[package.metadata]
+docs = false
+
+[package.lints]
+all = true
+
+[[bin]]
+name = "a"
+
+[[bin]]
+name = "b"
+
+[dependencies]
+"#,
+        doc.to_string(),
+    );
+
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.set_preserve_order(true);
+    assert!(doc.preserve_order());
+    edit(&mut doc);
+    let expected = r#"[package]
+name = "demo"
+
+[[bin]]
+name = "a"
+
+[[bin]]
+name = "b"
+
+[dependencies]
+
+[package.metadata]
+docs = false
+
+[package.lints]
+all = true
+"#;
+    assert_eq(expected, doc.to_string());
+    let spans = doc.symbols().map(|s| s.span()).collect::<Vec<_>>();
+    assert!(spans.windows(2).all(|w| w[0].end <= w[1].start));
+    assert_eq!(&expected[spans.last().unwrap().clone()], "all");
+
+    doc.set_preserve_order(false);
+    assert!(!doc.preserve_order());
+}
+
+#[test]
+fn test_make_explicit() {
+    let input = r#"top = 1
+dotted.key = 2
+
+[z]
+[a.b.c]
+x = 1
+
+[[a.list]]
+[a.list.inner.deep]
+y = 2
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.as_table_mut().sort_values();
+    assert!(doc["a"].as_table().unwrap().is_implicit());
+    doc.as_table_mut().make_explicit_recursive();
+    assert!(!doc["a"].as_table().unwrap().is_implicit());
+    assert!(doc["dotted"].as_table().unwrap().is_implicit());
+    assert_eq(
+        r#"dotted.key = 2
+top = 1
+
+[z]
+
+[a]
+
+[a.b]
+[a.b.c]
+x = 1
+
+[[a.list]]
+
+[a.list.inner]
+[a.list.inner.deep]
+y = 2
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn test_float_preserving_repr_style() {
+    let cases = [
+        ("1e200", 2.5e200, "2.5e200"),
+        ("1.0e200", 3e200, "3.0e200"),
+        ("6.02E+23", 6.5e-23, "6.5E-23"),
+        ("+1.5", 2.0, "+2.0"),
+        ("+1.5", -2.0, "-2.0"),
+        ("1_000_000.0", 1234567.0, "1_234_567.0"),
+        ("3.141_592", 1.234567, "1.234_567"),
+        ("0.5", 1e20, "100000000000000000000.0"),
+        ("inf", 1.5, "1.5"),
+        ("1e5", f64::NAN, "nan"),
+    ];
+    for (input, value, expected) in cases {
+        let mut v = input.parse::<Value>().unwrap();
+        match &mut v {
+            Value::Float(f) => f.set_value_preserving_repr_style(value),
+            _ => unreachable!(),
+        }
+        assert_eq!(v.to_string(), expected, "{input} as {value}");
+        if !value.is_nan() {
+            assert_eq!(expected.parse::<Value>().unwrap().as_float(), Some(value));
+        }
+    }
+}
+
+#[test]
+fn test_default_decor_provider() {
+    let mut doc = "[package]\nname = 'demo'\n".parse::<Document>().unwrap();
+    doc.set_default_decor_provider(|path, kind| match (kind, path) {
+        (ItemKind::ArrayOfTables, _) => Decor::new("\n# bin\n", " # entry"),
+        (ItemKind::Table, _) => Decor::new("\n\n", ""),
+        (ItemKind::Value, ["package", ..]) => Decor::new("", " # set"),
+        (ItemKind::Value, _) => Decor::new("    ", ""),
+    });
+    doc["package"]["version"] = value("1.0");
+    doc["bin"] = Item::ArrayOfTables(Default::default());
+    doc["bin"]
+        .as_array_of_tables_mut()
+        .unwrap()
+        .push(Table::from_iter([("name", "cli")]));
+    let expected = r#"[package]
+name = 'demo'
+version = "1.0" # set
+
+# bin
+[[bin]] # entry
+    name = "cli"
+"#;
+    assert_eq(expected, doc.to_string());
+
+    let output = doc.to_string();
+    for t in doc.trivia() {
+        assert_eq!(&output[t.span()], t.as_str());
+    }
+    assert_eq!(doc.audit_decor().count(), 0);
+
+    let first = "[first]\n".parse::<Document>().unwrap();
+    let mut doc = Document::new();
+    doc["first"] = first["first"].clone();
+    doc["first"].as_table_mut().unwrap().decor_mut().clear();
+    doc.set_default_decor_provider(|_, _| Decor::new("\n\n", ""));
+    assert_eq("[first]\n", doc.to_string());
+
+    doc.clear_default_decor_provider();
+    doc["a"] = value(1);
+    assert_eq("a = 1\n\n[first]\n", doc.to_string());
+}
+
+#[test]
+#[cfg(feature = "debug-validate")]
+fn test_debug_validate() {
+    let input = "a = 1\n\n[t]\nx = [{ a = 1 }, 2.5]\n";
+    let doc = input.parse::<Document>().unwrap();
+    assert_eq(input, doc.to_string());
+
+    let panic_message = |doc: Document| {
+        let err = std::panic::catch_unwind(|| doc.to_string()).unwrap_err();
+        *err.downcast::<String>().unwrap()
+    };
+
+    let mut injected = doc.clone();
+    injected["a"]
+        .as_value_mut()
+        .unwrap()
+        .decor_mut()
+        .set_suffix("\nb = 2");
+    let message = panic_message(injected);
+    assert!(
+        message.starts_with(
+            "`Document` output does not parse back to the same document, at `b`: not in the document"
+        ),
+        "{}",
+        message
+    );
+
+    let mut injected = doc.clone();
+    injected["t"]["x"][0]["a"]
+        .as_value_mut()
+        .unwrap()
+        .decor_mut()
+        .set_suffix(", b = 2");
+    let message = panic_message(injected);
+    assert!(
+        message.contains("at `t.x[0].b`: not in the document"),
+        "{}",
+        message
+    );
+
+    let mut invalid = doc;
+    invalid["a"]
+        .as_value_mut()
+        .unwrap()
+        .decor_mut()
+        .set_suffix(" a");
+    let message = panic_message(invalid);
+    assert!(
+        message.starts_with("`Document` output is not valid TOML"),
+        "{}",
+        message
+    );
+}
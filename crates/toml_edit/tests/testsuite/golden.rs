@@ -0,0 +1,387 @@
+//! Golden tests for the default format, see "Output stability" in the crate docs
+
+use snapbox::assert_eq;
+use toml_edit::{
+    array, value, Array, ArrayOfTables, Document, EncodingVersion, FormatOptions, InlineTable,
+    Item, Table, Value,
+};
+
+#[test]
+#[cfg(feature = "datetime")]
+fn golden_scalars() {
+    let mut doc = Document::new();
+    doc["string"] = value("hello");
+    doc["quote"] = value(r#"say "hi""#);
+    doc["both_quotes"] = value(r#"it's "quoted""#);
+    doc["multiline"] = value("one\ntwo\n");
+    doc["control"] = value("tab\there\u{7}");
+    doc["unicode"] = value("héllo ✓");
+    doc["integer"] = value(42);
+    doc["negative"] = value(-17);
+    doc["max"] = value(i64::MAX);
+    doc["float"] = value(1.5);
+    doc["whole_float"] = value(3.0);
+    doc["small_float"] = value(1e-10);
+    doc["large_float"] = value(1e20);
+    doc["negative_zero"] = value(-0.0);
+    doc["inf"] = value(f64::INFINITY);
+    doc["neg_inf"] = value(f64::NEG_INFINITY);
+    doc["nan"] = value(f64::NAN);
+    doc["bool"] = value(true);
+    doc["datetime"] = value(
+        "1979-05-27T07:32:00.999999-07:00"
+            .parse::<toml_edit::Datetime>()
+            .unwrap(),
+    );
+    doc["date"] = value("1979-05-27".parse::<toml_edit::Datetime>().unwrap());
+    doc["time"] = value("07:32:00".parse::<toml_edit::Datetime>().unwrap());
+
+    assert_eq(
+        r#"string = "hello"
+quote = "say \"hi\""
+both_quotes = "it's \"quoted\""
+multiline = """
+one
+two
+"""
+control = "tab\there\u0007"
+unicode = "héllo ✓"
+integer = 42
+negative = -17
+max = 9223372036854775807
+float = 1.5
+whole_float = 3.0
+small_float = 0.0000000001
+large_float = 100000000000000000000.0
+negative_zero = -0.0
+inf = inf
+neg_inf = -inf
+nan = nan
+bool = true
+datetime = 1979-05-27T07:32:00.999999-07:00
+date = 1979-05-27
+time = 07:32:00
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn golden_keys() {
+    let mut doc = Document::new();
+    doc["bare-key_1"] = value(1);
+    doc["with space"] = value(2);
+    doc["with.dot"] = value(3);
+    doc[r#"with"quote"#] = value(4);
+    doc[""] = value(5);
+    doc["ключ"] = value(6);
+
+    // The TOML 1.1 draft allows Unicode letters in bare keys
+    let unicode = if cfg!(feature = "toml-1.1") {
+        "ключ"
+    } else {
+        r#""ключ""#
+    };
+    assert_eq(
+        format!(
+            r#"bare-key_1 = 1
+"with space" = 2
+"with.dot" = 3
+"with\"quote" = 4
+"" = 5
+{} = 6
+"#,
+            unicode
+        ),
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn golden_collections() {
+    let mut doc = Document::new();
+    doc["empty_array"] = value(Array::new());
+    doc["array"] = value(Array::from_iter([1, 2, 3]));
+    let mut nested = Array::new();
+    nested.push(Array::from_iter(["a", "b"]));
+    nested.push(InlineTable::from_iter([("x", 1)]));
+    doc["nested"] = value(nested);
+    doc["empty_inline"] = value(InlineTable::new());
+    let mut inline = InlineTable::new();
+    inline.insert("a", Value::from(1));
+    inline.insert("b", Value::from("two"));
+    doc["inline"] = value(inline);
+
+    let mut table = Table::new();
+    table["key"] = value("value");
+    let mut child = Table::new();
+    child["deep"] = value(true);
+    table["child"] = Item::Table(child);
+    doc["table"] = Item::Table(table);
+
+    let mut empty = Table::new();
+    empty.set_implicit(true);
+    let mut grandchild = Table::new();
+    grandchild["x"] = value(1);
+    empty["grandchild"] = Item::Table(grandchild);
+    doc["implicit"] = Item::Table(empty);
+
+    let mut tables = ArrayOfTables::new();
+    let mut first = Table::new();
+    first["name"] = value("first");
+    tables.push(first);
+    tables.push(Table::new());
+    doc["entries"] = Item::ArrayOfTables(tables);
+    doc["inline_tables"] = array();
+
+    assert_eq(
+        r#"empty_array = []
+array = [1, 2, 3]
+nested = [["a", "b"], { x = 1 }]
+empty_inline = {}
+inline = { a = 1, b = "two" }
+
+[table]
+key = "value"
+
+[table.child]
+deep = true
+
+[implicit.grandchild]
+x = 1
+
+[[entries]]
+name = "first"
+
+[[entries]]
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn golden_fmt() {
+    let input = r#"
+a    =   [ 1 ,2,
+  3, ]
+b={x=1,   y =  [ "z" ]}
+
+[  c . d  ]
+e='f'
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.fmt_recursive();
+
+    assert_eq(
+        r#"a = [1, 2, 3]
+b = { x = 1, y = ["z"] }
+
+[c.d]
+e = "f"
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn golden_pinned_version() {
+    let mut doc = Document::new();
+    doc["string"] = value("it's");
+    doc["float"] = value(0.1_f32 as f64);
+    doc["array"] = value(Array::from_iter([1, 2]));
+    let mut table = Table::new();
+    table["inline"] = value(InlineTable::from_iter([("x", 1)]));
+    doc["table"] = Item::Table(table);
+
+    // The output of each version must never change, unlike the latest default format
+    assert_eq(
+        r#"string = "it's"
+float = 0.10000000149011612
+array = [1, 2]
+
+[table]
+inline = { x = 1 }
+"#,
+        doc.to_string_with(EncodingVersion::V0_17),
+    );
+    assert_eq!(doc.to_string_with(EncodingVersion::LATEST), doc.to_string());
+}
+
+#[test]
+fn golden_version_syntax() {
+    let mut doc = Document::new();
+    doc["ключ"] = value("\u{1b}[0m");
+
+    // Only the `toml-1.1` feature's version writes TOML 1.1 syntax
+    assert_eq(
+        r#""ключ" = "\u001B[0m"
+"#,
+        doc.to_string_with(EncodingVersion::V0_17),
+    );
+    #[cfg(feature = "toml-1.1")]
+    {
+        let toml_1_1 = r#"ключ = "\e[0m"
+"#;
+        assert_eq(toml_1_1, doc.to_string_with(EncodingVersion::V0_17Toml1_1));
+        assert_eq(toml_1_1, doc.to_string());
+    }
+    assert_eq!(doc.to_string_with(EncodingVersion::LATEST), doc.to_string());
+}
+
+#[test]
+fn golden_deterministic_format() {
+    let input = r#"
+# generated
+[package]
+name="demo"
+keywords=[ "a-long-keyword", "another-long-keyword", "yet-another-long-keyword", "more" ]
+[dependencies.serde]
+version="1"
+"#;
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.format(&FormatOptions::deterministic());
+
+    // The output of these options must never change, unlike `FormatOptions::new`
+    assert_eq(
+        r#"# generated
+[package]
+name = "demo"
+keywords = [
+    "a-long-keyword",
+    "another-long-keyword",
+    "yet-another-long-keyword",
+    "more",
+]
+
+[dependencies.serde]
+version = "1"
+"#,
+        doc.to_string_with(EncodingVersion::V0_17),
+    );
+}
+
+#[test]
+fn golden_deterministic_options() {
+    // Spelled out so changing the defaults of `FormatOptions::new` can't change these
+    let pinned = FormatOptions::new()
+        .indent(4)
+        .indent_tables(false)
+        .space_around_eq(true)
+        .array_width(80)
+        .trailing_comma(true)
+        .trailing_newline(true);
+    assert_eq!(FormatOptions::deterministic(), pinned);
+
+    let input = "[a]\nx=1\n[a.b]\nlist=[\"aaaaaaaaaaaaaaaaaaaaaa\",\"bbbbbbbbbbbbbbbbbbbbbbbbbb\",\"cccccccccccccccccccccc\"]\n# end";
+    let mut doc = input.parse::<Document>().unwrap();
+    doc.format(&FormatOptions::deterministic());
+    assert_eq(
+        r#"[a]
+x = 1
+
+[a.b]
+list = [
+    "aaaaaaaaaaaaaaaaaaaaaa",
+    "bbbbbbbbbbbbbbbbbbbbbbbbbb",
+    "cccccccccccccccccccccc",
+]
+# end
+"#,
+        doc.to_string_with(EncodingVersion::V0_17),
+    );
+}
+
+#[test]
+fn float_round_trip() {
+    let mut floats = vec![
+        0.1,
+        1.0 / 3.0,
+        -2.5e-300,
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        5e-324,
+        1e21,
+        123456789012345680.0,
+        -0.0,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    // Arbitrary bit patterns from a fixed-seed xorshift, covering subnormals and all exponents
+    let mut state = 0x9E37_79B9_7F4A_7C15_u64;
+    for _ in 0..10_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let f = f64::from_bits(state);
+        if !f.is_nan() {
+            floats.push(f);
+        }
+    }
+
+    for f in floats {
+        let rendered = value(f).to_string();
+        let parsed = rendered.parse::<Value>().unwrap();
+        let parsed = parsed.as_float().unwrap();
+        assert_eq!(
+            f.to_bits(),
+            parsed.to_bits(),
+            "{} rendered as {}",
+            f,
+            rendered
+        );
+        let rerendered = value(parsed).to_string();
+        assert_eq!(rendered, rerendered);
+    }
+}
+
+#[test]
+fn float_round_trip_edge_cases() {
+    let largest_subnormal = f64::from_bits(0x000F_FFFF_FFFF_FFFF);
+    let floats = [
+        5e-324,
+        -5e-324,
+        largest_subnormal,
+        f64::MIN_POSITIVE,
+        f64::from(0.1_f32),
+        f64::from(f32::MIN_POSITIVE),
+        f64::from(f32::from_bits(1)),
+        f64::from(f32::MAX),
+        -0.0,
+    ];
+    for f in floats {
+        let rendered = value(f).to_string();
+        let parsed = rendered.parse::<Value>().unwrap();
+        assert_eq!(
+            f.to_bits(),
+            parsed.as_float().unwrap().to_bits(),
+            "{} rendered as {}",
+            f,
+            rendered
+        );
+    }
+    assert_eq!(value(-0.0).to_string(), "-0.0");
+    // A widened `f32` is written with the digits of its exact value as an `f64`
+    assert_eq!(value(f64::from(0.1_f32)).to_string(), "0.10000000149011612");
+
+    let input = "a = 4.9406564584124654e-324\nb = 2.2250738585072009E-308\nc = -0.0\nd = 0.1000000000000000055511151231257827\ne = 1_000.250000000000000\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    let before = doc
+        .iter()
+        .map(|(_, item)| item.as_float().unwrap().to_bits())
+        .collect::<Vec<_>>();
+    doc.format(&FormatOptions::new().shortest_floats(true));
+    assert_eq(
+        "a = 5.0e-324\nb = 2.225073858507201E-308\nc = -0.0\nd = 0.1\ne = 1_000.25\n",
+        doc.to_string(),
+    );
+    let after = doc
+        .to_string()
+        .parse::<Document>()
+        .unwrap()
+        .iter()
+        .map(|(_, item)| item.as_float().unwrap().to_bits())
+        .collect::<Vec<_>>();
+    assert_eq!(before, after);
+}
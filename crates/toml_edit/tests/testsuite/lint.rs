@@ -0,0 +1,335 @@
+use snapbox::assert_eq;
+use toml_edit::lint::{mixed_table_definitions, KeyCase, KeyPolicy, StyleLintKind, StyleLints};
+
+#[test]
+fn mixed_table_definitions_reports_both_sites() {
+    let input = r#"[fruit]
+apple.color = "red"
+apple.taste.sweet = true
+
+[fruit.apple.texture]
+smooth = true
+
+[fruit.banana]
+color = "yellow"
+"#;
+    let lints = mixed_table_definitions(input).unwrap();
+    assert_eq!(lints.len(), 1);
+    let lint = &lints[0];
+    assert_eq!(lint.path(), ["fruit", "apple"]);
+    assert_eq(r#"apple.color = "red""#, &input[lint.dotted_span()]);
+    assert_eq("[fruit.apple.texture]", &input[lint.header_span()]);
+    assert_eq(
+        "table `fruit.apple` is defined by both dotted keys and a sub-table header; define `fruit.apple` with a `[fruit.apple]` header instead of dotted keys",
+        lint.to_string(),
+    );
+
+    let doc = input.parse::<toml_edit::Document>().unwrap();
+    assert_eq(input, doc.to_string());
+    assert_eq!(
+        doc["fruit"]["apple"]["texture"]["smooth"].as_bool(),
+        Some(true)
+    );
+
+    let normalized = r#"[fruit.apple]
+color = "red"
+taste.sweet = true
+
+[fruit.apple.texture]
+smooth = true
+
+[fruit.banana]
+color = "yellow"
+"#;
+    assert!(normalized.parse::<toml_edit::Document>().is_ok());
+    assert!(mixed_table_definitions(normalized).unwrap().is_empty());
+}
+
+#[test]
+fn mixed_table_definitions_header_first() {
+    let input = r#"[a.b.c]
+d = 1
+
+[a]
+b.e = 2
+"#;
+    let lints = mixed_table_definitions(input).unwrap();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].path(), ["a", "b"]);
+    assert_eq("b.e = 2", &input[lints[0].dotted_span()]);
+    assert_eq("[a.b.c]", &input[lints[0].header_span()]);
+}
+
+#[test]
+fn mixed_table_definitions_array_of_tables() {
+    let input = r#"[[a]]
+b.c = 1
+
+[[a]]
+[a.b.d]
+e = 2
+
+[[a]]
+b.c = 3
+[a.b.d]
+e = 4
+"#;
+    let lints = mixed_table_definitions(input).unwrap();
+    assert_eq!(lints.len(), 1);
+    assert_eq("b.c = 3", &input[lints[0].dotted_span()]);
+    assert_eq("[a.b.d]", &input[lints[0].header_span()]);
+}
+
+#[test]
+fn mixed_table_definitions_consistent() {
+    let input = r#"a.b.c = 1
+a.d = "x"
+
+[e]
+f.g = 2
+
+[h.i]
+j = 3
+"#;
+    assert!(mixed_table_definitions(input).unwrap().is_empty());
+    assert!(mixed_table_definitions("a = ").is_err());
+}
+
+#[test]
+fn audit_keys_throughout_document() {
+    let input = r#"plain = 1
+"with.dot" = 2
+"" = 3
+a_rather_long_key_name = 4
+inline = { "ключ" = 5, nested = [{ "x y" = 6 }] }
+
+[[items]]
+"naïve" = true
+"#;
+    let doc = input.parse::<toml_edit::Document>().unwrap();
+    // The TOML 1.1 draft allows Unicode letters in bare keys
+    let needs_quotes = !cfg!(feature = "toml-1.1");
+    let anomalies = doc
+        .audit_keys(16)
+        .into_iter()
+        .map(|a| {
+            (
+                a.path().join("/"),
+                a.needs_quotes(),
+                a.is_non_ascii(),
+                a.is_too_long(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        anomalies,
+        [
+            ("with.dot".to_owned(), true, false, false),
+            ("".to_owned(), true, false, false),
+            ("a_rather_long_key_name".to_owned(), false, false, true),
+            ("inline/ключ".to_owned(), needs_quotes, true, false),
+            ("inline/nested/x y".to_owned(), true, false, false),
+            ("items/naïve".to_owned(), needs_quotes, true, false),
+        ]
+    );
+}
+
+#[test]
+fn key_policy_spans_and_suggestions() {
+    let input = r#"top_level = 1
+camelCase.inner-key = 2
+
+[ server . "Max Connections" ]
+HTTPPort = 80
+table = { innerKey = 3 }
+
+[server."Max Connections"]
+camelCase = 4
+"#;
+    let violations = KeyPolicy::new()
+        .case(KeyCase::Snake)
+        .max_len(12)
+        .check(input)
+        .unwrap();
+    let violations = violations
+        .iter()
+        .map(|v| {
+            (
+                v.path().join("/"),
+                &input[v.span()],
+                v.suggestion().map(str::to_owned),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        violations,
+        [
+            (
+                "camelCase".to_owned(),
+                "camelCase",
+                Some("camel_case".to_owned())
+            ),
+            (
+                "camelCase/inner-key".to_owned(),
+                "inner-key",
+                Some("inner_key".to_owned())
+            ),
+            (
+                "server/Max Connections".to_owned(),
+                r#""Max Connections""#,
+                Some("max_connections".to_owned())
+            ),
+            (
+                "server/Max Connections/HTTPPort".to_owned(),
+                "HTTPPort",
+                Some("httpport".to_owned())
+            ),
+            (
+                "server/Max Connections/table/innerKey".to_owned(),
+                "table = { innerKey = 3 }",
+                Some("inner_key".to_owned())
+            ),
+            (
+                "server/Max Connections/camelCase".to_owned(),
+                "camelCase",
+                Some("camel_case".to_owned())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn key_policy_display() {
+    let input = "some_very_long_key = 1\nvery-longKey = 2\n";
+    let violations = KeyPolicy::new()
+        .case(KeyCase::Kebab)
+        .max_len(10)
+        .check(input)
+        .unwrap();
+    let messages = violations.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "key `some_very_long_key` is not kebab-case and is longer than 10 bytes; rename it to `some-very-long-key`",
+            "key `very-longKey` is not kebab-case and is longer than 10 bytes; rename it to `very-long-key`",
+        ]
+    );
+}
+
+#[test]
+fn convert_key_case_throughout_document() {
+    let input = r#"topLevel = 1
+"quoted key" = { innerKey = [{ deepKey = 2 }] }
+
+# Servers
+[[serverList]]
+max-conn = 1
+max_conn = 2
+maxConn = 3
+max_retries = 4
+"#;
+    let mut doc = input.parse::<toml_edit::Document>().unwrap();
+    let skipped = doc.convert_key_case(KeyCase::Snake);
+    assert_eq!(
+        skipped,
+        [["server_list", "max-conn"], ["server_list", "maxConn"],]
+    );
+    assert_eq(
+        r#"top_level = 1
+quoted_key = { inner_key = [{ deep_key = 2 }] }
+
+# Servers
+[[server_list]]
+max-conn = 1
+max_conn = 2
+maxConn = 3
+max_retries = 4
+"#,
+        doc.to_string(),
+    );
+
+    let mut doc = "a-b = 1\nb_c = 2\n".parse::<toml_edit::Document>().unwrap();
+    assert!(doc.convert_key_case(KeyCase::Kebab).is_empty());
+    assert_eq("a-b = 1\nb-c = 2\n", doc.to_string());
+}
+
+#[test]
+fn style_lints_report_each_check() {
+    let input = "name = \"demo\" \n\
+                 path = 'src/lib.rs'\n\
+                 \n\
+                 [dependencies]\n\
+                 \x20   serde = { version = '1', features = [\"derive\"] }\n\
+                 \x20   log = \"0.4\"\n\
+                 \ttoml = \"0.5\"\n\
+                 \x20 anyhow = \"1\" # errors\t\n";
+    let lints = StyleLints::all().check(input).unwrap();
+    let lints = lints
+        .iter()
+        .map(|l| (l.kind(), l.path().join("."), &input[l.span()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lints,
+        [
+            (StyleLintKind::TrailingWhitespace, "".to_owned(), " "),
+            (
+                StyleLintKind::MixedStringStyle,
+                "path".to_owned(),
+                "'src/lib.rs'"
+            ),
+            (
+                StyleLintKind::MixedStringStyle,
+                "dependencies.serde.version".to_owned(),
+                "serde = { version = '1', features = [\"derive\"] }"
+            ),
+            (
+                StyleLintKind::UnsortedKeys,
+                "dependencies.log".to_owned(),
+                "log = \"0.4\""
+            ),
+            (
+                StyleLintKind::InconsistentIndentation,
+                "dependencies.toml".to_owned(),
+                "\ttoml = \"0.5\""
+            ),
+            (
+                StyleLintKind::InconsistentIndentation,
+                "dependencies.anyhow".to_owned(),
+                "  anyhow = \"1\""
+            ),
+            (
+                StyleLintKind::UnsortedKeys,
+                "dependencies.anyhow".to_owned(),
+                "anyhow = \"1\""
+            ),
+            (
+                StyleLintKind::TrailingWhitespace,
+                "dependencies".to_owned(),
+                "\t"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn style_lints_configuration() {
+    let input = "[dev-dependencies]\nb = \"1\"\na = 'C:\\path'\nc = '\"quoted\"'\n";
+    assert!(StyleLints::new().check(input).unwrap().is_empty());
+    let lints = StyleLints::new().sorted_keys(true).check(input).unwrap();
+    assert_eq!(lints.len(), 1);
+    assert_eq(
+        "key `dev-dependencies.a` is out of order",
+        lints[0].to_string(),
+    );
+    // Literal strings that would need escapes as basic strings fit the style
+    assert!(StyleLints::new()
+        .string_styles(true)
+        .check(input)
+        .unwrap()
+        .is_empty());
+    assert!(StyleLints::all()
+        .sorted_keys(false)
+        .check("[[bin]]\nname = \"b\"\n\n[[bin]]\nname = \"a\"\n")
+        .unwrap()
+        .is_empty());
+}
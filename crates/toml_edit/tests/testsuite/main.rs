@@ -4,6 +4,8 @@ mod convert;
 mod edit;
 mod enum_external_deserialize;
 mod formatter;
+mod golden;
+mod lint;
 mod macros;
 mod parse;
 mod pretty;
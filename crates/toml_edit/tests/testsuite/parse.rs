@@ -1,5 +1,5 @@
 use snapbox::assert_eq;
-use toml_edit::{Document, Key, Value};
+use toml_edit::{Document, Expected, Item, Key, SourceDisplay, Value};
 
 macro_rules! parse {
     ($s:expr, $ty:ty) => {{
@@ -93,3 +93,1080 @@ fn test_key_unification() {
 
     assert_eq(doc.to_string(), expected);
 }
+
+#[test]
+fn test_key_original_repr() {
+    let toml = r#"
+bare = 1
+"quotedA" = 2
+'literal' = 3
+inline = { "a.b" = 4 }
+"#;
+    let mut doc = parse!(toml, Document);
+
+    let repr = |doc: &Document, key: &str| {
+        doc.get_original_repr(key)
+            .map(|r| r.as_raw().to_owned())
+            .unwrap()
+    };
+    assert_eq(repr(&doc, "bare"), "bare");
+    assert_eq(repr(&doc, "quotedA"), r#""quotedA""#);
+    assert_eq(repr(&doc, "literal"), "'literal'");
+    let inline = doc["inline"].as_inline_table().unwrap();
+    assert_eq(
+        inline.get_original_repr("a.b").unwrap().as_raw(),
+        r#""a.b""#,
+    );
+    assert!(doc.get_original_repr("missing").is_none());
+
+    doc.insert("new", toml_edit::value(5));
+    assert!(doc.get_original_repr("new").is_none());
+    assert!(Key::new("new").as_repr().is_none());
+}
+
+#[test]
+fn test_events() {
+    use toml_edit::events::{Event, Events};
+
+    let input = r#"# leading
+[package]  # header
+name = "foo"
+a . b= [ 1,
+  2 ]
+
+[[bin]]
+"#;
+    let mut events = Events::new(input);
+    let mut spans = Vec::new();
+    let mut summary = Vec::new();
+    loop {
+        let start = events.offset();
+        let event = match events.next() {
+            Some(event) => event.unwrap(),
+            None => break,
+        };
+        spans.push(&input[start..events.offset()]);
+        summary.push(match event {
+            Event::Whitespace(w) => format!("ws {:?}", w),
+            Event::Comment(c) => format!("comment {:?}", c),
+            Event::TableHeader { path, is_array } => {
+                let path = path.iter().map(|k| k.get()).collect::<Vec<_>>();
+                format!("header {:?} {}", path, is_array)
+            }
+            Event::KeyValue { path, value } => {
+                let path = path.iter().map(|k| k.get()).collect::<Vec<_>>();
+                format!("keyval {:?} {:?}", path, value.to_string())
+            }
+        });
+    }
+    assert_eq!(spans.concat(), input);
+    assert_eq!(
+        summary,
+        [
+            r##"comment "# leading""##,
+            r#"ws "\n""#,
+            r#"header ["package"] false"#,
+            r#"ws "  ""#,
+            r##"comment "# header""##,
+            r#"ws "\n""#,
+            r#"keyval ["name"] " \"foo\"""#,
+            r#"ws "\n""#,
+            r#"keyval ["a", "b"] " [ 1,\n  2 ]""#,
+            r#"ws "\n\n""#,
+            r#"header ["bin"] true"#,
+            r#"ws "\n""#,
+        ]
+    );
+}
+
+#[test]
+fn test_events_error() {
+    use toml_edit::events::Events;
+
+    let events = Events::new("a = 1\nb = 2 c = 3\n").collect::<Vec<_>>();
+    assert_eq!(events.len(), 5);
+    assert!(events[..4].iter().all(|e| e.is_ok()));
+    let err = events[4].as_ref().unwrap_err();
+    assert_eq!(err.line_col(), Some((1, 6)));
+}
+
+#[test]
+fn test_borrowed_document() {
+    use std::borrow::Cow;
+    use toml_edit::borrowed::{Document, Value};
+
+    let input = r#"title = "TOML"
+escaped = "a\tb"
+'quoted key' = 0xff_ff # hex
+dotted.a = 1
+dotted.b = [1, 'two', { x = 1979-05-27 }]
+
+[owner]
+name = { first = "Tom", last.name = "Preston-Werner" }
+
+[[servers]]
+ip = "10.0.0.1"
+
+[[servers]]
+ip = "10.0.0.2"
+"#;
+    let doc = Document::parse(input).unwrap();
+    let within_input = |s: &str| input.as_bytes().as_ptr_range().contains(&s.as_ptr());
+
+    let title = doc.get("title").and_then(|i| i.as_value()).unwrap();
+    assert!(matches!(title, Value::String(s) if matches!(s.value(), Cow::Borrowed(_))));
+    assert!(within_input(title.as_str().unwrap()));
+    assert_eq!(title.raw(), "\"TOML\"");
+
+    let escaped = doc.get("escaped").and_then(|i| i.as_value()).unwrap();
+    assert!(matches!(escaped, Value::String(s) if matches!(s.value(), Cow::Owned(_))));
+    assert_eq!(escaped.as_str(), Some("a\tb"));
+
+    let (key, hex) = doc.get_key_value("quoted key").unwrap();
+    assert_eq!(key.raw(), "'quoted key'");
+    assert!(within_input(key.get()));
+    assert_eq!(hex.as_integer(), Some(0xffff));
+    assert_eq!(hex.as_value().unwrap().raw(), "0xff_ff");
+
+    let dotted = doc.get("dotted").and_then(|i| i.as_table()).unwrap();
+    assert!(dotted.is_dotted());
+    assert_eq!(dotted.len(), 2);
+    let array = dotted.get("b").and_then(|i| i.as_array()).unwrap();
+    assert_eq!(array[1].as_str(), Some("two"));
+    let x = array[2].as_inline_table().unwrap().get("x").unwrap();
+    assert!(matches!(x.as_value(), Some(Value::Datetime("1979-05-27"))));
+
+    let name = doc.get("owner").and_then(|i| i.get("name")).unwrap();
+    assert_eq!(name.get("first").and_then(|i| i.as_str()), Some("Tom"));
+    let last = name.get("last").and_then(|i| i.get("name"));
+    assert_eq!(last.and_then(|i| i.as_str()), Some("Preston-Werner"));
+
+    let servers = doc
+        .get("servers")
+        .and_then(|i| i.as_array_of_tables())
+        .unwrap();
+    let ips = servers
+        .iter()
+        .map(|t| t.get("ip").and_then(|i| i.as_str()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ips, ["10.0.0.1", "10.0.0.2"]);
+}
+
+#[test]
+fn test_borrowed_document_errors() {
+    use toml_edit::borrowed;
+    use toml_edit::ParseOptions;
+
+    let options = ParseOptions::new();
+    let limited = ParseOptions::new()
+        .max_depth(3)
+        .max_keys(3)
+        .max_array_len(2)
+        .max_string_len(4);
+    let cases = [
+        ("a = 1\na = 2\n", options),
+        ("a.b = 1\na.b.c = 2\n", options),
+        ("[a]\nb = 1\n[a]\n", options),
+        ("[a]\nb.c = 1\n[a.b]\n", options),
+        ("[[a]]\n[a]\n", options),
+        ("[a]\n[[a]]\n", options),
+        ("a = 1\n[a.b]\n", options),
+        ("a = { b = 1, b = 2 }\n", options),
+        ("a = { b = 1 }\na.c = 2\n", options),
+        ("a = 1 b = 2\n", options),
+        ("a = 9223372036854775808\n", options),
+        ("a = [[[1]]]\n", limited),
+        ("a = 1\nb = 2\nc = 3\nd = 4\n", limited),
+        ("a = [1, 2, 3]\n", limited),
+        ("[[a]]\n[[a]]\n[[a]]\n", limited),
+        ("a = \"abcde\"\n", limited),
+    ];
+    for (input, options) in cases {
+        let owned = Document::parse_with(input, options).unwrap_err();
+        let borrowed = borrowed::Document::parse_with(input, options).unwrap_err();
+        assert_eq!(
+            borrowed.to_string(),
+            owned.to_string(),
+            "Input: {:?}",
+            input
+        );
+    }
+
+    let input = "mask = 0xffff_ffff_ffff_ff00\n";
+    let options = ParseOptions::new().lenient_prefixed_integers(true);
+    let doc = borrowed::Document::parse_with(input, options).unwrap();
+    let mask = doc.get("mask").and_then(|i| i.as_value()).unwrap();
+    assert_eq!(mask.as_i128(), Some(0xffff_ffff_ffff_ff00));
+    assert_eq!(mask.as_integer(), None);
+}
+
+#[test]
+fn test_reparse_edit() {
+    let input = r#"# leading
+top = 1
+
+[a]
+# comment on b
+b = 1   # trailing
+c.d = 2
+
+[[e]]
+f = 1
+
+[[e]]
+f = 2
+
+[a.sub]
+g = 3
+"#;
+    let edits = [
+        // Within a key/value
+        ("b = 1", "b = 100"),
+        // Adding a dotted key
+        ("c.d = 2\n", "c.d = 2\nc.x = 'x'\n"),
+        // Within the second array element
+        ("f = 2", "f = [2, 3]"),
+        // Within the root table
+        ("top = 1\n", "top = 1\nnew = true\n"),
+        // Spanning a header, requiring a full reparse
+        ("c.d = 2\n\n[[e]]", "c.d = 2\n\n[[x]]"),
+        // Adding a header
+        ("g = 3", "g = 3\n[h]"),
+        // Adding a trailing comment to a table
+        ("f = 1\n", "f = 1\n# the end\n"),
+        // Removing a newline
+        ("f = 1\n", "f = 1"),
+    ];
+    for (old, new) in edits {
+        let start = input.find(old).unwrap();
+        let range = start..start + old.len();
+        let mut doc = input.parse::<Document>().unwrap();
+        let edited = input.replacen(old, new, 1);
+        let result = doc.reparse_edit(input, range, new);
+        match edited.parse::<Document>() {
+            Ok(expected) => {
+                result.unwrap();
+                assert_eq!(doc.to_string(), expected.to_string(), "replacing {:?}", old);
+                assert_eq!(doc.to_string(), edited, "replacing {:?}", old);
+            }
+            Err(_) => {
+                assert!(result.is_err(), "replacing {:?}", old);
+                assert_eq!(doc.to_string(), input);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reparse_edit_conflict() {
+    let input = "[a]\nb = 1\n\n[a.c]\nd = 1\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    let start = input.find("b = 1").unwrap();
+    // `c` is already defined by a header
+    assert!(doc.reparse_edit(input, start..start + 5, "c = 1").is_err());
+    assert_eq!(doc.to_string(), input);
+}
+
+#[test]
+fn test_reparse_edit_nested_array_of_tables() {
+    let input = "[[a]]\n[[a.b]]\nx = 1\n[[a]]\n[[a.b]]\nx = 2\n[[a.b]]\nx = 3\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    // `[[a.b]]` is counted within each element of `a`
+    let start = input.find("x = 2").unwrap();
+    doc.reparse_edit(input, start..start + 5, "x = 9").unwrap();
+    assert_eq!(doc["a"][1]["b"][0]["x"].as_integer(), Some(9));
+    assert_eq!(doc["a"][1]["b"][1]["x"].as_integer(), Some(3));
+    assert_eq!(doc.to_string(), input.replace("x = 2", "x = 9"));
+}
+
+#[test]
+fn test_reparse_edit_is_incremental() {
+    let input = "[a]\nb = 1\n\n[c]\nd = 2\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    // Only the edited table is reparsed, so changes elsewhere in the tree survive
+    doc["c"]["d"] = toml_edit::value(3);
+    let start = input.find("b = 1").unwrap();
+    doc.reparse_edit(input, start..start + 5, "b = 5").unwrap();
+    assert_eq!(doc.to_string(), "[a]\nb = 5\n\n[c]\nd = 3\n");
+}
+
+#[test]
+fn test_table_conflict() {
+    let input = "[[a.b]]\nc = 1\n\n[x]\n\n[a.b]\nc = 2\n";
+    let err = input.parse::<Document>().unwrap_err();
+    let conflict = err.table_conflict().unwrap();
+    assert_eq!(conflict.path(), ["a", "b"]);
+    assert_eq!(
+        conflict.existing_kind(),
+        toml_edit::TableKind::ArrayOfTables
+    );
+    assert_eq!(&input[conflict.existing_span()], "[[a.b]]");
+    assert_eq!(conflict.conflicting_kind(), toml_edit::TableKind::Table);
+    assert_eq!(&input[conflict.conflicting_span()], "[a.b]");
+    snapbox::assert_eq(
+        "TOML parse error at line 6, column 1
+  |
+6 | [a.b]
+  | ^
+Invalid table header
+Table `a.b` was already defined as an array of tables
+",
+        err.to_string(),
+    );
+
+    // Tables implied by other headers aren't header conflicts
+    let err = "[a.b]\n[[a]]\n".parse::<Document>().unwrap_err();
+    assert!(err.table_conflict().is_none());
+}
+
+#[test]
+fn test_header_under_dotted_table() {
+    let input = "[fruit]\napple.color = \"red\"\napple.taste.sweet = true\n\n[fruit.apple.texture]\nsmooth = true\n\n[[fruit.apple.seeds]]\nsize = 1\n";
+    let doc = input.parse::<Document>().unwrap();
+    assert_eq(input, doc.to_string());
+    assert_eq!(doc["fruit"]["apple"]["color"].as_str(), Some("red"));
+    assert_eq!(
+        doc["fruit"]["apple"]["seeds"][0]["size"].as_integer(),
+        Some(1)
+    );
+
+    // Headers may add sub-tables to a table defined by dotted keys but not redefine it
+    for header in ["[fruit.apple]", "[fruit.apple.taste]", "[[fruit.apple]]"] {
+        let input = format!("[fruit]\napple.taste.sweet = true\n{}\n", header);
+        let err = input.parse::<Document>().unwrap_err();
+        assert!(err.to_string().contains("Duplicate key"), "{}", err);
+    }
+}
+
+#[test]
+fn test_expected() {
+    let err = "a = 1.e2".parse::<Document>().unwrap_err();
+    assert_eq!(
+        err.expected().collect::<Vec<_>>(),
+        [Expected::Description("digit")]
+    );
+    let err = "[[a]\n".parse::<Document>().unwrap_err();
+    let expected = err.expected().collect::<Vec<_>>();
+    assert_eq!(expected, [Expected::Char('.'), Expected::Literal("]]")]);
+    assert_eq!(expected[1].to_string(), "`]]`");
+    let err = "[a]\n[a]\n".parse::<Document>().unwrap_err();
+    assert_eq!(err.expected().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "unicode-normalization")]
+fn test_normalize_keys() {
+    use toml_edit::ParseOptions;
+
+    let options = ParseOptions::new().normalize_keys(true);
+    // Decomposed (NFD) and composed (NFC) spellings of the same keys
+    let input =
+        "t = { \"nai\u{308}ve\" = { x = 1 } }\n\n[\"cafe\u{301}\".a]\nx = 1\n\n[\"caf\u{e9}\".b]\n";
+    let doc = Document::parse_with(input, options).unwrap();
+    assert_eq!(doc["caf\u{e9}"]["a"]["x"].as_integer(), Some(1));
+    assert!(doc["caf\u{e9}"]["b"].is_table());
+    assert_eq!(doc["t"]["na\u{ef}ve"]["x"].as_integer(), Some(1));
+    // Headers share the key of the table they name, as it was first written
+    assert_eq!(doc.to_string(), input.replace("caf\u{e9}", "cafe\u{301}"));
+
+    let input = "t = { \"nai\u{308}ve\" = 1, \"na\u{ef}ve\" = 2 }\n";
+    assert!(input.parse::<Document>().is_ok());
+    assert!(Document::parse_with(input, options).is_err());
+
+    let input = "[\"caf\u{e9}\"]\n[\"cafe\u{301}\"]\n";
+    assert!(input.parse::<Document>().is_ok());
+    assert!(Document::parse_with(input, options).is_err());
+}
+
+#[test]
+fn test_display_with_source() {
+    let input = "[[a.b]]\nc = 1\n\n[x]\n\n[a.b]\nc = 2\n";
+    let err = input.parse::<Document>().unwrap_err();
+    snapbox::assert_eq(
+        "TOML parse error at line 6, column 1
+  |
+4 | [x]
+5 | 
+6 | [a.b]
+  | ^^^^^
+7 | c = 2
+  |
+Invalid table header
+Table `a.b` was already defined as an array of tables
+",
+        err.display_with(SourceDisplay::new(input).context_lines(2))
+            .to_string(),
+    );
+
+    // Without context, only the underline differs from `Display`
+    let input = "a = 1\nb = 2\nc = 3\nd = 4\ne = 5\nf = 6\ng = 7\nh = 8\ni = 9\nj = tru\n";
+    let err = input.parse::<Document>().unwrap_err();
+    assert_eq!(
+        err.display_with(SourceDisplay::new(input)).to_string(),
+        err.to_string().replacen("^\n", "^^^\n", 1)
+    );
+    snapbox::assert_eq(
+        "TOML parse error at line 10, column 5
+   |
+ 9 | i = 9
+10 | j = tru
+   |     ^^^
+   |
+Invalid string
+Expected `\"`, `'`
+",
+        err.display_with(SourceDisplay::new(input).context_lines(1))
+            .to_string(),
+    );
+}
+
+#[test]
+fn test_lenient_table_headers() {
+    let options = toml_edit::ParseOptions::new().lenient_table_headers(true);
+    let input = "[[a]]\nb = 1\n\n[a]\nb = 2\n\n[[a]]\nb = 3\n";
+    let doc = Document::parse_with(input, options).unwrap();
+    assert_eq!(doc["a"].as_array_of_tables().unwrap().len(), 3);
+    assert_eq!(
+        doc.to_string(),
+        "[[a]]\nb = 1\n\n[[a]]\nb = 2\n\n[[a]]\nb = 3\n"
+    );
+
+    assert!(Document::parse_with("[a.b]\n[[a]]\n", options).is_err());
+}
+
+#[test]
+fn test_limits() {
+    use toml_edit::{Limit, ParseOptions};
+
+    let input = r#"a = "four"
+b = [1, 2, 3]
+c = { d = 1, e = { f = 2 } }
+
+[[g]]
+[[g]]
+"#;
+    let options = ParseOptions::new()
+        .max_size(input.len())
+        .max_keys(8)
+        .max_array_len(3)
+        .max_string_len(4);
+    Document::parse_with(input, options).unwrap();
+
+    let cases = [
+        (options.max_size(input.len() - 1), Limit::Size),
+        (options.max_keys(7), Limit::Keys),
+        (options.max_array_len(2), Limit::ArrayLength),
+        (options.max_string_len(3), Limit::StringLength),
+        (options.max_depth(2), Limit::Depth),
+    ];
+    for (options, limit) in cases {
+        let err = Document::parse_with(input, options).unwrap_err();
+        assert_eq!(err.limit_exceeded(), Some(limit), "{}", err);
+    }
+
+    let err = Document::parse_with("key = 1", options.max_string_len(2)).unwrap_err();
+    assert_eq!(err.limit_exceeded(), Some(Limit::StringLength));
+    snapbox::assert_eq(
+        "TOML parse error at line 1, column 1
+  |
+1 | key = 1
+  | ^
+String is longer than the limit of 2 bytes
+",
+        err.to_string(),
+    );
+}
+
+#[test]
+fn test_escape_e() {
+    let input = "a = \"\\e[1mbold\\e[0m\"\n";
+    let doc = input.parse::<Document>();
+    if cfg!(feature = "toml-1.1") {
+        let doc = doc.unwrap();
+        assert_eq!(doc["a"].as_str(), Some("\u{1b}[1mbold\u{1b}[0m"));
+        assert_eq!(doc.to_string(), input);
+        assert_eq!(toml_edit::value("\u{1b}[0m").to_string(), r#""\e[0m""#);
+    } else {
+        assert!(doc.is_err());
+        assert_eq!(toml_edit::value("\u{1b}[0m").to_string(), r#""\u001B[0m""#);
+    }
+}
+
+#[test]
+fn test_lax() {
+    use toml_edit::{ParseOptions, WarningKind};
+
+    let input = r#"a = 1
+b = # to be filled in
+c = [1, 2],
+d = { x = 1, }
+
+[t]
+e.f = "x"
+e.f = "y"
+"#;
+    assert!(input.parse::<Document>().is_err());
+
+    let (doc, warnings) =
+        Document::parse_with_warnings(input, ParseOptions::new().lax(true)).unwrap();
+    let warnings = warnings
+        .iter()
+        .map(|w| (w.kind(), w.key(), &input[w.span()]))
+        .collect::<Vec<_>>();
+    let mut expected = vec![
+        (WarningKind::MissingValue, "b", "b ="),
+        (WarningKind::TrailingComma, "c", "c = [1, 2],"),
+        (WarningKind::TrailingComma, "d", "d = { x = 1, }"),
+        (WarningKind::DuplicateKey, "e.f", r#"e.f = "y""#),
+    ];
+    let mut output = r#"a = 1
+b = "" # to be filled in
+c = [1, 2]
+d = { x = 1 }
+
+[t]
+e.f = "y"
+"#
+    .to_owned();
+    if cfg!(feature = "toml-1.1") {
+        expected.remove(2);
+        output = output.replace("{ x = 1 }", "{ x = 1, }");
+    }
+    assert_eq!(warnings, expected);
+    assert_eq(output, doc.to_string());
+    assert_eq!(doc["t"]["e"]["f"].as_str(), Some("y"));
+}
+
+#[test]
+fn test_on_duplicate_key() {
+    use toml_edit::{DuplicateKeyPolicy, ParseOptions, WarningKind};
+
+    let input = r#"name = "first"
+dep = { version = "1", version = "2", path.dir = "a", path.dir = "b" }
+
+[t]
+x.y = 1
+x.y = 2
+name = "t"
+"#;
+    let parse = |policy, kind| {
+        let options = ParseOptions::new().on_duplicate_key(policy);
+        let (doc, warnings) = Document::parse_with_warnings(input, options).unwrap();
+        let warnings = warnings
+            .iter()
+            .map(|w| (w.kind(), w.key()))
+            .collect::<Vec<_>>();
+        assert_eq!(warnings, [(kind, "dep"), (kind, "dep"), (kind, "x.y")]);
+        doc.to_string()
+    };
+    assert!(Document::parse_with(input, ParseOptions::new()).is_err());
+    assert_eq(
+        r#"name = "first"
+dep = { version = "1", path.dir = "a" }
+
+[t]
+x.y = 1
+name = "t"
+"#,
+        parse(
+            DuplicateKeyPolicy::FirstWins,
+            WarningKind::DroppedDuplicateKey,
+        ),
+    );
+    assert_eq(
+        r#"name = "first"
+dep = { version = "2", path.dir = "b" }
+
+[t]
+x.y = 2
+name = "t"
+"#,
+        parse(DuplicateKeyPolicy::LastWins, WarningKind::DuplicateKey),
+    );
+
+    // Tables are never redefined
+    let options = ParseOptions::new().on_duplicate_key(DuplicateKeyPolicy::LastWins);
+    assert!(Document::parse_with("a.b = 1\na = 2\n", options).is_err());
+    assert!(Document::parse_with("[a]\n[a]\n", options).is_err());
+    assert!(Document::parse_with("a = { b = 1, b.c = 2 }\n", options).is_err());
+    // `lax` replaces values within inline tables too
+    let options = ParseOptions::new().lax(true);
+    let (doc, warnings) = Document::parse_with_warnings(input, options).unwrap();
+    assert_eq!(doc["dep"]["version"].as_str(), Some("2"));
+    assert_eq!(doc["t"]["x"]["y"].as_integer(), Some(2));
+    assert_eq!(warnings.len(), 3);
+    let input = "a = [{ b = 1, b = 2 }]\n";
+    let (doc, warnings) = Document::parse_with_warnings(input, options).unwrap();
+    assert_eq!(doc.to_string(), "a = [{ b = 2 }]\n");
+    assert_eq!(warnings[0].kind(), WarningKind::DuplicateKey);
+    assert!(Document::parse_with("a = { b = 1, b.c = 2 }\n", options).is_err());
+}
+
+#[test]
+fn test_opaque_datetimes() {
+    use toml_edit::ParseOptions;
+
+    let input = "odt = 1979-05-27T00:32:00.999999-07:00\nld = 1979-05-27 # date\ndates = [07:32:00, { t = 1979-05-27t07:32:00 }]\n";
+    let options = ParseOptions::new().opaque_datetimes(true);
+    let doc = Document::parse_with(input, options).unwrap();
+    let opaque = |item: &Item| item.as_opaque_datetime().unwrap().as_str().to_owned();
+    assert_eq!(opaque(&doc["odt"]), "1979-05-27T00:32:00.999999-07:00");
+    assert_eq!(opaque(&doc["ld"]), "1979-05-27");
+    assert_eq!(opaque(&doc["dates"][0]), "07:32:00");
+    assert_eq!(opaque(&doc["dates"][1]["t"]), "1979-05-27t07:32:00");
+    assert!(doc["ld"].is_datetime());
+    assert_eq!(doc["ld"].as_str(), None);
+    assert_eq(input, doc.to_string());
+
+    // Formatting keeps the text, unquoted
+    let mut formatted = doc.clone();
+    formatted.fmt();
+    assert_eq(
+        "odt = 1979-05-27T00:32:00.999999-07:00\nld = 1979-05-27\ndates = [07:32:00, { t = 1979-05-27t07:32:00 }]\n",
+        formatted.to_string(),
+    );
+
+    // Invalid datetimes are still errors
+    assert!(Document::parse_with("d = 1979-13-27\n", options).is_err());
+    assert!("1979-13-27".parse::<toml_edit::OpaqueDatetime>().is_err());
+
+    #[cfg(feature = "datetime")]
+    {
+        let interpreted = Document::parse_with(input, ParseOptions::new()).unwrap();
+        assert!(interpreted["ld"].as_datetime().is_some());
+        assert_eq!(
+            interpreted["odt"].as_datetime(),
+            Some(&doc["odt"].as_opaque_datetime().unwrap().to_datetime())
+        );
+    }
+}
+
+#[test]
+fn test_decimal_comma() {
+    use toml_edit::{ParseOptions, WarningKind};
+
+    let input = "ratio = 2,75 # measured\nneg = -0,5\nint = 3\nfloat = 2.5\n";
+    assert!(input.parse::<Document>().is_err());
+
+    let options = ParseOptions::new().decimal_comma(true);
+    let (doc, warnings) = Document::parse_with_warnings(input, options).unwrap();
+    assert_eq!(doc["ratio"].as_float(), Some(2.75));
+    assert_eq!(doc["neg"].as_float(), Some(-0.5));
+    assert_eq(
+        "ratio = 2.75 # measured\nneg = -0.5\nint = 3\nfloat = 2.5\n",
+        doc.to_string(),
+    );
+    let fixes = warnings
+        .iter()
+        .map(|w| {
+            assert_eq!(w.kind(), WarningKind::DecimalComma);
+            let (span, fixed) = w.fix().unwrap();
+            (&input[span], fixed)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fixes, [("2,75", "2.75"), ("-0,5", "-0.5")]);
+    assert_eq!(
+        warnings[0].to_string(),
+        "decimal comma in `ratio`, read as `2.75`"
+    );
+
+    // Commas within arrays keep separating values
+    let (doc, warnings) = Document::parse_with_warnings("array = [3,14]\n", options).unwrap();
+    assert_eq!(doc["array"].as_array().map(|a| a.len()), Some(2));
+    assert!(warnings.is_empty());
+
+    let ambiguous = [
+        "thousands = 1,000\n",
+        "inline = { a = 3,14 }\n",
+        "two = 3,14,15\n",
+    ];
+    for input in ambiguous {
+        assert!(Document::parse_with(input, options).is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn test_ambiguous_key_warnings() {
+    use toml_edit::{ParseOptions, WarningKind};
+
+    let input = r#"1979-05-27 = "released"
+3.14 = "pi"
+"2000-01-01" = "quoted"
+"3"."15" = "quoted"
+server.1.port = 80
+404 = "not found"
+
+[releases]
+v.2024-01-31 = true
+"#;
+    let (doc, warnings) = Document::parse_with_warnings(input, ParseOptions::new()).unwrap();
+    assert_eq!(doc["3"]["14"].as_str(), Some("pi"));
+    let warnings = warnings
+        .iter()
+        .map(|w| {
+            assert_eq!(w.kind(), WarningKind::AmbiguousKey);
+            (w.key().to_owned(), &input[w.span()])
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        [
+            ("1979-05-27".to_owned(), r#"1979-05-27 = "released""#),
+            ("3.14".to_owned(), r#"3.14 = "pi""#),
+            ("v.2024-01-31".to_owned(), "v.2024-01-31 = true"),
+        ]
+    );
+    let (_, warnings) = Document::parse_with_warnings("3.14 = 1\n", ParseOptions::new()).unwrap();
+    assert_eq!(
+        warnings[0].to_string(),
+        "key `3.14` looks like a value, but is read as a key"
+    );
+}
+
+#[test]
+fn test_integer_out_of_range() {
+    use toml_edit::ParseOptions;
+
+    let err = "id = 0xffff_ffff_ffff_ffff"
+        .parse::<Document>()
+        .unwrap_err();
+    assert_eq(
+        "TOML parse error at line 1, column 6
+  |
+1 | id = 0xffff_ffff_ffff_ffff
+  |      ^
+Hexadecimal integer is larger than the maximum of 0x7fffffffffffffff
+",
+        err.to_string(),
+    );
+    let err = "id = 0o1000000000000000000000"
+        .parse::<Document>()
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("maximum of 0o777777777777777777777"));
+    let err = "id = 9223372036854775808".parse::<Document>().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("outside of the range -9223372036854775808 to 9223372036854775807"));
+
+    let options = ParseOptions::new().lenient_prefixed_integers(true);
+    let input = "id = 0xffff_ffff_ffff_ffff\nmask = [0b1000000000000000000000000000000000000000000000000000000000000000]\n";
+    let doc = Document::parse_with(input, options).unwrap();
+    assert_eq!(doc["id"].as_u64(), Some(u64::MAX));
+    assert_eq!(doc["id"].as_integer(), None);
+    assert!(matches!(
+        doc["id"].as_value(),
+        Some(Value::WideInteger(v)) if *v.value() == u64::MAX as i128
+    ));
+    assert_eq!(doc["mask"][0].as_u64(), Some(1 << 63));
+    assert_eq(input, doc.to_string());
+
+    let err = Document::parse_with("id = 0x1_0000_0000_0000_0000", options).unwrap_err();
+    assert!(err.to_string().contains("maximum of 0xffffffffffffffff"));
+    assert!(Document::parse_with("id = 9223372036854775808", options).is_err());
+}
+
+#[test]
+fn test_calendar_dates() {
+    let doc = "leap = 2024-02-29\nend = 2023-04-30T12:00:00Z\n"
+        .parse::<Document>()
+        .unwrap();
+    assert!(doc["leap"].is_datetime());
+
+    let err = "d = 2023-02-29".parse::<Document>().unwrap_err();
+    assert_eq(
+        "TOML parse error at line 1, column 13
+  |
+1 | d = 2023-02-29
+  |             ^
+Invalid date-time
+Day is out of range, 2023-02 has 28 days
+",
+        err.to_string(),
+    );
+    let err = "d = 1900-02-29T00:00:00Z".parse::<Document>().unwrap_err();
+    assert!(err.to_string().contains("1900-02 has 28 days"));
+    let err = "d = 2023-04-31".parse::<Document>().unwrap_err();
+    assert!(err.to_string().contains("2023-04 has 30 days"));
+
+    assert!("2023-02-30".parse::<toml_edit::OpaqueDatetime>().is_err());
+    #[cfg(feature = "datetime")]
+    {
+        assert!("2023-02-30".parse::<toml_edit::Datetime>().is_err());
+        assert!("2024-02-29".parse::<toml_edit::Datetime>().is_ok());
+    }
+}
+
+#[test]
+#[cfg(feature = "datetime")]
+fn test_leap_second() {
+    for raw in ["1990-12-31T23:59:60Z", "1990-12-31T23:59:60", "23:59:60.5"] {
+        let doc = format!("t = {}\n", raw).parse::<Document>().unwrap();
+        let parsed = doc["t"].as_datetime().unwrap();
+        assert_eq!(parsed.to_string(), raw);
+        assert_eq!(raw.parse::<toml_edit::Datetime>().unwrap(), *parsed);
+        assert!(toml_edit::is_valid_rfc3339(raw));
+    }
+    assert!("t = 23:59:61".parse::<Document>().is_err());
+    assert!("23:59:61".parse::<toml_edit::Datetime>().is_err());
+    assert!(!toml_edit::is_valid_rfc3339("23:59:61"));
+}
+
+#[test]
+#[cfg(feature = "datetime")]
+fn test_sub_nanosecond_secfrac() {
+    let input = "time = 17:45:00.123456789012345\nwhen = 1979-05-27t07:32:00.9999999999+07:00\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq(input, doc.to_string());
+
+    for (_, item) in doc.iter_mut() {
+        item.as_value_mut().unwrap().fmt_recursive();
+    }
+    assert_eq(
+        "time = 17:45:00.123456789012345\nwhen = 1979-05-27T07:32:00.9999999999+07:00\n",
+        doc.to_string(),
+    );
+    let when = doc["when"].as_value().unwrap();
+    match when {
+        toml_edit::Value::Datetime(when) => assert_eq!(when.secfrac(), Some("9999999999")),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_unicode_bare_keys() {
+    let input = "ключ = 1\n[café.naïve]\n²x = 2\n";
+    let doc = input.parse::<Document>();
+    if cfg!(feature = "toml-1.1") {
+        let doc = doc.unwrap();
+        assert_eq!(doc["ключ"].as_integer(), Some(1));
+        assert_eq!(doc["café"]["naïve"]["²x"].as_integer(), Some(2));
+        assert_eq!(doc.to_string(), input);
+        assert_eq!(Key::new("日本語").to_string(), "日本語");
+        assert!("a→b = 1".parse::<Document>().is_err());
+    } else {
+        assert!(doc.is_err());
+        assert_eq!(Key::new("日本語").to_string(), r#""日本語""#);
+    }
+}
+
+#[test]
+fn test_multiline_inline_table() {
+    let input = r#"point = {
+    x = 1, # the x coordinate
+    y.z = 2,
+}
+empty = {
+}
+"#;
+    let doc = input.parse::<Document>();
+    if cfg!(feature = "toml-1.1") {
+        let doc = doc.unwrap();
+        assert_eq!(doc.to_string(), input);
+        let point = doc["point"].as_inline_table().unwrap();
+        assert!(point.trailing_comma());
+        assert_eq!(point.get("x").and_then(|x| x.as_integer()), Some(1));
+
+        let mut doc = doc;
+        doc["point"].as_inline_table_mut().unwrap().fmt_recursive();
+        doc["empty"].as_inline_table_mut().unwrap().fmt();
+        assert_eq!(doc.to_string(), "point = { x = 1, y.z = 2 }\nempty = {}\n");
+    } else {
+        assert!(doc.is_err());
+    }
+
+    let comma_only = "a = { , }\n".parse::<Document>();
+    assert!(comma_only.is_err());
+}
+
+#[test]
+fn test_bom() {
+    let input = "\u{FEFF}# from a Windows editor\r\na = 1\r\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(doc.has_bom());
+    assert_eq!(doc.to_string(), input);
+
+    doc.set_bom(false);
+    assert_eq!(doc.to_string(), "# from a Windows editor\r\na = 1\r\n");
+
+    let doc = "a = 1\n".parse::<Document>().unwrap();
+    assert!(!doc.has_bom());
+    let mut doc = Document::new();
+    doc.set_bom(true);
+    doc["a"] = toml_edit::value(1);
+    assert_eq("\u{FEFF}a = 1\n", doc.to_string());
+}
+
+#[test]
+fn test_newline_style() {
+    use toml_edit::NewlineStyle;
+
+    let input =
+        "# header\r\na = [\r\n  1,\r\n]\r\ns = \"\"\"\r\nline\r\n\"\"\"\r\n\r\n[t]\r\nb = 2\r\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.newline_style(), NewlineStyle::CrLf);
+    assert_eq!(doc.to_string(), input);
+
+    doc["t"]["c"] = toml_edit::value(3);
+    doc["u"] = toml_edit::table();
+    doc["u"]["d"] = toml_edit::value(4);
+    let expected = input.to_owned() + "c = 3\r\n\r\n[u]\r\nd = 4\r\n";
+    assert_eq!(doc.to_string(), expected);
+
+    // Strings keep the line endings they were written with
+    doc.set_newline_style(NewlineStyle::Lf);
+    let lf = expected
+        .replace("\r\n", "\n")
+        .replace("\"\"\"\nline\n\"\"\"", "\"\"\"\r\nline\r\n\"\"\"");
+    assert_eq!(doc.to_string(), lf);
+    doc.set_newline_style(NewlineStyle::CrLf);
+    assert_eq!(doc.to_string(), expected);
+
+    // Only the line endings the document writes itself follow its style
+    let input = "a = 1\nb = [\r\n2]\r\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.newline_style(), NewlineStyle::Lf);
+    assert_eq!(doc.to_string(), "a = 1\nb = [\r\n2]\n");
+    doc.set_newline_style(NewlineStyle::Lf);
+    assert_eq!(doc.to_string(), "a = 1\nb = [\n2]\n");
+
+    let input = "a = 1\nb = '''x\r\ny'''\n";
+    let doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.to_string(), input);
+    let input = "a = 1\r\nb = '''x\ny'''\r\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.to_string(), input);
+    doc["c"] = toml_edit::value("p\nq\nr\ns");
+    assert_eq!(
+        doc.to_string(),
+        format!("{}c = \"\"\"\r\np\r\nq\r\nr\r\ns\"\"\"\r\n", input)
+    );
+    let trailing = doc.trivia().last().unwrap();
+    assert_eq!(
+        trailing.span(),
+        doc.to_string().len()..doc.to_string().len()
+    );
+}
+
+#[test]
+fn test_trivia() {
+    use toml_edit::TriviaKind;
+
+    let input = r#"# leading
+top = [ 1 , # one
+  2, ]  # array
+
+[ a . b ]  # header
+c . d =  { e = 1, f = "x" }
+  # before bin
+[[bin]]
+name='x'
+# trailing
+"#;
+    let doc = input.parse::<Document>().unwrap();
+    let trivia = doc.trivia().collect::<Vec<_>>();
+    let mut end = 0;
+    for t in &trivia {
+        assert_eq!(&input[t.span()], t.as_str(), "{:?}", t);
+        assert!(end <= t.span().start, "{:?}", t);
+        end = t.span().end;
+    }
+
+    let comments = trivia
+        .iter()
+        .filter(|t| t.as_str().contains('#'))
+        .map(|t| (t.kind(), t.path().join(".")))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        comments,
+        [
+            (TriviaKind::KeyPrefix, "top".to_owned()),
+            (TriviaKind::ValuePrefix, "top".to_owned()),
+            (TriviaKind::ValueSuffix, "top".to_owned()),
+            (TriviaKind::HeaderSuffix, "a.b".to_owned()),
+            (TriviaKind::HeaderPrefix, "bin".to_owned()),
+            (TriviaKind::Trailing, "".to_owned()),
+        ]
+    );
+    let inline = trivia
+        .iter()
+        .filter(|t| t.path().len() == 5)
+        .map(|t| t.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(inline, [" ", " ", " ", "", " ", " ", " ", " "]);
+
+    // Spans follow the line endings of the output
+    let mut doc = "a = 1\r\n# b\r\nb = 2\r\n".parse::<Document>().unwrap();
+    doc.set_newline_style(toml_edit::NewlineStyle::Lf);
+    let output = doc.to_string();
+    for t in doc.trivia() {
+        assert_eq!(&output[t.span()], t.as_str().replace('\r', ""));
+    }
+}
+
+#[test]
+fn test_symbols() {
+    use toml_edit::SymbolKind;
+
+    let input = r#"top = [ { x = 1 } ]
+
+[ a . "b" ]  # header
+c . d =  { e = 1 }
+[[bin]]
+name='x'
+"#;
+    let doc = input.parse::<Document>().unwrap();
+    let symbols = doc
+        .symbols()
+        .map(|s| (s.path().join("."), s.kind(), &input[s.span()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        symbols,
+        [
+            ("top".to_owned(), SymbolKind::Key, "top"),
+            ("top.x".to_owned(), SymbolKind::Key, "x"),
+            ("a.b".to_owned(), SymbolKind::Table, r#"[ a . "b" ]"#),
+            ("a.b.c".to_owned(), SymbolKind::Key, "c"),
+            ("a.b.c.d".to_owned(), SymbolKind::Key, "d"),
+            ("a.b.c.d.e".to_owned(), SymbolKind::Key, "e"),
+            ("bin".to_owned(), SymbolKind::ArrayOfTables, "[[bin]]"),
+            ("bin.name".to_owned(), SymbolKind::Key, "name"),
+        ]
+    );
+
+    let mut doc = "\u{FEFF}[a]\r\nb = 1\r\n".parse::<Document>().unwrap();
+    doc.set_newline_style(toml_edit::NewlineStyle::CrLf);
+    let output = doc.to_string();
+    let spans = doc.symbols().map(|s| &output[s.span()]).collect::<Vec<_>>();
+    assert_eq!(spans, ["[a]", "b"]);
+}
+
+#[test]
+fn test_raw_repr() {
+    let input = r#"[package]
+name = "demo" # the name
+version = '1.0'
+keywords = [ "a" , # first
+  'b' ]
+metadata = {docs=true}
+
+[[bin]]
+name = "x"
+"#;
+    let doc = input.parse::<Document>().unwrap();
+    let package = &doc["package"];
+    let raw = |item: &Item| item.to_repr().unwrap().as_raw().to_owned();
+    assert_eq!(raw(&package["name"]), r#""demo""#);
+    assert_eq!(raw(&package["version"]), "'1.0'");
+    assert_eq!(raw(&package["keywords"]), "[ \"a\" , # first\n  'b' ]");
+    assert_eq!(raw(&package["metadata"]), "{docs=true}");
+    assert_eq!(
+        package["name"].as_repr().map(|r| r.as_raw()),
+        Some(r#""demo""#)
+    );
+    assert!(package["keywords"].as_repr().is_none());
+    assert!(input.contains(&raw(package)));
+    assert!(doc["bin"].to_repr().is_none());
+    assert!(Item::None.to_repr().is_none());
+}
@@ -704,3 +704,266 @@ debug = true
         raw,
     );
 }
+
+#[test]
+fn serialize_into_formatted_document() {
+    #[derive(Serialize)]
+    struct Dependency {
+        version: String,
+        features: Vec<String>,
+    }
+
+    let mut doc = r#"# Manifest
+[dependencies]
+regex = "1.0"  # pinned
+"#
+    .parse::<toml_edit::Document>()
+    .unwrap();
+    let dep = Dependency {
+        version: "1.0".to_owned(),
+        features: vec!["derive".to_owned()],
+    };
+    doc["dependencies"]["serde"] = toml_edit::ser::to_item(&dep).unwrap();
+    let features = toml_edit::ser::to_value(&["std", "alloc"]).unwrap();
+    doc["dependencies"]["serde"]["features"]
+        .as_array_mut()
+        .unwrap()
+        .extend(features.as_array().unwrap().iter());
+
+    assert_eq(
+        r#"# Manifest
+[dependencies]
+regex = "1.0"  # pinned
+serde = { version = "1.0", features = ["derive", "std", "alloc"] }
+"#,
+        doc.to_string(),
+    );
+
+    let table = toml_edit::ser::to_item(&dep).unwrap().into_table().unwrap();
+    doc["dependencies"]["serde"] = toml_edit::Item::Table(table);
+    assert_eq(
+        r#"# Manifest
+[dependencies]
+regex = "1.0"  # pinned
+
+[dependencies.serde]
+version = "1.0"
+features = ["derive"]
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn deserialize_borrowed_subtree() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Profile {
+        opt_level: u8,
+        lto: bool,
+    }
+
+    let doc = r#"
+[profile.release]
+opt_level = 3
+lto = true
+
+[profile.dev]
+opt_level = 0
+lto = { enabled = false }
+"#
+    .parse::<toml_edit::Document>()
+    .unwrap();
+
+    let release = Profile::deserialize(&doc["profile"]["release"]).unwrap();
+    assert_eq!(
+        release,
+        Profile {
+            opt_level: 3,
+            lto: true
+        }
+    );
+
+    let table = doc["profile"]["release"].as_table().unwrap();
+    assert_eq!(Profile::deserialize(table).unwrap(), release);
+
+    let opt_level = u8::deserialize(doc["profile"]["dev"]["opt_level"].as_value().unwrap());
+    assert_eq!(opt_level.unwrap(), 0);
+
+    let err = Profile::deserialize(&doc["profile"]["dev"]).unwrap_err();
+    assert_eq(
+        "invalid type: map, expected a boolean for key `lto`",
+        err.to_string(),
+    );
+
+    // The document is still usable after deserializing from it
+    assert_eq!(doc["profile"]["dev"]["opt_level"].as_integer(), Some(0));
+}
+
+#[test]
+fn deserialize_borrowed_wide_integers() {
+    let doc = "x = 3\n".parse::<toml_edit::Document>().unwrap();
+
+    assert_eq!(i128::deserialize(doc["x"].as_value().unwrap()).unwrap(), 3);
+    assert_eq!(u128::deserialize(doc["x"].as_value().unwrap()).unwrap(), 3);
+    assert_eq!(i128::deserialize(&doc["x"]).unwrap(), 3);
+    assert_eq!(u128::deserialize(&doc["x"]).unwrap(), 3);
+}
+
+#[test]
+fn f32_shortest_repr() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Floats {
+        a: f32,
+        b: f32,
+        c: f32,
+    }
+    let floats = Floats {
+        a: 0.1,
+        b: 16_777_216.0,
+        c: f32::NEG_INFINITY,
+    };
+    let toml = toml_edit::easy::to_string(&floats).unwrap();
+    assert_eq(
+        r#"a = 0.1
+b = 16777216.0
+c = -inf
+"#,
+        &toml,
+    );
+    assert_eq!(toml_edit::easy::from_str::<Floats>(&toml).unwrap(), floats);
+
+    // Subnormals and a negative zero keep their bits through widening to `f64`
+    let floats = Floats {
+        a: f32::from_bits(1),
+        b: f32::MIN_POSITIVE,
+        c: -0.0,
+    };
+    let toml = toml_edit::easy::to_string(&floats).unwrap();
+    assert_eq(
+        r#"a = 0.000000000000000000000000000000000000000000001
+b = 0.000000000000000000000000000000000000011754944
+c = -0.0
+"#,
+        &toml,
+    );
+    let parsed = toml_edit::easy::from_str::<Floats>(&toml).unwrap();
+    assert_eq!(parsed.a.to_bits(), floats.a.to_bits());
+    assert_eq!(parsed.b.to_bits(), floats.b.to_bits());
+    assert_eq!(parsed.c.to_bits(), floats.c.to_bits());
+}
+
+#[test]
+fn u64_above_i64_max() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Ids {
+        small: u64,
+        large: u64,
+        offset: i64,
+    }
+    let ids = Ids {
+        small: 42,
+        large: u64::MAX - 1,
+        offset: -1,
+    };
+    assert_eq!(
+        toml_edit::ser::to_string(&ids).unwrap_err().to_string(),
+        "out-of-range value for u64 type"
+    );
+    let serializer = toml_edit::ser::Serializer::new().wide_integers(true);
+    let toml = toml_edit::ser::to_document_with(&ids, serializer)
+        .unwrap()
+        .to_string();
+    assert_eq(
+        r#"small = 42
+large = 0xfffffffffffffffe
+offset = -1
+"#,
+        &toml,
+    );
+
+    assert!(toml_edit::de::from_str::<Ids>(&toml).is_err());
+    let options = toml_edit::ParseOptions::new().lenient_prefixed_integers(true);
+    let doc = toml_edit::Document::parse_with(&toml, options).unwrap();
+    assert_eq!(doc["large"].as_u64(), Some(u64::MAX - 1));
+    assert_eq!(doc["offset"].as_u64(), None);
+    assert_eq!(toml_edit::de::from_document::<Ids>(doc).unwrap(), ids);
+}
+
+#[test]
+fn extended_integers() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Counters {
+        requests: u64,
+        total: i128,
+        debt: i128,
+        small: i128,
+    }
+    let input = r#"requests = 18_446_744_073_709_551_615
+total = 100000000000000000000000
+debt = -9223372036854775809
+small = -5
+"#;
+    assert!(toml_edit::Document::parse_with(input, toml_edit::ParseOptions::new()).is_err());
+    let options = toml_edit::ParseOptions::new().extended_integers(true);
+    let doc = toml_edit::Document::parse_with(input, options).unwrap();
+    assert_eq(input, doc.to_string());
+    assert_eq!(doc["requests"].as_u64(), Some(u64::MAX));
+    assert_eq!(doc["total"].as_u64(), None);
+    assert_eq!(doc["debt"].as_i128(), Some(i64::MIN as i128 - 1));
+    assert!(matches!(
+        doc["requests"].as_value(),
+        Some(toml_edit::Value::WideInteger(v)) if *v.value() == u64::MAX as i128
+    ));
+    assert_eq!(doc["requests"].as_integer(), None);
+    assert!(doc["requests"].is_integer());
+    assert_eq!(doc["small"].as_integer(), Some(-5));
+    let mut formatted = doc.clone();
+    formatted.fmt();
+    assert_eq(input, formatted.to_string());
+
+    let counters = toml_edit::de::from_document::<Counters>(doc).unwrap();
+    let expected = Counters {
+        requests: u64::MAX,
+        total: 100_000_000_000_000_000_000_000,
+        debt: i64::MIN as i128 - 1,
+        small: -5,
+    };
+    assert_eq!(counters, expected);
+
+    assert_eq!(
+        toml_edit::ser::to_string(&counters)
+            .unwrap_err()
+            .to_string(),
+        "out-of-range value for u64 type"
+    );
+    let serializer = toml_edit::ser::Serializer::new().wide_integers(true);
+    let toml = toml_edit::ser::to_document_with(&counters, serializer)
+        .unwrap()
+        .to_string();
+    assert_eq(
+        r#"requests = 0xffffffffffffffff
+total = 100000000000000000000000
+debt = -9223372036854775809
+small = -5
+"#,
+        &toml,
+    );
+    let doc = toml_edit::Document::parse_with(&toml, options).unwrap();
+    assert_eq!(
+        toml_edit::de::from_document::<Counters>(doc).unwrap(),
+        counters
+    );
+    let total = std::collections::BTreeMap::from([("total", 1_i128 << 100)]);
+    assert_eq!(
+        toml_edit::ser::to_string(&total).unwrap_err().to_string(),
+        "out-of-range value for i128 type"
+    );
+    let small = std::collections::BTreeMap::from([("small", -5_i128)]);
+    assert_eq("small = -5\n", toml_edit::ser::to_string(&small).unwrap());
+    let serializer = toml_edit::ser::Serializer::new().wide_integers(true);
+    assert!(toml_edit::ser::to_document_with(
+        &std::collections::BTreeMap::from([("a", u128::MAX)]),
+        serializer
+    )
+    .is_err());
+}
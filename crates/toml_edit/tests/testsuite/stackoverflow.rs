@@ -52,3 +52,22 @@ fn inline_dotted_key_recursion_limit() {
         assert_eq!(document.is_ok(), is_ok, "depth: {}", depth);
     }
 }
+
+#[test]
+fn configured_recursion_limit() {
+    let inputs = [
+        |depth| format!("x={}{}", &"[".repeat(depth), &"]".repeat(depth)),
+        |depth| format!("x={}true{}", &"{ x = ".repeat(depth), &"}".repeat(depth)),
+        |depth| format!("[x{}]", &".x".repeat(depth)),
+        |depth| format!("x{} = true", &".x".repeat(depth)),
+    ];
+    let cases = [(10, 1, true), (10, 20, false), (200, 150, true)];
+    for input in inputs {
+        for (max_depth, depth, is_ok) in cases {
+            let input = input(depth);
+            let options = toml_edit::ParseOptions::new().max_depth(max_depth);
+            let document = toml_edit::Document::parse_with(&input, options);
+            assert_eq!(document.is_ok(), is_ok, "{} at {}", depth, max_depth);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+/* C bindings for toml_edit, a format-preserving TOML editor.
+ *
+ * All strings are NUL-terminated UTF-8.  Strings returned through `out` parameters must be
+ * released with `toml_edit_string_free` and documents with `toml_edit_document_free`.
+ *
+ * Keys are TOML key expressions, so `a.b` refers to key `b` in table `a`.
+ */
+
+#ifndef TOML_EDIT_H
+#define TOML_EDIT_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum toml_edit_status {
+    TOML_EDIT_OK = 0,
+    TOML_EDIT_NULL_POINTER = 1,
+    TOML_EDIT_INVALID_UTF8 = 2,
+    TOML_EDIT_PARSE_ERROR = 3,
+    TOML_EDIT_NOT_FOUND = 4,
+    TOML_EDIT_NOT_A_VALUE = 5,
+    TOML_EDIT_INTERIOR_NUL = 6,
+} toml_edit_status;
+
+typedef struct TomlEditDocument toml_edit_document;
+
+/* Parse `input` into a new document. */
+toml_edit_status toml_edit_parse(const char *input, toml_edit_document **out);
+
+/* Look up the value at `key`, returning its TOML representation. */
+toml_edit_status toml_edit_get(const toml_edit_document *doc, const char *key, char **out);
+
+/* Set the value at `key` to the TOML expression `value`, creating tables as needed. */
+toml_edit_status toml_edit_set(toml_edit_document *doc, const char *key, const char *value);
+
+/* Remove the value or table at `key`. */
+toml_edit_status toml_edit_remove(toml_edit_document *doc, const char *key);
+
+/* Render the document. */
+toml_edit_status toml_edit_to_string(const toml_edit_document *doc, char **out);
+
+void toml_edit_document_free(toml_edit_document *doc);
+
+void toml_edit_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TOML_EDIT_H */
@@ -0,0 +1,222 @@
+//! # `toml_edit_capi`
+//!
+//! C bindings for [`toml_edit`], exposing parsing, lookup, modification, and rendering of
+//! documents while preserving their formatting.
+//!
+//! All strings crossing the boundary are NUL-terminated UTF-8.  Strings and documents handed
+//! out by this library are owned by the caller and must be released with
+//! [`toml_edit_string_free`] and [`toml_edit_document_free`] respectively.
+//!
+//! Keys are given as TOML key expressions, so `a.b` refers to key `b` in table `a` and
+//! `a."b.c"` to key `b.c`.
+
+#![warn(missing_docs)]
+#![warn(rust_2018_idioms)]
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use toml_edit::{Document, Item, PathError, Value};
+
+/// Result of a call into the library.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TomlEditStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The document, key, or value could not be parsed
+    ParseError = 3,
+    /// The key does not exist
+    NotFound = 4,
+    /// The key refers to a table rather than a value, or a parent key is not a table
+    NotAValue = 5,
+    /// A returned string would contain a NUL byte
+    InteriorNul = 6,
+}
+
+/// An opaque handle to a parsed document.
+pub struct TomlEditDocument {
+    doc: Document,
+}
+
+/// Parse `input` into a new document, stored in `out`.
+///
+/// # Safety
+///
+/// `input` must be a valid NUL-terminated string and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_parse(
+    input: *const c_char,
+    out: *mut *mut TomlEditDocument,
+) -> TomlEditStatus {
+    if out.is_null() {
+        return TomlEditStatus::NullPointer;
+    }
+    let input = match to_str(input) {
+        Ok(input) => input,
+        Err(status) => return status,
+    };
+    match input.parse::<Document>() {
+        Ok(doc) => {
+            *out = Box::into_raw(Box::new(TomlEditDocument { doc }));
+            TomlEditStatus::Ok
+        }
+        Err(_) => TomlEditStatus::ParseError,
+    }
+}
+
+/// Look up the value at `key`, storing its TOML representation in `out`.
+///
+/// # Safety
+///
+/// `doc` must come from [`toml_edit_parse`], `key` must be a valid NUL-terminated string, and
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_get(
+    doc: *const TomlEditDocument,
+    key: *const c_char,
+    out: *mut *mut c_char,
+) -> TomlEditStatus {
+    if doc.is_null() || out.is_null() {
+        return TomlEditStatus::NullPointer;
+    }
+    let key = match to_str(key) {
+        Ok(key) => key,
+        Err(status) => return status,
+    };
+    let value = match (*doc).doc.get_path(key) {
+        Ok(Some(Item::Value(value))) => value,
+        Ok(Some(Item::Table(_))) | Ok(Some(Item::ArrayOfTables(_))) => {
+            return TomlEditStatus::NotAValue
+        }
+        Ok(Some(Item::None)) | Ok(None) => return TomlEditStatus::NotFound,
+        Err(_) => return TomlEditStatus::ParseError,
+    };
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    to_c_string(value.to_string(), out)
+}
+
+/// Set the value at `key` to the TOML expression `value`, creating tables as needed.
+///
+/// When replacing an existing value, its surrounding whitespace and comments are kept.
+///
+/// # Safety
+///
+/// `doc` must come from [`toml_edit_parse`] and `key` and `value` must be valid NUL-terminated
+/// strings.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_set(
+    doc: *mut TomlEditDocument,
+    key: *const c_char,
+    value: *const c_char,
+) -> TomlEditStatus {
+    if doc.is_null() {
+        return TomlEditStatus::NullPointer;
+    }
+    let key = match to_str(key) {
+        Ok(key) => key,
+        Err(status) => return status,
+    };
+    let value = match to_str(value).map(str::parse::<Value>) {
+        Ok(Ok(value)) => value,
+        Ok(Err(_)) => return TomlEditStatus::ParseError,
+        Err(status) => return status,
+    };
+    match (*doc).doc.set_path(key, value) {
+        Ok(()) => TomlEditStatus::Ok,
+        Err(PathError::Parse(_)) => TomlEditStatus::ParseError,
+        Err(_) => TomlEditStatus::NotAValue,
+    }
+}
+
+/// Remove the value or table at `key`.
+///
+/// # Safety
+///
+/// `doc` must come from [`toml_edit_parse`] and `key` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_remove(
+    doc: *mut TomlEditDocument,
+    key: *const c_char,
+) -> TomlEditStatus {
+    if doc.is_null() {
+        return TomlEditStatus::NullPointer;
+    }
+    let key = match to_str(key) {
+        Ok(key) => key,
+        Err(status) => return status,
+    };
+    match (*doc).doc.remove_path(key) {
+        Ok(Some(_)) => TomlEditStatus::Ok,
+        Ok(None) => TomlEditStatus::NotFound,
+        Err(_) => TomlEditStatus::ParseError,
+    }
+}
+
+/// Render the document, storing the result in `out`.
+///
+/// # Safety
+///
+/// `doc` must come from [`toml_edit_parse`] and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_to_string(
+    doc: *const TomlEditDocument,
+    out: *mut *mut c_char,
+) -> TomlEditStatus {
+    if doc.is_null() || out.is_null() {
+        return TomlEditStatus::NullPointer;
+    }
+    to_c_string((*doc).doc.to_string(), out)
+}
+
+/// Release a document.  Passing null is a no-op.
+///
+/// # Safety
+///
+/// `doc` must come from [`toml_edit_parse`] and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_document_free(doc: *mut TomlEditDocument) {
+    if !doc.is_null() {
+        drop(Box::from_raw(doc));
+    }
+}
+
+/// Release a string returned by this library.  Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must have been returned by this library and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn toml_edit_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Result<&'a str, TomlEditStatus> {
+    if s.is_null() {
+        return Err(TomlEditStatus::NullPointer);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| TomlEditStatus::InvalidUtf8)
+}
+
+unsafe fn to_c_string(s: String, out: *mut *mut c_char) -> TomlEditStatus {
+    match CString::new(s) {
+        Ok(s) => {
+            *out = s.into_raw();
+            TomlEditStatus::Ok
+        }
+        Err(_) => {
+            *out = ptr::null_mut();
+            TomlEditStatus::InteriorNul
+        }
+    }
+}
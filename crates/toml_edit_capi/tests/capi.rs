@@ -0,0 +1,111 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use toml_edit_capi::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+unsafe fn take(s: *mut c_char) -> String {
+    let owned = CStr::from_ptr(s).to_str().unwrap().to_owned();
+    toml_edit_string_free(s);
+    owned
+}
+
+unsafe fn parse(input: &str) -> *mut TomlEditDocument {
+    let mut doc = ptr::null_mut();
+    assert_eq!(
+        toml_edit_parse(c(input).as_ptr(), &mut doc),
+        TomlEditStatus::Ok
+    );
+    doc
+}
+
+unsafe fn render(doc: *const TomlEditDocument) -> String {
+    let mut out = ptr::null_mut();
+    assert_eq!(toml_edit_to_string(doc, &mut out), TomlEditStatus::Ok);
+    take(out)
+}
+
+#[test]
+fn edit_preserves_formatting() {
+    unsafe {
+        let doc = parse(
+            r#"[package]
+name   = "foo" # the name
+"#,
+        );
+
+        let mut out = ptr::null_mut();
+        let status = toml_edit_get(doc, c("package.name").as_ptr(), &mut out);
+        assert_eq!(status, TomlEditStatus::Ok);
+        assert_eq!(take(out), r#""foo""#);
+
+        let status = toml_edit_set(doc, c("package.name").as_ptr(), c("'bar'").as_ptr());
+        assert_eq!(status, TomlEditStatus::Ok);
+        let status = toml_edit_set(doc, c("package.version").as_ptr(), c(r#""0.1.0""#).as_ptr());
+        assert_eq!(status, TomlEditStatus::Ok);
+        let status = toml_edit_set(doc, c("dependencies.serde").as_ptr(), c("\"1\"").as_ptr());
+        assert_eq!(status, TomlEditStatus::Ok);
+
+        assert_eq!(
+            render(doc),
+            r#"[package]
+name   = 'bar' # the name
+version = "0.1.0"
+
+[dependencies]
+serde = "1"
+"#
+        );
+
+        let status = toml_edit_remove(doc, c("package.version").as_ptr());
+        assert_eq!(status, TomlEditStatus::Ok);
+        let status = toml_edit_remove(doc, c("dependencies").as_ptr());
+        assert_eq!(status, TomlEditStatus::Ok);
+        assert_eq!(
+            render(doc),
+            r#"[package]
+name   = 'bar' # the name
+"#
+        );
+
+        toml_edit_document_free(doc);
+    }
+}
+
+#[test]
+fn errors() {
+    unsafe {
+        let mut doc = ptr::null_mut();
+        let status = toml_edit_parse(c("a = ").as_ptr(), &mut doc);
+        assert_eq!(status, TomlEditStatus::ParseError);
+        assert!(doc.is_null());
+        let status = toml_edit_parse(ptr::null(), &mut doc);
+        assert_eq!(status, TomlEditStatus::NullPointer);
+
+        let doc = parse("a = 1\n[b]\n");
+        let mut out = ptr::null_mut();
+        let status = toml_edit_get(doc, c("missing").as_ptr(), &mut out);
+        assert_eq!(status, TomlEditStatus::NotFound);
+        let status = toml_edit_get(doc, c("b").as_ptr(), &mut out);
+        assert_eq!(status, TomlEditStatus::NotAValue);
+        let status = toml_edit_get(doc, c("a..").as_ptr(), &mut out);
+        assert_eq!(status, TomlEditStatus::ParseError);
+
+        let status = toml_edit_set(doc, c("a.b").as_ptr(), c("1").as_ptr());
+        assert_eq!(status, TomlEditStatus::NotAValue);
+        let status = toml_edit_set(doc, c("b").as_ptr(), c("1").as_ptr());
+        assert_eq!(status, TomlEditStatus::NotAValue);
+        let status = toml_edit_set(doc, c("a").as_ptr(), c("[1,").as_ptr());
+        assert_eq!(status, TomlEditStatus::ParseError);
+
+        let status = toml_edit_remove(doc, c("missing").as_ptr());
+        assert_eq!(status, TomlEditStatus::NotFound);
+
+        assert_eq!(render(doc), "a = 1\n[b]\n");
+        toml_edit_document_free(doc);
+    }
+}
@@ -0,0 +1,16 @@
+#![no_main]
+
+use toml_edit::events::Events;
+
+libfuzzer_sys::fuzz_target!(|data| {
+    if let Ok(data) = std::str::from_utf8(data) {
+        let mut events = Events::new(data);
+        let mut offset = events.offset();
+        while let Some(Ok(_)) = events.next() {
+            // Every event must consume input and end on a character boundary
+            assert!(events.offset() > offset, "no progress at {}", offset);
+            assert!(data.is_char_boundary(events.offset()));
+            offset = events.offset();
+        }
+    }
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use toml_edit::Key;
+
+libfuzzer_sys::fuzz_target!(|data| {
+    if let Ok(data) = std::str::from_utf8(data) {
+        if let Ok(keys) = Key::parse(data) {
+            for key in keys {
+                let toml = key.to_repr().as_raw().to_owned();
+                let reparsed = toml.parse::<Key>();
+                assert!(
+                    reparsed.is_ok(),
+                    "Failed to parse `key.to_repr()`: {}\n```\n{}\n```",
+                    reparsed.unwrap_err(),
+                    toml
+                );
+                assert_eq!(reparsed.unwrap().get(), key.get());
+            }
+        }
+    }
+});
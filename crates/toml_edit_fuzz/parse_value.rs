@@ -0,0 +1,19 @@
+#![no_main]
+
+use toml_edit::Value;
+
+libfuzzer_sys::fuzz_target!(|data| {
+    if let Ok(data) = std::str::from_utf8(data) {
+        if let Ok(value) = data.parse::<Value>() {
+            let toml = value.to_string();
+            let value = toml.parse::<Value>();
+            assert!(
+                value.is_ok(),
+                "Failed to parse `value.to_string()`: {}\n```\n{}\n```",
+                value.unwrap_err(),
+                toml
+            );
+            assert_eq!(value.unwrap().to_string(), toml);
+        }
+    }
+});
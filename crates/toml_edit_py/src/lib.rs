@@ -0,0 +1,174 @@
+//! # `toml_edit_py`
+//!
+//! Python bindings for [`toml_edit`], built with the `python` feature.
+//!
+//! The `toml_edit` Python module provides a `Document` class for looking up, modifying, and
+//! rendering TOML documents while preserving their formatting.
+
+#![cfg(feature = "python")]
+#![warn(missing_docs)]
+#![warn(rust_2018_idioms)]
+
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyLong, PyString, PyTuple};
+
+use toml_edit::{Array, InlineTable, Item, PathError, Value};
+
+/// A TOML document that preserves formatting when edited.
+#[pyclass(name = "Document", module = "toml_edit")]
+#[derive(Clone)]
+pub struct PyDocument {
+    doc: toml_edit::Document,
+}
+
+#[pymethods]
+impl PyDocument {
+    /// Parse `input`, raising `ValueError` if it isn't valid TOML.
+    #[new]
+    #[pyo3(signature = (input = ""))]
+    fn new(input: &str) -> PyResult<Self> {
+        let doc = input
+            .parse::<toml_edit::Document>()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { doc })
+    }
+
+    /// Look up the value or table at `key`, raising `KeyError` if it doesn't exist.
+    fn get(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        let item = self
+            .doc
+            .get_path(key)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .ok_or_else(|| PyKeyError::new_err(key.to_owned()))?;
+        item_to_py(py, item)
+    }
+
+    /// Set the value at `key`, creating tables as needed.
+    ///
+    /// When replacing an existing value, its surrounding whitespace and comments are kept.
+    fn set(&mut self, key: &str, value: &PyAny) -> PyResult<()> {
+        let value = py_to_value(value)?;
+        self.doc.set_path(key, value).map_err(|e| match e {
+            PathError::Parse(e) => PyValueError::new_err(e.to_string()),
+            e => PyTypeError::new_err(e.to_string()),
+        })
+    }
+
+    /// Remove the value or table at `key`, raising `KeyError` if it doesn't exist.
+    fn remove(&mut self, key: &str) -> PyResult<()> {
+        self.doc
+            .remove_path(key)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .map(|_| ())
+            .ok_or_else(|| PyKeyError::new_err(key.to_owned()))
+    }
+
+    /// Render the document.
+    #[pyo3(name = "to_string")]
+    fn render(&self) -> String {
+        self.doc.to_string()
+    }
+
+    fn __str__(&self) -> String {
+        self.doc.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Document({:?})", self.doc.to_string())
+    }
+}
+
+/// Edit TOML documents while preserving comments and formatting.
+#[pymodule]
+#[pyo3(name = "toml_edit")]
+fn module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyDocument>()?;
+    Ok(())
+}
+
+fn item_to_py(py: Python<'_>, item: &Item) -> PyResult<PyObject> {
+    match item {
+        Item::None => Ok(py.None()),
+        Item::Value(v) => value_to_py(py, v),
+        Item::Table(t) => table_to_py(py, t.iter()),
+        Item::ArrayOfTables(a) => {
+            let tables = a
+                .iter()
+                .map(|t| table_to_py(py, t.iter()))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, tables).into())
+        }
+    }
+}
+
+fn table_to_py<'t>(
+    py: Python<'_>,
+    entries: impl Iterator<Item = (&'t str, &'t Item)>,
+) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    for (k, v) in entries {
+        dict.set_item(k, item_to_py(py, v)?)?;
+    }
+    Ok(dict.into())
+}
+
+fn value_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    let obj = match value {
+        Value::String(v) => v.value().into_py(py),
+        Value::Integer(v) => v.value().into_py(py),
+        Value::WideInteger(v) => v.value().into_py(py),
+        Value::Float(v) => v.value().into_py(py),
+        Value::Boolean(v) => v.value().into_py(py),
+        Value::Datetime(v) => v.value().to_string().into_py(py),
+        Value::OpaqueDatetime(v) => v.value().as_str().into_py(py),
+        Value::Array(a) => {
+            let values = a
+                .iter()
+                .map(|v| value_to_py(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values).into()
+        }
+        Value::InlineTable(t) => {
+            let dict = PyDict::new(py);
+            for (k, v) in t.iter() {
+                dict.set_item(k, value_to_py(py, v)?)?;
+            }
+            dict.into()
+        }
+    };
+    Ok(obj)
+}
+
+fn py_to_value(obj: &PyAny) -> PyResult<Value> {
+    // `bool` is a subclass of `int`, so check it first
+    if let Ok(b) = obj.downcast::<PyBool>() {
+        Ok(b.is_true().into())
+    } else if let Ok(i) = obj.downcast::<PyLong>() {
+        Ok(i.extract::<i64>()?.into())
+    } else if let Ok(f) = obj.downcast::<PyFloat>() {
+        Ok(f.value().into())
+    } else if let Ok(s) = obj.downcast::<PyString>() {
+        Ok(s.to_str()?.into())
+    } else if obj.is_instance_of::<PyList>()? || obj.is_instance_of::<PyTuple>()? {
+        let mut array = Array::new();
+        for v in obj.iter()? {
+            array.push(py_to_value(v?)?);
+        }
+        Ok(array.into())
+    } else if let Ok(d) = obj.downcast::<PyDict>() {
+        let mut table = InlineTable::new();
+        for (k, v) in d.iter() {
+            let k = k
+                .downcast::<PyString>()
+                .map_err(|_| PyTypeError::new_err("table keys must be strings"))?;
+            table.insert(k.to_str()?, py_to_value(v)?);
+        }
+        Ok(table.into())
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "unsupported type `{}`",
+            obj.get_type().name()?
+        )))
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "python")]
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use toml_edit_py::PyDocument;
+
+fn run(code: &str) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let locals = PyDict::new(py);
+        locals
+            .set_item("Document", py.get_type::<PyDocument>())
+            .unwrap();
+        if let Err(err) = py.run(code, None, Some(locals)) {
+            err.print(py);
+            panic!("python code failed");
+        }
+    });
+}
+
+#[test]
+fn edit_preserves_formatting() {
+    run(r#"
+doc = Document('[package]\nname = "foo"  # the name\nversion = "0.1.0"\n')
+assert doc.get("package.version") == "0.1.0"
+assert doc.get("package") == {"name": "foo", "version": "0.1.0"}
+
+doc.set("package.version", "0.2.0")
+doc.set("package.keywords", ["toml", "edit"])
+doc.set("package.metadata.docs", {"all-features": True, "ratio": 0.5})
+doc.set("dependencies.serde", 1)
+assert str(doc) == '''[package]
+name = "foo"  # the name
+version = "0.2.0"
+keywords = ["toml", "edit"]
+
+[package.metadata]
+docs = { all-features = true, ratio = 0.5 }
+
+[dependencies]
+serde = 1
+''', str(doc)
+
+doc.remove("package.metadata")
+doc.remove("dependencies")
+assert str(doc) == '[package]\nname = "foo"  # the name\nversion = "0.2.0"\nkeywords = ["toml", "edit"]\n'
+"#);
+}
+
+#[test]
+fn errors() {
+    run(r#"
+try:
+    Document("a = ")
+    raise AssertionError("expected ValueError")
+except ValueError:
+    pass
+
+doc = Document("a = 1\n[b]\n")
+for key in ["missing", "a.b"]:
+    try:
+        doc.get(key)
+        raise AssertionError("expected KeyError")
+    except KeyError:
+        pass
+try:
+    doc.set("a.b", 1)
+    raise AssertionError("expected TypeError")
+except TypeError:
+    pass
+try:
+    doc.set("c", object())
+    raise AssertionError("expected TypeError")
+except TypeError:
+    pass
+assert str(doc) == "a = 1\n[b]\n"
+"#);
+}
@@ -0,0 +1,216 @@
+//! # `toml_edit_wasm`
+//!
+//! JavaScript bindings for [`toml_edit`], built with `wasm-bindgen`.
+//!
+//! [`Document`] looks up, modifies, and renders TOML documents while preserving their
+//! formatting, and [`events`] reports the span of each piece of a document.
+
+#![warn(missing_docs)]
+#![warn(rust_2018_idioms)]
+
+use js_sys::{Array, BigInt, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use toml_edit::events::{Event, Events};
+use toml_edit::{InlineTable, Item, Value};
+
+// Largest integer a JavaScript number represents exactly, `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// A TOML document that preserves formatting when edited.
+#[wasm_bindgen]
+pub struct Document {
+    doc: toml_edit::Document,
+}
+
+#[wasm_bindgen]
+impl Document {
+    /// Parse `input`, throwing if it isn't valid TOML.
+    #[wasm_bindgen(constructor)]
+    pub fn new(input: &str) -> Result<Document, JsError> {
+        let doc = input.parse::<toml_edit::Document>()?;
+        Ok(Self { doc })
+    }
+
+    /// Look up the value or table at `key`, returning `undefined` if it doesn't exist.
+    pub fn get(&self, key: &str) -> Result<JsValue, JsError> {
+        match self.doc.get_path(key)? {
+            Some(item) => item_to_js(item),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Set the value at `key`, creating tables as needed.
+    ///
+    /// When replacing an existing value, its surrounding whitespace and comments are kept.
+    pub fn set(&mut self, key: &str, value: JsValue) -> Result<(), JsError> {
+        let value = js_to_value(&value)?;
+        self.doc.set_path(key, value)?;
+        Ok(())
+    }
+
+    /// Remove the value or table at `key`, returning whether it existed.
+    pub fn remove(&mut self, key: &str) -> Result<bool, JsError> {
+        Ok(self.doc.remove_path(key)?.is_some())
+    }
+
+    /// Render the document.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn render(&self) -> String {
+        self.doc.to_string()
+    }
+}
+
+/// Parse `input` into a list of `{ kind, start, end }` objects covering the whole input.
+///
+/// `kind` is one of `whitespace`, `comment`, `tableHeader`, `arrayHeader`, or `keyValue`.
+/// Headers and key/value pairs also have a `path` of key names.  `start` and `end` are UTF-16
+/// offsets, as used by JavaScript strings.
+#[wasm_bindgen]
+pub fn events(input: &str) -> Result<Array, JsError> {
+    let result = Array::new();
+    let mut events = Events::new(input);
+    let mut utf16_offset = 0;
+    loop {
+        let start = events.offset();
+        let event = match events.next() {
+            Some(event) => event?,
+            None => break,
+        };
+        let end = events.offset();
+        let (kind, path) = match event {
+            Event::Whitespace(_) => ("whitespace", None),
+            Event::Comment(_) => ("comment", None),
+            Event::TableHeader { path, is_array } => {
+                let kind = if is_array {
+                    "arrayHeader"
+                } else {
+                    "tableHeader"
+                };
+                (kind, Some(path))
+            }
+            Event::KeyValue { path, .. } => ("keyValue", Some(path)),
+        };
+
+        let utf16_start = utf16_offset;
+        utf16_offset += input[start..end].encode_utf16().count();
+        let obj = Object::new();
+        set(&obj, "kind", &kind.into())?;
+        set(&obj, "start", &(utf16_start as f64).into())?;
+        set(&obj, "end", &(utf16_offset as f64).into())?;
+        if let Some(path) = path {
+            let keys = path
+                .iter()
+                .map(|k| JsValue::from_str(k.get()))
+                .collect::<Array>();
+            set(&obj, "path", &keys)?;
+        }
+        result.push(&obj);
+    }
+    Ok(result)
+}
+
+fn set(obj: &Object, key: &str, value: &JsValue) -> Result<(), JsError> {
+    Reflect::set(obj, &key.into(), value)
+        .map(|_| ())
+        .map_err(|_| JsError::new("failed to set property"))
+}
+
+fn item_to_js(item: &Item) -> Result<JsValue, JsError> {
+    match item {
+        Item::None => Ok(JsValue::UNDEFINED),
+        Item::Value(v) => value_to_js(v),
+        Item::Table(t) => table_to_js(t.iter()),
+        Item::ArrayOfTables(a) => {
+            let tables = Array::new();
+            for t in a.iter() {
+                tables.push(&table_to_js(t.iter())?);
+            }
+            Ok(tables.into())
+        }
+    }
+}
+
+fn table_to_js<'t>(entries: impl Iterator<Item = (&'t str, &'t Item)>) -> Result<JsValue, JsError> {
+    let obj = Object::new();
+    for (k, v) in entries {
+        set(&obj, k, &item_to_js(v)?)?;
+    }
+    Ok(obj.into())
+}
+
+fn value_to_js(value: &Value) -> Result<JsValue, JsError> {
+    let js = match value {
+        Value::String(v) => JsValue::from_str(v.value()),
+        Value::Integer(v) => {
+            let i = *v.value();
+            if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&i) {
+                JsValue::from_f64(i as f64)
+            } else {
+                BigInt::from(i).into()
+            }
+        }
+        Value::WideInteger(v) => BigInt::from(*v.value()).into(),
+        Value::Float(v) => JsValue::from_f64(*v.value()),
+        Value::Boolean(v) => JsValue::from_bool(*v.value()),
+        Value::Datetime(v) => JsValue::from_str(&v.value().to_string()),
+        Value::OpaqueDatetime(v) => JsValue::from_str(v.value().as_str()),
+        Value::Array(a) => {
+            let values = Array::new();
+            for v in a.iter() {
+                values.push(&value_to_js(v)?);
+            }
+            values.into()
+        }
+        Value::InlineTable(t) => {
+            let obj = Object::new();
+            for (k, v) in t.iter() {
+                set(&obj, k, &value_to_js(v)?)?;
+            }
+            obj.into()
+        }
+    };
+    Ok(js)
+}
+
+fn js_to_value(js: &JsValue) -> Result<Value, JsError> {
+    if let Some(s) = js.as_string() {
+        Ok(s.into())
+    } else if let Some(b) = js.as_bool() {
+        Ok(b.into())
+    } else if let Some(f) = js.as_f64() {
+        // JavaScript doesn't distinguish integers from floats, so go by the value
+        if f.fract() == 0.0 && (-MAX_SAFE_INTEGER as f64..=MAX_SAFE_INTEGER as f64).contains(&f) {
+            Ok((f as i64).into())
+        } else {
+            Ok(f.into())
+        }
+    } else if let Some(i) = js.dyn_ref::<BigInt>() {
+        let i =
+            i64::try_from(i.clone()).map_err(|_| JsError::new("integer out of range for TOML"))?;
+        Ok(i.into())
+    } else if Array::is_array(js) {
+        let array = Array::from(js);
+        let mut values = toml_edit::Array::new();
+        for v in array.iter() {
+            values.push(js_to_value(&v)?);
+        }
+        Ok(values.into())
+    } else if js.is_object() {
+        let mut table = InlineTable::new();
+        for entry in Object::entries(js.unchecked_ref::<Object>()).iter() {
+            let entry = Array::from(&entry);
+            let key = entry
+                .get(0)
+                .as_string()
+                .ok_or_else(|| JsError::new("table keys must be strings"))?;
+            table.insert(&key, js_to_value(&entry.get(1))?);
+        }
+        Ok(table.into())
+    } else {
+        Err(JsError::new(
+            "unsupported value, expected a string, number, boolean, array, or object",
+        ))
+    }
+}
@@ -0,0 +1,57 @@
+//! Run in JavaScript with `wasm-pack test --node`.  Only tests that don't create JavaScript
+//! values also run natively with `cargo test`.
+
+use toml_edit_wasm::Document;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsValue;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn remove_preserves_formatting() {
+    let mut doc = Document::new("[package]  # the package\nname = \"foo\"\nversion = \"0.1.0\"\n")
+        .unwrap_or_else(|_| panic!("valid TOML"));
+    assert!(doc.remove("package.version").unwrap_or(false));
+    assert!(!doc.remove("package.version").unwrap_or(true));
+    assert!(!doc.remove("missing.key").unwrap_or(true));
+    assert_eq!(doc.render(), "[package]  # the package\nname = \"foo\"\n");
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn get_and_set() {
+    let mut doc = Document::new("[package]\nname = \"foo\"  # the name\n").unwrap();
+    assert_eq!(doc.get("package.name").unwrap().as_string().unwrap(), "foo");
+    assert!(doc.get("package.missing").unwrap().is_undefined());
+
+    doc.set("package.name", JsValue::from_str("bar")).unwrap();
+    doc.set("package.edition", JsValue::from_f64(2021.0))
+        .unwrap();
+    doc.set("package.ratio", JsValue::from_f64(0.5)).unwrap();
+    assert_eq!(
+        doc.render(),
+        "[package]\nname = \"bar\"  # the name\nedition = 2021\nratio = 0.5\n"
+    );
+
+    assert!(doc.set("package", JsValue::from_f64(1.0)).is_err());
+    assert!(doc.set("package.name.first", JsValue::TRUE).is_err());
+    assert!(doc.get("a.").is_err());
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn events_cover_the_input() {
+    let events = toml_edit_wasm::events("a = 1 # one\n").unwrap();
+    let kinds = events
+        .iter()
+        .map(|e| {
+            js_sys::Reflect::get(&e, &"kind".into())
+                .unwrap()
+                .as_string()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, ["keyValue", "whitespace", "comment", "whitespace"]);
+}